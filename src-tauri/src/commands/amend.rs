@@ -1,8 +1,19 @@
-use super::utils::open_repo;
+use super::hooks::{run_commit_msg, run_pre_commit};
+use super::models::AmendResult;
+use super::signing::{create_signed_commit, move_head};
+use super::utils::{assert_no_conflicts, normalize_unicode, open_repo, repo_signature};
 
 /// Amend the last commit with a new message and/or staged changes.
+/// If `include_worktree` is true, tracked modifications are staged first
+/// (like `git commit -a --amend`); untracked files are left alone.
+/// The commit hooks run as for `create_commit` unless `skip_hooks` is set.
 #[tauri::command]
-pub fn amend_commit(repo_path: String, message: String) -> Result<String, String> {
+pub fn amend_commit(
+    repo_path: String,
+    message: String,
+    include_worktree: Option<bool>,
+    skip_hooks: Option<bool>,
+) -> Result<AmendResult, String> {
     let repo = open_repo(&repo_path)?;
 
     let head = repo
@@ -15,6 +26,20 @@ pub fn amend_commit(repo_path: String, message: String) -> Result<String, String
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+    assert_no_conflicts(&index)?;
+
+    let auto_staged_paths = if include_worktree.unwrap_or(false) {
+        stage_tracked_changes(&repo, &mut index)?
+    } else {
+        Vec::new()
+    };
+    let message = if skip_hooks.unwrap_or(false) {
+        message
+    } else {
+        run_pre_commit(&repo, &mut index, !auto_staged_paths.is_empty())?;
+        run_commit_msg(&repo, &message)?
+    };
+
     let tree_oid = index
         .write_tree()
         .map_err(|e| format!("트리 쓰기 실패: {}", e))?;
@@ -22,22 +47,70 @@ pub fn amend_commit(repo_path: String, message: String) -> Result<String, String
         .find_tree(tree_oid)
         .map_err(|e| format!("트리 찾기 실패: {}", e))?;
 
-    let signature = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let signature = repo_signature(&repo)?;
 
-    head_commit
-        .amend(
-            Some("HEAD"),
-            Some(&signature),
-            Some(&signature),
-            None,
-            Some(&message),
-            Some(&tree),
-        )
-        .map_err(|e| format!("커밋 수정 실패: {}", e))?;
+    let parents: Vec<git2::Commit> = head_commit.parents().collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    let signed =
+        create_signed_commit(&repo, &signature, &signature, &message, &tree, &parent_refs)?;
+    let (commit_oid, signature_type) = match signed {
+        Some(created) => {
+            move_head(&repo, created.oid, "commit (amend)", &message)?;
+            (created.oid, created.signature_type)
+        }
+        None => {
+            let oid = head_commit
+                .amend(
+                    Some("HEAD"),
+                    Some(&signature),
+                    Some(&signature),
+                    None,
+                    Some(&message),
+                    Some(&tree),
+                )
+                .map_err(|e| format!("커밋 수정 실패: {}", e))?;
+            (oid, None)
+        }
+    };
+
+    // Persist the auto-staged index only once the amend has succeeded
+    if !auto_staged_paths.is_empty() {
+        index
+            .write()
+            .map_err(|e| format!("인덱스 저장 실패: {}", e))?;
+    }
 
-    Ok("커밋 수정 완료".to_string())
+    Ok(AmendResult {
+        commit_sha: commit_oid.to_string(),
+        message: "커밋 수정 완료".to_string(),
+        auto_staged_paths,
+        signed: signature_type.is_some(),
+        signature_type,
+    })
+}
+
+/// Stage modified and deleted tracked files into `index` (in memory, like `git add -u`).
+/// Returns the staged paths.
+fn stage_tracked_changes(
+    repo: &git2::Repository,
+    index: &mut git2::Index,
+) -> Result<Vec<String>, String> {
+    let diff = repo
+        .diff_index_to_workdir(Some(index), None)
+        .map_err(|e| format!("작업 트리 변경 확인 실패: {}", e))?;
+    let paths: Vec<String> = diff
+        .deltas()
+        .filter_map(|d| d.new_file().path().or_else(|| d.old_file().path()))
+        .filter_map(|p| p.to_str())
+        .map(normalize_unicode)
+        .collect();
+
+    if !paths.is_empty() {
+        index
+            .update_all(["*"].iter(), None)
+            .map_err(|e| format!("변경 사항 스테이징 실패: {}", e))?;
+    }
+    Ok(paths)
 }
 
 /// Get the message of the last commit.
@@ -66,7 +139,10 @@ mod tests {
     fn setup_test_repo() -> (TempDir, PathBuf) {
         let temp_dir = TempDir::new().unwrap();
         let repo_path = temp_dir.path().to_path_buf();
-        Repository::init(&repo_path).unwrap();
+        let repo = Repository::init(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
         (temp_dir, repo_path)
     }
 
@@ -108,6 +184,8 @@ mod tests {
         amend_commit(
             repo_path.to_str().unwrap().to_string(),
             "Amended message".to_string(),
+            None,
+            None,
         )
         .unwrap();
 
@@ -115,10 +193,94 @@ mod tests {
         assert_eq!(amended, "Amended message");
     }
 
+    #[test]
+    fn test_amend_refuses_conflicted_index() {
+        let (_temp, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        create_test_file(&repo_path, "file.txt", "base");
+        stage_file(&repo, "file.txt");
+        let base = repo.find_commit(create_commit(&repo, "Base")).unwrap();
+        let default_branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        repo.branch("other", &base, false).unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        create_test_file(&repo_path, "file.txt", "theirs");
+        stage_file(&repo, "file.txt");
+        create_commit(&repo, "Theirs");
+
+        repo.set_head(&format!("refs/heads/{}", default_branch)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        create_test_file(&repo_path, "file.txt", "ours");
+        stage_file(&repo, "file.txt");
+        create_commit(&repo, "Ours");
+
+        let other = repo.find_reference("refs/heads/other").unwrap();
+        let annotated = repo.reference_to_annotated_commit(&other).unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+
+        let err = amend_commit(
+            repo_path.to_str().unwrap().to_string(),
+            "Amended".to_string(),
+            None,
+            None,
+        )
+        .unwrap_err();
+        let payload: crate::commands::models::CommandError =
+            serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "unresolved_conflicts");
+        assert_eq!(payload.details["paths"], serde_json::json!(["file.txt"]));
+        assert_eq!(
+            get_last_commit_message(repo_path.to_str().unwrap().to_string()).unwrap(),
+            "Ours"
+        );
+    }
+
+    #[test]
+    fn test_amend_include_worktree_stages_tracked_only() {
+        let (_temp, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        create_test_file(&repo_path, "tracked.txt", "v1");
+        stage_file(&repo, "tracked.txt");
+        create_commit(&repo, "Initial commit");
+
+        // Edit without staging, plus a brand-new untracked file
+        create_test_file(&repo_path, "tracked.txt", "v2");
+        create_test_file(&repo_path, "untracked.txt", "new");
+
+        let result = amend_commit(
+            repo_path.to_str().unwrap().to_string(),
+            "Amended with edits".to_string(),
+            Some(true),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.auto_staged_paths, vec!["tracked.txt"]);
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id().to_string(), result.commit_sha);
+        assert_eq!(head.message(), Some("Amended with edits"));
+        let tree = head.tree().unwrap();
+        let blob = repo
+            .find_blob(tree.get_name("tracked.txt").unwrap().id())
+            .unwrap();
+        assert_eq!(blob.content(), b"v2");
+        assert!(tree.get_name("untracked.txt").is_none());
+
+        let status = repo.status_file(Path::new("untracked.txt")).unwrap();
+        assert!(status.contains(git2::Status::WT_NEW));
+        assert!(repo.status_file(Path::new("tracked.txt")).unwrap().is_empty());
+    }
+
     #[test]
     fn test_amend_no_commits() {
         let (_temp, repo_path) = setup_test_repo();
-        let result = amend_commit(repo_path.to_str().unwrap().to_string(), "Test".to_string());
+        let result = amend_commit(
+            repo_path.to_str().unwrap().to_string(),
+            "Test".to_string(),
+            None,
+            None,
+        );
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,248 @@
+//! App-level persistence under the user's config directory.
+//!
+//! Files live in `<config>/gitmul/` as `{ "schema_version": N, "data": ... }`.
+//! Earlier releases used `<config>/gitflow/` with bare JSON; that directory is
+//! copied over once on first access and still read as a fallback.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const APP_DIR_NAME: &str = "gitmul";
+/// Pre-rename directory; only read from, never written. Drop after one release.
+const LEGACY_APP_DIR_NAME: &str = "gitflow";
+
+/// A typed file stored in the app data directory.
+pub trait AppData: Serialize + DeserializeOwned + Default {
+    /// File name inside the app data directory.
+    const FILE_NAME: &'static str;
+    /// Schema version written by `save`.
+    const SCHEMA_VERSION: u32;
+
+    /// Convert a payload written with an older `version` (0 = legacy bare JSON).
+    /// Returning `None` discards it and falls back to `Default`.
+    fn upgrade(_version: u32, data: serde_json::Value) -> Option<Self> {
+        serde_json::from_value(data).ok()
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Envelope<T> {
+    schema_version: u32,
+    data: T,
+}
+
+/// Load `T`, falling back to the legacy directory and then to `T::default()`.
+/// Unreadable or corrupted files yield the default rather than an error.
+pub fn load<T: AppData>() -> Result<T, String> {
+    let base = dirs::config_dir().ok_or_else(|| "설정 디렉토리를 찾을 수 없습니다".to_string())?;
+    load_in(&base)
+}
+
+/// Save `T` atomically (temp file + rename) into the app data directory.
+pub fn save<T: AppData>(value: &T) -> Result<(), String> {
+    let base = dirs::config_dir().ok_or_else(|| "설정 디렉토리를 찾을 수 없습니다".to_string())?;
+    save_in(&base, value)
+}
+
+/// The app data directory under `base`, created (and migrated from the legacy
+/// name) if needed.
+fn app_data_dir_in(base: &Path) -> Result<PathBuf, String> {
+    let dir = base.join(APP_DIR_NAME);
+    if !dir.exists() {
+        let legacy = base.join(LEGACY_APP_DIR_NAME);
+        if legacy.is_dir() {
+            migrate_legacy_dir(&legacy, &dir)?;
+        }
+    }
+    if !dir.exists() {
+        fs::create_dir_all(&dir).map_err(|e| format!("설정 디렉토리 생성 실패: {}", e))?;
+    }
+    Ok(dir)
+}
+
+/// Copy the legacy directory into a staging directory, then rename it into
+/// place so a half-finished copy is never mistaken for a migrated one.
+fn migrate_legacy_dir(legacy: &Path, target: &Path) -> Result<(), String> {
+    let staging =
+        target.with_file_name(format!("{}.migrating-{}", APP_DIR_NAME, std::process::id()));
+    let _ = fs::remove_dir_all(&staging);
+
+    let result = copy_dir(legacy, &staging).and_then(|_| fs::rename(&staging, target));
+    if let Err(e) = result {
+        let _ = fs::remove_dir_all(&staging);
+        // Another instance may have finished the migration first
+        if !target.exists() {
+            return Err(format!("설정 디렉토리 이전 실패: {}", e));
+        }
+    }
+    Ok(())
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            fs::copy(entry.path(), dest)?;
+        }
+    }
+    Ok(())
+}
+
+fn load_in<T: AppData>(base: &Path) -> Result<T, String> {
+    let path = app_data_dir_in(base)?.join(T::FILE_NAME);
+    let legacy_path = base.join(LEGACY_APP_DIR_NAME).join(T::FILE_NAME);
+
+    let content = if path.exists() {
+        fs::read_to_string(&path)
+    } else if legacy_path.exists() {
+        fs::read_to_string(&legacy_path)
+    } else {
+        return Ok(T::default());
+    };
+    let value = match content.map(|c| serde_json::from_str::<serde_json::Value>(&c)) {
+        Ok(Ok(value)) => value,
+        _ => return Ok(T::default()),
+    };
+    decode(value)
+}
+
+fn decode<T: AppData>(value: serde_json::Value) -> Result<T, String> {
+    let versioned = value
+        .as_object()
+        .filter(|obj| obj.len() == 2 && obj.contains_key("data"))
+        .and_then(|obj| obj.get("schema_version"))
+        .and_then(|v| v.as_u64());
+
+    let (version, data) = match versioned {
+        Some(version) => {
+            let version = version as u32;
+            let data = value.get("data").cloned().unwrap_or_default();
+            (version, data)
+        }
+        None => (0, value),
+    };
+
+    if version > T::SCHEMA_VERSION {
+        return Err(format!(
+            "{}은(는) 더 최신 버전(v{})에서 저장되었습니다. 앱을 업데이트하세요.",
+            T::FILE_NAME,
+            version
+        ));
+    }
+    if version == T::SCHEMA_VERSION {
+        return Ok(serde_json::from_value(data).unwrap_or_default());
+    }
+    Ok(T::upgrade(version, data).unwrap_or_default())
+}
+
+fn save_in<T: AppData>(base: &Path, value: &T) -> Result<(), String> {
+    let path = app_data_dir_in(base)?.join(T::FILE_NAME);
+    let envelope = Envelope {
+        schema_version: T::SCHEMA_VERSION,
+        data: value,
+    };
+    let content =
+        serde_json::to_string_pretty(&envelope).map_err(|e| format!("JSON 직렬화 실패: {}", e))?;
+
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, content).map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+    fs::rename(&tmp, &path).map_err(|e| format!("파일 쓰기 실패: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::models::RecentRepo;
+    use tempfile::tempdir;
+
+    fn repo(path: &str) -> RecentRepo {
+        RecentRepo {
+            path: path.to_string(),
+            name: path.to_string(),
+            last_opened: 1,
+        }
+    }
+
+    #[test]
+    fn test_fresh_install() {
+        let base = tempdir().unwrap();
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert!(repos.is_empty());
+
+        save_in(base.path(), &vec![repo("/a")]).unwrap();
+        assert!(base.path().join("gitmul/recent_repos.json").exists());
+        assert!(!base.path().join("gitflow").exists());
+
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].path, "/a");
+    }
+
+    #[test]
+    fn test_migrates_legacy_dir() {
+        let base = tempdir().unwrap();
+        let legacy = base.path().join("gitflow");
+        fs::create_dir_all(&legacy).unwrap();
+        let legacy_file = legacy.join("recent_repos.json");
+        fs::write(
+            &legacy_file,
+            serde_json::to_string(&vec![repo("/old")]).unwrap(),
+        )
+        .unwrap();
+
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert_eq!(repos[0].path, "/old");
+        assert!(base.path().join("gitmul/recent_repos.json").exists());
+
+        // Writes go only to the new location
+        save_in(base.path(), &vec![repo("/new")]).unwrap();
+        let legacy_content = fs::read_to_string(&legacy_file).unwrap();
+        assert!(legacy_content.contains("/old"));
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert_eq!(repos[0].path, "/new");
+    }
+
+    #[test]
+    fn test_legacy_fallback_when_new_dir_lacks_file() {
+        let base = tempdir().unwrap();
+        fs::create_dir_all(base.path().join("gitmul")).unwrap();
+        fs::create_dir_all(base.path().join("gitflow")).unwrap();
+        fs::write(
+            base.path().join("gitflow/recent_repos.json"),
+            serde_json::to_string(&vec![repo("/old")]).unwrap(),
+        )
+        .unwrap();
+
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert_eq!(repos[0].path, "/old");
+    }
+
+    #[test]
+    fn test_corrupted_old_file_falls_back_to_default() {
+        let base = tempdir().unwrap();
+        fs::create_dir_all(base.path().join("gitflow")).unwrap();
+        fs::write(base.path().join("gitflow/recent_repos.json"), "{not json").unwrap();
+
+        let repos: Vec<RecentRepo> = load_in(base.path()).unwrap();
+        assert!(repos.is_empty());
+        assert!(base.path().join("gitmul").is_dir());
+    }
+
+    #[test]
+    fn test_newer_schema_is_rejected() {
+        let base = tempdir().unwrap();
+        fs::create_dir_all(base.path().join("gitmul")).unwrap();
+        fs::write(
+            base.path().join("gitmul/recent_repos.json"),
+            r#"{"schema_version": 99, "data": []}"#,
+        )
+        .unwrap();
+        assert!(load_in::<Vec<RecentRepo>>(base.path()).is_err());
+    }
+}
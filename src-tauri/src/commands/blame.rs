@@ -0,0 +1,330 @@
+use git2::{BlameOptions, ErrorCode, Oid, Repository};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::history::RenameTracker;
+use super::models::{BlameLine, FileBlame};
+use super::utils::{normalize_unicode, open_repo, resolve_revision, safe_repo_path};
+
+/// Files longer than this are not blamed unless the caller raises the limit.
+const DEFAULT_MAX_BLAME_LINES: usize = 20_000;
+
+/// Commit metadata repeated on every line the commit owns.
+struct BlameCommit {
+    author: String,
+    author_email: String,
+    timestamp: i64,
+    summary: String,
+}
+
+/// Line-by-line authorship of a file, like `git blame`.
+///
+/// Without `commit_sha` the working tree file is blamed, so lines that are
+/// not committed yet come back with `is_uncommitted`. Files with more than
+/// `max_lines` lines (default 20 000) are returned with `skipped` set and no
+/// lines. A line's `original_path` is the file's name in the commit that
+/// owns it, following renames like `get_file_history`.
+#[tauri::command]
+pub async fn get_file_blame(
+    repo_path: String,
+    file_path: String,
+    commit_sha: Option<String>,
+    max_lines: Option<usize>,
+) -> Result<FileBlame, String> {
+    let normalized_path = normalize_unicode(&file_path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    let repo = open_repo(&repo_path)?;
+
+    let commit = commit_sha
+        .as_deref()
+        .map(|rev| resolve_revision(&repo, rev))
+        .transpose()?;
+    let content = match &commit {
+        Some(commit) => {
+            let tree = commit
+                .tree()
+                .map_err(|e| format!("트리 접근 실패: {}", e))?;
+            let entry = tree
+                .get_path(Path::new(&normalized_path))
+                .map_err(|e| format!("파일 찾기 실패: {}", e))?;
+            let object = entry
+                .to_object(&repo)
+                .map_err(|e| format!("오브젝트 접근 실패: {}", e))?;
+            let blob = object.as_blob().ok_or("Blob이 아닙니다")?;
+            blob.content().to_vec()
+        }
+        None => std::fs::read(&full_path).map_err(|e| format!("파일 읽기 실패: {}", e))?,
+    };
+
+    let text = String::from_utf8_lossy(&content);
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    let mut result = FileBlame {
+        file_path: normalized_path.clone(),
+        commit_sha: commit.as_ref().map(|c| c.id().to_string()),
+        total_lines: lines.len(),
+        skipped: false,
+        lines: Vec::new(),
+    };
+    if lines.len() > max_lines.unwrap_or(DEFAULT_MAX_BLAME_LINES) {
+        result.skipped = true;
+        return Ok(result);
+    }
+
+    let mut opts = BlameOptions::new();
+    if let Some(commit) = &commit {
+        opts.newest_commit(commit.id());
+    }
+    let committed = match repo.blame_file(Path::new(&normalized_path), Some(&mut opts)) {
+        Ok(blame) => Some(blame),
+        // A file that was never committed is entirely uncommitted
+        Err(e) if commit.is_none() && e.code() == ErrorCode::NotFound => None,
+        Err(e) => return Err(format!("Blame 실패: {}", e)),
+    };
+    // The working tree copy may have edits on top of the committed file
+    let buffered = match (&committed, &commit) {
+        (Some(blame), None) => Some(
+            blame
+                .blame_buffer(&content)
+                .map_err(|e| format!("Blame 실패: {}", e))?,
+        ),
+        _ => None,
+    };
+    let blame = buffered.as_ref().or(committed.as_ref());
+    // Names per commit as the file history reports them
+    let tracker = match (&committed, &commit) {
+        (None, _) => None,
+        (Some(_), Some(commit)) => Some(commit.id()),
+        (Some(_), None) => repo.head().and_then(|h| h.peel_to_commit()).ok().map(|c| c.id()),
+    }
+    .and_then(|from| RenameTracker::for_path(&repo, &normalized_path, from).ok());
+
+    let mut commits: HashMap<Oid, BlameCommit> = HashMap::new();
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        let hunk = blame.and_then(|b| b.get_line(line_number));
+        let oid = hunk.as_ref().map_or(Oid::zero(), |h| h.final_commit_id());
+        let mut entry = BlameLine {
+            line_number,
+            content: line.to_string(),
+            commit_sha: oid.to_string(),
+            author: String::new(),
+            author_email: String::new(),
+            timestamp: 0,
+            original_line_number: line_number,
+            original_path: normalized_path.clone(),
+            summary: String::new(),
+            is_uncommitted: oid.is_zero(),
+        };
+
+        if let Some(hunk) = hunk.filter(|_| !oid.is_zero()) {
+            entry.original_line_number =
+                hunk.orig_start_line() + (line_number - hunk.final_start_line());
+            let tracked = tracker.as_ref().and_then(|t| t.path_in(oid));
+            if let Some(path) = tracked {
+                entry.original_path = path.to_string();
+            } else if let Some(path) = hunk.path() {
+                entry.original_path = normalize_unicode(&path.to_string_lossy());
+            }
+            if let Some(info) = blame_commit(&repo, &mut commits, oid) {
+                entry.author = info.author.clone();
+                entry.author_email = info.author_email.clone();
+                entry.timestamp = info.timestamp;
+                entry.summary = info.summary.clone();
+            }
+        }
+        result.lines.push(entry);
+    }
+
+    Ok(result)
+}
+
+fn blame_commit<'c>(
+    repo: &Repository,
+    cache: &'c mut HashMap<Oid, BlameCommit>,
+    oid: Oid,
+) -> Option<&'c BlameCommit> {
+    match cache.entry(oid) {
+        Entry::Occupied(entry) => Some(entry.into_mut()),
+        Entry::Vacant(slot) => {
+            let commit = repo.find_commit(oid).ok()?;
+            let author = commit.author();
+            Some(slot.insert(BlameCommit {
+                author: normalize_unicode(author.name().unwrap_or("")),
+                author_email: author.email().unwrap_or("").to_string(),
+                timestamp: author.when().seconds(),
+                summary: commit.summary().unwrap_or("").to_string(),
+            }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::history::{get_file_history, get_path_identity_chain};
+    use git2::Signature;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn commit_as(repo: &Repository, author: &str, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now(author, "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_blame_korean_crlf_file() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        fs::write(root.join("메모.txt"), "첫 줄\r\n둘째 줄\r\n셋째 줄\r\n").unwrap();
+        let first = commit_as(&repo, "김철수", "메모 추가\n\n본문");
+        fs::write(root.join("메모.txt"), "첫 줄\r\n바뀐 줄\r\n셋째 줄\r\n").unwrap();
+        let second = commit_as(&repo, "이영희", "둘째 줄 수정");
+
+        // NFD path input resolves to the same file
+        let nfd_path: String = {
+            use unicode_normalization::UnicodeNormalization;
+            "메모.txt".nfd().collect()
+        };
+        let blame = get_file_blame(repo_path.clone(), nfd_path, None, None)
+            .await
+            .unwrap();
+        assert_eq!(blame.file_path, "메모.txt");
+        assert!(!blame.skipped);
+        let contents: Vec<&str> = blame.lines.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(contents, vec!["첫 줄", "바뀐 줄", "셋째 줄"]);
+        let shas: Vec<String> = blame.lines.iter().map(|l| l.commit_sha.clone()).collect();
+        assert_eq!(
+            shas,
+            vec![first.to_string(), second.to_string(), first.to_string()]
+        );
+        assert_eq!(blame.lines[1].author, "이영희");
+        assert_eq!(blame.lines[0].summary, "메모 추가");
+        assert_eq!(blame.lines[2].original_line_number, 3);
+
+        // Historical blame sees only the first commit
+        let old = get_file_blame(
+            repo_path.clone(),
+            "메모.txt".to_string(),
+            Some(first.to_string()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(old.commit_sha, Some(first.to_string()));
+        assert_eq!(old.lines[1].content, "둘째 줄");
+        assert!(old.lines.iter().all(|l| l.commit_sha == first.to_string()));
+
+        // Working tree edits are reported as uncommitted
+        fs::write(
+            root.join("메모.txt"),
+            "첫 줄\r\n바뀐 줄\r\n셋째 줄\r\n넷째 줄\r\n",
+        )
+        .unwrap();
+        let working = get_file_blame(repo_path, "메모.txt".to_string(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(working.total_lines, 4);
+        assert!(!working.lines[2].is_uncommitted);
+        assert!(working.lines[3].is_uncommitted);
+        assert!(working.lines[3].author.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_blame_line_limit_and_untracked_file() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        let body: String = (0..50).map(|i| format!("line {}\n", i)).collect();
+        fs::write(root.join("long.txt"), &body).unwrap();
+        commit_as(&repo, "Test User", "Add long file");
+
+        let skipped = get_file_blame(repo_path.clone(), "long.txt".to_string(), None, Some(10))
+            .await
+            .unwrap();
+        assert!(skipped.skipped);
+        assert_eq!(skipped.total_lines, 50);
+        assert!(skipped.lines.is_empty());
+
+        fs::write(root.join("new.txt"), "fresh\n").unwrap();
+        let untracked = get_file_blame(repo_path, "new.txt".to_string(), None, None)
+            .await
+            .unwrap();
+        assert_eq!(untracked.lines.len(), 1);
+        assert!(untracked.lines[0].is_uncommitted);
+    }
+
+    #[tokio::test]
+    async fn test_blame_paths_follow_renames_like_history() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        let body: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+
+        fs::write(root.join("원본.txt"), &body).unwrap();
+        let added = commit_as(&repo, "Test User", "Add file");
+        fs::rename(root.join("원본.txt"), root.join("middle.txt")).unwrap();
+        commit_as(&repo, "Test User", "First rename");
+        fs::write(root.join("middle.txt"), format!("{}middle\n", body)).unwrap();
+        let middle_edit = commit_as(&repo, "Test User", "Edit middle");
+        fs::rename(root.join("middle.txt"), root.join("final.txt")).unwrap();
+        commit_as(&repo, "Test User", "Second rename");
+        fs::write(root.join("final.txt"), format!("{}middle\nfinal\n", body)).unwrap();
+        let final_edit = commit_as(&repo, "Test User", "Edit final");
+
+        let blame = get_file_blame(repo_path.clone(), "final.txt".to_string(), None, None)
+            .await
+            .unwrap();
+        let expected = [
+            (0, added, "원본.txt"),
+            (20, middle_edit, "middle.txt"),
+            (21, final_edit, "final.txt"),
+        ];
+        for (line, oid, path) in expected {
+            assert_eq!(blame.lines[line].commit_sha, oid.to_string());
+            assert_eq!(blame.lines[line].original_path, path);
+        }
+
+        // The names blame reports are exactly those of the file history
+        let history = get_file_history(repo_path.clone(), "final.txt".to_string(), None).unwrap();
+        let renamed_from: Vec<&str> = history
+            .iter()
+            .filter_map(|h| h.old_path.as_deref())
+            .collect();
+        assert_eq!(renamed_from, vec!["middle.txt", "원본.txt"]);
+        let chain = get_path_identity_chain(repo_path, "final.txt".to_string(), None).unwrap();
+        let mut history_paths: Vec<&str> = chain.iter().map(|c| c.path.as_str()).collect();
+        let mut blame_paths: Vec<&str> = blame
+            .lines
+            .iter()
+            .map(|l| l.original_path.as_str())
+            .collect();
+        history_paths.sort_unstable();
+        blame_paths.sort_unstable();
+        blame_paths.dedup();
+        assert_eq!(blame_paths, history_paths);
+    }
+}
@@ -1,176 +1,884 @@
-use git2::BranchType;
+use std::collections::HashMap;
 
-use super::models::BranchInfo;
-use super::utils::{ensure_utf8_config, normalize_unicode, open_repo};
+use git2::{BranchType, ConfigLevel, Oid, Repository};
 
-/// List all branches (local and remote).
+use super::diff::collect_diff_stats;
+use super::git::commit_info;
+use super::models::{
+    ArchivedBranch, BranchBackupInfo, BranchComparison, BranchDivergence, BranchInfo,
+    BranchSwitchResult, BranchSyncStatus, BranchTracking, CommitInfo, DeletedBranch,
+    GoneBranchResult, HeadState, Page, RenamedBranch,
+};
+use super::relative_time::{format_relative_time, relative_date_locale, Locale};
+use super::remote::{delete_remote_branch, prune_remote};
+use super::stash::save_stash;
+use super::tags::push_tag;
+use super::utils::{
+    collect_page, command_error, configured_upstream, ensure_utf8_config, head_label, head_state,
+    normalize_unicode, open_repo, page_window, repo_signature, resolve_revision, safe_checkout,
+};
+
+/// Allowed clock skew (seconds) when pruning candidate refs by commit time.
+/// A descendant is normally never older than its ancestor, but commits made on
+/// machines with a wrong clock can be, so keep a generous margin.
+const CLOCK_SKEW_SLACK_SECS: i64 = 24 * 60 * 60;
+/// Commits listed per side by `get_branch_divergence` unless told otherwise.
+const DEFAULT_DIVERGENCE_LIMIT: usize = 100;
+
+/// List branches: `scope` is "local", "remote" or "all" (the default).
+/// Remote HEAD pseudo-branches (`origin/HEAD`) are left out.
+/// If `with_relative_dates` is true, fills `relative_date` in the given `locale` ("ko" | "en").
+/// If `include_merge_status` is true, fills `is_merged` (one ancestry check
+/// per branch, so it is off by default).
+/// If `include_tracking` is true, fills `ahead` / `behind` against each local
+/// branch's upstream (a graph walk per branch, also off by default).
+/// If `details` is false, only `name`, `is_current`, `is_remote` and
+/// `commit_sha` are filled and no commit is read (the other flags are
+/// ignored). With 5,000 branches on distinct commits this is two to three
+/// times faster, so the sidebar can list names first and fetch details later.
+///
+/// Compatibility wrapper returning a plain list; new callers should use
+/// `list_branches_page`.
+#[tauri::command]
+pub async fn list_branches(
+    repo_path: String,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+    include_merge_status: Option<bool>,
+    include_tracking: Option<bool>,
+    scope: Option<String>,
+    details: Option<bool>,
+) -> Result<Vec<BranchInfo>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let repo = open_repo(&repo_path)?;
+    let scope = branch_scope(scope.as_deref())?;
+    if !details.unwrap_or(true) {
+        return Ok(
+            find_branches(&repo, scope, None, BranchSort::CurrentFirst, false)?
+                .into_iter()
+                .map(|entry| {
+                    branch_summary(entry.name, entry.is_current, entry.is_remote, entry.tip)
+                })
+                .collect(),
+        );
+    }
+    collect_branches(
+        &repo,
+        scope,
+        relative_locale,
+        include_merge_status.unwrap_or(false),
+        include_tracking.unwrap_or(false),
+    )
+}
+
+/// One page of `list_branches`, for repositories with thousands of branches.
+///
+/// `query` keeps branches whose name contains it (case-insensitive, NFC);
+/// `total` then counts the matches. `sort` is "current_first" (the default:
+/// current branch, then local, then remote, most recent commit first within
+/// each), "name" or "recent"; it applies before paging. The per-branch
+/// lookups (upstream, tracking, merge status) only run for the returned page.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn list_branches_page(
+    repo_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+    include_merge_status: Option<bool>,
+    include_tracking: Option<bool>,
+    scope: Option<String>,
+    query: Option<String>,
+    sort: Option<String>,
+) -> Result<Page<BranchInfo>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let (offset, limit) = page_window(cursor.as_deref(), limit)?;
+    let repo = open_repo(&repo_path)?;
+    let entries = find_branches(
+        &repo,
+        branch_scope(scope.as_deref())?,
+        query.as_deref(),
+        branch_sort(sort.as_deref())?,
+        true,
+    )?;
+    let total = entries.len() as u64;
+    let page = collect_page(
+        entries.into_iter().map(Ok::<_, String>),
+        offset,
+        limit,
+        Some(total),
+    )?;
+
+    let details = BranchDetails::new(
+        &repo,
+        relative_locale,
+        include_merge_status.unwrap_or(false),
+        include_tracking.unwrap_or(false),
+    );
+    Ok(Page {
+        items: page
+            .items
+            .into_iter()
+            .map(|entry| branch_info(&repo, entry, &details))
+            .collect(),
+        total: page.total,
+        has_more: page.has_more,
+        next_cursor: page.next_cursor,
+    })
+}
+
+/// Delete every local branch merged into HEAD, except the current branch
+/// and branches checked out in another worktree. With `dry_run`, only
+/// returns what would be deleted.
+#[tauri::command]
+pub async fn delete_merged_branches(
+    repo_path: String,
+    dry_run: bool,
+) -> Result<Vec<DeletedBranch>, String> {
+    let repo = open_repo(&repo_path)?;
+    let merged: Vec<DeletedBranch> =
+        collect_branches(&repo, Some(BranchType::Local), None, true, false)?
+            .into_iter()
+            .filter(|b| b.is_merged && !b.is_current && b.checked_out_in.is_none())
+            .map(|b| DeletedBranch {
+                name: b.name,
+                tip_sha: b.commit_sha,
+            })
+            .collect();
+    if dry_run {
+        return Ok(merged);
+    }
+
+    for deleted in &merged {
+        repo.find_branch(&deleted.name, BranchType::Local)
+            .and_then(|mut branch| branch.delete())
+            .map_err(|e| format!("브랜치 '{}' 삭제 실패: {}", deleted.name, e))?;
+    }
+    Ok(merged)
+}
+
+/// Local branches whose configured upstream no longer exists, e.g. after the
+/// branch was deleted on the remote and pruned here. With `prune_first`, every
+/// remote is fetched with prune beforehand so the answer is up to date.
+#[tauri::command]
+pub async fn list_gone_branches(
+    repo_path: String,
+    prune_first: Option<bool>,
+) -> Result<Vec<BranchInfo>, String> {
+    if prune_first.unwrap_or(false) {
+        let remotes = open_repo(&repo_path)?
+            .remotes()
+            .map_err(|e| format!("원격 목록 조회 실패: {}", e))?;
+        for remote in remotes.iter().flatten() {
+            prune_remote(repo_path.clone(), remote.to_string(), None).await?;
+        }
+    }
+    Ok(collect_branches(
+        &open_repo(&repo_path)?,
+        Some(BranchType::Local),
+        None,
+        false,
+        false,
+    )?
+    .into_iter()
+    .filter(|b| b.upstream_gone)
+    .collect())
+}
+
+/// Delete the branches `list_gone_branches` reports, one `delete_branch` each,
+/// and return a result per branch instead of stopping at the first failure.
+/// The current branch is never deleted. Without `force`, branches with
+/// commits not merged into HEAD fail with the `branch_not_merged` error.
+/// With `dry_run`, nothing is deleted. Each result reports, from before the
+/// delete, which other refs still contain the branch tip.
 #[tauri::command]
-pub async fn list_branches(repo_path: String) -> Result<Vec<BranchInfo>, String> {
+pub async fn prune_gone_branches(
+    repo_path: String,
+    dry_run: bool,
+    force: bool,
+) -> Result<Vec<GoneBranchResult>, String> {
     let repo = open_repo(&repo_path)?;
-    let mut branches = Vec::new();
+    let mut results = Vec::new();
+    for branch in list_gone_branches(repo_path.clone(), None).await? {
+        let tip = Oid::from_str(&branch.commit_sha)
+            .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))?;
+        let local_ref = format!("refs/heads/{}", branch.name);
+        let containing_refs = find_containing_refs(&repo, tip, &local_ref)?;
+        let outcome = if branch.is_current {
+            Err("현재 브랜치는 삭제할 수 없습니다".to_string())
+        } else if dry_run {
+            Ok(false)
+        } else {
+            delete_branch(repo_path.clone(), branch.name.clone(), Some(force))
+                .await
+                .map(|_| true)
+        };
+        results.push(GoneBranchResult {
+            name: branch.name,
+            tip_sha: branch.commit_sha,
+            upstream: branch.upstream,
+            deleted: outcome == Ok(true),
+            error: outcome.err(),
+            backed_up: !containing_refs.is_empty(),
+            containing_refs,
+        });
+    }
+    Ok(results)
+}
+
+/// Branch type to list for a `scope` of "local", "remote" or "all" (None).
+fn branch_scope(scope: Option<&str>) -> Result<Option<BranchType>, String> {
+    match scope.unwrap_or("all") {
+        "local" => Ok(Some(BranchType::Local)),
+        "remote" => Ok(Some(BranchType::Remote)),
+        "all" => Ok(None),
+        other => Err(format!("알 수 없는 브랜치 범위: {}", other)),
+    }
+}
+
+/// Order of listed branches; see `list_branches_page`.
+#[derive(Debug, Clone, Copy)]
+enum BranchSort {
+    CurrentFirst,
+    Name,
+    Recent,
+}
+
+fn branch_sort(sort: Option<&str>) -> Result<BranchSort, String> {
+    match sort.unwrap_or("current_first") {
+        "current_first" => Ok(BranchSort::CurrentFirst),
+        "name" => Ok(BranchSort::Name),
+        "recent" => Ok(BranchSort::Recent),
+        other => Err(format!("알 수 없는 브랜치 정렬: {}", other)),
+    }
+}
+
+/// A branch found by `find_branches`, before `branch_info` fills the rest.
+struct BranchEntry<'r> {
+    branch: git2::Branch<'r>,
+    name: String,
+    is_remote: bool,
+    is_current: bool,
+    tip: Oid,
+    /// Tip commit, when `find_branches` was asked to read commits
+    commit: Option<git2::Commit<'r>>,
+}
+
+/// What `branch_info` needs besides the branch, computed once per listing.
+struct BranchDetails {
+    now: i64,
+    relative_locale: Option<Locale>,
+    /// HEAD to check merge status against, when requested
+    merge_target: Option<Oid>,
+    include_tracking: bool,
+    checkouts: HashMap<String, String>,
+    /// Snapshot for branch descriptions, read once for the whole listing
+    config: Option<git2::Config>,
+}
+
+impl BranchDetails {
+    fn new(
+        repo: &Repository,
+        relative_locale: Option<Locale>,
+        include_merge_status: bool,
+        include_tracking: bool,
+    ) -> Self {
+        BranchDetails {
+            now: chrono::Utc::now().timestamp(),
+            relative_locale,
+            merge_target: if include_merge_status {
+                repo.head().ok().and_then(|head| head.target())
+            } else {
+                None
+            },
+            include_tracking,
+            checkouts: other_worktree_checkouts(repo),
+            config: repo.config().and_then(|mut config| config.snapshot()).ok(),
+        }
+    }
+}
+
+fn collect_branches(
+    repo: &Repository,
+    scope: Option<BranchType>,
+    relative_locale: Option<Locale>,
+    include_merge_status: bool,
+    include_tracking: bool,
+) -> Result<Vec<BranchInfo>, String> {
+    let details = BranchDetails::new(
+        repo,
+        relative_locale,
+        include_merge_status,
+        include_tracking,
+    );
+    Ok(
+        find_branches(repo, scope, None, BranchSort::CurrentFirst, true)?
+            .into_iter()
+            .map(|entry| branch_info(repo, entry, &details))
+            .collect(),
+    )
+}
+
+/// Branches of `scope` (None: all) whose name contains `query`, in `sort`
+/// order. Only the name is looked at before filtering, so a narrow query
+/// stays cheap in a repository with thousands of branches. Without
+/// `with_commits` no commit is read, and commit time plays no part in `sort`.
+fn find_branches<'r>(
+    repo: &'r Repository,
+    scope: Option<BranchType>,
+    query: Option<&str>,
+    sort: BranchSort,
+    with_commits: bool,
+) -> Result<Vec<BranchEntry<'r>>, String> {
+    let query = query
+        .map(|query| normalize_unicode(query.trim()).to_lowercase())
+        .filter(|query| !query.is_empty());
+    let mut entries = Vec::new();
 
-    // Iterate all branches (local + remote)
     let branch_iter = repo
-        .branches(None)
+        .branches(scope)
         .map_err(|e| format!("브랜치 목록 조회 실패: {}", e))?;
 
     for branch_result in branch_iter {
         let (branch, branch_type) =
             branch_result.map_err(|e| format!("브랜치 읽기 실패: {}", e))?;
+        // `origin/HEAD` only names the remote's default branch
+        if branch.get().kind() == Some(git2::ReferenceType::Symbolic) {
+            continue;
+        }
 
-        let name = branch
-            .name()
-            .map_err(|e| format!("브랜치 이름 읽기 실패: {}", e))?
-            .unwrap_or("unknown")
-            .to_string();
+        let name = normalize_unicode(
+            branch
+                .name()
+                .map_err(|e| format!("브랜치 이름 읽기 실패: {}", e))?
+                .unwrap_or("unknown"),
+        );
+        if let Some(query) = &query {
+            if !name.to_lowercase().contains(query.as_str()) {
+                continue;
+            }
+        }
 
-        let normalized_name = normalize_unicode(&name);
+        let (tip, commit) = if with_commits {
+            match branch.get().peel_to_commit() {
+                Ok(c) => (c.id(), Some(c)),
+                Err(_) => continue, // skip branches that can't resolve to a commit
+            }
+        } else {
+            match branch.get().target() {
+                Some(tip) => (tip, None),
+                None => continue,
+            }
+        };
         let is_remote = branch_type == BranchType::Remote;
         let is_current = !is_remote && branch.is_head();
-
-        let commit = match branch.get().peel_to_commit() {
-            Ok(c) => c,
-            Err(_) => continue, // skip branches that can't resolve to a commit
-        };
-
-        branches.push(BranchInfo {
-            name: normalized_name,
-            is_current,
+        entries.push(BranchEntry {
+            branch,
+            name,
             is_remote,
-            commit_sha: commit.id().to_string(),
-            commit_message: commit
-                .message()
-                .unwrap_or("")
-                .lines()
-                .next()
-                .unwrap_or("")
-                .to_string(),
-            author: commit.author().name().unwrap_or("Unknown").to_string(),
-            timestamp: commit.time().seconds(),
+            is_current,
+            tip,
+            commit,
         });
     }
 
-    // Current branch first, then local, then remote
-    branches.sort_by(|a, b| {
-        b.is_current
+    let time = |entry: &BranchEntry| entry.commit.as_ref().map_or(0, |c| c.time().seconds());
+    entries.sort_by(|a, b| match sort {
+        // Current branch first, then local, then remote; most recent first within each
+        BranchSort::CurrentFirst => b
+            .is_current
             .cmp(&a.is_current)
             .then(a.is_remote.cmp(&b.is_remote))
+            .then(time(b).cmp(&time(a)))
+            .then_with(|| a.name.cmp(&b.name)),
+        BranchSort::Name => a.name.cmp(&b.name),
+        BranchSort::Recent => time(b).cmp(&time(a)).then_with(|| a.name.cmp(&b.name)),
     });
-    Ok(branches)
+    Ok(entries)
 }
 
-/// Get current branch name.
-#[tauri::command]
-pub async fn get_current_branch(repo_path: String) -> Result<String, String> {
-    let repo = open_repo(&repo_path)?;
-    let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+/// A branch with only its name, kind and tip sha, for `details: false`.
+fn branch_summary(name: String, is_current: bool, is_remote: bool, tip: Oid) -> BranchInfo {
+    BranchInfo {
+        name,
+        is_current,
+        is_remote,
+        remote_name: None,
+        commit_sha: tip.to_string(),
+        commit_message: String::new(),
+        author: String::new(),
+        timestamp: 0,
+        relative_date: None,
+        upstream: None,
+        ahead: 0,
+        behind: 0,
+        upstream_gone: false,
+        checked_out_in: None,
+        is_merged: false,
+        description: None,
+    }
+}
+
+/// Fill in a found branch: upstream, tracking counts, merge status, ...
+fn branch_info(repo: &Repository, entry: BranchEntry, details: &BranchDetails) -> BranchInfo {
+    let BranchEntry {
+        branch,
+        name,
+        is_remote,
+        is_current,
+        tip,
+        commit,
+    } = entry;
+    let Some(commit) = commit else {
+        return branch_summary(name, is_current, is_remote, tip);
+    };
+    let refname = branch.get().name().unwrap_or("");
+
+    let local_name = if is_remote {
+        None
+    } else {
+        refname.strip_prefix("refs/heads/")
+    };
+    let upstream_ref = local_name.and_then(|raw_name| configured_upstream(repo, raw_name));
+    let description = local_name
+        .zip(details.config.as_ref())
+        .and_then(|(raw_name, config)| {
+            config
+                .get_string(&format!("branch.{}.description", raw_name))
+                .ok()
+                .map(|description| normalize_unicode(&description))
+        });
+    let upstream_oid = upstream_ref
+        .as_deref()
+        .and_then(|upstream| repo.refname_to_id(upstream).ok());
+    // Upstream configured but its remote-tracking ref is gone (deleted remotely + pruned)
+    let upstream_gone = upstream_ref.is_some() && upstream_oid.is_none();
+    let (ahead, behind) = match upstream_oid {
+        Some(upstream_oid) if details.include_tracking => repo
+            .graph_ahead_behind(commit.id(), upstream_oid)
+            .map(|(ahead, behind)| (ahead as u32, behind as u32))
+            .unwrap_or((0, 0)),
+        _ => (0, 0),
+    };
+
+    let remote_name = if is_remote {
+        repo.branch_remote_name(refname)
+            .ok()
+            .and_then(|buf| buf.as_str().map(normalize_unicode))
+            // A ref left behind by a removed remote: its first component
+            .or_else(|| name.split('/').next().map(str::to_string))
+    } else {
+        None
+    };
+    let checked_out_in = if is_remote {
+        None
+    } else {
+        details.checkouts.get(refname).cloned()
+    };
 
-    if !head.is_branch() {
-        return Err("HEAD가 분리되어 있습니다 (detached HEAD)".to_string());
+    // Merged: the tip is HEAD or one of its ancestors
+    let is_merged = details.merge_target.is_some_and(|head_id| {
+        commit.id() == head_id
+            || repo
+                .graph_descendant_of(head_id, commit.id())
+                .unwrap_or(false)
+    });
+    let author = commit.author().name().unwrap_or("Unknown").to_string();
+
+    BranchInfo {
+        name,
+        is_current,
+        is_remote,
+        remote_name,
+        commit_sha: commit.id().to_string(),
+        commit_message: commit
+            .message()
+            .unwrap_or("")
+            .lines()
+            .next()
+            .unwrap_or("")
+            .to_string(),
+        author,
+        timestamp: commit.time().seconds(),
+        relative_date: details
+            .relative_locale
+            .map(|l| format_relative_time(commit.time().seconds(), details.now, l)),
+        upstream: upstream_ref.map(|upstream| normalize_unicode(upstream_short_name(&upstream))),
+        ahead,
+        behind,
+        upstream_gone,
+        checked_out_in,
+        is_merged,
+        description,
     }
+}
 
-    let branch_name = head
-        .shorthand()
-        .ok_or("브랜치 이름을 가져올 수 없습니다")?
-        .to_string();
+/// Get current branch name, or "HEAD detached at <short sha>" when HEAD is
+/// detached.
+///
+/// Compatibility wrapper returning a display name; new callers should use
+/// `get_head_state`.
+#[tauri::command]
+pub async fn get_current_branch(repo_path: String) -> Result<String, String> {
+    let repo = open_repo(&repo_path)?;
+    let state = head_state(&repo)?;
+    Ok(head_label(&repo, &state))
+}
 
-    Ok(normalize_unicode(&branch_name))
+/// Where HEAD points: branch name (None when detached) and commit. An unborn
+/// HEAD has its branch name and an empty `sha`.
+#[tauri::command]
+pub async fn get_head_state(repo_path: String) -> Result<HeadState, String> {
+    let repo = open_repo(&repo_path)?;
+    head_state(&repo)
 }
 
-/// Create a new branch from HEAD.
+/// Create a new branch from HEAD, or from `start_point`: a SHA, tag, branch
+/// or remote-tracking branch such as "origin/feature" (annotated tags are
+/// peeled to their commit). A branch started from a remote-tracking branch
+/// tracks it.
+///
+/// Fails with `branch_exists` when the name is taken and with
+/// `start_point_not_found` when `start_point` does not resolve.
 #[tauri::command]
-pub async fn create_branch(repo_path: String, branch_name: String) -> Result<String, String> {
+pub async fn create_branch(
+    repo_path: String,
+    branch_name: String,
+    start_point: Option<String>,
+) -> Result<String, String> {
     let normalized_name = normalize_unicode(&branch_name);
     let repo = open_repo(&repo_path)?;
+    new_branch(&repo, &normalized_name, start_point)?;
+    Ok(format!("브랜치 '{}' 생성 완료", normalized_name))
+}
 
-    let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
-    let commit = head
-        .peel_to_commit()
-        .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?;
+/// Create `branch_name` at `start_point` and check it out, in one step.
+/// Local changes are carried over unless the switch would overwrite them;
+/// then it fails with `checkout_conflicts` listing the files and the new
+/// branch is removed again.
+#[tauri::command]
+pub async fn create_and_switch_branch(
+    repo_path: String,
+    branch_name: String,
+    start_point: Option<String>,
+) -> Result<HeadState, String> {
+    let repo = open_repo(&repo_path)?;
+    // Best-effort: ensure Korean file names work correctly after checkout
+    let _ = ensure_utf8_config(&repo);
+
+    let mut branch = new_branch(&repo, &normalize_unicode(&branch_name), start_point)?;
+    if let Err(e) = checkout_branch(&repo, &branch) {
+        // Roll back: the branch must not outlive a failed switch
+        let _ = branch.delete();
+        return Err(e);
+    }
+    head_state(&repo)
+}
+
+/// `create_branch` without the message: the new branch, tracking
+/// `start_point` when it is a remote-tracking branch.
+fn new_branch<'r>(
+    repo: &'r Repository,
+    normalized_name: &str,
+    start_point: Option<String>,
+) -> Result<git2::Branch<'r>, String> {
+    if repo.find_branch(normalized_name, BranchType::Local).is_ok() {
+        return Err(command_error(
+            "branch_exists",
+            format!("브랜치 '{}'가 이미 존재합니다", normalized_name),
+            serde_json::json!({ "branch": normalized_name }),
+        ));
+    }
 
-    repo.branch(&normalized_name, &commit, false)
+    let (commit, upstream) = match start_point {
+        None => {
+            let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+            let commit = head
+                .peel_to_commit()
+                .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?;
+            (commit, None)
+        }
+        Some(start_point) => {
+            let start_point = normalize_unicode(start_point.trim());
+            let commit = repo
+                .revparse_single(&start_point)
+                .and_then(|object| object.peel_to_commit())
+                .map_err(|_| {
+                    command_error(
+                        "start_point_not_found",
+                        format!("시작 지점 '{}'을(를) 찾을 수 없습니다", start_point),
+                        serde_json::json!({ "start_point": start_point }),
+                    )
+                })?;
+            (commit, remote_tracking_name(repo, &start_point))
+        }
+    };
+
+    let mut branch = repo
+        .branch(normalized_name, &commit, false)
         .map_err(|e| format!("브랜치 생성 실패: {}", e))?;
+    if let Some(upstream) = upstream {
+        branch
+            .set_upstream(Some(&upstream))
+            .map_err(|e| format!("업스트림 설정 실패: {}", e))?;
+    }
+    Ok(branch)
+}
 
-    Ok(format!("브랜치 '{}' 생성 완료", normalized_name))
+/// Check out `branch` without overwriting local changes (`safe_checkout`),
+/// then point HEAD at it. The previous tree is restored if HEAD cannot move.
+fn checkout_branch(repo: &Repository, branch: &git2::Branch) -> Result<(), String> {
+    let reference = branch.get();
+    let reference_name = reference.name().ok_or("유효하지 않은 브랜치 참조입니다")?;
+    let commit = reference
+        .peel_to_commit()
+        .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))?;
+    let previous_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    safe_checkout(repo, commit.as_object())?;
+    repo.set_head(reference_name).map_err(|e| {
+        restore_worktree(repo, previous_tree.as_ref(), commit.as_object());
+        format!("HEAD 변경 실패: {}", e)
+    })
+}
+
+/// "origin/feature" when `revspec` names a remote-tracking branch (and no
+/// local branch, which `revparse_single` would prefer).
+fn remote_tracking_name(repo: &Repository, revspec: &str) -> Option<String> {
+    let name = revspec.strip_prefix("refs/remotes/").unwrap_or(revspec);
+    let shadowed = revspec == name && repo.find_branch(name, BranchType::Local).is_ok();
+    if shadowed || repo.find_branch(name, BranchType::Remote).is_err() {
+        return None;
+    }
+    Some(name.to_string())
 }
 
 /// Switch to a different branch.
-/// Returns an error if there are uncommitted changes to prevent data loss.
-/// If `force` is true, checkout proceeds even with uncommitted changes.
+/// Local changes are carried over, like `git switch`; when the switch would
+/// overwrite them (or an untracked file is in the way) it fails with
+/// `checkout_conflicts` listing the files. It also fails if the branch is
+/// checked out in another worktree.
+/// With `stash_changes`, local changes are stashed first (reported as
+/// `stash_index`) and restored if the switch fails anyway.
+/// If `force` is true, checkout proceeds in all of these cases and local
+/// changes are discarded (like
+/// `git switch --ignore-other-worktrees --discard-changes`).
 #[tauri::command]
 pub async fn switch_branch(
     repo_path: String,
     branch_name: String,
     force: Option<bool>,
-) -> Result<String, String> {
+    stash_changes: Option<bool>,
+) -> Result<BranchSwitchResult, String> {
     let normalized_name = normalize_unicode(&branch_name);
-    let repo = open_repo(&repo_path)?;
+    let mut repo = open_repo(&repo_path)?;
     // Best-effort: ensure Korean file names work correctly after checkout
     let _ = ensure_utf8_config(&repo);
     let force = force.unwrap_or(false);
 
-    // 미저장 변경사항 감지 (force 모드가 아닐 때만)
     if !force {
-        let statuses = repo
-            .statuses(None)
-            .map_err(|e| format!("상태 확인 실패: {}", e))?;
-        let has_changes = statuses.iter().any(|s| {
-            s.status().intersects(
-                git2::Status::INDEX_NEW
-                    | git2::Status::INDEX_MODIFIED
-                    | git2::Status::INDEX_DELETED
-                    | git2::Status::WT_MODIFIED
-                    | git2::Status::WT_DELETED,
-            )
-        });
-        if has_changes {
-            return Err(
-                "미저장 변경사항이 있습니다. 먼저 커밋하거나 스태시한 후 브랜치를 전환하세요."
-                    .to_string(),
-            );
-        }
+        ensure_not_checked_out_elsewhere(&repo, &normalized_name)?;
     }
-
-    let branch = repo
+    let reference_name = repo
         .find_branch(&normalized_name, BranchType::Local)
-        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))?;
-
-    let reference_name = branch
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))?
         .get()
         .name()
-        .ok_or("유효하지 않은 브랜치 참조입니다")?;
-    repo.set_head(reference_name)
-        .map_err(|e| format!("HEAD 변경 실패: {}", e))?;
+        .ok_or("유효하지 않은 브랜치 참조입니다")?
+        .to_string();
 
-    let mut checkout_builder = git2::build::CheckoutBuilder::new();
     if force {
+        // libgit2's set_head refuses branches checked out in other worktrees
+        repo.reference_symbolic("HEAD", &reference_name, true, "checkout: forced switch")
+            .map_err(|e| format!("HEAD 변경 실패: {}", e))?;
+        let mut checkout_builder = git2::build::CheckoutBuilder::new();
+        checkout_builder.force();
+        repo.checkout_head(Some(&mut checkout_builder))
+            .map_err(|e| format!("체크아웃 실패: {}", e))?;
+        return Ok(BranchSwitchResult {
+            head: head_state(&repo)?,
+            stash_index: None,
+        });
+    }
+
+    let stashed = if stash_changes.unwrap_or(false) {
+        let from = head_label(&repo, &head_state(&repo)?);
+        let message = format!(
+            "'{}' 브랜치로 전환 전 자동 스태시 (from {})",
+            normalized_name, from
+        );
+        save_stash(&mut repo, &message, false)?
+    } else {
+        None
+    };
+
+    let switched = repo
+        .find_branch(&normalized_name, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))
+        .and_then(|branch| checkout_branch(&repo, &branch));
+    if let Err(e) = switched {
+        if stashed.is_some() {
+            let mut options = git2::StashApplyOptions::new();
+            options.reinstantiate_index();
+            let _ = repo.stash_pop(0, Some(&mut options));
+        }
+        return Err(e);
+    }
+
+    Ok(BranchSwitchResult {
+        head: head_state(&repo)?,
+        stash_index: stashed.map(|_| 0),
+    })
+}
+
+/// Check out any revision (branch, tag, SHA, `HEAD~n`).
+/// With `create_branch`, a new branch is created at that commit and checked out;
+/// without it HEAD is detached. Local changes are never overwritten: a blocked
+/// checkout fails with `checkout_conflicts` and removes the branch it created.
+#[tauri::command]
+pub async fn switch_to_revision(
+    repo_path: String,
+    revspec: String,
+    create_branch: Option<String>,
+) -> Result<HeadState, String> {
+    let repo = open_repo(&repo_path)?;
+    // Best-effort: ensure Korean file names work correctly after checkout
+    let _ = ensure_utf8_config(&repo);
+
+    let commit = resolve_revision(&repo, &normalize_unicode(&revspec))?;
+    let new_branch = match create_branch {
+        Some(name) => {
+            let name = normalize_unicode(&name);
+            let branch = repo
+                .branch(&name, &commit, false)
+                .map_err(|e| format!("브랜치 생성 실패: {}", e))?;
+            Some(branch)
+        }
+        None => None,
+    };
+
+    let previous_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let switched = safe_checkout(&repo, commit.as_object()).and_then(|_| {
+        let moved = match &new_branch {
+            Some(branch) => {
+                let reference_name = branch
+                    .get()
+                    .name()
+                    .ok_or("유효하지 않은 브랜치 참조입니다")?;
+                repo.set_head(reference_name)
+                    .map_err(|e| format!("HEAD 변경 실패: {}", e))
+            }
+            None => repo
+                .set_head_detached(commit.id())
+                .map_err(|e| format!("HEAD 변경 실패: {}", e)),
+        };
+        if moved.is_err() {
+            restore_worktree(&repo, previous_tree.as_ref(), commit.as_object());
+        }
+        moved
+    });
+
+    if let Err(e) = switched {
+        // Roll back: the branch must not outlive a failed switch
+        if let Some(mut branch) = new_branch {
+            let _ = branch.delete();
+        }
+        return Err(e);
+    }
+
+    head_state(&repo)
+}
+
+/// Detach HEAD at `commit_sha` (any revision works) to inspect an old state.
+/// Like `switch_branch`, local changes the checkout would overwrite make it
+/// fail with `checkout_conflicts`; with `force` they are discarded instead.
+#[tauri::command]
+pub async fn checkout_commit(
+    repo_path: String,
+    commit_sha: String,
+    force: Option<bool>,
+) -> Result<HeadState, String> {
+    let repo = open_repo(&repo_path)?;
+    // Best-effort: ensure Korean file names work correctly after checkout
+    let _ = ensure_utf8_config(&repo);
+    let commit = resolve_revision(&repo, &normalize_unicode(&commit_sha))?;
+    let previous_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+
+    if force.unwrap_or(false) {
+        let mut checkout_builder = git2::build::CheckoutBuilder::new();
         checkout_builder.force();
+        repo.checkout_tree(commit.as_object(), Some(&mut checkout_builder))
+            .map_err(|e| format!("체크아웃 실패: {}", e))?;
+    } else {
+        safe_checkout(&repo, commit.as_object())?;
+    }
+    if let Err(e) = repo.set_head_detached(commit.id()) {
+        restore_worktree(&repo, previous_tree.as_ref(), commit.as_object());
+        return Err(format!("HEAD 변경 실패: {}", e));
+    }
+    head_state(&repo)
+}
+
+/// After HEAD failed to move, put back the files `checked_out` changed so
+/// the working tree matches the HEAD still in place. Only paths differing
+/// between the two trees are touched; the safe checkout that just ran
+/// proved they carried no local edits. Best-effort.
+fn restore_worktree(
+    repo: &Repository,
+    previous_tree: Option<&git2::Tree>,
+    checked_out: &git2::Object,
+) {
+    let (Some(previous), Ok(current)) = (previous_tree, checked_out.peel_to_tree()) else {
+        return;
+    };
+    let Ok(diff) = repo.diff_tree_to_tree(Some(previous), Some(&current), None) else {
+        return;
+    };
+    let mut builder = git2::build::CheckoutBuilder::new();
+    builder.force().remove_untracked(true);
+    let mut changed = false;
+    for delta in diff.deltas() {
+        for path in [delta.old_file().path(), delta.new_file().path()].into_iter().flatten() {
+            builder.path(path);
+            changed = true;
+        }
     }
-    repo.checkout_head(Some(&mut checkout_builder))
-        .map_err(|e| format!("체크아웃 실패: {}", e))?;
+    if changed {
+        let _ = repo.checkout_tree(previous.as_object(), Some(&mut builder));
+    }
+}
 
-    Ok(format!("'{}' 브랜치로 전환 완료", normalized_name))
+/// Re-attach HEAD to `branch` after `checkout_commit`; a safe
+/// `switch_branch` without stashing.
+#[tauri::command]
+pub async fn return_to_branch(repo_path: String, branch: String) -> Result<HeadState, String> {
+    switch_branch(repo_path, branch, None, None)
+        .await
+        .map(|switched| switched.head)
 }
 
-/// Delete a branch.
-/// By default, refuses to delete unmerged branches to prevent data loss.
+/// Delete a branch and return its tip, so the delete can be undone by
+/// recreating the branch there.
+/// By default, refuses to delete unmerged branches to prevent data loss: the
+/// `branch_not_merged` error counts the commits no other ref keeps.
 /// If `force` is true, deletes even if not merged into the current branch.
+/// Branches checked out in another worktree are never deleted.
 #[tauri::command]
 pub async fn delete_branch(
     repo_path: String,
     branch_name: String,
     force: Option<bool>,
-) -> Result<String, String> {
+) -> Result<DeletedBranch, String> {
     let normalized_name = normalize_unicode(&branch_name);
     let repo = open_repo(&repo_path)?;
     let force = force.unwrap_or(false);
 
     let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
-    let current_branch = head.shorthand().unwrap_or("");
-
-    if current_branch == normalized_name {
+    // A detached HEAD has no current branch (`shorthand` would be "HEAD")
+    if head.is_branch() && head.shorthand() == Some(normalized_name.as_str()) {
         return Err("현재 브랜치는 삭제할 수 없습니다".to_string());
     }
+    ensure_not_checked_out_elsewhere(&repo, &normalized_name)?;
 
     // 병합 여부 확인 (force 모드가 아닐 때만)
     if !force {
@@ -191,9 +899,22 @@ pub async fn delete_branch(
             .map_err(|e| format!("Merge-base 확인 실패: {}", e))?;
 
         if merge_base != target_commit.id() {
-            return Err(format!(
-                "브랜치 '{}'는 현재 브랜치에 병합되지 않았습니다. 강제 삭제하려면 force 옵션을 사용하세요.",
-                normalized_name
+            let local_ref = format!("refs/heads/{}", normalized_name);
+            let containing_refs = find_containing_refs(&repo, target_commit.id(), &local_ref)?;
+            let unmerged_commits = count_commits_only_on(&repo, target_commit.id(), &local_ref)?;
+            return Err(command_error(
+                "branch_not_merged",
+                format!(
+                    "브랜치 '{}'는 현재 브랜치에 병합되지 않았습니다. 강제 삭제하려면 force 옵션을 사용하세요.",
+                    normalized_name
+                ),
+                serde_json::json!({
+                    "branch": normalized_name,
+                    "tip_sha": target_commit.id().to_string(),
+                    "unmerged_commits": unmerged_commits,
+                    "backed_up": !containing_refs.is_empty(),
+                    "containing_refs": containing_refs,
+                }),
             ));
         }
     }
@@ -202,137 +923,2518 @@ pub async fn delete_branch(
         .find_branch(&normalized_name, BranchType::Local)
         .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))?;
 
+    let tip_sha = branch
+        .get()
+        .target()
+        .map(|id| id.to_string())
+        .unwrap_or_default();
     branch
         .delete()
         .map_err(|e| format!("브랜치 삭제 실패: {}", e))?;
 
-    Ok(format!("브랜치 '{}' 삭제 완료", normalized_name))
+    Ok(DeletedBranch {
+        name: normalized_name,
+        tip_sha,
+    })
 }
 
-/// Get the number of commits a branch is ahead/behind relative to a base branch.
-/// Returns (ahead, behind) counts.
+/// Keep a branch as the annotated tag `archive/<branch>` and delete it, so
+/// its history stays reachable without cluttering the branch list.
+///
+/// With `delete_remote` and an upstream on a remote, the tag is then pushed
+/// there and the upstream branch deleted. Those steps do not fail the
+/// command: each is reported in the result, and the remote branch is only
+/// deleted once the tag is safely on the remote. An existing tag fails with
+/// `tag_exists` before anything is changed.
 #[tauri::command]
-pub async fn get_branch_divergence(
+pub async fn archive_branch(
     repo_path: String,
-    branch: String,
-    base: String,
-) -> Result<(usize, usize), String> {
-    let normalized_branch = normalize_unicode(&branch);
-    let normalized_base = normalize_unicode(&base);
+    branch_name: String,
+    delete_remote: bool,
+) -> Result<ArchivedBranch, String> {
+    let normalized_name = normalize_unicode(&branch_name);
     let repo = open_repo(&repo_path)?;
 
-    let branch_ref = repo
-        .find_branch(&normalized_branch, BranchType::Local)
-        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_branch, e))?;
-    let base_ref = repo
-        .find_branch(&normalized_base, BranchType::Local)
-        .map_err(|e| format!("기준 브랜치 '{}' 찾기 실패: {}", normalized_base, e))?;
+    let mut branch = repo
+        .find_branch(&normalized_name, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))?;
+    if branch.is_head() {
+        return Err("현재 브랜치는 보관할 수 없습니다".to_string());
+    }
+    ensure_not_checked_out_elsewhere(&repo, &normalized_name)?;
+    let tag_name = format!("archive/{}", normalized_name);
+    if repo
+        .refname_to_id(&format!("refs/tags/{}", tag_name))
+        .is_ok()
+    {
+        return Err(command_error(
+            "tag_exists",
+            format!("태그 '{}'가 이미 존재합니다", tag_name),
+            serde_json::json!({ "tag": tag_name }),
+        ));
+    }
 
-    let branch_commit = branch_ref
+    let tip = branch
         .get()
-        .peel_to_commit()
+        .peel(git2::ObjectType::Commit)
         .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))?;
-    let base_commit = base_ref
-        .get()
-        .peel_to_commit()
-        .map_err(|e| format!("기준 브랜치 커밋 접근 실패: {}", e))?;
+    // `branch.<name>.remote` of "." is an upstream in this repository
+    let upstream = branch_settings(&local_config(&repo)?, &normalized_name)?;
+    let setting = |key: &str| {
+        upstream
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.clone())
+    };
+    let remote_branch = setting("remote").filter(|remote| remote != ".").zip(
+        setting("merge").and_then(|merge| merge.strip_prefix("refs/heads/").map(str::to_string)),
+    );
 
-    let (ahead, behind) = repo
-        .graph_ahead_behind(branch_commit.id(), base_commit.id())
-        .map_err(|e| format!("Ahead/Behind 계산 실패: {}", e))?;
+    let message = format!(
+        "Archived branch '{}' on {}\n",
+        normalized_name,
+        chrono::Local::now().format("%Y-%m-%d")
+    );
+    repo.tag(&tag_name, &tip, &repo_signature(&repo)?, &message, false)
+        .map_err(|e| format!("태그 생성 실패: {}", e))?;
+    if let Err(e) = branch.delete() {
+        let _ = repo.tag_delete(&tag_name);
+        return Err(format!("브랜치 삭제 실패: {}", e));
+    }
+
+    let mut archived = ArchivedBranch {
+        branch: normalized_name,
+        tag_name,
+        tip_sha: tip.id().to_string(),
+        remote: None,
+        tag_pushed: false,
+        tag_push_error: None,
+        remote_branch_deleted: false,
+        remote_delete_error: None,
+    };
+    let Some((remote, remote_branch)) = remote_branch.filter(|_| delete_remote) else {
+        return Ok(archived);
+    };
+    archived.remote = Some(remote.clone());
+    match push_tag(repo_path.clone(), remote.clone(), archived.tag_name.clone()) {
+        Ok(()) => archived.tag_pushed = true,
+        Err(e) => archived.tag_push_error = Some(e),
+    }
+    if !archived.tag_pushed {
+        archived.remote_delete_error =
+            Some("태그를 푸시하지 못해 원격 브랜치를 남겨 두었습니다".to_string());
+    } else if let Err(e) = delete_remote_branch(repo_path, remote, remote_branch, None).await {
+        archived.remote_delete_error = Some(e);
+    } else {
+        archived.remote_branch_deleted = true;
+    }
+    Ok(archived)
+}
+
+/// Commits reachable from `tip` but from neither HEAD nor any branch,
+/// remote-tracking branch or tag other than `exclude_ref`: what deleting
+/// `exclude_ref` would leave only in the reflog.
+fn count_commits_only_on(repo: &Repository, tip: Oid, exclude_ref: &str) -> Result<usize, String> {
+    let mut walk = repo
+        .revwalk()
+        .map_err(|e| format!("히스토리 탐색 실패: {}", e))?;
+    walk.push(tip)
+        .map_err(|e| format!("히스토리 탐색 실패: {}", e))?;
+    if let Ok(head) = repo.head().and_then(|head| head.peel_to_commit()) {
+        let _ = walk.hide(head.id());
+    }
+    let references = repo
+        .references()
+        .map_err(|e| format!("참조 목록 조회 실패: {}", e))?;
+    for reference in references.flatten() {
+        let Some(name) = reference.name() else {
+            continue;
+        };
+        let is_keeper = name.starts_with("refs/remotes/")
+            || name.starts_with("refs/tags/")
+            || name.starts_with("refs/heads/");
+        if !is_keeper || name == exclude_ref {
+            continue;
+        }
+        if let Ok(commit) = reference.peel_to_commit() {
+            let _ = walk.hide(commit.id());
+        }
+    }
+    Ok(walk.flatten().count())
+}
+
+/// Branch refs checked out in worktrees other than `repo`'s own, mapped to
+/// that worktree's working directory. Covers the main worktree when `repo`
+/// is a linked one; worktrees that can't be opened (e.g. deleted folders
+/// awaiting prune) are skipped.
+fn other_worktree_checkouts(repo: &Repository) -> HashMap<String, String> {
+    let mut checkouts = HashMap::new();
+    let own_workdir = repo.workdir().map(|p| p.to_path_buf());
+    let mut record = |other: &Repository| {
+        let workdir = match other.workdir() {
+            Some(dir) if Some(dir.to_path_buf()) != own_workdir => dir,
+            _ => return,
+        };
+        if let Ok(head) = other.head() {
+            if let (true, Some(name)) = (head.is_branch(), head.name()) {
+                let path = workdir.to_string_lossy();
+                checkouts.insert(
+                    name.to_string(),
+                    path.trim_end_matches(['/', '\\']).to_string(),
+                );
+            }
+        }
+    };
+
+    if repo.is_worktree() {
+        // A linked worktree's git dir names the shared one in `commondir`
+        let common = std::fs::read_to_string(repo.path().join("commondir"))
+            .ok()
+            .map(|dir| repo.path().join(dir.trim()));
+        if let Some(Ok(main)) = common.map(Repository::open) {
+            record(&main);
+        }
+    }
+    if let Ok(names) = repo.worktrees() {
+        for name in names.iter().flatten() {
+            let opened = repo
+                .find_worktree(name)
+                .and_then(|wt| Repository::open_from_worktree(&wt));
+            if let Ok(other) = opened {
+                record(&other);
+            }
+        }
+    }
+    checkouts
+}
 
-    Ok((ahead, behind))
+/// Fail with `branch_checked_out` if another worktree has `branch` checked out.
+fn ensure_not_checked_out_elsewhere(repo: &Repository, branch: &str) -> Result<(), String> {
+    let reference = format!("refs/heads/{}", branch);
+    match other_worktree_checkouts(repo).remove(&reference) {
+        Some(path) => Err(command_error(
+            "branch_checked_out",
+            format!(
+                "브랜치 '{}'는 다른 작업 트리({})에서 체크아웃되어 있습니다",
+                branch, path
+            ),
+            serde_json::json!({ "branch": branch, "path": path }),
+        )),
+        None => Ok(()),
+    }
 }
 
-/// Rename a branch.
+/// Check whether a branch tip is reachable from any remote-tracking ref, tag,
+/// or other local branch, i.e. whether deleting the branch loses no commits.
 #[tauri::command]
-pub async fn rename_branch(
+pub async fn is_branch_backed_up(
     repo_path: String,
-    old_name: String,
-    new_name: String,
-) -> Result<String, String> {
-    let normalized_old = normalize_unicode(&old_name);
-    let normalized_new = normalize_unicode(&new_name);
+    branch_name: String,
+) -> Result<BranchBackupInfo, String> {
+    let normalized_name = normalize_unicode(&branch_name);
     let repo = open_repo(&repo_path)?;
 
-    let mut branch = repo
-        .find_branch(&normalized_old, BranchType::Local)
-        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_old, e))?;
+    let branch = repo
+        .find_branch(&normalized_name, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_name, e))?;
+    let tip = branch
+        .get()
+        .peel_to_commit()
+        .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))?;
 
-    branch
-        .rename(&normalized_new, false)
-        .map_err(|e| format!("브랜치 이름 변경 실패: {}", e))?;
+    let local_ref = format!("refs/heads/{}", normalized_name);
+    let containing_refs = find_containing_refs(&repo, tip.id(), &local_ref)?;
 
-    Ok(format!(
-        "브랜치 '{}' → '{}' 이름 변경 완료",
-        normalized_old, normalized_new
-    ))
+    Ok(BranchBackupInfo {
+        branch: normalized_name,
+        commit_sha: tip.id().to_string(),
+        is_backed_up: !containing_refs.is_empty(),
+        containing_refs,
+    })
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use git2::{Repository, Signature};
-    use tempfile::tempdir;
-
-    fn setup_test_repo() -> (tempfile::TempDir, String) {
-        let temp_dir = tempdir().unwrap();
-        let repo_path = temp_dir.path().to_str().unwrap().to_string();
-        let repo = Repository::init(&repo_path).unwrap();
+/// Collect all refs (except `exclude_ref`) whose history contains `target`.
+///
+/// Candidates whose tip is older than the target (minus clock-skew slack)
+/// cannot be descendants and are skipped without a graph walk.
+fn find_containing_refs(
+    repo: &Repository,
+    target: Oid,
+    exclude_ref: &str,
+) -> Result<Vec<String>, String> {
+    let target_time = repo
+        .find_commit(target)
+        .map_err(|e| format!("커밋 찾기 실패: {}", e))?
+        .time()
+        .seconds();
 
-        let sig = Signature::now("Test User", "test@example.com").unwrap();
-        let tree_id = { repo.index().unwrap().write_tree().unwrap() };
-        let tree = repo.find_tree(tree_id).unwrap();
-        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
-            .unwrap();
+    let references = repo
+        .references()
+        .map_err(|e| format!("참조 목록 조회 실패: {}", e))?;
 
-        (temp_dir, repo_path)
+    let mut containing = Vec::new();
+    for reference in references.flatten() {
+        let Some(name) = reference.name() else {
+            continue;
+        };
+        let is_candidate = name.starts_with("refs/remotes/")
+            || name.starts_with("refs/tags/")
+            || name.starts_with("refs/heads/");
+        if !is_candidate || name == exclude_ref || name.ends_with("/HEAD") {
+            continue;
+        }
+        let Ok(commit) = reference.peel_to_commit() else {
+            continue;
+        };
+        if commit.time().seconds() + CLOCK_SKEW_SLACK_SECS < target_time {
+            continue;
+        }
+        let contains = commit.id() == target
+            || repo
+                .graph_descendant_of(commit.id(), target)
+                .unwrap_or(false);
+        if contains {
+            containing.push(normalize_unicode(name));
+        }
     }
 
-    #[tokio::test]
-    async fn test_create_and_list_branches() {
-        let (_temp_dir, repo_path) = setup_test_repo();
-        let result = create_branch(repo_path.clone(), "feature/test".to_string()).await;
-        assert!(result.is_ok());
-
-        let branches = list_branches(repo_path).await.unwrap();
-        assert_eq!(branches.len(), 2);
-        assert!(branches.iter().any(|b| b.name == "feature/test"));
-    }
+    containing.sort();
+    Ok(containing)
+}
+
+/// Ahead/behind counts of the current branch against its upstream.
+///
+/// A detached HEAD, a branch without upstream and an upstream whose ref is
+/// gone are told apart by `detached`, `upstream` and `upstream_gone`.
+#[tauri::command]
+pub async fn get_branch_sync_status(repo_path: String) -> Result<BranchSyncStatus, String> {
+    let repo = open_repo(&repo_path)?;
+    branch_sync_status(&repo)
+}
+
+pub(super) fn branch_sync_status(repo: &Repository) -> Result<BranchSyncStatus, String> {
+    let mut status = BranchSyncStatus::default();
+    if repo.head_detached().unwrap_or(false) {
+        status.detached = true;
+        return Ok(status);
+    }
+    // Unborn HEAD: nothing committed, nothing to compare
+    let Ok(head) = repo.head() else {
+        return Ok(status);
+    };
+    let Some(branch_name) = head.shorthand() else {
+        return Ok(status);
+    };
+    let Some(upstream_ref) = configured_upstream(repo, branch_name) else {
+        return Ok(status);
+    };
+
+    status.upstream = Some(normalize_unicode(upstream_short_name(&upstream_ref)));
+    let upstream_oid = match repo.refname_to_id(&upstream_ref) {
+        Ok(oid) => oid,
+        Err(_) => {
+            status.upstream_gone = true;
+            return Ok(status);
+        }
+    };
+    status.has_upstream = true;
+
+    let head_oid = head
+        .peel_to_commit()
+        .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?
+        .id();
+    let (ahead, behind) = repo
+        .graph_ahead_behind(head_oid, upstream_oid)
+        .map_err(|e| format!("Ahead/Behind 계산 실패: {}", e))?;
+    status.ahead = ahead as u32;
+    status.behind = behind as u32;
+    Ok(status)
+}
+
+/// "origin/main" for "refs/remotes/origin/main"; "main" for a local upstream.
+fn upstream_short_name(upstream_ref: &str) -> &str {
+    upstream_ref
+        .strip_prefix("refs/remotes/")
+        .or_else(|| upstream_ref.strip_prefix("refs/heads/"))
+        .unwrap_or(upstream_ref)
+}
+
+/// Make local `branch_name` track `upstream` ("origin/main"), or stop
+/// tracking anything when `upstream` is None, and return the resulting
+/// `branch.<name>.remote` / `branch.<name>.merge` configuration.
+///
+/// The remote-tracking ref must exist; when it does not (e.g. the remote
+/// was never fetched), fails with an `upstream_not_found` error naming the
+/// remote to fetch.
+#[tauri::command]
+pub async fn set_upstream(
+    repo_path: String,
+    branch_name: String,
+    upstream: Option<String>,
+) -> Result<BranchTracking, String> {
+    let branch_name = normalize_unicode(&branch_name);
+    let upstream = upstream.map(|u| normalize_unicode(u.trim()));
+    let repo = open_repo(&repo_path)?;
+    let mut branch = repo
+        .find_branch(&branch_name, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", branch_name, e))?;
+
+    if let Some(upstream) = &upstream {
+        let remotes = repo
+            .remotes()
+            .map_err(|e| format!("리모트 목록 조회 실패: {}", e))?;
+        // Remote names may contain '/': take the longest one that prefixes `upstream`
+        let remote = remotes
+            .iter()
+            .flatten()
+            .filter(|remote| {
+                upstream
+                    .strip_prefix(remote)
+                    .is_some_and(|rest| rest.len() > 1 && rest.starts_with('/'))
+            })
+            .max_by_key(|remote| remote.len())
+            .ok_or_else(|| format!("'{}'에 해당하는 리모트가 없습니다", upstream))?
+            .to_string();
+        if repo
+            .find_reference(&format!("refs/remotes/{}", upstream))
+            .is_err()
+        {
+            return Err(command_error(
+                "upstream_not_found",
+                format!(
+                    "원격 추적 브랜치 '{}'가 없습니다. 먼저 '{}' 리모트를 fetch 하세요.",
+                    upstream, remote
+                ),
+                serde_json::json!({ "upstream": upstream, "remote": remote }),
+            ));
+        }
+    }
+
+    branch
+        .set_upstream(upstream.as_deref())
+        .map_err(|e| format!("업스트림 설정 실패: {}", e))?;
+    branch_tracking(&repo, &branch_name)
+}
+
+/// The tracking configuration of local `branch_name` as stored in config.
+fn branch_tracking(repo: &Repository, branch_name: &str) -> Result<BranchTracking, String> {
+    let config = repo
+        .config()
+        .and_then(|mut config| config.snapshot())
+        .map_err(|e| format!("Git 설정 접근 실패: {}", e))?;
+    let read = |key: &str| {
+        config
+            .get_str(&format!("branch.{}.{}", branch_name, key))
+            .ok()
+            .map(normalize_unicode)
+    };
+    Ok(BranchTracking {
+        branch: branch_name.to_string(),
+        upstream: configured_upstream(repo, branch_name)
+            .map(|upstream| normalize_unicode(upstream_short_name(&upstream))),
+        remote: read("remote"),
+        merge: read("merge"),
+    })
+}
+
+/// Commits reachable from `branch_a` but not `branch_b` and vice versa, most
+/// recent first, plus their merge base. Each list holds at most `limit`
+/// commits (default 100); the `truncated` flags say whether more were left
+/// out. Accepts local or remote-tracking names. Branches without a common
+/// ancestor fail with an `unrelated_histories` error.
+#[tauri::command]
+pub async fn get_branch_divergence(
+    repo_path: String,
+    branch_a: String,
+    branch_b: String,
+    limit: Option<usize>,
+) -> Result<BranchDivergence, String> {
+    let normalized_a = normalize_unicode(&branch_a);
+    let normalized_b = normalize_unicode(&branch_b);
+    let limit = limit.unwrap_or(DEFAULT_DIVERGENCE_LIMIT);
+    let repo = open_repo(&repo_path)?;
+
+    let commit_a = find_branch_commit(&repo, &normalized_a)?;
+    let commit_b = find_branch_commit(&repo, &normalized_b)?;
+    let merge_base = match repo.merge_base(commit_a.id(), commit_b.id()) {
+        Ok(oid) => oid,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            return Err(command_error(
+                "unrelated_histories",
+                format!(
+                    "'{}'와(과) '{}'의 공통 조상을 찾을 수 없습니다 (관련 없는 히스토리)",
+                    normalized_a, normalized_b
+                ),
+                serde_json::json!({ "branch_a": normalized_a, "branch_b": normalized_b }),
+            ))
+        }
+        Err(e) => return Err(format!("Merge-base 확인 실패: {}", e)),
+    };
+
+    let (only_in_a, only_in_a_truncated) =
+        commits_only_in(&repo, commit_a.id(), commit_b.id(), limit)?;
+    let (only_in_b, only_in_b_truncated) =
+        commits_only_in(&repo, commit_b.id(), commit_a.id(), limit)?;
+    Ok(BranchDivergence {
+        branch_a: normalized_a,
+        branch_b: normalized_b,
+        merge_base: merge_base.to_string(),
+        only_in_a,
+        only_in_a_truncated,
+        only_in_b,
+        only_in_b_truncated,
+    })
+}
+
+/// Up to `limit` commits reachable from `tip` but not from `hidden`, most
+/// recent first, and whether any were left out.
+fn commits_only_in(
+    repo: &Repository,
+    tip: Oid,
+    hidden: Oid,
+    limit: usize,
+) -> Result<(Vec<CommitInfo>, bool), String> {
+    let mut revwalk = repo
+        .revwalk()
+        .map_err(|e| format!("Revwalk 생성 실패: {}", e))?;
+    revwalk
+        .push(tip)
+        .map_err(|e| format!("Revwalk push 실패: {}", e))?;
+    revwalk
+        .hide(hidden)
+        .map_err(|e| format!("Revwalk hide 실패: {}", e))?;
+    revwalk
+        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+        .map_err(|e| format!("정렬 설정 실패: {}", e))?;
+
+    let mut commits = Vec::new();
+    for oid_result in revwalk {
+        let oid = oid_result.map_err(|e| format!("OID 읽기 실패: {}", e))?;
+        if commits.len() == limit {
+            return Ok((commits, true));
+        }
+        let commit = repo
+            .find_commit(oid)
+            .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+        commits.push(commit_info(&commit));
+    }
+    Ok((commits, false))
+}
+
+/// Compare two branches: commits on `head_branch` missing from `base_branch`
+/// plus the file diff between their tips. Accepts local or remote-tracking names.
+/// Branches without a common ancestor fail with an `unrelated_histories` error.
+#[tauri::command]
+pub async fn compare_branches(
+    repo_path: String,
+    base_branch: String,
+    head_branch: String,
+) -> Result<BranchComparison, String> {
+    let normalized_base = normalize_unicode(&base_branch);
+    let normalized_head = normalize_unicode(&head_branch);
+    let repo = open_repo(&repo_path)?;
+
+    let base_commit = find_branch_commit(&repo, &normalized_base)?;
+    let head_commit = find_branch_commit(&repo, &normalized_head)?;
+
+    let merge_base = match repo.merge_base(base_commit.id(), head_commit.id()) {
+        Ok(oid) => oid,
+        Err(e) if e.code() == git2::ErrorCode::NotFound => {
+            return Err(command_error(
+                "unrelated_histories",
+                format!(
+                    "'{}'와(과) '{}'의 공통 조상을 찾을 수 없습니다 (관련 없는 히스토리)",
+                    normalized_base, normalized_head
+                ),
+                serde_json::json!({
+                    "base_branch": normalized_base,
+                    "head_branch": normalized_head,
+                }),
+            ))
+        }
+        Err(e) => return Err(format!("Merge-base 확인 실패: {}", e)),
+    };
+    let (ahead, behind) = repo
+        .graph_ahead_behind(head_commit.id(), base_commit.id())
+        .map_err(|e| format!("Ahead/Behind 계산 실패: {}", e))?;
+
+    let (commits, _) = commits_only_in(&repo, head_commit.id(), base_commit.id(), usize::MAX)?;
+
+    let base_tree = base_commit
+        .tree()
+        .map_err(|e| format!("트리 접근 실패: {}", e))?;
+    let head_tree = head_commit
+        .tree()
+        .map_err(|e| format!("트리 접근 실패: {}", e))?;
+    let mut opts = git2::DiffOptions::new();
+    opts.ignore_whitespace_eol(true);
+    opts.force_text(true);
+    let mut diff = repo
+        .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let files = collect_diff_stats(&repo, &mut diff)?;
+
+    Ok(BranchComparison {
+        base_branch: normalized_base,
+        head_branch: normalized_head,
+        merge_base: merge_base.to_string(),
+        ahead,
+        behind,
+        commits,
+        files,
+    })
+}
+
+/// Resolve a local branch name, falling back to a remote-tracking one (`origin/main`).
+fn find_branch_commit<'r>(repo: &'r Repository, name: &str) -> Result<git2::Commit<'r>, String> {
+    let branch = repo
+        .find_branch(name, BranchType::Local)
+        .or_else(|_| repo.find_branch(name, BranchType::Remote))
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", name, e))?;
+    branch
+        .get()
+        .peel_to_commit()
+        .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))
+}
+
+/// Rename a local branch, carrying its `branch.<name>.*` settings (upstream,
+/// rebase, ...) over to the new name. Renaming onto an existing branch fails
+/// with `branch_exists` unless `force` is set, which replaces that branch.
+///
+/// `pushed_under_old_name` reports that the upstream still exists under the
+/// old name, so the UI can suggest pushing the new name and deleting the old
+/// remote branch.
+#[tauri::command]
+pub async fn rename_branch(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+    force: Option<bool>,
+) -> Result<RenamedBranch, String> {
+    let normalized_old = normalize_unicode(&old_name);
+    let normalized_new = normalize_unicode(&new_name);
+    let repo = open_repo(&repo_path)?;
+
+    let mut branch = repo
+        .find_branch(&normalized_old, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_old, e))?;
+    let replaces_existing = normalized_new != normalized_old
+        && repo.find_branch(&normalized_new, BranchType::Local).is_ok();
+    if replaces_existing && !force.unwrap_or(false) {
+        return Err(command_error(
+            "branch_exists",
+            format!("브랜치 '{}'가 이미 존재합니다", normalized_new),
+            serde_json::json!({ "branch": normalized_new }),
+        ));
+    }
+
+    let mut config = local_config(&repo)?;
+    let settings = branch_settings(&config, &normalized_old)?;
+    // libgit2 moves the config section by prefix, taking the settings of
+    // branches named "<old>.<more>" along; they are put back below
+    let siblings = branch_config_entries(&config, &normalized_old)?
+        .into_iter()
+        .filter(|(key, _)| key.contains('.'))
+        .collect::<Vec<_>>();
+    if replaces_existing {
+        remove_branch_settings(&mut config, &normalized_new)?;
+    }
+
+    branch
+        .rename(&normalized_new, replaces_existing)
+        .map_err(|e| format!("브랜치 이름 변경 실패: {}", e))?;
+
+    for (key, value) in &siblings {
+        let _ = config.remove(&format!("branch.{}.{}", normalized_new, key));
+        config
+            .set_str(&format!("branch.{}.{}", normalized_old, key), value)
+            .map_err(|e| format!("브랜치 설정 복원 실패: {}", e))?;
+    }
+
+    // libgit2 may or may not have moved the section already; make sure
+    for (key, value) in &settings {
+        let _ = config.remove(&format!("branch.{}.{}", normalized_old, key));
+        config
+            .set_str(&format!("branch.{}.{}", normalized_new, key), value)
+            .map_err(|e| format!("브랜치 설정 이동 실패: {}", e))?;
+    }
+
+    let upstream_ref = configured_upstream(&repo, &normalized_new);
+    let pushed_under_old_name = upstream_ref.as_deref().is_some_and(|upstream| {
+        repo.refname_to_id(upstream).is_ok()
+            && settings.iter().any(|(key, value)| {
+                key == "merge" && *value == format!("refs/heads/{}", normalized_old)
+            })
+    });
+    Ok(RenamedBranch {
+        old_name: normalized_old,
+        new_name: normalized_new,
+        upstream: upstream_ref.map(|upstream| normalize_unicode(upstream_short_name(&upstream))),
+        pushed_under_old_name,
+    })
+}
+
+/// Copy a local branch with its `branch.<name>.*` settings (upstream,
+/// description, ...), like `git branch --copy`; handy as a backup before a
+/// risky rebase. An existing `new_name` fails with `branch_exists` unless
+/// `force` is set, which replaces that branch and its settings.
+#[tauri::command]
+pub async fn copy_branch(
+    repo_path: String,
+    source_name: String,
+    new_name: String,
+    force: Option<bool>,
+) -> Result<BranchInfo, String> {
+    let normalized_source = normalize_unicode(&source_name);
+    let normalized_new = normalize_unicode(&new_name);
+    for name in [&normalized_source, &normalized_new] {
+        if !git2::Reference::is_valid_name(&format!("refs/heads/{}", name)) {
+            return Err(format!("잘못된 브랜치 이름입니다: {}", name));
+        }
+    }
+    if normalized_source == normalized_new {
+        return Err("같은 이름으로는 복사할 수 없습니다".to_string());
+    }
+    let repo = open_repo(&repo_path)?;
+
+    let source = repo
+        .find_branch(&normalized_source, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_source, e))?;
+    let commit = source
+        .get()
+        .peel_to_commit()
+        .map_err(|e| format!("브랜치 커밋 접근 실패: {}", e))?;
+    let replaces_existing = repo.find_branch(&normalized_new, BranchType::Local).is_ok();
+    if replaces_existing {
+        if !force.unwrap_or(false) {
+            return Err(command_error(
+                "branch_exists",
+                format!("브랜치 '{}'가 이미 존재합니다", normalized_new),
+                serde_json::json!({ "branch": normalized_new }),
+            ));
+        }
+        ensure_not_checked_out_elsewhere(&repo, &normalized_new)?;
+    }
+
+    let copy = repo
+        .branch(&normalized_new, &commit, replaces_existing)
+        .map_err(|e| format!("브랜치 복사 실패: {}", e))?;
+    let mut config = local_config(&repo)?;
+    let settings = branch_settings(&config, &normalized_source)?;
+    if replaces_existing {
+        remove_branch_settings(&mut config, &normalized_new)?;
+    }
+    for (key, value) in &settings {
+        config
+            .set_str(&format!("branch.{}.{}", normalized_new, key), value)
+            .map_err(|e| format!("브랜치 설정 복사 실패: {}", e))?;
+    }
+
+    let is_current = copy.is_head();
+    let entry = BranchEntry {
+        branch: copy,
+        name: normalized_new,
+        is_remote: false,
+        is_current,
+        tip: commit.id(),
+        commit: Some(commit),
+    };
+    Ok(branch_info(
+        &repo,
+        entry,
+        &BranchDetails::new(&repo, None, false, false),
+    ))
+}
+
+/// `branch.<name>.description` of a local branch, as used by
+/// `git request-pull`; None when it has none.
+#[tauri::command]
+pub async fn get_branch_description(
+    repo_path: String,
+    branch: String,
+) -> Result<Option<String>, String> {
+    let normalized_branch = normalize_unicode(&branch);
+    let repo = open_repo(&repo_path)?;
+    repo.find_branch(&normalized_branch, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_branch, e))?;
+    let config = repo
+        .config()
+        .and_then(|mut config| config.snapshot())
+        .map_err(|e| format!("설정 읽기 실패: {}", e))?;
+    Ok(config
+        .get_string(&format!("branch.{}.description", normalized_branch))
+        .ok()
+        .map(|description| normalize_unicode(&description)))
+}
+
+/// Set (or with None / blank text, remove) the description of a local
+/// branch. Multi-line text is kept as is.
+#[tauri::command]
+pub async fn set_branch_description(
+    repo_path: String,
+    branch: String,
+    description: Option<String>,
+) -> Result<(), String> {
+    let normalized_branch = normalize_unicode(&branch);
+    let repo = open_repo(&repo_path)?;
+    repo.find_branch(&normalized_branch, BranchType::Local)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_branch, e))?;
+    let key = format!("branch.{}.description", normalized_branch);
+    let mut config = local_config(&repo)?;
+    match description.filter(|description| !description.trim().is_empty()) {
+        Some(description) => config
+            .set_str(&key, &normalize_unicode(&description))
+            .map_err(|e| format!("브랜치 설명 저장 실패: {}", e)),
+        None => match config.remove(&key) {
+            Err(e) if e.code() != git2::ErrorCode::NotFound => {
+                Err(format!("브랜치 설명 삭제 실패: {}", e))
+            }
+            _ => Ok(()),
+        },
+    }
+}
+
+/// The repository's own config file, where branch settings live.
+fn local_config(repo: &Repository) -> Result<git2::Config, String> {
+    repo.config()
+        .and_then(|config| config.open_level(ConfigLevel::Local))
+        .map_err(|e| format!("설정 읽기 실패: {}", e))
+}
+
+fn remove_branch_settings(config: &mut git2::Config, branch_name: &str) -> Result<(), String> {
+    for (key, _) in branch_settings(config, branch_name)? {
+        let _ = config.remove(&format!("branch.{}.{}", branch_name, key));
+    }
+    Ok(())
+}
+
+/// `branch.<name>.*` entries of `config` as (variable, value), e.g.
+/// ("merge", "refs/heads/main"). Entries of branches whose name merely
+/// starts with `<name>.` (`branch.v1.2.merge` for `v1`) are not included.
+fn branch_settings(
+    config: &git2::Config,
+    branch_name: &str,
+) -> Result<Vec<(String, String)>, String> {
+    Ok(branch_config_entries(config, branch_name)?
+        .into_iter()
+        .filter(|(key, _)| !key.contains('.'))
+        .collect())
+}
+
+/// Every entry whose name starts with `branch.<name>.`, as (rest of the
+/// name, value); this includes those of branches named `<name>.<more>`.
+fn branch_config_entries(
+    config: &git2::Config,
+    branch_name: &str,
+) -> Result<Vec<(String, String)>, String> {
+    let prefix = format!("branch.{}.", branch_name);
+    let mut settings = Vec::new();
+    let mut entries = config
+        .entries(None)
+        .map_err(|e| format!("설정 읽기 실패: {}", e))?;
+    while let Some(entry) = entries.next() {
+        let entry = entry.map_err(|e| format!("설정 읽기 실패: {}", e))?;
+        if let (Some(name), Some(value)) = (entry.name(), entry.value()) {
+            if let Some(key) = name.strip_prefix(&prefix) {
+                settings.push((key.to_string(), value.to_string()));
+            }
+        }
+    }
+    Ok(settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use std::path::Path;
+    use tempfile::tempdir;
+    use unicode_normalization::UnicodeNormalization;
+
+    fn setup_test_repo() -> (tempfile::TempDir, String) {
+        let temp_dir = tempdir().unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        let repo = Repository::init(&repo_path).unwrap();
+
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = { repo.index().unwrap().write_tree().unwrap() };
+        let tree = repo.find_tree(tree_id).unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+
+        (temp_dir, repo_path)
+    }
 
     #[tokio::test]
-    async fn test_korean_branch_name() {
+    async fn test_create_and_list_branches() {
         let (_temp_dir, repo_path) = setup_test_repo();
-        let result = create_branch(repo_path.clone(), "기능/테스트".to_string()).await;
+        let result = create_branch(repo_path.clone(), "feature/test".to_string(), None).await;
         assert!(result.is_ok());
 
-        let branches = list_branches(repo_path).await.unwrap();
-        assert!(branches.iter().any(|b| b.name == "기능/테스트"));
+        let branches = list_branches(repo_path, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(branches.len(), 2);
+        assert!(branches.iter().any(|b| b.name == "feature/test"));
     }
 
     #[tokio::test]
-    async fn test_switch_branch() {
+    async fn test_list_branches_scope_and_order() {
         let (_temp_dir, repo_path) = setup_test_repo();
-        create_branch(repo_path.clone(), "develop".to_string())
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = parent.tree().unwrap();
+        let commit_at = |refname: &str, seconds: i64| {
+            let sig = Signature::new(
+                "Test User",
+                "test@example.com",
+                &git2::Time::new(seconds, 0),
+            )
+            .unwrap();
+            repo.commit(Some(refname), &sig, &sig, refname, &tree, &[&parent])
+                .unwrap()
+        };
+        commit_at("refs/heads/오래됨", 1_000);
+        commit_at("refs/heads/최근", 3_000);
+        commit_at("refs/remotes/origin/main", 2_000);
+        commit_at("refs/remotes/upstream/기능", 4_000);
+        repo.reference_symbolic(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/main",
+            false,
+            "test",
+        )
+        .unwrap();
+
+        let names = |branches: &[BranchInfo]| -> Vec<String> {
+            branches.iter().map(|b| b.name.clone()).collect()
+        };
+        let all = list_branches(repo_path.clone(), None, None, None, None, None, None)
             .await
             .unwrap();
-        let result = switch_branch(repo_path.clone(), "develop".to_string(), None).await;
-        assert!(result.is_ok());
+        assert_eq!(
+            names(&all),
+            vec![
+                main.clone(),
+                "최근".into(),
+                "오래됨".into(),
+                "upstream/기능".into(),
+                "origin/main".into()
+            ]
+        );
+        assert_eq!(all[3].remote_name.as_deref(), Some("upstream"));
+        assert!(all[..3]
+            .iter()
+            .all(|b| !b.is_remote && b.remote_name.is_none()));
 
-        let current = get_current_branch(repo_path).await.unwrap();
-        assert_eq!(current, "develop");
+        let local = list_branches(
+            repo_path.clone(),
+            None,
+            None,
+            None,
+            None,
+            Some("local".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(names(&local), vec![main, "최근".into(), "오래됨".into()]);
+        let remote = list_branches(
+            repo_path.clone(),
+            None,
+            None,
+            None,
+            None,
+            Some("remote".into()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            names(&remote),
+            vec!["upstream/기능".to_string(), "origin/main".into()]
+        );
+        assert!(
+            list_branches(repo_path, None, None, None, None, Some("tags".into()), None)
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
-    async fn test_delete_branch() {
+    async fn test_list_branches_page_query_and_sort() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = parent.tree().unwrap();
+        for (name, seconds) in [
+            ("기능/로그인", 3_000),
+            ("기능/검색", 1_000),
+            ("fix/Login", 2_000),
+        ] {
+            let sig = Signature::new(
+                "Test User",
+                "test@example.com",
+                &git2::Time::new(seconds, 0),
+            )
+            .unwrap();
+            repo.commit(
+                Some(&format!("refs/heads/{}", name)),
+                &sig,
+                &sig,
+                name,
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+        }
+        let page = |query: Option<&str>, sort: Option<&str>, cursor: Option<String>| {
+            list_branches_page(
+                repo_path.clone(),
+                cursor,
+                Some(2),
+                None,
+                None,
+                None,
+                None,
+                None,
+                query.map(str::to_string),
+                sort.map(str::to_string),
+            )
+        };
+        let names = |page: &Page<BranchInfo>| -> Vec<String> {
+            page.items.iter().map(|b| b.name.clone()).collect()
+        };
+
+        // Decomposed input still matches
+        let nfd: String = "기능/".nfd().collect();
+        let first = page(Some(&nfd), Some("name"), None).await.unwrap();
+        assert_eq!(first.total, Some(2));
+        assert_eq!(names(&first), vec!["기능/검색", "기능/로그인"]);
+        assert!(!first.has_more);
+
+        let first = page(Some("LOGIN"), None, None).await.unwrap();
+        assert_eq!(names(&first), vec!["fix/Login"]);
+
+        let first = page(None, Some("recent"), None).await.unwrap();
+        assert_eq!(first.total, Some(4));
+        assert_eq!(names(&first), vec![main.clone(), "기능/로그인".into()]);
+        let second = page(None, Some("recent"), first.next_cursor).await.unwrap();
+        assert_eq!(names(&second), vec!["fix/Login", "기능/검색"]);
+        assert!(!second.has_more);
+
+        let current_first = page(None, None, None).await.unwrap();
+        assert_eq!(names(&current_first), vec![main, "기능/로그인".into()]);
+        assert!(page(None, Some("size"), None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_branches_page_with_thousands_of_branches() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        for i in 0..5_000 {
+            repo.branch(&format!("feature/{:04}", i), &head, false)
+                .unwrap();
+        }
+
+        let started = std::time::Instant::now();
+        let page = list_branches_page(
+            repo_path,
+            None,
+            Some(50),
+            Some(true),
+            None,
+            Some(true),
+            Some(true),
+            Some("local".into()),
+            Some("feature/04".into()),
+            Some("name".into()),
+        )
+        .await
+        .unwrap();
+        let elapsed = started.elapsed();
+        assert_eq!(page.total, Some(100));
+        assert_eq!(page.items.len(), 50);
+        assert_eq!(page.items[0].name, "feature/0400");
+        assert!(page.has_more);
+        assert!(
+            elapsed < std::time::Duration::from_secs(2),
+            "took {:?}",
+            elapsed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_branches_without_details_skips_commits() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let mut parent = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = parent.tree().unwrap();
+        for i in 0..5_000 {
+            let name = format!("feature/{:04}", i);
+            let id = repo
+                .commit(
+                    Some(&format!("refs/heads/{}", name)),
+                    &sig,
+                    &sig,
+                    &name,
+                    &tree,
+                    &[&parent],
+                )
+                .unwrap();
+            parent = repo.find_commit(id).unwrap();
+        }
+
+        let timed = |details: bool| {
+            let repo_path = repo_path.clone();
+            async move {
+                let started = std::time::Instant::now();
+                let branches =
+                    list_branches(repo_path, None, None, None, None, None, Some(details))
+                        .await
+                        .unwrap();
+                (branches, started.elapsed())
+            }
+        };
+        let (full, full_time) = timed(true).await;
+        let (light, light_time) = timed(false).await;
+        assert_eq!(light.len(), 5_001);
+        assert!(light[0].is_current);
+        for (light, full) in light.iter().skip(1).zip(full.iter().skip(1)) {
+            assert!(light.commit_message.is_empty() && light.timestamp == 0);
+            assert!(light.name.starts_with("feature/") && full.name.starts_with("feature/"));
+        }
+        let tip = repo.find_branch("feature/4999", BranchType::Local).unwrap();
+        let light_tip = light.iter().find(|b| b.name == "feature/4999").unwrap();
+        assert_eq!(
+            light_tip.commit_sha,
+            tip.get().target().unwrap().to_string()
+        );
+        assert!(
+            light_time < full_time,
+            "light {:?}, full {:?}",
+            light_time,
+            full_time
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rename_branch_moves_tracking_config() {
         let (_temp_dir, repo_path) = setup_test_repo();
-        create_branch(repo_path.clone(), "temp".to_string())
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/기능", head.id(), false, "test")
+            .unwrap();
+        repo.branch("기능", &head, false)
+            .unwrap()
+            .set_upstream(Some("origin/기능"))
+            .unwrap();
+        repo.config()
+            .unwrap()
+            .set_bool("branch.기능.rebase", true)
+            .unwrap();
+        repo.branch("다른", &head, false).unwrap();
+
+        let err = rename_branch(repo_path.clone(), "기능".into(), "다른".into(), None)
+            .await
+            .unwrap_err();
+        let err: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "branch_exists");
+        assert_eq!(err.details["branch"], "다른");
+
+        let renamed = rename_branch(repo_path.clone(), "기능".into(), "새 기능".into(), None).await;
+        assert!(renamed.is_err(), "spaces are not allowed in branch names");
+        let renamed = rename_branch(repo_path.clone(), "기능".into(), "새기능".into(), None)
             .await
             .unwrap();
-        let result = delete_branch(repo_path.clone(), "temp".to_string(), None).await;
-        assert!(result.is_ok());
+        assert_eq!(renamed.upstream.as_deref(), Some("origin/기능"));
+        assert!(renamed.pushed_under_old_name);
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(config.get_str("branch.새기능.remote").unwrap(), "origin");
+        assert_eq!(
+            config.get_str("branch.새기능.merge").unwrap(),
+            "refs/heads/기능"
+        );
+        assert!(config.get_bool("branch.새기능.rebase").unwrap());
+        assert!(config.get_str("branch.기능.merge").is_err());
 
-        let branches = list_branches(repo_path).await.unwrap();
-        assert!(!branches.iter().any(|b| b.name == "temp"));
+        // Forcing replaces the existing branch and drops its settings
+        repo.config()
+            .unwrap()
+            .set_str("branch.다른.remote", "origin")
+            .unwrap();
+        let renamed = rename_branch(repo_path, "새기능".into(), "다른".into(), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(renamed.new_name, "다른");
+        assert!(repo.find_branch("새기능", BranchType::Local).is_err());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config.get_str("branch.다른.merge").unwrap(),
+            "refs/heads/기능"
+        );
+        assert!(config.get_str("branch.새기능.remote").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_branch_leaves_dotted_sibling_config() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let mut config = repo.config().unwrap();
+        for name in ["v1", "v1.2"] {
+            repo.branch(name, &head, false).unwrap();
+            config
+                .set_str(&format!("branch.{}.remote", name), "origin")
+                .unwrap();
+            config
+                .set_str(&format!("branch.{}.merge", name), &format!("refs/heads/{}", name))
+                .unwrap();
+        }
+
+        rename_branch(repo_path, "v1".into(), "renamed".into(), None)
+            .await
+            .unwrap();
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config.get_str("branch.renamed.merge").unwrap(),
+            "refs/heads/v1"
+        );
+        assert_eq!(
+            config.get_str("branch.v1.2.merge").unwrap(),
+            "refs/heads/v1.2"
+        );
+        assert_eq!(config.get_str("branch.v1.2.remote").unwrap(), "origin");
+        assert!(config.get_str("branch.renamed.2.merge").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_branch_keeps_tracking_and_description() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/기능", head.id(), false, "test")
+            .unwrap();
+        repo.branch("기능", &head, false)
+            .unwrap()
+            .set_upstream(Some("origin/기능"))
+            .unwrap();
+        let tip = commit_on_branch(&repo_path, "기능", "기능 작업");
+        repo.config()
+            .unwrap()
+            .set_str("branch.기능.description", "리베이스 전 작업")
+            .unwrap();
+
+        let nfd: String = "기능-백업".nfd().collect();
+        let copy = copy_branch(repo_path.clone(), "기능".into(), nfd, None)
+            .await
+            .unwrap();
+        assert_eq!(copy.name, "기능-백업");
+        assert_eq!(copy.commit_sha, tip.to_string());
+        assert_eq!(copy.upstream.as_deref(), Some("origin/기능"));
+        assert!(!copy.is_current && !copy.is_remote);
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(
+            config.get_str("branch.기능-백업.description").unwrap(),
+            "리베이스 전 작업"
+        );
+        assert_eq!(
+            config.get_str("branch.기능.merge").unwrap(),
+            "refs/heads/기능"
+        );
+
+        let err = copy_branch(repo_path.clone(), "기능".into(), "기능-백업".into(), None)
+            .await
+            .unwrap_err();
+        let err: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "branch_exists");
+        assert!(copy_branch(
+            repo_path.clone(),
+            "기능".into(),
+            "잘못된..이름".into(),
+            None
+        )
+        .await
+        .is_err());
+        assert!(
+            copy_branch(repo_path.clone(), "없음".into(), "새것".into(), None)
+                .await
+                .is_err()
+        );
+
+        // Forcing replaces the target and its settings
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let copy = copy_branch(repo_path, main, "기능-백업".into(), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(copy.commit_sha, head.id().to_string());
+        assert!(copy.upstream.is_none());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert!(config.get_str("branch.기능-백업.description").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_copy_branch_leaves_dotted_sibling_config() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("v1.2", &head, false).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.v1.2.remote", "origin").unwrap();
+        config.set_str("branch.v1.2.merge", "refs/heads/v1.2").unwrap();
+
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        copy_branch(repo_path.clone(), main.clone(), "v1".into(), None)
+            .await
+            .unwrap();
+        // Replacing v1 only clears v1's own settings
+        config.set_str("branch.v1.rebase", "true").unwrap();
+        copy_branch(repo_path, main, "v1".into(), Some(true))
+            .await
+            .unwrap();
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert!(config.get_str("branch.v1.rebase").is_err());
+        assert_eq!(config.get_str("branch.v1.2.remote").unwrap(), "origin");
+        assert_eq!(
+            config.get_str("branch.v1.2.merge").unwrap(),
+            "refs/heads/v1.2"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_branch_description_round_trip() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "장기/릴리스".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            get_branch_description(repo_path.clone(), "장기/릴리스".into())
+                .await
+                .unwrap(),
+            None
+        );
+
+        let description = "2.0 릴리스 준비\n\n- 한글 \"따옴표\"와 #주석 기호\n- 탭\t포함\n";
+        let nfd: String = description.nfd().collect();
+        set_branch_description(repo_path.clone(), "장기/릴리스".into(), Some(nfd))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_branch_description(repo_path.clone(), "장기/릴리스".into())
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(description)
+        );
+        // Survives a reload from disk, and shows in the detailed listing
+        let branches = list_branches(repo_path.clone(), None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let branch = branches.iter().find(|b| b.name == "장기/릴리스").unwrap();
+        assert_eq!(branch.description.as_deref(), Some(description));
+        assert!(branches
+            .iter()
+            .filter(|b| b.name != "장기/릴리스")
+            .all(|b| b.description.is_none()));
+
+        set_branch_description(repo_path.clone(), "장기/릴리스".into(), Some("  \n".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_branch_description(repo_path.clone(), "장기/릴리스".into())
+                .await
+                .unwrap(),
+            None
+        );
+        set_branch_description(repo_path.clone(), "장기/릴리스".into(), None)
+            .await
+            .unwrap();
+        assert!(get_branch_description(repo_path, "없음".into())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_unpushed_branch() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "wip".into(), None)
+            .await
+            .unwrap();
+        let renamed = rename_branch(repo_path, "wip".into(), "기능/작업".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(renamed.new_name, "기능/작업");
+        assert!(renamed.upstream.is_none() && !renamed.pushed_under_old_name);
+    }
+
+    #[tokio::test]
+    async fn test_korean_branch_name() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let result = create_branch(repo_path.clone(), "기능/테스트".to_string(), None).await;
+        assert!(result.is_ok());
+
+        let branches = list_branches(repo_path, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(branches.iter().any(|b| b.name == "기능/테스트"));
+    }
+
+    #[tokio::test]
+    async fn test_switch_branch() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "develop".to_string(), None)
+            .await
+            .unwrap();
+        let result = switch_branch(repo_path.clone(), "develop".to_string(), None, None).await;
+        assert!(result.is_ok());
+
+        let current = get_current_branch(repo_path).await.unwrap();
+        assert_eq!(current, "develop");
+    }
+
+    #[tokio::test]
+    async fn test_delete_branch() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "temp".to_string(), None)
+            .await
+            .unwrap();
+        let result = delete_branch(repo_path.clone(), "temp".to_string(), None).await;
+        assert!(result.is_ok());
+
+        let branches = list_branches(repo_path, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(!branches.iter().any(|b| b.name == "temp"));
+    }
+
+    /// Commit an empty tree change on top of `branch`, returning the new tip.
+    fn commit_on_branch(repo_path: &str, branch: &str, message: &str) -> git2::Oid {
+        let repo = Repository::open(repo_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo
+            .find_branch(branch, BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        let tree = parent.tree().unwrap();
+        repo.commit(
+            Some(&format!("refs/heads/{}", branch)),
+            &sig,
+            &sig,
+            message,
+            &tree,
+            &[&parent],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unpushed_branch_not_backed_up() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "wip".to_string(), None)
+            .await
+            .unwrap();
+        commit_on_branch(&repo_path, "wip", "Unpushed work");
+
+        let info = is_branch_backed_up(repo_path.clone(), "wip".to_string())
+            .await
+            .unwrap();
+        assert!(!info.is_backed_up);
+        assert!(info.containing_refs.is_empty());
+
+        let err = delete_branch(repo_path, "wip".to_string(), None)
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "branch_not_merged");
+        assert_eq!(payload.details["backed_up"], false);
+        assert_eq!(payload.details["unmerged_commits"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_merge_status_and_delete_merged_branches() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        create_branch(repo_path.clone(), "병합됨".to_string(), None)
+            .await
+            .unwrap();
+        commit_on_branch(&repo_path, &current, "main work");
+        create_branch(repo_path.clone(), "같음".to_string(), None)
+            .await
+            .unwrap();
+        create_branch(repo_path.clone(), "미병합".to_string(), None)
+            .await
+            .unwrap();
+        commit_on_branch(&repo_path, "미병합", "side work");
+
+        let merged_flags = |branches: Vec<BranchInfo>| {
+            let mut flags: Vec<(String, bool)> = branches
+                .into_iter()
+                .map(|b| (b.name, b.is_merged))
+                .collect();
+            flags.sort();
+            flags
+        };
+        let mut expected = vec![
+            (current.clone(), true),
+            ("같음".to_string(), true),
+            ("미병합".to_string(), false),
+            ("병합됨".to_string(), true),
+        ];
+        expected.sort();
+        let branches = list_branches(repo_path.clone(), None, None, Some(true), None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(merged_flags(branches), expected);
+        // Not computed unless asked for
+        let branches = list_branches(repo_path.clone(), None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(branches.iter().all(|b| !b.is_merged));
+
+        let mut planned: Vec<String> = delete_merged_branches(repo_path.clone(), true)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        planned.sort();
+        assert_eq!(planned, vec!["같음", "병합됨"]);
+        assert_eq!(
+            list_branches(repo_path.clone(), None, None, None, None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            4
+        );
+
+        let deleted = delete_merged_branches(repo_path.clone(), false)
+            .await
+            .unwrap();
+        assert_eq!(deleted.len(), 2);
+        let mut remaining: Vec<String> =
+            list_branches(repo_path, None, None, None, None, None, None)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|b| b.name)
+                .collect();
+        remaining.sort();
+        let mut expected = vec![current, "미병합".to_string()];
+        expected.sort();
+        assert_eq!(remaining, expected);
+    }
+
+    #[tokio::test]
+    async fn test_tracking_counts_per_branch() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        create_branch(repo_path.clone(), "기능".to_string(), None)
+            .await
+            .unwrap();
+        create_branch(repo_path.clone(), "고아".to_string(), None)
+            .await
+            .unwrap();
+        repo.find_branch("기능", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some(&current))
+            .unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("branch.고아.remote", "origin").unwrap();
+        config
+            .set_str("branch.고아.merge", "refs/heads/사라짐")
+            .unwrap();
+        commit_on_branch(&repo_path, "기능", "one");
+        commit_on_branch(&repo_path, "기능", "two");
+        commit_on_branch(&repo_path, &current, "upstream work");
+
+        let branches = list_branches(repo_path.clone(), None, None, None, Some(true), None, None)
+            .await
+            .unwrap();
+        let find = |name: &str| branches.iter().find(|b| b.name == name).unwrap();
+        let feature = find("기능");
+        assert_eq!(feature.upstream.as_deref(), Some(current.as_str()));
+        assert_eq!((feature.ahead, feature.behind), (2, 1));
+        assert!(!feature.upstream_gone);
+        let orphan = find("고아");
+        assert_eq!(orphan.upstream.as_deref(), Some("origin/사라짐"));
+        assert!(orphan.upstream_gone);
+        assert_eq!((orphan.ahead, orphan.behind), (0, 0));
+        assert_eq!(find(&current).upstream, None);
+
+        // Counts are only computed when asked for
+        let branches = list_branches(repo_path, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let feature = branches.iter().find(|b| b.name == "기능").unwrap();
+        assert_eq!(feature.upstream.as_deref(), Some(current.as_str()));
+        assert_eq!((feature.ahead, feature.behind), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn test_set_change_and_unset_upstream() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        for name in ["main", "기능"] {
+            repo.reference(
+                &format!("refs/remotes/origin/{}", name),
+                head,
+                false,
+                "test",
+            )
+            .unwrap();
+        }
+        create_branch(repo_path.clone(), "작업".to_string(), None)
+            .await
+            .unwrap();
+        let config_value = |key: &str| {
+            repo.config()
+                .unwrap()
+                .snapshot()
+                .unwrap()
+                .get_str(key)
+                .ok()
+                .map(str::to_string)
+        };
+
+        let tracking = set_upstream(repo_path.clone(), "작업".into(), Some("origin/main".into()))
+            .await
+            .unwrap();
+        assert_eq!(tracking.upstream.as_deref(), Some("origin/main"));
+        assert_eq!(
+            config_value("branch.작업.remote").as_deref(),
+            Some("origin")
+        );
+        assert_eq!(
+            config_value("branch.작업.merge").as_deref(),
+            Some("refs/heads/main")
+        );
+
+        let tracking = set_upstream(repo_path.clone(), "작업".into(), Some("origin/기능".into()))
+            .await
+            .unwrap();
+        assert_eq!(tracking.upstream.as_deref(), Some("origin/기능"));
+        assert_eq!(tracking.merge.as_deref(), Some("refs/heads/기능"));
+
+        let err = set_upstream(repo_path.clone(), "작업".into(), Some("origin/없음".into()))
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "upstream_not_found");
+        assert_eq!(payload.details["remote"], "origin");
+        assert!(set_upstream(
+            repo_path.clone(),
+            "작업".into(),
+            Some("upstream/main".into())
+        )
+        .await
+        .is_err());
+        // A failed change keeps the previous upstream
+        assert_eq!(
+            config_value("branch.작업.merge").as_deref(),
+            Some("refs/heads/기능")
+        );
+
+        let tracking = set_upstream(repo_path, "작업".into(), None).await.unwrap();
+        assert_eq!(tracking.upstream, None);
+        assert_eq!(tracking.remote, None);
+        assert_eq!(config_value("branch.작업.remote"), None);
+        assert_eq!(config_value("branch.작업.merge"), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_branch_from_start_point() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        let first = repo.head().unwrap().target().unwrap();
+        let second = commit_on_branch(&repo_path, &current, "second");
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        repo.tag(
+            "v1.0",
+            &repo.find_object(first, None).unwrap(),
+            &sig,
+            "릴리스",
+            false,
+        )
+        .unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/기능", second, false, "test")
+            .unwrap();
+        let tip = |name: &str| {
+            repo.find_branch(name, BranchType::Local)
+                .unwrap()
+                .get()
+                .target()
+                .unwrap()
+        };
+
+        create_branch(
+            repo_path.clone(),
+            "from-sha".into(),
+            Some(first.to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(tip("from-sha"), first);
+        create_branch(repo_path.clone(), "from-tag".into(), Some("v1.0".into()))
+            .await
+            .unwrap();
+        assert_eq!(tip("from-tag"), first);
+        assert!(configured_upstream(&repo, "from-tag").is_none());
+
+        // Decomposed input still names the NFC branch and ref
+        let decomposed: String = "기능".nfd().collect();
+        let origin_feature = format!("origin/{}", decomposed);
+        create_branch(repo_path.clone(), decomposed.clone(), Some(origin_feature))
+            .await
+            .unwrap();
+        assert_eq!(tip("기능"), second);
+        assert_eq!(
+            configured_upstream(&repo, "기능").as_deref(),
+            Some("refs/remotes/origin/기능")
+        );
+
+        let err = create_branch(repo_path.clone(), "기능".into(), None)
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "branch_exists");
+        let err = create_branch(repo_path, "새것".into(), Some("origin/없음".into()))
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "start_point_not_found");
+        assert_eq!(payload.details["start_point"], "origin/없음");
+    }
+
+    /// Commit `content` to `name` on the current branch.
+    fn commit_file(repo_path: &str, name: &str, content: &str) -> git2::Oid {
+        let repo = Repository::open(repo_path).unwrap();
+        std::fs::write(Path::new(repo_path).join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &[&parent])
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_switch_keeps_local_changes() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let old = repo.head().unwrap().target().unwrap();
+        commit_file(&repo_path, "메모.txt", "v1\n");
+        let file = Path::new(&repo_path).join("메모.txt");
+        std::fs::write(&file, "작업 중\n").unwrap();
+
+        // Same commit: the edit comes along
+        let state = create_and_switch_branch(repo_path.clone(), "새-작업".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(state.name.as_deref(), Some("새-작업"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "작업 중\n");
+
+        // A commit without the file would clobber the edit
+        let err = create_and_switch_branch(repo_path.clone(), "옛날".into(), Some(old.to_string()))
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "checkout_conflicts");
+        assert_eq!(payload.details["paths"], serde_json::json!(["메모.txt"]));
+        assert!(repo.find_branch("옛날", BranchType::Local).is_err());
+        assert_eq!(repo.head().unwrap().shorthand(), Some("새-작업"));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "작업 중\n");
+    }
+
+    fn conflict_paths(err: &str) -> serde_json::Value {
+        let payload: crate::commands::models::CommandError = serde_json::from_str(err).unwrap();
+        assert_eq!(payload.code, "checkout_conflicts");
+        payload.details["paths"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_checkout_commit_detaches_safely() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let old = commit_file(&repo_path, "메모.txt", "v1\n");
+        let new = commit_file(&repo_path, "메모.txt", "v2\n");
+        let file = Path::new(&repo_path).join("메모.txt");
+        std::fs::write(&file, "작업 중\n").unwrap();
+
+        let err = checkout_commit(repo_path.clone(), old.to_string(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(conflict_paths(&err), serde_json::json!(["메모.txt"]));
+        assert!(!repo.head_detached().unwrap());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "작업 중\n");
+
+        let head = checkout_commit(repo_path.clone(), old.to_string(), Some(true))
+            .await
+            .unwrap();
+        assert!(head.detached && head.name.is_none());
+        assert_eq!(head.sha, old.to_string());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1\n");
+
+        let head = return_to_branch(repo_path, main.clone()).await.unwrap();
+        assert_eq!(head.name, Some(main));
+        assert_eq!(head.sha, new.to_string());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2\n");
+    }
+
+    #[tokio::test]
+    async fn test_switch_branch_refuses_to_clobber_dirty_file() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        commit_file(&repo_path, "a.txt", "v1\n");
+        create_branch(repo_path.clone(), "이전".into(), None)
+            .await
+            .unwrap();
+        commit_file(&repo_path, "a.txt", "v2\n");
+        let file = Path::new(&repo_path).join("a.txt");
+        std::fs::write(&file, "로컬 수정\n").unwrap();
+
+        let err = switch_branch(repo_path.clone(), "이전".into(), None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(conflict_paths(&err), serde_json::json!(["a.txt"]));
+        assert_eq!(repo.head().unwrap().shorthand(), Some(current.as_str()));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "로컬 수정\n");
+
+        // Stashing first sets the edit aside and lets the switch through
+        let result = switch_branch(repo_path.clone(), "이전".into(), None, Some(true))
+            .await
+            .unwrap();
+        assert_eq!(result.head.name.as_deref(), Some("이전"));
+        assert_eq!(result.stash_index, Some(0));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v1\n");
+        let stashes = crate::commands::stash::stash_list(repo_path.clone(), None, None).unwrap();
+        assert_eq!(stashes.len(), 1);
+        assert!(stashes[0].message.contains("자동 스태시"));
+
+        // Nothing left to stash: no stash is made
+        let result = switch_branch(repo_path, current, None, Some(true))
+            .await
+            .unwrap();
+        assert_eq!(result.stash_index, None);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2\n");
+    }
+
+    #[tokio::test]
+    async fn test_switch_branch_refuses_untracked_collision() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        create_branch(repo_path.clone(), "추가".into(), None)
+            .await
+            .unwrap();
+        switch_branch(repo_path.clone(), "추가".into(), None, None)
+            .await
+            .unwrap();
+        commit_file(&repo_path, "새 파일.txt", "tracked\n");
+        switch_branch(repo_path.clone(), current.clone(), None, None)
+            .await
+            .unwrap();
+        let file = Path::new(&repo_path).join("새 파일.txt");
+        assert!(!file.exists());
+        std::fs::write(&file, "untracked\n").unwrap();
+
+        let err = switch_branch(repo_path.clone(), "추가".into(), None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(conflict_paths(&err), serde_json::json!(["새 파일.txt"]));
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "untracked\n");
+
+        // `force` discards it
+        switch_branch(repo_path, "추가".into(), Some(true), None)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "tracked\n");
+    }
+
+    #[tokio::test]
+    async fn test_force_delete_returns_tip_for_undo() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "실험".to_string(), None)
+            .await
+            .unwrap();
+        commit_on_branch(&repo_path, "실험", "first");
+        let tip = commit_on_branch(&repo_path, "실험", "second");
+        let repo = Repository::open(&repo_path).unwrap();
+        // The first commit is kept by a tag, so only one would be lost
+        let first = repo.find_commit(tip).unwrap().parent_id(0).unwrap();
+        repo.tag_lightweight("보관", &repo.find_object(first, None).unwrap(), false)
+            .unwrap();
+
+        let err = delete_branch(repo_path.clone(), "실험".to_string(), Some(false))
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.details["unmerged_commits"], 1);
+        assert_eq!(payload.details["tip_sha"], tip.to_string());
+
+        let deleted = delete_branch(repo_path.clone(), "실험".to_string(), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(deleted.name, "실험");
+        assert_eq!(deleted.tip_sha, tip.to_string());
+        assert!(repo.find_branch("실험", BranchType::Local).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pushed_and_merged_branch_backed_up() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        create_branch(repo_path.clone(), "feature".to_string(), None)
+            .await
+            .unwrap();
+        let tip = commit_on_branch(&repo_path, "feature", "Feature work");
+
+        // Simulate a push by creating the remote-tracking ref
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.reference("refs/remotes/origin/feature", tip, false, "push")
+            .unwrap();
+
+        let info = is_branch_backed_up(repo_path.clone(), "feature".to_string())
+            .await
+            .unwrap();
+        assert!(info.is_backed_up);
+        assert_eq!(info.containing_refs, vec!["refs/remotes/origin/feature"]);
+
+        // A merged branch is covered by the branch it was merged into
+        create_branch(repo_path.clone(), "merged".to_string(), None)
+            .await
+            .unwrap();
+        let info = is_branch_backed_up(repo_path, "merged".to_string())
+            .await
+            .unwrap();
+        assert!(info.is_backed_up);
+        assert!(info
+            .containing_refs
+            .contains(&"refs/heads/feature".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_branch_sync_status() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        let status = get_branch_sync_status(repo_path.clone()).await.unwrap();
+        assert!(status.upstream.is_none() && !status.has_upstream);
+        assert!(!status.upstream_gone && !status.detached);
+
+        // Local and remote each gained one commit since they split
+        create_branch(repo_path.clone(), "remote-work".to_string(), None)
+            .await
+            .unwrap();
+        let remote_tip = commit_on_branch(&repo_path, "remote-work", "Remote work");
+        commit_on_branch(&repo_path, &current, "Local work");
+        let upstream_ref = format!("refs/remotes/origin/{}", current);
+        repo.reference(&upstream_ref, remote_tip, true, "fetch")
+            .unwrap();
+        repo.remote("origin", "https://example.com/repo.git")
+            .unwrap();
+        let mut config = repo.config().unwrap();
+        config
+            .set_str(&format!("branch.{}.remote", current), "origin")
+            .unwrap();
+        config
+            .set_str(
+                &format!("branch.{}.merge", current),
+                &format!("refs/heads/{}", current),
+            )
+            .unwrap();
+
+        let status = get_branch_sync_status(repo_path.clone()).await.unwrap();
+        assert_eq!(status.upstream, Some(format!("origin/{}", current)));
+        assert!(status.has_upstream);
+        assert_eq!((status.ahead, status.behind), (1, 1));
+        let info = crate::commands::git::open_repository(repo_path.clone())
+            .await
+            .unwrap();
+        assert_eq!((info.sync.ahead, info.sync.behind), (1, 1));
+
+        // Pruned remote branch: still configured, but gone
+        repo.find_reference(&upstream_ref)
+            .unwrap()
+            .delete()
+            .unwrap();
+        let status = get_branch_sync_status(repo_path.clone()).await.unwrap();
+        assert!(status.upstream_gone && !status.has_upstream);
+        assert_eq!(status.upstream, Some(format!("origin/{}", current)));
+
+        let head = repo.head().unwrap().target().unwrap();
+        repo.set_head_detached(head).unwrap();
+        let status = get_branch_sync_status(repo_path).await.unwrap();
+        assert!(status.detached && status.upstream.is_none());
+    }
+
+    fn commit_file_on_branch(repo_path: &str, branch: &str, file: &str, content: &str) {
+        let repo = Repository::open(repo_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo
+            .find_branch(branch, BranchType::Local)
+            .unwrap()
+            .get()
+            .peel_to_commit()
+            .unwrap();
+        let blob = repo.blob(content.as_bytes()).unwrap();
+        let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+        builder.insert(file, blob, 0o100644).unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        repo.commit(
+            Some(&format!("refs/heads/{}", branch)),
+            &sig,
+            &sig,
+            &format!("Add {}", file),
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compare_branches() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        create_branch(repo_path.clone(), "feature".to_string(), None)
+            .await
+            .unwrap();
+        commit_file_on_branch(&repo_path, "feature", "a.txt", "one\ntwo\n");
+        commit_file_on_branch(&repo_path, "feature", "b.txt", "three\n");
+
+        let cmp = compare_branches(repo_path.clone(), main.clone(), "feature".to_string())
+            .await
+            .unwrap();
+        assert_eq!((cmp.ahead, cmp.behind), (2, 0));
+        assert_eq!(cmp.commits.len(), 2);
+        assert_eq!(cmp.commits[0].message, "Add b.txt");
+        let mut files: Vec<(String, u32)> = cmp
+            .files
+            .iter()
+            .map(|f| (f.file_path.clone(), f.additions))
+            .collect();
+        files.sort();
+        assert_eq!(files, vec![("a.txt".into(), 2), ("b.txt".into(), 1)]);
+
+        // Base fully contains head: nothing to list
+        let reverse = compare_branches(repo_path.clone(), "feature".to_string(), main.clone())
+            .await
+            .unwrap();
+        assert!(reverse.commits.is_empty());
+        assert_eq!((reverse.ahead, reverse.behind), (0, 2));
+
+        // Remote-tracking names resolve too
+        let tip = repo.find_branch("feature", BranchType::Local).unwrap();
+        let tip = tip.get().target().unwrap();
+        repo.reference("refs/remotes/origin/feature", tip, false, "test")
+            .unwrap();
+        let remote = compare_branches(repo_path, main, "origin/feature".to_string())
+            .await
+            .unwrap();
+        assert_eq!(remote.commits.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_compare_unrelated_branches_errors() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        repo.commit(Some("refs/heads/orphan"), &sig, &sig, "Orphan", &tree, &[])
+            .unwrap();
+
+        let err = compare_branches(repo_path, main.clone(), "orphan".to_string())
+            .await
+            .unwrap_err();
+        let err: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "unrelated_histories");
+        assert!(err.message.contains("공통 조상"));
+        assert_eq!(err.details["base_branch"], serde_json::json!(main));
+        assert_eq!(err.details["head_branch"], "orphan");
+    }
+
+    #[tokio::test]
+    async fn test_branch_divergence_lists_both_sides() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let base = repo.head().unwrap().target().unwrap();
+        create_branch(repo_path.clone(), "기능".to_string(), None)
+            .await
+            .unwrap();
+        for i in 0..3 {
+            commit_on_branch(&repo_path, "기능", &format!("기능 {}", i));
+        }
+        let main_tip = commit_on_branch(&repo_path, &main, "메인 작업");
+        repo.reference(
+            "refs/remotes/origin/기능",
+            repo.revparse_single("기능").unwrap().id(),
+            false,
+            "test",
+        )
+        .unwrap();
+
+        let nfd: String = "origin/기능".nfd().collect();
+        let divergence = get_branch_divergence(repo_path.clone(), nfd, main.clone(), None)
+            .await
+            .unwrap();
+        assert_eq!(divergence.branch_a, "origin/기능");
+        assert_eq!(divergence.merge_base, base.to_string());
+        let messages: Vec<&str> = divergence
+            .only_in_a
+            .iter()
+            .map(|c| c.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["기능 2", "기능 1", "기능 0"]);
+        assert!(!divergence.only_in_a_truncated);
+        assert_eq!(divergence.only_in_b.len(), 1);
+        assert_eq!(divergence.only_in_b[0].sha, main_tip.to_string());
+
+        let capped = get_branch_divergence(repo_path, "기능".into(), main, Some(2))
+            .await
+            .unwrap();
+        assert_eq!(capped.only_in_a.len(), 2);
+        assert!(capped.only_in_a_truncated);
+        assert!(!capped.only_in_b_truncated);
+    }
+
+    #[tokio::test]
+    async fn test_branch_divergence_unrelated_histories() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        repo.commit(Some("refs/heads/orphan"), &sig, &sig, "Orphan", &tree, &[])
+            .unwrap();
+
+        let err = get_branch_divergence(repo_path.clone(), main, "orphan".into(), None)
+            .await
+            .unwrap_err();
+        let err: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "unrelated_histories");
+        assert_eq!(err.details["branch_b"], "orphan");
+        assert!(
+            get_branch_divergence(repo_path, "없음".into(), "orphan".into(), None)
+                .await
+                .is_err()
+        );
+    }
+
+    /// Commit `file` on the current branch and sync the working tree to it.
+    fn commit_and_checkout(repo_path: &str, file: &str, content: &str) -> git2::Oid {
+        let repo = Repository::open(repo_path).unwrap();
+        let branch = repo.head().unwrap().shorthand().unwrap().to_string();
+        commit_file_on_branch(repo_path, &branch, file, content);
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        repo.checkout_head(Some(checkout.force())).unwrap();
+        let tip = repo.head().unwrap().target().unwrap();
+        tip
+    }
+
+    #[tokio::test]
+    async fn test_switch_to_tag_creates_branch() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let v1 = commit_and_checkout(&repo_path, "a.txt", "v1\n");
+        commit_and_checkout(&repo_path, "a.txt", "v2\n");
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.tag_lightweight("v1.0", &repo.find_object(v1, None).unwrap(), false)
+            .unwrap();
+
+        let state = switch_to_revision(
+            repo_path.clone(),
+            "v1.0".to_string(),
+            Some("hotfix".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(state.name.as_deref(), Some("hotfix"));
+        assert!(!state.detached);
+        assert_eq!(state.sha, v1.to_string());
+        let content = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v1\n");
+        // The original branch is untouched
+        let main_tip = repo.find_branch(&main, BranchType::Local).unwrap();
+        assert_ne!(main_tip.get().target().unwrap(), v1);
+    }
+
+    #[tokio::test]
+    async fn test_switch_to_sha_detaches_head() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let v1 = commit_and_checkout(&repo_path, "a.txt", "v1\n");
+        commit_and_checkout(&repo_path, "a.txt", "v2\n");
+
+        let short_sha = v1.to_string()[..8].to_string();
+        let state = switch_to_revision(repo_path.clone(), short_sha, None)
+            .await
+            .unwrap();
+        assert_eq!(state.name, None);
+        assert!(state.detached);
+        assert_eq!(state.sha, v1.to_string());
+        let content = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "v1\n");
+
+        assert!(
+            switch_to_revision(repo_path, "no-such-rev".to_string(), None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_detached_head_is_reported_not_an_error() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let v1 = commit_and_checkout(&repo_path, "a.txt", "v1\n");
+        create_branch(repo_path.clone(), "정리".to_string(), None)
+            .await
+            .unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.set_head_detached(v1).unwrap();
+
+        let short_sha = repo
+            .find_object(v1, None)
+            .unwrap()
+            .short_id()
+            .unwrap()
+            .as_str()
+            .unwrap()
+            .to_string();
+        let label = format!("HEAD detached at {}", short_sha);
+        assert_eq!(get_current_branch(repo_path.clone()).await.unwrap(), label);
+        let state = get_head_state(repo_path.clone()).await.unwrap();
+        assert!(state.detached && state.name.is_none());
+        assert_eq!(state.sha, v1.to_string());
+
+        let info = crate::commands::git::open_repository(repo_path.clone())
+            .await
+            .unwrap();
+        assert_eq!(info.current_branch, label);
+        assert!(info.is_detached);
+        assert_eq!(info.head_sha, v1.to_string());
+
+        // Branch operations still work without a current branch
+        delete_branch(repo_path.clone(), "정리".to_string(), None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_switch_to_revision_rolls_back_on_dirty_tree() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let v1 = commit_and_checkout(&repo_path, "a.txt", "v1\n");
+        let v2 = commit_and_checkout(&repo_path, "a.txt", "v2\n");
+        std::fs::write(temp_dir.path().join("a.txt"), "local edit\n").unwrap();
+
+        let err = switch_to_revision(
+            repo_path.clone(),
+            v1.to_string(),
+            Some("attempt".to_string()),
+        )
+        .await
+        .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "checkout_conflicts");
+        assert_eq!(payload.details["paths"], serde_json::json!(["a.txt"]));
+
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo.find_branch("attempt", BranchType::Local).is_err());
+        assert!(!repo.head_detached().unwrap());
+        assert_eq!(repo.head().unwrap().target().unwrap(), v2);
+        let content = std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap();
+        assert_eq!(content, "local edit\n");
+    }
+
+    #[tokio::test]
+    async fn test_checkout_restores_worktree_when_head_is_locked() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        let v1 = commit_file(&repo_path, "a.txt", "v1\n");
+        create_branch(repo_path.clone(), "이전".into(), None)
+            .await
+            .unwrap();
+        commit_file(&repo_path, "a.txt", "v2\n");
+        let v2 = commit_file(&repo_path, "b.txt", "new\n");
+        let file = temp_dir.path().join("a.txt");
+        let added = temp_dir.path().join("b.txt");
+        // A held HEAD.lock makes set_head fail after the checkout ran
+        let lock = repo.path().join("HEAD.lock");
+        std::fs::write(&lock, "").unwrap();
+
+        let err = checkout_commit(repo_path.clone(), v1.to_string(), None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("HEAD 변경 실패"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2\n");
+        assert!(added.exists());
+
+        let err = switch_to_revision(repo_path.clone(), v1.to_string(), Some("시도".into()))
+            .await
+            .unwrap_err();
+        assert!(err.contains("HEAD 변경 실패"), "{}", err);
+        assert!(repo.find_branch("시도", BranchType::Local).is_err());
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2\n");
+
+        let err = switch_branch(repo_path.clone(), "이전".into(), None, None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("HEAD 변경 실패"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "v2\n");
+
+        std::fs::remove_file(&lock).unwrap();
+        assert_eq!(repo.head().unwrap().shorthand(), Some(current.as_str()));
+        assert_eq!(repo.head().unwrap().target().unwrap(), v2);
+        assert!(repo.statuses(None).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_branch_checked_out_in_other_worktree() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let hotfix = repo.branch("hotfix", &head, false).unwrap();
+        let wt_dir = tempdir().unwrap();
+        let wt_path = wt_dir.path().join("repo-hotfix");
+        let mut opts = git2::WorktreeAddOptions::new();
+        opts.reference(Some(hotfix.get()));
+        repo.worktree("hotfix", &wt_path, Some(&opts)).unwrap();
+
+        let branches = list_branches(repo_path.clone(), None, None, None, None, None, None)
+            .await
+            .unwrap();
+        let listed = branches.iter().find(|b| b.name == "hotfix").unwrap();
+        let expected = wt_path.to_string_lossy().to_string();
+        assert_eq!(listed.checked_out_in.as_deref(), Some(expected.as_str()));
+        let current = branches.iter().find(|b| b.is_current).unwrap();
+        assert_eq!(current.checked_out_in, None);
+
+        let err = delete_branch(repo_path.clone(), "hotfix".to_string(), Some(true))
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "branch_checked_out");
+        assert_eq!(payload.details["path"], serde_json::json!(expected));
+        assert!(repo.find_branch("hotfix", BranchType::Local).is_ok());
+
+        let err = switch_branch(repo_path.clone(), "hotfix".to_string(), None, None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("branch_checked_out"));
+        switch_branch(repo_path.clone(), "hotfix".to_string(), Some(true), None)
+            .await
+            .unwrap();
+
+        // Seen from the linked worktree, the main checkout is the other one
+        let main = temp_dir.path().to_string_lossy().to_string();
+        let from_worktree = list_branches(expected, None, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(from_worktree
+            .iter()
+            .any(|b| b.checked_out_in.as_deref() == Some(main.as_str())));
+    }
+
+    #[tokio::test]
+    async fn test_prune_gone_branches_after_remote_delete() {
+        use crate::commands::remote::{fetch_remote_with_events, push_changes_with_events};
+
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let origin_dir = tempdir().unwrap();
+        let origin_path = origin_dir.path().join("origin.git");
+        let origin = Repository::init_bare(&origin_path).unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.remote("origin", origin_path.to_str().unwrap())
+            .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("병합됨", &head, false).unwrap();
+        repo.branch("미병합", &head, false).unwrap();
+        let unmerged = commit_on_branch(&repo_path, "미병합", "remote only work");
+        repo.branch("유지", &head, false).unwrap();
+        for branch in ["병합됨", "미병합", "유지"] {
+            push_changes_with_events(
+                None,
+                repo_path.clone(),
+                "origin".into(),
+                branch.into(),
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, false)
+            .await
+            .unwrap();
+        for branch in ["병합됨", "미병합", "유지"] {
+            repo.find_branch(branch, BranchType::Local)
+                .unwrap()
+                .set_upstream(Some(&format!("origin/{}", branch)))
+                .unwrap();
+        }
+        // The current branch is gone too, but must survive
+        repo.set_head("refs/heads/병합됨").unwrap();
+
+        for branch in ["병합됨", "미병합"] {
+            origin
+                .find_reference(&format!("refs/heads/{}", branch))
+                .unwrap()
+                .delete()
+                .unwrap();
+        }
+        // Not pruned yet: the remote-tracking refs still exist
+        assert!(list_gone_branches(repo_path.clone(), None)
+            .await
+            .unwrap()
+            .is_empty());
+        let gone: Vec<String> = list_gone_branches(repo_path.clone(), Some(true))
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.name)
+            .collect();
+        assert_eq!(gone.len(), 2);
+        assert!(gone.contains(&"병합됨".to_string()) && gone.contains(&"미병합".to_string()));
+
+        let dry_run = prune_gone_branches(repo_path.clone(), true, false)
+            .await
+            .unwrap();
+        assert!(dry_run.iter().all(|r| !r.deleted));
+        assert!(repo.find_branch("미병합", BranchType::Local).is_ok());
+        // The unmerged commit only lived on the deleted remote branch
+        let unpushed = dry_run.iter().find(|r| r.name == "미병합").unwrap();
+        assert!(!unpushed.backed_up && unpushed.containing_refs.is_empty());
+        let covered = dry_run.iter().find(|r| r.name == "병합됨").unwrap();
+        assert!(covered.backed_up);
+        assert!(covered.containing_refs.contains(&"refs/heads/유지".to_string()));
+        assert!(covered
+            .containing_refs
+            .contains(&"refs/remotes/origin/유지".to_string()));
+
+        let results = prune_gone_branches(repo_path.clone(), false, false)
+            .await
+            .unwrap();
+        let current = results.iter().find(|r| r.name == "병합됨").unwrap();
+        assert!(!current.deleted && current.error.is_some());
+        let kept = results.iter().find(|r| r.name == "미병합").unwrap();
+        assert!(!kept.deleted);
+        assert_eq!(kept.tip_sha, unmerged.to_string());
+        assert_eq!(kept.upstream.as_deref(), Some("origin/미병합"));
+        let err: crate::commands::models::CommandError =
+            serde_json::from_str(kept.error.as_deref().unwrap()).unwrap();
+        assert_eq!(err.code, "branch_not_merged");
+
+        let results = prune_gone_branches(repo_path, false, true).await.unwrap();
+        assert!(results.iter().any(|r| r.name == "미병합" && r.deleted));
+        assert!(repo.find_branch("미병합", BranchType::Local).is_err());
+        assert!(repo.find_branch("병합됨", BranchType::Local).is_ok());
+        assert!(repo.find_branch("유지", BranchType::Local).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_archive_branch_tags_and_deletes() {
+        use crate::commands::remote::{fetch_remote_with_events, push_changes_with_events};
+
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let origin_dir = tempdir().unwrap();
+        let origin_path = origin_dir.path().join("origin.git");
+        let origin = Repository::init_bare(&origin_path).unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        repo.remote("origin", origin_path.to_str().unwrap())
+            .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("실험", &head, false).unwrap();
+        let tip = commit_on_branch(&repo_path, "실험", "unmerged experiment");
+        push_changes_with_events(
+            None,
+            repo_path.clone(),
+            "origin".into(),
+            "실험".into(),
+            false,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, false)
+            .await
+            .unwrap();
+        repo.find_branch("실험", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/실험"))
+            .unwrap();
+        repo.branch("로컬", &head, false).unwrap();
+
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert!(archive_branch(repo_path.clone(), current, true)
+            .await
+            .is_err());
+
+        let archived = archive_branch(repo_path.clone(), "실험".into(), true)
+            .await
+            .unwrap();
+        assert_eq!(archived.tag_name, "archive/실험");
+        assert_eq!(archived.tip_sha, tip.to_string());
+        assert_eq!(archived.remote.as_deref(), Some("origin"));
+        assert!(archived.tag_pushed && archived.remote_branch_deleted);
+        let tag = repo
+            .revparse_single("archive/실험")
+            .unwrap()
+            .into_tag()
+            .unwrap();
+        assert_eq!(tag.target_id(), tip);
+        assert!(tag.message().unwrap().contains("'실험'"));
+        assert!(repo.find_branch("실험", BranchType::Local).is_err());
+        assert!(origin.refname_to_id("refs/tags/archive/실험").is_ok());
+        assert!(origin.refname_to_id("refs/heads/실험").is_err());
+
+        // No upstream: nothing is attempted remotely
+        let archived = archive_branch(repo_path.clone(), "로컬".into(), true)
+            .await
+            .unwrap();
+        assert!(archived.remote.is_none() && !archived.tag_pushed);
+        assert!(origin.refname_to_id("refs/tags/archive/로컬").is_err());
+
+        repo.branch("로컬", &head, false).unwrap();
+        let err = archive_branch(repo_path, "로컬".into(), false)
+            .await
+            .unwrap_err();
+        let err: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "tag_exists");
+        assert!(repo.find_branch("로컬", BranchType::Local).is_ok());
     }
 }
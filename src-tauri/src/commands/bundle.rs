@@ -1,15 +1,31 @@
 //! Git Bundle operations.
 //!
-//! Uses `git` CLI because `git2` (libgit2) has no bundle API.
+//! Uses `git` CLI (resolved via `cli`) because `git2` (libgit2) has no bundle API.
 //! Bundle files allow transferring Git objects without a network connection.
 
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Child, Stdio};
+use std::sync::Mutex;
 
 use serde::{Deserialize, Serialize};
 
+use super::cli::{git_command, run_with_timeout, GIT_TIMEOUT};
+use super::git::open_repository;
+use super::models::RepositoryInfo;
+use super::repos::add_recent_repo;
 use super::utils::open_repo;
 
+/// Event emitted while `clone_from_bundle` runs.
+const CLONE_PROGRESS_EVENT: &str = "bundle-clone-progress";
+
+// Running bundle clones keyed by target path, so they can be cancelled.
+lazy_static::lazy_static! {
+    static ref RUNNING_CLONES: Mutex<HashMap<String, Child>> = Mutex::new(HashMap::new());
+    static ref CANCELLED_CLONES: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
 // ============================================================================
 // Models
 // ============================================================================
@@ -33,6 +49,17 @@ pub struct BundleVerifyResult {
     pub refs: Vec<String>,
 }
 
+/// Progress of a running bundle clone (parsed from `git clone --progress`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BundleCloneProgress {
+    pub target_path: String,
+    /// e.g. "Receiving objects", "Resolving deltas"
+    pub phase: String,
+    /// 0-100 within the current phase
+    pub percent: u32,
+    pub message: String,
+}
+
 /// Info about available refs for bundling.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BundleRefInfo {
@@ -103,7 +130,7 @@ pub async fn create_bundle(
     // Validate repo exists
     let _ = open_repo(&repo_path)?;
 
-    let mut cmd = Command::new("git");
+    let mut cmd = git_command()?;
     cmd.current_dir(&repo_path);
     cmd.args(["bundle", "create", &output_path]);
 
@@ -115,8 +142,7 @@ pub async fn create_bundle(
         }
     }
 
-    let output = cmd
-        .output()
+    let output = run_with_timeout(&mut cmd, GIT_TIMEOUT)
         .map_err(|e| format!("git bundle 실행 실패: {}", e))?;
 
     if output.status.success() {
@@ -153,20 +179,22 @@ pub async fn verify_bundle(
     }
 
     // Verify
-    let verify_output = Command::new("git")
+    let mut verify_cmd = git_command()?;
+    verify_cmd
         .current_dir(&repo_path)
-        .args(["bundle", "verify", &bundle_path])
-        .output()
+        .args(["bundle", "verify", &bundle_path]);
+    let verify_output = run_with_timeout(&mut verify_cmd, GIT_TIMEOUT)
         .map_err(|e| format!("git bundle verify 실행 실패: {}", e))?;
 
     let stdout = String::from_utf8_lossy(&verify_output.stdout);
     let stderr = String::from_utf8_lossy(&verify_output.stderr);
 
     // List refs in bundle
-    let list_output = Command::new("git")
+    let mut list_cmd = git_command()?;
+    list_cmd
         .current_dir(&repo_path)
-        .args(["bundle", "list-heads", &bundle_path])
-        .output()
+        .args(["bundle", "list-heads", &bundle_path]);
+    let list_output = run_with_timeout(&mut list_cmd, GIT_TIMEOUT)
         .map_err(|e| format!("git bundle list-heads 실행 실패: {}", e))?;
 
     let refs: Vec<String> = String::from_utf8_lossy(&list_output.stdout)
@@ -220,10 +248,9 @@ pub async fn fetch_from_bundle(
 
     let _ = open_repo(&repo_path)?;
 
-    let output = Command::new("git")
-        .current_dir(&repo_path)
-        .args(["fetch", &bundle_path])
-        .output()
+    let mut cmd = git_command()?;
+    cmd.current_dir(&repo_path).args(["fetch", &bundle_path]);
+    let output = run_with_timeout(&mut cmd, GIT_TIMEOUT)
         .map_err(|e| format!("git fetch (bundle) 실행 실패: {}", e))?;
 
     if output.status.success() {
@@ -244,28 +271,244 @@ pub async fn fetch_from_bundle(
 }
 
 /// Clone from a bundle file into a new directory.
+///
+/// Progress is emitted as `bundle-clone-progress` events. On success the new
+/// repository is added to the recent list and its `RepositoryInfo` returned.
 #[tauri::command]
 pub async fn clone_from_bundle(
+    window: tauri::Window,
     bundle_path: String,
     target_path: String,
-) -> Result<String, String> {
-    if !Path::new(&bundle_path).exists() {
+) -> Result<RepositoryInfo, String> {
+    clone_bundle_with_progress(&bundle_path, &target_path, move |progress| {
+        let _ = window.emit(CLONE_PROGRESS_EVENT, progress);
+    })
+    .await
+}
+
+/// Cancel a running `clone_from_bundle` and remove the partial target directory.
+#[tauri::command]
+pub async fn cancel_bundle_clone(target_path: String) -> Result<(), String> {
+    let mut running = RUNNING_CLONES.lock().unwrap();
+    let child = running
+        .get_mut(&target_path)
+        .ok_or("진행 중인 번들 클론이 없습니다")?;
+    CANCELLED_CLONES.lock().unwrap().insert(target_path.clone());
+    child
+        .kill()
+        .map_err(|e| format!("번들 클론 취소 실패: {}", e))?;
+    Ok(())
+}
+
+async fn clone_bundle_with_progress<F>(
+    bundle_path: &str,
+    target_path: &str,
+    on_progress: F,
+) -> Result<RepositoryInfo, String>
+where
+    F: Fn(BundleCloneProgress) + Send + 'static,
+{
+    if !Path::new(bundle_path).exists() {
         return Err("번들 파일이 존재하지 않습니다".to_string());
     }
 
-    if Path::new(&target_path).exists() {
+    let target = Path::new(target_path);
+    if target.exists() {
         return Err("대상 경로가 이미 존재합니다".to_string());
     }
-
-    let output = Command::new("git")
-        .args(["clone", &bundle_path, &target_path])
-        .output()
+    ensure_parent_writable(target)?;
+
+    // No timeout: clones are long-running, report progress, and can be cancelled
+    let mut child = git_command()?
+        .args(["clone", "--progress", bundle_path, target_path])
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
         .map_err(|e| format!("git clone (bundle) 실행 실패: {}", e))?;
 
-    if output.status.success() {
-        Ok(format!("번들에서 클론 완료: {}", target_path))
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        Err(format!("번들에서 클론 실패: {}", stderr.trim()))
+    let stderr = child.stderr.take().ok_or("git 출력 스트림 접근 실패")?;
+    RUNNING_CLONES
+        .lock()
+        .unwrap()
+        .insert(target_path.to_string(), child);
+
+    // Reading git's output and waiting for it block, so keep them off the
+    // async runtime
+    let clone_target = target_path.to_string();
+    let (output, status) = tokio::task::spawn_blocking(move || {
+        let output = read_clone_output(stderr, &clone_target, on_progress);
+        let status = RUNNING_CLONES
+            .lock()
+            .unwrap()
+            .remove(&clone_target)
+            .map(|mut child| child.wait());
+        (output, status)
+    })
+    .await
+    .map_err(|e| format!("번들 클론 작업 실패: {}", e))?;
+    let cancelled = CANCELLED_CLONES.lock().unwrap().remove(target_path);
+
+    let succeeded = matches!(status, Some(Ok(s)) if s.success());
+    if cancelled || !succeeded {
+        // Never leave a half-written repository behind
+        let _ = std::fs::remove_dir_all(target);
+        if cancelled {
+            return Err("번들 클론이 취소되었습니다".to_string());
+        }
+        return Err(format!("번들에서 클론 실패: {}", output.trim()));
+    }
+
+    let canonical = target
+        .canonicalize()
+        .map_err(|e| format!("경로 정규화 실패: {}", e))?
+        .to_string_lossy()
+        .to_string();
+
+    // open_repository applies ensure_utf8_config to the new clone
+    let info = open_repository(canonical.clone()).await?;
+    add_recent_repo(canonical).await?;
+    Ok(info)
+}
+
+/// Collect the lines git writes to `stderr` during a clone, reporting the
+/// progress lines to `on_progress` as they arrive.
+fn read_clone_output<F>(mut stderr: impl Read, target_path: &str, on_progress: F) -> String
+where
+    F: Fn(BundleCloneProgress),
+{
+    // git separates progress updates with '\r' and finished phases with '\n'
+    let mut output = String::new();
+    let mut line = Vec::new();
+    let mut buf = [0u8; 1024];
+    while let Ok(n) = stderr.read(&mut buf) {
+        if n == 0 {
+            break;
+        }
+        for &byte in &buf[..n] {
+            if byte != b'\r' && byte != b'\n' {
+                line.push(byte);
+                continue;
+            }
+            let text = String::from_utf8_lossy(&line).trim().to_string();
+            line.clear();
+            if text.is_empty() {
+                continue;
+            }
+            if let Some((phase, percent)) = parse_git_progress(&text) {
+                on_progress(BundleCloneProgress {
+                    target_path: target_path.to_string(),
+                    phase,
+                    percent,
+                    message: text.clone(),
+                });
+            }
+            output.push_str(&text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Parse a git progress line such as "Receiving objects:  42% (21/50)".
+fn parse_git_progress(line: &str) -> Option<(String, u32)> {
+    let line = line.strip_prefix("remote: ").unwrap_or(line);
+    let (phase, rest) = line.split_once(':')?;
+    let percent = rest.trim_start().split('%').next()?.trim().parse::<u32>().ok()?;
+    Some((phase.trim().to_string(), percent.min(100)))
+}
+
+/// Fail early when the clone target's parent directory is missing or read-only.
+fn ensure_parent_writable(target: &Path) -> Result<(), String> {
+    let parent = match target.parent() {
+        Some(p) if !p.as_os_str().is_empty() => p,
+        _ => Path::new("."),
+    };
+    if !parent.is_dir() {
+        return Err(format!(
+            "대상 상위 폴더가 존재하지 않습니다: {}",
+            parent.display()
+        ));
+    }
+    let probe = parent.join(format!(".gitmul-write-test-{}", std::process::id()));
+    std::fs::write(&probe, b"")
+        .map_err(|e| format!("대상 상위 폴더에 쓸 수 없습니다: {}", e))?;
+    let _ = std::fs::remove_file(&probe);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::utils::ScopedConfigHome;
+    use git2::{Repository, Signature};
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().join("origin");
+        let repo = Repository::init(&repo_path).unwrap();
+        std::fs::write(repo_path.join("README.md"), "# 번들 테스트\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("README.md")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        (temp_dir, repo_path.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn test_parse_git_progress() {
+        assert_eq!(
+            parse_git_progress("Receiving objects:  42% (21/50)"),
+            Some(("Receiving objects".to_string(), 42))
+        );
+        assert_eq!(
+            parse_git_progress("Resolving deltas: 100% (10/10), done."),
+            Some(("Resolving deltas".to_string(), 100))
+        );
+        assert_eq!(parse_git_progress("Cloning into 'x'..."), None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_clone_from_bundle_registers_repo() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let _config_home = ScopedConfigHome::set(&temp_dir.path().join("config"));
+
+        let bundle_path = temp_dir.path().join("repo.bundle");
+        let bundle_path = bundle_path.to_str().unwrap().to_string();
+        create_bundle(repo_path, bundle_path.clone(), vec![])
+            .await
+            .unwrap();
+
+        let target = temp_dir.path().join("clone");
+        let target = target.to_str().unwrap().to_string();
+        let events = std::sync::Arc::new(Mutex::new(Vec::new()));
+        let events_cb = std::sync::Arc::clone(&events);
+        let info = clone_bundle_with_progress(&bundle_path, &target, move |p| {
+            events_cb.lock().unwrap().push(p)
+        })
+        .await
+        .unwrap();
+
+        assert!(Repository::open(&info.path).is_ok());
+        let recent = crate::commands::repos::get_recent_repos().await.unwrap();
+        assert!(recent.iter().any(|r| r.path == info.path));
+        assert!(events.lock().unwrap().iter().all(|p| p.percent <= 100));
+    }
+
+    #[tokio::test]
+    async fn test_clone_from_bundle_rejects_missing_parent() {
+        let (temp_dir, _repo_path) = setup_test_repo();
+        let bundle = temp_dir.path().join("missing.bundle");
+        std::fs::write(&bundle, b"not a bundle").unwrap();
+        let target = temp_dir.path().join("no/such/dir/clone");
+        let result =
+            clone_bundle_with_progress(bundle.to_str().unwrap(), target.to_str().unwrap(), |_| {})
+                .await;
+        assert!(result.unwrap_err().contains("상위 폴더"));
     }
 }
@@ -1,7 +1,8 @@
 use git2::Oid;
 
 use super::models::CherryPickResult;
-use super::utils::open_repo;
+use super::signing::commit_to_head;
+use super::utils::{assert_no_conflicts, open_repo, repo_signature};
 
 /// Cherry-pick a commit.
 #[tauri::command]
@@ -34,13 +35,13 @@ pub fn cherry_pick(repo_path: String, commit_sha: String) -> Result<CherryPickRe
             success: false,
             conflicts,
             message: format!("체리픽 중 충돌이 발생했습니다: {} 개 파일", num_conflicts),
+            signed: false,
+            signature_type: None,
         });
     }
 
     // Auto-commit
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let sig = repo_signature(&repo)?;
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
@@ -61,13 +62,14 @@ pub fn cherry_pick(repo_path: String, commit_sha: String) -> Result<CherryPickRe
         "Cherry-pick: {}",
         commit.message().unwrap_or("No message")
     );
-    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])
-        .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+    let created = commit_to_head(&repo, &sig, &sig, &message, &tree, &[&parent], "commit")?;
 
     Ok(CherryPickResult {
         success: true,
         conflicts: vec![],
         message: "체리픽이 성공적으로 완료되었습니다".to_string(),
+        signed: created.signature_type.is_some(),
+        signature_type: created.signature_type,
     })
 }
 
@@ -79,13 +81,9 @@ pub fn cherry_pick_continue(repo_path: String) -> Result<(), String> {
     let index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
-    if index.has_conflicts() {
-        return Err("아직 해결되지 않은 충돌이 있습니다".to_string());
-    }
+    assert_no_conflicts(&index)?;
 
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let sig = repo_signature(&repo)?;
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
@@ -107,8 +105,7 @@ pub fn cherry_pick_continue(repo_path: String) -> Result<(), String> {
     let message = std::fs::read_to_string(&cherry_msg)
         .unwrap_or_else(|_| "Cherry-pick commit".to_string());
 
-    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])
-        .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+    commit_to_head(&repo, &sig, &sig, &message, &tree, &[&parent], "commit")?;
 
     let _ = std::fs::remove_file(git_dir.join("CHERRY_PICK_HEAD"));
     let _ = std::fs::remove_file(git_dir.join("MERGE_MSG"));
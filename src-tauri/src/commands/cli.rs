@@ -0,0 +1,348 @@
+//! Locating and running the `git` command-line tool.
+//!
+//! GUI launches on macOS get a minimal PATH and stock Windows installs often
+//! have no `git` on PATH at all, so the executable is resolved explicitly:
+//! user override → PATH → well-known install locations, each validated with
+//! `git --version`. Commands run with a sanitized environment and a timeout.
+
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::app_data::{self, AppData};
+use super::models::GitCliInfo;
+use super::utils::command_error;
+
+/// Default timeout for one-shot git invocations.
+pub const GIT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+/// Timeout for the `git --version` probe of each candidate.
+const VERSION_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Environment variables passed through to child processes. Everything else,
+/// notably `GIT_DIR` / `GIT_WORK_TREE` / `GIT_INDEX_FILE` leaking from a parent
+/// git process, is dropped.
+const ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "SYSTEMROOT",
+    "WINDIR",
+    "COMSPEC",
+    "TEMP",
+    "TMP",
+    "TMPDIR",
+    "LANG",
+    "XDG_CONFIG_HOME",
+    "SSH_AUTH_SOCK",
+    "SSH_AGENT_PID",
+    "GIT_SSH",
+    "GIT_SSH_COMMAND",
+];
+
+lazy_static::lazy_static! {
+    static ref RESOLVED_GIT: Mutex<Option<GitCliInfo>> = Mutex::new(None);
+}
+
+/// Persisted CLI preferences.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CliSettings {
+    /// Explicit git executable chosen by the user
+    pub git_path: Option<String>,
+}
+
+impl AppData for CliSettings {
+    const FILE_NAME: &'static str = "cli_settings.json";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+/// The git executable in use, resolving (and caching) it on first call.
+pub fn git_cli() -> Result<GitCliInfo, String> {
+    if let Some(info) = RESOLVED_GIT.lock().ok().and_then(|cached| cached.clone()) {
+        return Ok(info);
+    }
+    let settings: CliSettings = app_data::load().unwrap_or_default();
+    let override_path = settings.git_path.map(PathBuf::from);
+    let path_var = std::env::var_os("PATH");
+    let info = resolve_git_in(
+        override_path.as_deref(),
+        path_var.as_deref(),
+        &fallback_locations(),
+    )?;
+    if let Ok(mut cached) = RESOLVED_GIT.lock() {
+        *cached = Some(info.clone());
+    }
+    Ok(info)
+}
+
+/// A `git` command using the resolved executable and a sanitized environment.
+pub fn git_command() -> Result<Command, String> {
+    let info = git_cli()?;
+    Ok(sanitized_command(Path::new(&info.path)))
+}
+
+/// A command for `program` with only `ENV_ALLOWLIST` inherited.
+/// Interactive credential prompts are disabled so a child can never hang on stdin.
+pub fn sanitized_command(program: &Path) -> Command {
+    let mut cmd = Command::new(program);
+    cmd.env_clear();
+    for key in ENV_ALLOWLIST {
+        if let Some(value) = std::env::var_os(key) {
+            cmd.env(key, value);
+        }
+    }
+    cmd.env("GIT_TERMINAL_PROMPT", "0");
+    cmd.stdin(Stdio::null());
+    cmd
+}
+
+/// Run `cmd` to completion, killing it after `timeout`.
+///
+/// Times out with a `git_cli_timeout` error; stdout/stderr are captured as with
+/// `Command::output`.
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> Result<Output, String> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("명령 실행 실패: {}", e))?;
+
+    // Drain pipes on threads so a chatty child can't block on a full pipe
+    let mut stdout = child.stdout.take();
+    let mut stderr = child.stderr.take();
+    let stdout_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(out) = stdout.as_mut() {
+            let _ = out.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stderr_reader = std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(err) = stderr.as_mut() {
+            let _ = err.read_to_end(&mut buf);
+        }
+        buf
+    });
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() >= deadline => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(command_error(
+                    "git_cli_timeout",
+                    format!(
+                        "명령이 {}초 안에 끝나지 않아 중단했습니다",
+                        timeout.as_secs()
+                    ),
+                    serde_json::json!({ "timeout_secs": timeout.as_secs() }),
+                ));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(20)),
+            Err(e) => return Err(format!("명령 대기 실패: {}", e)),
+        }
+    };
+
+    Ok(Output {
+        status,
+        stdout: stdout_reader.join().unwrap_or_default(),
+        stderr: stderr_reader.join().unwrap_or_default(),
+    })
+}
+
+/// Resolve git from an override, then `path_var`, then `fallbacks`.
+/// Candidates that fail `git --version` are skipped.
+fn resolve_git_in(
+    override_path: Option<&Path>,
+    path_var: Option<&OsStr>,
+    fallbacks: &[PathBuf],
+) -> Result<GitCliInfo, String> {
+    let exe_name = if cfg!(windows) { "git.exe" } else { "git" };
+    let mut candidates: Vec<(PathBuf, &str)> = Vec::new();
+    if let Some(path) = override_path {
+        candidates.push((path.to_path_buf(), "override"));
+    }
+    if let Some(path_var) = path_var {
+        for dir in std::env::split_paths(path_var) {
+            candidates.push((dir.join(exe_name), "path"));
+        }
+    }
+    for path in fallbacks {
+        candidates.push((path.clone(), "fallback"));
+    }
+
+    for (path, source) in candidates {
+        if !path.is_file() {
+            continue;
+        }
+        if let Some(version) = probe_version(&path) {
+            return Ok(GitCliInfo {
+                path: path.to_string_lossy().to_string(),
+                version,
+                source: source.to_string(),
+            });
+        }
+    }
+    Err(git_cli_missing())
+}
+
+/// `git --version` output if `path` is a working git executable.
+fn probe_version(path: &Path) -> Option<String> {
+    let mut cmd = sanitized_command(path);
+    cmd.arg("--version");
+    let output = run_with_timeout(&mut cmd, VERSION_PROBE_TIMEOUT).ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && version.starts_with("git version")).then_some(version)
+}
+
+/// Well-known install locations not always on a GUI app's PATH.
+fn fallback_locations() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if cfg!(windows) {
+        for var in ["ProgramFiles", "ProgramFiles(x86)", "ProgramW6432"] {
+            if let Some(dir) = std::env::var_os(var) {
+                paths.push(PathBuf::from(&dir).join(r"Git\cmd\git.exe"));
+                paths.push(PathBuf::from(&dir).join(r"Git\bin\git.exe"));
+            }
+        }
+        if let Some(dir) = std::env::var_os("LOCALAPPDATA") {
+            paths.push(PathBuf::from(dir).join(r"Programs\Git\cmd\git.exe"));
+        }
+    } else {
+        for path in [
+            "/opt/homebrew/bin/git",
+            "/usr/local/bin/git",
+            "/usr/bin/git",
+            "/Library/Developer/CommandLineTools/usr/bin/git",
+        ] {
+            paths.push(PathBuf::from(path));
+        }
+    }
+    paths
+}
+
+fn git_cli_missing() -> String {
+    let hints: &[&str] = if cfg!(windows) {
+        &[
+            "https://git-scm.com/download/win 에서 Git for Windows를 설치하세요",
+            "winget install --id Git.Git",
+        ]
+    } else if cfg!(target_os = "macos") {
+        &["xcode-select --install", "brew install git"]
+    } else {
+        &["sudo apt install git", "sudo dnf install git"]
+    };
+    command_error(
+        "git_cli_missing",
+        "git 실행 파일을 찾을 수 없습니다. Git을 설치하거나 설정에서 경로를 지정하세요.",
+        serde_json::json!({ "hints": hints }),
+    )
+}
+
+/// Which git executable is in use (path, version, how it was found).
+#[tauri::command]
+pub async fn get_cli_info() -> Result<GitCliInfo, String> {
+    git_cli()
+}
+
+/// Set (or clear with `None`) the git executable override.
+/// The path is validated before it is saved.
+#[tauri::command]
+pub async fn set_git_cli_path(path: Option<String>) -> Result<GitCliInfo, String> {
+    if let Some(path) = &path {
+        if probe_version(Path::new(path)).is_none() {
+            return Err(format!("유효한 git 실행 파일이 아닙니다: {}", path));
+        }
+    }
+    app_data::save(&CliSettings { git_path: path })?;
+    if let Ok(mut cached) = RESOLVED_GIT.lock() {
+        *cached = None;
+    }
+    git_cli()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    /// Write an executable script named `git` into `dir` printing `version_line`.
+    fn fake_git(dir: &Path, version_line: &str) -> PathBuf {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join("git");
+        std::fs::write(&path, format!("#!/bin/sh\necho \"{}\"\n", version_line)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_resolution_order() {
+        let temp = tempdir().unwrap();
+        let custom = fake_git(&temp.path().join("custom"), "git version 9.0.0-override");
+        let on_path = fake_git(&temp.path().join("bin"), "git version 9.0.0-path");
+        let fallback = fake_git(&temp.path().join("opt"), "git version 9.0.0-fallback");
+        let path_var =
+            std::env::join_paths([temp.path().join("empty"), temp.path().join("bin")]).unwrap();
+        let fallbacks = vec![fallback.clone()];
+
+        let info = resolve_git_in(Some(&custom), Some(&path_var), &fallbacks).unwrap();
+        assert_eq!(
+            (info.source.as_str(), info.version.as_str()),
+            ("override", "git version 9.0.0-override")
+        );
+
+        let info = resolve_git_in(None, Some(&path_var), &fallbacks).unwrap();
+        assert_eq!(info.source, "path");
+        assert_eq!(Path::new(&info.path), on_path);
+
+        let info = resolve_git_in(None, None, &fallbacks).unwrap();
+        assert_eq!(info.source, "fallback");
+        assert_eq!(Path::new(&info.path), fallback);
+    }
+
+    #[test]
+    fn test_invalid_candidates_are_skipped() {
+        let temp = tempdir().unwrap();
+        let broken = fake_git(&temp.path().join("broken"), "not git");
+        let fallback = fake_git(&temp.path().join("opt"), "git version 9.0.0-fallback");
+        let path_var = std::env::join_paths([temp.path().join("broken")]).unwrap();
+
+        let info = resolve_git_in(Some(&broken), Some(&path_var), &[fallback]).unwrap();
+        assert_eq!(info.source, "fallback");
+
+        let err = resolve_git_in(Some(&broken), Some(&path_var), &[]).unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "git_cli_missing");
+        assert!(payload.details["hints"]
+            .as_array()
+            .is_some_and(|h| !h.is_empty()));
+    }
+
+    #[test]
+    #[serial]
+    fn test_sanitized_command_drops_git_env_and_times_out() {
+        std::env::set_var("GIT_DIR", "/tmp/leaked");
+        let mut cmd = sanitized_command(Path::new("/bin/sh"));
+        cmd.args(["-c", "echo \"${GIT_DIR:-unset}\""]);
+        let output = run_with_timeout(&mut cmd, Duration::from_secs(10)).unwrap();
+        std::env::remove_var("GIT_DIR");
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "unset");
+
+        let mut slow = sanitized_command(Path::new("/bin/sh"));
+        slow.args(["-c", "sleep 5"]);
+        let err = run_with_timeout(&mut slow, Duration::from_millis(200)).unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "git_cli_timeout");
+    }
+}
@@ -1,54 +1,25 @@
-use git2::{AutotagOption, Cred, FetchOptions, RemoteCallbacks};
+use git2::{AutotagOption, FetchOptions};
+
+use super::credentials::{connectable_url, credential_callbacks, remote_error};
+use super::remote::fetch_depth;
 
 /// Clone a remote repository into the given local path.
 ///
-/// Supports both HTTPS (credential helper) and SSH (key files).
+/// Authenticates like the other remote commands (see `credentials`).
 /// Returns the absolute path to the cloned repository.
+/// With `depth`, only the last `depth` commits are cloned (`git clone --depth`).
 #[tauri::command]
-pub async fn clone_repository(url: String, target_path: String) -> Result<String, String> {
-    let tried_count = std::cell::Cell::new(0u32);
-
-    let mut callbacks = RemoteCallbacks::new();
-    callbacks.credentials(move |_url, username_from_url, allowed_types| {
-        let username = username_from_url.unwrap_or("git");
-        let attempt = tried_count.get();
-        tried_count.set(attempt + 1);
-
-        if allowed_types.is_ssh_key() {
-            let home = dirs::home_dir().unwrap_or_default();
-            let key_files = ["id_ed25519", "id_ecdsa", "id_rsa", "id_dsa"];
-            let existing_keys: Vec<_> = key_files
-                .iter()
-                .map(|k| home.join(".ssh").join(k))
-                .filter(|p| p.exists())
-                .collect();
-
-            if let Some(key_path) = existing_keys.get(attempt as usize) {
-                return Cred::ssh_key(username, None, key_path, None);
-            }
-            return Cred::ssh_key_from_agent(username);
-        }
-
-        if allowed_types.is_user_pass_plaintext() {
-            return Cred::credential_helper(
-                &git2::Config::open_default().unwrap_or_else(|_| git2::Config::new().unwrap()),
-                _url,
-                username_from_url,
-            );
-        }
-
-        if allowed_types.is_default() {
-            return Cred::default();
-        }
-
-        Err(git2::Error::from_str(
-            "인증 방법을 찾을 수 없습니다. SSH 키를 ~/.ssh/에 설치하거나 Git Credential Manager를 설정하세요.",
-        ))
-    });
-
+pub async fn clone_repository(
+    url: String,
+    target_path: String,
+    depth: Option<u32>,
+) -> Result<String, String> {
     let mut fetch_options = FetchOptions::new();
-    fetch_options.remote_callbacks(callbacks);
+    fetch_options.remote_callbacks(credential_callbacks(None, None));
     fetch_options.download_tags(AutotagOption::All);
+    if let Some(depth) = depth {
+        fetch_options.depth(fetch_depth(depth));
+    }
 
     let mut builder = git2::build::RepoBuilder::new();
     builder.fetch_options(fetch_options);
@@ -61,9 +32,16 @@ pub async fn clone_repository(url: String, target_path: String) -> Result<String
             .map_err(|e| format!("대상 디렉토리 생성 실패: {}", e))?;
     }
 
-    let _repo = builder
-        .clone(&url, target)
-        .map_err(|e| format!("클론 실패: {}", e))?;
+    let config = git2::Config::open_default().ok();
+    let resolved = connectable_url(&url, config.as_ref());
+    let repo = builder
+        .clone(resolved.as_deref().unwrap_or(&url), target)
+        .map_err(|e| remote_error("클론 실패", e))?;
+    if resolved.is_some() {
+        // Keep the alias the user gave, as `git clone` would
+        repo.remote_set_url("origin", &url)
+            .map_err(|e| format!("원격 URL 설정 실패: {}", e))?;
+    }
 
     // Return canonical (absolute) path
     let canonical = target
@@ -72,3 +50,4 @@ pub async fn clone_repository(url: String, target_path: String) -> Result<String
 
     Ok(canonical.to_string_lossy().to_string())
 }
+
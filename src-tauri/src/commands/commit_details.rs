@@ -0,0 +1,352 @@
+//! Commit detail payloads for the log view, with a bounded in-process cache so
+//! scrolling through history doesn't recompute the same diffs.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use git2::{Oid, Repository};
+
+use super::diff::commit_file_changes;
+use super::git::{commit_info, ref_decorations};
+use super::models::CommitDetails;
+use super::utils::{open_repo, resolve_revision};
+
+/// Commits kept across all repositories before the least recently used is evicted.
+const MAX_CACHED_DETAILS: usize = 512;
+
+/// Commit content never changes, so only the ref decorations (which move)
+/// are left out of the cache and read fresh on every request.
+struct CachedDetails {
+    details: CommitDetails,
+    last_used: u64,
+}
+
+#[derive(Default)]
+struct DetailCache {
+    entries: HashMap<(String, Oid), CachedDetails>,
+    clock: u64,
+}
+
+impl DetailCache {
+    fn get(&mut self, key: &(String, Oid)) -> Option<CommitDetails> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.entries.get_mut(key).map(|entry| {
+            entry.last_used = clock;
+            entry.details.clone()
+        })
+    }
+
+    fn contains(&self, key: &(String, Oid)) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    fn insert(&mut self, key: (String, Oid), details: CommitDetails) {
+        if !self.entries.contains_key(&key) && self.entries.len() >= MAX_CACHED_DETAILS {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        let last_used = self.clock;
+        self.entries
+            .insert(key, CachedDetails { details, last_used });
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref DETAIL_CACHE: Mutex<DetailCache> = Mutex::new(DetailCache::default());
+}
+
+#[cfg(test)]
+static DETAIL_COMPUTATIONS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Commit info, changed files with line stats, and ref decorations for `sha`.
+/// Abbreviated SHAs and other revisions (`HEAD~2`) are resolved first.
+#[tauri::command]
+pub async fn get_commit_details(repo_path: String, sha: String) -> Result<CommitDetails, String> {
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repo(&repo_path)?;
+        let oid = resolve_revision(&repo, &sha)?.id();
+        let key = (cache_key(&repo), oid);
+
+        let cached = DETAIL_CACHE
+            .lock()
+            .ok()
+            .and_then(|mut cache| cache.get(&key));
+        let mut details = match cached {
+            Some(details) => details,
+            None => {
+                let details = compute_details(&repo, oid)?;
+                if let Ok(mut cache) = DETAIL_CACHE.lock() {
+                    cache.insert(key, details.clone());
+                }
+                details
+            }
+        };
+        details.refs = ref_decorations(&repo)?.remove(&oid).unwrap_or_default();
+        Ok(details)
+    })
+    .await
+    .map_err(|e| format!("커밋 상세 조회 실패: {}", e))?
+}
+
+/// Compute and cache details for a batch of visible log rows in one blocking
+/// task. Returns how many were newly computed; already cached SHAs and SHAs
+/// that no longer resolve (e.g. after a rebase) are skipped.
+#[tauri::command]
+pub async fn prefetch_commit_details(
+    repo_path: String,
+    shas: Vec<String>,
+) -> Result<usize, String> {
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repo(&repo_path)?;
+        let repo_key = cache_key(&repo);
+        let mut computed = 0;
+
+        for sha in shas.iter().take(MAX_CACHED_DETAILS) {
+            let oid = match Oid::from_str(sha) {
+                Ok(oid) => oid,
+                Err(_) => continue,
+            };
+            let key = (repo_key.clone(), oid);
+            if DETAIL_CACHE
+                .lock()
+                .map(|c| c.contains(&key))
+                .unwrap_or(false)
+            {
+                continue;
+            }
+            if repo.find_commit(oid).is_err() {
+                continue;
+            }
+            let details = compute_details(&repo, oid)?;
+            if let Ok(mut cache) = DETAIL_CACHE.lock() {
+                cache.insert(key, details);
+            }
+            computed += 1;
+        }
+        Ok(computed)
+    })
+    .await
+    .map_err(|e| format!("커밋 상세 미리 불러오기 실패: {}", e))?
+}
+
+fn cache_key(repo: &Repository) -> String {
+    repo.path().to_string_lossy().to_string()
+}
+
+fn compute_details(repo: &Repository, oid: Oid) -> Result<CommitDetails, String> {
+    #[cfg(test)]
+    DETAIL_COMPUTATIONS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+    let commit = repo
+        .find_commit(oid)
+        .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    let files = commit_file_changes(repo, &commit)?;
+    let signature_type = repo
+        .extract_signature(&oid, None)
+        .ok()
+        .map(|(signature, _)| signature_kind(&signature).to_string());
+    let details = CommitDetails {
+        commit: commit_info(&commit),
+        author_timestamp: commit.author().when().seconds(),
+        committer: commit.committer().name().unwrap_or("Unknown").to_string(),
+        committer_email: commit.committer().email().unwrap_or("").to_string(),
+        signed: signature_type.is_some(),
+        signature_type,
+        additions: files.iter().map(|f| f.additions).sum(),
+        deletions: files.iter().map(|f| f.deletions).sum(),
+        files,
+        refs: Vec::new(),
+    };
+    Ok(details)
+}
+
+/// Signature format by its armor header, the way git tells them apart.
+fn signature_kind(signature: &[u8]) -> &'static str {
+    if signature.starts_with(b"-----BEGIN SSH SIGNATURE-----") {
+        "ssh"
+    } else if signature.starts_with(b"-----BEGIN SIGNED MESSAGE-----") {
+        "x509"
+    } else {
+        "gpg"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use serial_test::serial;
+    use std::sync::atomic::Ordering;
+    use tempfile::TempDir;
+
+    fn setup_repo(commits: usize) -> (TempDir, String, Vec<String>) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = Signature::now("테스트", "test@example.com").unwrap();
+        let mut shas = Vec::new();
+        for i in 0..commits {
+            std::fs::write(temp_dir.path().join("파일.txt"), format!("{}\n", i)).unwrap();
+            let mut index = repo.index().unwrap();
+            index.add_path(std::path::Path::new("파일.txt")).unwrap();
+            index.write().unwrap();
+            let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+            let parents: Vec<&git2::Commit> = parent.iter().collect();
+            let oid = repo
+                .commit(
+                    Some("HEAD"),
+                    &sig,
+                    &sig,
+                    &format!("커밋 {}", i),
+                    &tree,
+                    &parents,
+                )
+                .unwrap();
+            shas.push(oid.to_string());
+        }
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, path, shas)
+    }
+
+    fn computations() -> usize {
+        DETAIL_COMPUTATIONS.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_prefetched_details_are_cache_hits() {
+        let (_temp_dir, repo_path, shas) = setup_repo(3);
+
+        let before = computations();
+        let computed = prefetch_commit_details(repo_path.clone(), shas.clone())
+            .await
+            .unwrap();
+        assert_eq!(computed, 3);
+        assert_eq!(computations() - before, 3);
+
+        // Already cached: nothing new to compute
+        let again = prefetch_commit_details(repo_path.clone(), shas.clone())
+            .await
+            .unwrap();
+        assert_eq!(again, 0);
+
+        let details = get_commit_details(repo_path.clone(), shas[1].clone())
+            .await
+            .unwrap();
+        assert_eq!(computations() - before, 3);
+        assert_eq!(details.commit.message, "커밋 1");
+        assert_eq!((details.additions, details.deletions), (1, 1));
+        assert_eq!(details.files[0].path, "파일.txt");
+
+        // Decorations are current even for cached entries
+        let repo = Repository::open(&repo_path).unwrap();
+        let target = repo
+            .find_object(Oid::from_str(&shas[1]).unwrap(), None)
+            .unwrap();
+        repo.tag_lightweight("v1", &target, false).unwrap();
+        let details = get_commit_details(repo_path, shas[1].clone())
+            .await
+            .unwrap();
+        assert_eq!(details.refs, vec!["v1".to_string()]);
+        assert_eq!(computations() - before, 3);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_details_show_committer_and_signature() {
+        let (temp_dir, repo_path, shas) = setup_repo(1);
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let author =
+            Signature::new("원작자", "author@example.com", &git2::Time::new(1_000, 0)).unwrap();
+        let committer = Signature::new(
+            "커미터",
+            "committer@example.com",
+            &git2::Time::new(2_000, 0),
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("파일.txt"), "signed\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("파일.txt")).unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let buffer = repo
+            .commit_create_buffer(&author, &committer, "서명", &tree, &[&head])
+            .unwrap();
+        let signed = repo
+            .commit_signed(
+                std::str::from_utf8(&buffer).unwrap(),
+                "-----BEGIN PGP SIGNATURE-----\n\nfake\n-----END PGP SIGNATURE-----",
+                None,
+            )
+            .unwrap()
+            .to_string();
+
+        // Abbreviated SHAs resolve like in git
+        let details = get_commit_details(repo_path.clone(), signed[..8].to_string())
+            .await
+            .unwrap();
+        assert_eq!(details.commit.sha, signed);
+        assert_eq!(details.commit.author, "원작자");
+        assert_eq!(details.author_timestamp, 1_000);
+        assert_eq!(details.committer, "커미터");
+        assert_eq!(details.committer_email, "committer@example.com");
+        assert_eq!(details.commit.timestamp, 2_000);
+        assert!(details.signed);
+        assert_eq!(details.signature_type.as_deref(), Some("gpg"));
+        assert_eq!(details.commit.parent_ids, vec![shas[0].clone()]);
+
+        let unsigned = get_commit_details(repo_path.clone(), "HEAD".to_string())
+            .await
+            .unwrap();
+        assert_eq!(unsigned.commit.sha, shas[0]);
+        assert!(!unsigned.signed && unsigned.signature_type.is_none());
+        assert_eq!(unsigned.committer, unsigned.commit.author);
+
+        assert!(get_commit_details(repo_path, "없는-리비전".to_string())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_prefetch_skips_unknown_shas() {
+        let (_temp_dir, repo_path, shas) = setup_repo(1);
+        let batch = vec![
+            "not-a-sha".to_string(),
+            "0123456789012345678901234567890123456789".to_string(),
+            shas[0].clone(),
+        ];
+        assert_eq!(prefetch_commit_details(repo_path, batch).await.unwrap(), 1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_cache_evicts_least_recently_used() {
+        let mut cache = DetailCache::default();
+        let (_temp_dir, repo_path, _) = setup_repo(1);
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        let details = compute_details(&repo, head).unwrap();
+
+        let key = |i: usize| (format!("repo-{}", i), head);
+        for i in 0..MAX_CACHED_DETAILS {
+            cache.insert(key(i), details.clone());
+        }
+        // Touch the oldest entry so the second one becomes the LRU
+        assert!(cache.get(&key(0)).is_some());
+        cache.insert(key(MAX_CACHED_DETAILS), details);
+
+        assert_eq!(cache.entries.len(), MAX_CACHED_DETAILS);
+        assert!(cache.contains(&key(0)));
+        assert!(!cache.contains(&key(1)));
+        assert!(cache.contains(&key(MAX_CACHED_DETAILS)));
+    }
+}
@@ -0,0 +1,262 @@
+//! Commit message lint for the commit box, run live while typing.
+//!
+//! Checks the rules most teams enforce with commitlint: subject length,
+//! a blank line between subject and body, the Conventional Commits
+//! `type(scope)!: description` header and trailing whitespace. Lengths are
+//! counted in grapheme clusters of the NFC text, so a Hangul syllable is one
+//! character however it was typed. No repository access: it is cheap enough
+//! to call on every keystroke.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::models::{CommitLintConfig, LintFinding};
+use super::utils::normalize_unicode;
+
+const DEFAULT_MAX_SUBJECT_LENGTH: usize = 72;
+const DEFAULT_TYPES: &[&str] = &[
+    "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore", "revert",
+];
+/// Subjects git writes itself; they are never in Conventional Commits form.
+const GENERATED_PREFIXES: &[&str] = &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// Lint `message`, returning findings in line order. Lines starting with `#`
+/// are comments (git strips them) and are ignored.
+#[tauri::command]
+pub fn validate_commit_message(
+    message: String,
+    config: Option<CommitLintConfig>,
+) -> Result<Vec<LintFinding>, String> {
+    let config = config.unwrap_or_default();
+    let max_subject_length = config
+        .max_subject_length
+        .unwrap_or(DEFAULT_MAX_SUBJECT_LENGTH);
+    if max_subject_length == 0 {
+        return Err("제목 최대 길이는 1 이상이어야 합니다".to_string());
+    }
+
+    let message = normalize_unicode(&message);
+    // (line number, text) without comment lines
+    let lines: Vec<(usize, &str)> = message
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.strip_suffix('\r').unwrap_or(line)))
+        .filter(|(_, line)| !line.starts_with('#'))
+        .collect();
+    let mut findings = Vec::new();
+
+    let (subject_line, subject) = lines.first().copied().unwrap_or((1, ""));
+    if subject.trim().is_empty() {
+        findings.push(finding(
+            "subject-empty",
+            "error",
+            "제목이 비어 있습니다",
+            subject_line,
+        ));
+        return Ok(findings);
+    }
+
+    let length = subject.graphemes(true).count();
+    if length > max_subject_length {
+        findings.push(finding(
+            "header-max-length",
+            "warning",
+            format!(
+                "제목이 {}자로 최대 {}자를 넘습니다",
+                length, max_subject_length
+            ),
+            subject_line,
+        ));
+    }
+
+    let generated = GENERATED_PREFIXES.iter().any(|p| subject.starts_with(p));
+    if config.conventional.unwrap_or(true) && !generated {
+        let allowed: Vec<&str> = match &config.allowed_types {
+            Some(types) => types.iter().map(String::as_str).collect(),
+            None => DEFAULT_TYPES.to_vec(),
+        };
+        findings.extend(check_header(subject, subject_line, &allowed));
+    }
+
+    if let Some(&(line, text)) = lines.get(1) {
+        if !text.trim().is_empty() {
+            findings.push(finding(
+                "body-leading-blank",
+                "warning",
+                "제목과 본문 사이에 빈 줄이 필요합니다",
+                line,
+            ));
+        }
+    }
+
+    for &(line, text) in &lines {
+        if text.ends_with([' ', '\t']) {
+            findings.push(finding(
+                "trailing-whitespace",
+                "warning",
+                "줄 끝에 공백이 있습니다",
+                line,
+            ));
+        }
+    }
+    findings.sort_by_key(|f| f.line);
+    Ok(findings)
+}
+
+/// Check `type(scope)!: description` in `subject`.
+fn check_header(subject: &str, line: usize, allowed_types: &[&str]) -> Vec<LintFinding> {
+    let format_error = || {
+        vec![finding(
+            "header-format",
+            "error",
+            "제목은 '타입(범위): 설명' 형식이어야 합니다 (예: feat(ui): 로그인 추가)",
+            line,
+        )]
+    };
+    let Some((header, description)) = subject.split_once(':') else {
+        return format_error();
+    };
+    let header = header.strip_suffix('!').unwrap_or(header);
+    let (commit_type, scope) = match header.split_once('(') {
+        Some((commit_type, rest)) => match rest.strip_suffix(')') {
+            Some(scope) => (commit_type, Some(scope)),
+            None => return format_error(),
+        },
+        None => (header, None),
+    };
+    if commit_type.is_empty() || commit_type.contains(char::is_whitespace) {
+        return format_error();
+    }
+
+    let mut findings = Vec::new();
+    if !allowed_types.contains(&commit_type) {
+        findings.push(finding(
+            "type-enum",
+            "error",
+            format!(
+                "'{}'은(는) 허용된 타입이 아닙니다 (허용: {})",
+                commit_type,
+                allowed_types.join(", ")
+            ),
+            line,
+        ));
+    }
+    if let Some(scope) = scope {
+        if scope.trim().is_empty() || scope.contains(['(', ')']) {
+            findings.push(finding(
+                "scope-format",
+                "error",
+                "범위가 비어 있거나 괄호를 포함합니다",
+                line,
+            ));
+        }
+    }
+    match description.strip_prefix(' ') {
+        Some(text) if !text.trim().is_empty() => {}
+        Some(_) => findings.push(finding(
+            "subject-empty",
+            "error",
+            "콜론 뒤에 설명이 필요합니다",
+            line,
+        )),
+        None => findings.push(finding(
+            "header-format",
+            "error",
+            "콜론 뒤에 공백 한 칸이 필요합니다",
+            line,
+        )),
+    }
+    findings
+}
+
+fn finding(rule: &str, severity: &str, message: impl Into<String>, line: usize) -> LintFinding {
+    LintFinding {
+        rule: rule.to_string(),
+        severity: severity.to_string(),
+        message: message.into(),
+        line,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unicode_normalization::UnicodeNormalization;
+
+    fn rules(message: &str, config: Option<CommitLintConfig>) -> Vec<(String, usize)> {
+        validate_commit_message(message.to_string(), config)
+            .unwrap()
+            .into_iter()
+            .map(|f| (f.rule, f.line))
+            .collect()
+    }
+
+    #[test]
+    fn test_conventional_headers() {
+        assert!(rules("feat(ui): 로그인 화면 추가", None).is_empty());
+        assert!(rules("fix!: 잘못된 인코딩 수정\n\n본문입니다.", None).is_empty());
+        assert!(rules("Merge branch 'main' into 기능", None).is_empty());
+        assert_eq!(
+            rules("로그인 추가", None),
+            vec![("header-format".into(), 1)]
+        );
+        assert_eq!(rules("feature: 추가", None), vec![("type-enum".into(), 1)]);
+        assert_eq!(
+            rules("feat(): 추가", None),
+            vec![("scope-format".into(), 1)]
+        );
+        assert_eq!(rules("feat:추가", None), vec![("header-format".into(), 1)]);
+        assert_eq!(
+            rules("feat: ", None),
+            vec![
+                ("subject-empty".into(), 1),
+                ("trailing-whitespace".into(), 1)
+            ]
+        );
+        assert_eq!(rules("# 주석만\n", None), vec![("subject-empty".into(), 1)]);
+    }
+
+    #[test]
+    fn test_custom_types_and_korean_length() {
+        let config = CommitLintConfig {
+            conventional: None,
+            allowed_types: Some(vec!["기능".into(), "수정".into()]),
+            max_subject_length: Some(10),
+        };
+        // "기능: 한글 제목" is 9 graphemes, even when typed decomposed
+        let decomposed: String = "기능: 한글 제목".nfd().collect();
+        assert!(rules(&decomposed, Some(config.clone())).is_empty());
+        assert_eq!(
+            rules("기능: 한글 제목이 너무 깁니다", Some(config.clone())),
+            vec![("header-max-length".into(), 1)]
+        );
+        assert_eq!(
+            rules("feat: 추가", Some(config)),
+            vec![("type-enum".into(), 1)]
+        );
+        assert!(validate_commit_message(
+            "x".into(),
+            Some(CommitLintConfig {
+                max_subject_length: Some(0),
+                ..Default::default()
+            })
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_body_rules() {
+        let plain = Some(CommitLintConfig {
+            conventional: Some(false),
+            ..Default::default()
+        });
+        assert!(rules("제목\n# 주석\n\n본문", plain.clone()).is_empty());
+        assert_eq!(
+            rules("제목 \n바로 본문\n\n끝\t", plain),
+            vec![
+                ("trailing-whitespace".into(), 1),
+                ("body-leading-blank".into(), 2),
+                ("trailing-whitespace".into(), 4),
+            ]
+        );
+    }
+}
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use super::utils::open_repo;
+
+use git2::{Config, ConfigLevel, Repository};
+
+use super::utils::{normalize_unicode, open_repo};
 
 /// Get git config entries for the repository.
 /// Returns local config values for common keys.
@@ -68,15 +71,6 @@ pub async fn get_remote_url(repo_path: String, remote_name: String) -> Result<St
     Ok(remote.url().unwrap_or("").to_string())
 }
 
-/// Set the remote URL for a given remote name.
-#[tauri::command]
-pub async fn set_remote_url(repo_path: String, remote_name: String, url: String) -> Result<(), String> {
-    let repo = open_repo(&repo_path)?;
-    repo.remote_set_url(&remote_name, &url)
-        .map_err(|e| format!("리모트 URL 변경 실패: {}", e))?;
-    Ok(())
-}
-
 /// List git hooks and their enabled status.
 /// Returns a map of hook name -> (exists, content_preview).
 #[tauri::command]
@@ -139,9 +133,278 @@ pub async fn toggle_git_hook(repo_path: String, hook_name: String, enable: bool)
     Ok(())
 }
 
+/// Keys `get_config_value` / `set_config_value` accept.
+const EDITABLE_KEYS: &[&str] = &["core.autocrlf", "pull.rebase", "init.defaultBranch"];
+
+/// Effective `user.name` / `user.email` and the level each one comes from.
+#[tauri::command]
+pub async fn get_git_identity(repo_path: String) -> Result<GitIdentity, String> {
+    let repo = open_repo(&repo_path)?;
+    let mut config = repo.config().map_err(|e| format!("Git 설정 접근 실패: {}", e))?;
+    let snapshot = config.snapshot().map_err(|e| format!("Git 설정 스냅샷 실패: {}", e))?;
+
+    let (name, name_level) = effective_value(&snapshot, "user.name");
+    let (email, email_level) = effective_value(&snapshot, "user.email");
+    Ok(GitIdentity {
+        name,
+        email,
+        name_level,
+        email_level,
+    })
+}
+
+/// Set `user.name` and `user.email` in the repository config, or in the
+/// global config with `global`, and return the resulting identity.
+#[tauri::command]
+pub async fn set_git_identity(
+    repo_path: String,
+    name: String,
+    email: String,
+    global: bool,
+) -> Result<GitIdentity, String> {
+    let name = normalize_unicode(name.trim());
+    let email = email.trim().to_string();
+    validate_identity_part("이름", &name)?;
+    validate_identity_part("이메일", &email)?;
+
+    let repo = open_repo(&repo_path)?;
+    let mut config = writable_config(&repo, global)?;
+    config
+        .set_str("user.name", &name)
+        .map_err(|e| format!("설정 변경 실패: {}", e))?;
+    config
+        .set_str("user.email", &email)
+        .map_err(|e| format!("설정 변경 실패: {}", e))?;
+    get_git_identity(repo_path).await
+}
+
+/// Effective value of one of `EDITABLE_KEYS`.
+#[tauri::command]
+pub async fn get_config_value(repo_path: String, key: String) -> Result<ConfigValue, String> {
+    let key = editable_key(&key)?;
+    let repo = open_repo(&repo_path)?;
+    let mut config = repo.config().map_err(|e| format!("Git 설정 접근 실패: {}", e))?;
+    let snapshot = config.snapshot().map_err(|e| format!("Git 설정 스냅샷 실패: {}", e))?;
+
+    let (value, level) = effective_value(&snapshot, key);
+    Ok(ConfigValue {
+        key: key.to_string(),
+        value,
+        level,
+    })
+}
+
+/// Set one of `EDITABLE_KEYS` in the repository config, or in the global
+/// config with `global`, and return its effective value.
+#[tauri::command]
+pub async fn set_config_value(
+    repo_path: String,
+    key: String,
+    value: String,
+    global: bool,
+) -> Result<ConfigValue, String> {
+    let key = editable_key(&key)?;
+    let value = validate_config_value(key, &value)?;
+
+    let repo = open_repo(&repo_path)?;
+    let mut config = writable_config(&repo, global)?;
+    config
+        .set_str(key, &value)
+        .map_err(|e| format!("설정 변경 실패: {}", e))?;
+    get_config_value(repo_path, key.to_string()).await
+}
+
+/// `key`'s value and the level ("local", "global", ...) it is set at.
+fn effective_value(config: &Config, key: &str) -> (Option<String>, Option<String>) {
+    match config.get_entry(key) {
+        Ok(entry) => (
+            entry.value().map(normalize_unicode),
+            Some(level_name(entry.level()).to_string()),
+        ),
+        Err(_) => (None, None),
+    }
+}
+
+fn level_name(level: ConfigLevel) -> &'static str {
+    match level {
+        ConfigLevel::Local => "local",
+        ConfigLevel::Global => "global",
+        ConfigLevel::XDG => "xdg",
+        ConfigLevel::System | ConfigLevel::ProgramData => "system",
+        _ => "app",
+    }
+}
+
+/// The repository's own config file, or the user's global one (the existing
+/// `~/.gitconfig` or XDG file, else a new `~/.gitconfig`).
+fn writable_config(repo: &Repository, global: bool) -> Result<Config, String> {
+    if !global {
+        return repo
+            .config()
+            .and_then(|config| config.open_level(ConfigLevel::Local))
+            .map_err(|e| format!("Git 설정 접근 실패: {}", e));
+    }
+    let path = Config::find_global()
+        .or_else(|_| Config::find_xdg())
+        .ok()
+        .or_else(|| dirs::home_dir().map(|home| home.join(".gitconfig")))
+        .ok_or("전역 Git 설정 파일 위치를 찾을 수 없습니다")?;
+    Config::open(&path).map_err(|e| format!("전역 Git 설정 열기 실패: {}", e))
+}
+
+/// Identity parts end up in `Name <email>` commit headers.
+fn validate_identity_part(label: &str, value: &str) -> Result<(), String> {
+    if value.is_empty() {
+        return Err(format!("{}이(가) 비어 있습니다", label));
+    }
+    if value.contains(['<', '>', '\n', '\r']) {
+        return Err(format!("{}에 '<', '>' 또는 줄바꿈을 쓸 수 없습니다", label));
+    }
+    Ok(())
+}
+
+/// `key` spelled as in `EDITABLE_KEYS` (config keys are case-insensitive).
+fn editable_key(key: &str) -> Result<&'static str, String> {
+    EDITABLE_KEYS
+        .iter()
+        .find(|allowed| allowed.eq_ignore_ascii_case(key.trim()))
+        .copied()
+        .ok_or_else(|| format!("'{}'은(는) 변경할 수 없는 설정입니다", key))
+}
+
+/// `value` as it should be stored for `key`, or an error when git would
+/// reject it.
+fn validate_config_value(key: &str, value: &str) -> Result<String, String> {
+    let value = normalize_unicode(value.trim());
+    let allowed: &[&str] = match key {
+        "core.autocrlf" => &["true", "false", "input"],
+        "pull.rebase" => &["true", "false", "merges", "interactive"],
+        _ => {
+            let valid = git2::Reference::is_valid_name(&format!("refs/heads/{}", value));
+            if !valid {
+                return Err(format!("'{}'은(는) 올바른 브랜치 이름이 아닙니다", value));
+            }
+            return Ok(value);
+        }
+    };
+    let lower = value.to_lowercase();
+    if allowed.contains(&lower.as_str()) {
+        Ok(lower)
+    } else {
+        Err(format!(
+            "{} 값은 {} 중 하나여야 합니다",
+            key,
+            allowed.join(", ")
+        ))
+    }
+}
+
 #[derive(serde::Serialize)]
 pub struct HookInfo {
     pub name: String,
     pub enabled: bool,
     pub has_sample: bool,
 }
+
+/// Commit identity as git resolves it. Levels are "local", "global", "xdg",
+/// "system" or "app"; None when the value is not set anywhere.
+#[derive(serde::Serialize)]
+pub struct GitIdentity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub name_level: Option<String>,
+    pub email_level: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ConfigValue {
+    pub key: String,
+    pub value: Option<String>,
+    pub level: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, repo_path)
+    }
+
+    #[tokio::test]
+    async fn test_set_local_identity() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let identity = set_git_identity(
+            repo_path.clone(),
+            " 홍길동 ".into(),
+            "hong@example.com".into(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(identity.name.as_deref(), Some("홍길동"));
+        assert_eq!(identity.email.as_deref(), Some("hong@example.com"));
+        assert_eq!(identity.name_level.as_deref(), Some("local"));
+        assert_eq!(identity.email_level.as_deref(), Some("local"));
+
+        assert!(
+            set_git_identity(repo_path.clone(), "".into(), "a@b.c".into(), false)
+                .await
+                .is_err()
+        );
+        assert!(
+            set_git_identity(repo_path, "홍 <x>".into(), "a@b.c".into(), false)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config_value_whitelist() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let value = set_config_value(
+            repo_path.clone(),
+            "pull.rebase".into(),
+            "TRUE".into(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(value.value.as_deref(), Some("true"));
+        assert_eq!(value.level.as_deref(), Some("local"));
+
+        let value = set_config_value(
+            repo_path.clone(),
+            "init.defaultbranch".into(),
+            "메인".into(),
+            false,
+        )
+        .await
+        .unwrap();
+        assert_eq!(value.key, "init.defaultBranch");
+        assert_eq!(value.value.as_deref(), Some("메인"));
+
+        for (key, value) in [
+            ("core.autocrlf", "sometimes"),
+            ("init.defaultBranch", "a..b"),
+            ("user.name", "x"),
+            ("core.hooksPath", "/tmp"),
+        ] {
+            assert!(
+                set_config_value(repo_path.clone(), key.into(), value.into(), false)
+                    .await
+                    .is_err(),
+                "{} = {}",
+                key,
+                value
+            );
+        }
+        assert!(get_config_value(repo_path, "user.email".into())
+            .await
+            .is_err());
+    }
+}
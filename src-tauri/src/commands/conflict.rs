@@ -1,7 +1,14 @@
 use std::path::Path;
 
-use super::models::{ConflictFile, ConflictInfo};
-use super::utils::{normalize_unicode, open_repo, read_blob_content};
+use super::diff::content_looks_binary;
+use super::models::{
+    ConflictFile, ConflictInfo, ConflictResolutionPreview, ConflictSuggestion, MergeFileVersion,
+    MergeFileVersions, VersionPairStats,
+};
+use super::utils::{
+    command_error, normalize_unicode, open_repo, read_blob_content, resolve_revision,
+    safe_repo_path,
+};
 
 /// Get list of conflicted files with content from each side.
 #[tauri::command]
@@ -80,70 +87,11 @@ pub fn resolve_conflict(
     content: Option<String>,
 ) -> Result<(), String> {
     let normalized_path = normalize_unicode(&file_path);
+    let file_full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
     let repo = open_repo(&repo_path)?;
-    let file_full_path = Path::new(&repo_path).join(&normalized_path);
-
-    match resolution.as_str() {
-        "ours" => {
-            let index = repo
-                .index()
-                .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
-            let conflict = index
-                .conflicts()
-                .map_err(|e| format!("충돌 접근 실패: {}", e))?
-                .find(|c| {
-                    if let Ok(conflict) = c {
-                        if let Some(our) = &conflict.our {
-                            return String::from_utf8_lossy(&our.path) == normalized_path;
-                        }
-                    }
-                    false
-                })
-                .ok_or("충돌 파일을 찾을 수 없습니다")?
-                .map_err(|e| format!("충돌 정보 읽기 실패: {}", e))?;
-
-            if let Some(our) = conflict.our {
-                let blob_content = read_blob_content(&repo, &our.id)
-                    .ok_or("우리 측 콘텐츠를 읽을 수 없습니다")?;
-                std::fs::write(&file_full_path, blob_content)
-                    .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
-            }
-        }
-        "theirs" => {
-            let index = repo
-                .index()
-                .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
-            let conflict = index
-                .conflicts()
-                .map_err(|e| format!("충돌 접근 실패: {}", e))?
-                .find(|c| {
-                    if let Ok(conflict) = c {
-                        if let Some(their) = &conflict.their {
-                            return String::from_utf8_lossy(&their.path) == normalized_path;
-                        }
-                    }
-                    false
-                })
-                .ok_or("충돌 파일을 찾을 수 없습니다")?
-                .map_err(|e| format!("충돌 정보 읽기 실패: {}", e))?;
-
-            if let Some(their) = conflict.their {
-                let blob_content = read_blob_content(&repo, &their.id)
-                    .ok_or("상대방 측 콘텐츠를 읽을 수 없습니다")?;
-                std::fs::write(&file_full_path, blob_content)
-                    .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
-            }
-        }
-        "manual" => {
-            if let Some(content) = content {
-                std::fs::write(&file_full_path, content)
-                    .map_err(|e| format!("파일 쓰기 실패: {}", e))?;
-            } else {
-                return Err("수동 해결 시 콘텐츠가 필요합니다".to_string());
-            }
-        }
-        _ => return Err(format!("알 수 없는 해결 방법: {}", resolution)),
-    }
+
+    let resolved = resolved_content(&repo, &normalized_path, &resolution, content)?;
+    std::fs::write(&file_full_path, resolved).map_err(|e| format!("파일 쓰기 실패: {}", e))?;
 
     // Stage the resolved file
     let mut index = repo
@@ -159,6 +107,376 @@ pub fn resolve_conflict(
     Ok(())
 }
 
+/// Preview what `resolve_conflict` would write, without touching the worktree or index.
+/// Includes a diff against the current working-tree file so the user sees what they'd lose.
+#[tauri::command]
+pub fn preview_conflict_resolution(
+    repo_path: String,
+    file_path: String,
+    resolution: String,
+    content: Option<String>,
+) -> Result<ConflictResolutionPreview, String> {
+    let normalized_path = normalize_unicode(&file_path);
+    let file_full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    let repo = open_repo(&repo_path)?;
+    let resolved = resolved_content(&repo, &normalized_path, &resolution, content)?;
+
+    let worktree = std::fs::read(&file_full_path).unwrap_or_default();
+    let is_binary = content_looks_binary(&resolved) || content_looks_binary(&worktree);
+    let sha = git2::Oid::hash_object(git2::ObjectType::Blob, &resolved)
+        .map_err(|e| format!("해시 계산 실패: {}", e))?
+        .to_string();
+
+    let mut preview = ConflictResolutionPreview {
+        path: normalized_path.clone(),
+        resolution,
+        is_binary,
+        content: None,
+        size: resolved.len() as u64,
+        sha,
+        line_count: 0,
+        additions: 0,
+        deletions: 0,
+        diff: String::new(),
+    };
+    if is_binary {
+        return Ok(preview);
+    }
+
+    preview.line_count = resolved.split_inclusive(|b| *b == b'\n').count() as u32;
+    let mut patch = git2::Patch::from_buffers(
+        &worktree,
+        Some(Path::new(&normalized_path)),
+        &resolved,
+        Some(Path::new(&normalized_path)),
+        None,
+    )
+    .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let (_, additions, deletions) = patch
+        .line_stats()
+        .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+    preview.additions = additions as u32;
+    preview.deletions = deletions as u32;
+    preview.diff = patch
+        .to_buf()
+        .map_err(|e| format!("Diff 출력 실패: {}", e))?
+        .as_str()
+        .unwrap_or("")
+        .to_string();
+    preview.content = Some(String::from_utf8_lossy(&resolved).into_owned());
+    Ok(preview)
+}
+
+/// Content a resolution produces: the conflicted blob of the chosen side, or `content` for "manual".
+fn resolved_content(
+    repo: &git2::Repository,
+    normalized_path: &str,
+    resolution: &str,
+    content: Option<String>,
+) -> Result<Vec<u8>, String> {
+    match resolution {
+        "ours" => conflict_side_content(
+            repo,
+            normalized_path,
+            |c| c.our.as_ref(),
+            "우리 측 콘텐츠를 읽을 수 없습니다",
+        ),
+        "theirs" => conflict_side_content(
+            repo,
+            normalized_path,
+            |c| c.their.as_ref(),
+            "상대방 측 콘텐츠를 읽을 수 없습니다",
+        ),
+        "manual" => content
+            .map(String::into_bytes)
+            .ok_or_else(|| "수동 해결 시 콘텐츠가 필요합니다".to_string()),
+        _ => Err(format!("알 수 없는 해결 방법: {}", resolution)),
+    }
+}
+
+/// Raw blob of one side (`pick`) of the conflict on `normalized_path`.
+fn conflict_side_content(
+    repo: &git2::Repository,
+    normalized_path: &str,
+    pick: fn(&git2::IndexConflict) -> Option<&git2::IndexEntry>,
+    unreadable: &str,
+) -> Result<Vec<u8>, String> {
+    let index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+    let conflict = index
+        .conflicts()
+        .map_err(|e| format!("충돌 접근 실패: {}", e))?
+        .find(|c| match c {
+            Ok(conflict) => pick(conflict)
+                .is_some_and(|entry| String::from_utf8_lossy(&entry.path) == normalized_path),
+            Err(_) => false,
+        })
+        .ok_or("충돌 파일을 찾을 수 없습니다")?
+        .map_err(|e| format!("충돌 정보 읽기 실패: {}", e))?;
+
+    let id = pick(&conflict)
+        .map(|entry| entry.id)
+        .ok_or("충돌 파일을 찾을 수 없습니다")?;
+    repo.find_blob(id)
+        .map(|blob| blob.content().to_vec())
+        .map_err(|_| unreadable.to_string())
+}
+
+/// Suggest resolutions for conflicts that are only conflicts on the surface:
+/// - re-merging while ignoring whitespace changes comes out clean, or
+/// - both sides only added lines, so keeping both (union) loses nothing.
+///
+/// Files with a real overlap, binary content, or a missing side get no entry.
+/// Nothing is written; the UI applies a suggestion through `resolve_conflict`.
+#[tauri::command]
+pub fn suggest_conflict_resolutions(repo_path: String) -> Result<Vec<ConflictSuggestion>, String> {
+    let repo = open_repo(&repo_path)?;
+    let index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+    let conflicts = index
+        .conflicts()
+        .map_err(|e| format!("충돌 정보 접근 실패: {}", e))?;
+
+    let mut suggestions = Vec::new();
+    for conflict in conflicts {
+        let conflict = conflict.map_err(|e| format!("충돌 항목 읽기 실패: {}", e))?;
+        let (ancestor, our, their) = match (&conflict.ancestor, &conflict.our, &conflict.their) {
+            (Some(a), Some(o), Some(t)) => (a, o, t),
+            _ => continue,
+        };
+        let read = |id| {
+            repo.find_blob(id)
+                .map(|blob| blob.content().to_vec())
+                .map_err(|e| format!("파일 내용 읽기 실패: {}", e))
+        };
+        let (base, ours, theirs) = (read(ancestor.id)?, read(our.id)?, read(their.id)?);
+        if [&base, &ours, &theirs]
+            .iter()
+            .any(|d| content_looks_binary(d))
+        {
+            continue;
+        }
+
+        let path = normalize_unicode(&String::from_utf8_lossy(&our.path));
+        let suggestion = |strategy: &str, merged: Vec<u8>| {
+            let (resolution, content) = if merged == ours {
+                ("ours", None)
+            } else if merged == theirs {
+                ("theirs", None)
+            } else {
+                (
+                    "manual",
+                    Some(String::from_utf8_lossy(&merged).into_owned()),
+                )
+            };
+            ConflictSuggestion {
+                path: path.clone(),
+                strategy: strategy.to_string(),
+                resolution: resolution.to_string(),
+                content,
+            }
+        };
+
+        let mut opts = git2::MergeOptions::new();
+        opts.ignore_whitespace(true);
+        if let Some(merged) = merge_contents(&repo, &base, &ours, &theirs, &mut opts)? {
+            suggestions.push(suggestion("ignore_whitespace", merged));
+            continue;
+        }
+
+        let only_additions = |side: &[u8]| -> Result<bool, String> {
+            let patch = git2::Patch::from_buffers(&base, None, side, None, None)
+                .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+            let (_, _, deletions) = patch
+                .line_stats()
+                .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+            Ok(deletions == 0)
+        };
+        if only_additions(&ours)? && only_additions(&theirs)? {
+            let mut opts = git2::MergeOptions::new();
+            opts.file_favor(git2::FileFavor::Union);
+            if let Some(merged) = merge_contents(&repo, &base, &ours, &theirs, &mut opts)? {
+                suggestions.push(suggestion("union", merged));
+            }
+        }
+    }
+    Ok(suggestions)
+}
+
+/// Three-way merge of raw file contents; `None` if conflicts remain.
+fn merge_contents(
+    repo: &git2::Repository,
+    base: &[u8],
+    ours: &[u8],
+    theirs: &[u8],
+    opts: &mut git2::MergeOptions,
+) -> Result<Option<Vec<u8>>, String> {
+    let tree_of = |data: &[u8]| -> Result<git2::Tree, git2::Error> {
+        let blob = repo.blob(data)?;
+        let mut builder = repo.treebuilder(None)?;
+        builder.insert("file", blob, 0o100644)?;
+        repo.find_tree(builder.write()?)
+    };
+    let merge_err = |e: git2::Error| format!("병합 시뮬레이션 실패: {}", e);
+    let (base, ours, theirs) = (
+        tree_of(base).map_err(merge_err)?,
+        tree_of(ours).map_err(merge_err)?,
+        tree_of(theirs).map_err(merge_err)?,
+    );
+    let merged = repo
+        .merge_trees(&base, &ours, &theirs, Some(opts))
+        .map_err(merge_err)?;
+    if merged.has_conflicts() {
+        return Ok(None);
+    }
+    match merged.get_path(Path::new("file"), 0) {
+        Some(entry) => Ok(Some(
+            repo.find_blob(entry.id)
+                .map_err(merge_err)?
+                .content()
+                .to_vec(),
+        )),
+        None => Ok(None),
+    }
+}
+
+/// Reconstruct the three-way view of `file_path` in an already recorded merge
+/// commit: merge base, ours (first parent), theirs (second parent) and the
+/// committed result. Sides where the file doesn't exist come back empty.
+/// Fails with `not_a_merge_commit` for commits with fewer than two parents.
+#[tauri::command]
+pub fn get_merge_file_versions(
+    repo_path: String,
+    merge_commit_sha: String,
+    file_path: String,
+) -> Result<MergeFileVersions, String> {
+    let normalized_path = normalize_unicode(&file_path);
+    let repo = open_repo(&repo_path)?;
+    let merge = resolve_revision(&repo, &merge_commit_sha)?;
+    if merge.parent_count() < 2 {
+        return Err(command_error(
+            "not_a_merge_commit",
+            format!(
+                "커밋 {}은(는) 병합 커밋이 아닙니다 (부모 {}개)",
+                &merge.id().to_string()[..7],
+                merge.parent_count()
+            ),
+            serde_json::json!({ "sha": merge.id().to_string(), "parents": merge.parent_count() }),
+        ));
+    }
+
+    let parent = |i| {
+        merge
+            .parent(i)
+            .map_err(|e| format!("부모 커밋 접근 실패: {}", e))
+    };
+    let (ours_commit, theirs_commit) = (parent(0)?, parent(1)?);
+    let base_commit = repo
+        .merge_base(ours_commit.id(), theirs_commit.id())
+        .ok()
+        .and_then(|oid| repo.find_commit(oid).ok());
+
+    let path = Path::new(&normalized_path);
+    let base = blob_at(&repo, base_commit.as_ref(), path)?;
+    let ours = blob_at(&repo, Some(&ours_commit), path)?;
+    let theirs = blob_at(&repo, Some(&theirs_commit), path)?;
+    let result = blob_at(&repo, Some(&merge), path)?;
+    if [&base, &ours, &theirs, &result].iter().all(|b| b.is_none()) {
+        return Err(format!(
+            "'{}' 파일이 병합의 어느 쪽에도 없습니다",
+            normalized_path
+        ));
+    }
+
+    Ok(MergeFileVersions {
+        path: normalized_path.clone(),
+        merge_commit: merge.id().to_string(),
+        merge_base: base_commit.as_ref().map(|c| c.id().to_string()),
+        base_to_ours: pair_stats(&base, &ours, path)?,
+        base_to_theirs: pair_stats(&base, &theirs, path)?,
+        ours_to_result: pair_stats(&ours, &result, path)?,
+        theirs_to_result: pair_stats(&theirs, &result, path)?,
+        base: file_version(base),
+        ours: file_version(ours),
+        theirs: file_version(theirs),
+        result: file_version(result),
+    })
+}
+
+/// Blob SHA and content of `path` in `commit`, `None` if it isn't there.
+fn blob_at(
+    repo: &git2::Repository,
+    commit: Option<&git2::Commit>,
+    path: &Path,
+) -> Result<Option<(git2::Oid, Vec<u8>)>, String> {
+    let commit = match commit {
+        Some(commit) => commit,
+        None => return Ok(None),
+    };
+    let tree = commit
+        .tree()
+        .map_err(|e| format!("트리 접근 실패: {}", e))?;
+    let entry = match tree.get_path(path) {
+        Ok(entry) if entry.kind() == Some(git2::ObjectType::Blob) => entry,
+        _ => return Ok(None),
+    };
+    let blob = repo
+        .find_blob(entry.id())
+        .map_err(|e| format!("파일 내용 읽기 실패: {}", e))?;
+    Ok(Some((entry.id(), blob.content().to_vec())))
+}
+
+fn file_version(blob: Option<(git2::Oid, Vec<u8>)>) -> MergeFileVersion {
+    match blob {
+        Some((oid, data)) => {
+            let is_binary = content_looks_binary(&data);
+            MergeFileVersion {
+                blob_sha: Some(oid.to_string()),
+                is_binary,
+                content: if is_binary {
+                    None
+                } else {
+                    Some(String::from_utf8_lossy(&data).into_owned())
+                },
+            }
+        }
+        None => MergeFileVersion {
+            blob_sha: None,
+            is_binary: false,
+            content: None,
+        },
+    }
+}
+
+/// Line stats from `old` to `new`; a missing side counts as empty and binary
+/// pairs report zero.
+fn pair_stats(
+    old: &Option<(git2::Oid, Vec<u8>)>,
+    new: &Option<(git2::Oid, Vec<u8>)>,
+    path: &Path,
+) -> Result<VersionPairStats, String> {
+    let empty = Vec::new();
+    let old = old.as_ref().map_or(&empty, |(_, data)| data);
+    let new = new.as_ref().map_or(&empty, |(_, data)| data);
+    if content_looks_binary(old) || content_looks_binary(new) {
+        return Ok(VersionPairStats {
+            additions: 0,
+            deletions: 0,
+        });
+    }
+    let patch = git2::Patch::from_buffers(old, Some(path), new, Some(path), None)
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let (_, additions, deletions) = patch
+        .line_stats()
+        .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+    Ok(VersionPairStats {
+        additions: additions as u32,
+        deletions: deletions as u32,
+    })
+}
+
 /// Abort merge (hard reset to HEAD).
 #[tauri::command]
 pub fn abort_merge(repo_path: String) -> Result<(), String> {
@@ -181,3 +499,263 @@ pub fn abort_merge(repo_path: String) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, content: &str, message: &str) -> git2::Oid {
+        fs::write(repo.workdir().unwrap().join("file.txt"), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// Repo mid-merge with `file.txt` conflicted: ours "ours\n", theirs "theirs\nmore\n".
+    fn setup_conflict() -> (TempDir, String) {
+        setup_conflict_with("base\n", "ours\n", "theirs\nmore\n")
+    }
+
+    fn setup_conflict_with(base: &str, ours: &str, theirs: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let base = commit_file(&repo, base, "Base");
+        let default_branch = repo.head().unwrap().name().unwrap().to_string();
+
+        repo.branch("other", &repo.find_commit(base).unwrap(), false)
+            .unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        commit_file(&repo, theirs, "Theirs");
+
+        repo.set_head(&default_branch).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(&repo, ours, "Ours");
+
+        let other = repo.find_reference("refs/heads/other").unwrap();
+        let annotated = repo.reference_to_annotated_commit(&other).unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+        assert!(repo.index().unwrap().has_conflicts());
+
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, path)
+    }
+
+    fn preview(
+        repo_path: &str,
+        resolution: &str,
+        content: Option<&str>,
+    ) -> ConflictResolutionPreview {
+        preview_conflict_resolution(
+            repo_path.to_string(),
+            "file.txt".to_string(),
+            resolution.to_string(),
+            content.map(str::to_string),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_preview_does_not_touch_worktree_or_index() {
+        let (temp_dir, repo_path) = setup_conflict();
+        let worktree_before = fs::read_to_string(temp_dir.path().join("file.txt")).unwrap();
+
+        let ours = preview(&repo_path, "ours", None);
+        assert_eq!(ours.content.as_deref(), Some("ours\n"));
+        assert_eq!(ours.line_count, 1);
+        assert!(!ours.is_binary);
+        // Worktree holds conflict markers; choosing ours drops them and their side
+        assert!(ours.deletions > 0);
+        assert!(ours.diff.contains("-theirs"));
+
+        let theirs = preview(&repo_path, "theirs", None);
+        assert_eq!(theirs.content.as_deref(), Some("theirs\nmore\n"));
+        assert_eq!(theirs.line_count, 2);
+        assert_eq!(theirs.size, 12);
+        assert_eq!(
+            theirs.sha,
+            git2::Oid::hash_object(git2::ObjectType::Blob, b"theirs\nmore\n")
+                .unwrap()
+                .to_string()
+        );
+
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+            worktree_before
+        );
+        assert!(Repository::open(&repo_path)
+            .unwrap()
+            .index()
+            .unwrap()
+            .has_conflicts());
+    }
+
+    #[test]
+    fn test_preview_manual_content() {
+        let (temp_dir, repo_path) = setup_conflict();
+        // Pretend the user already cleaned up the file to "ours\n"
+        fs::write(temp_dir.path().join("file.txt"), "ours\n").unwrap();
+
+        let manual = preview(&repo_path, "manual", Some("ours\nmerged\n"));
+        assert_eq!(manual.content.as_deref(), Some("ours\nmerged\n"));
+        assert_eq!((manual.additions, manual.deletions), (1, 0));
+        assert_eq!(manual.line_count, 2);
+
+        assert!(preview_conflict_resolution(
+            repo_path,
+            "file.txt".to_string(),
+            "manual".to_string(),
+            None
+        )
+        .is_err());
+    }
+
+    /// Commit a tree holding `files` on top of `parents` without touching the worktree.
+    fn commit_tree(
+        repo: &Repository,
+        files: &[(&str, &[u8])],
+        parents: &[git2::Oid],
+        message: &str,
+    ) -> git2::Oid {
+        let mut builder = repo.treebuilder(None).unwrap();
+        for (name, data) in files {
+            let blob = repo.blob(data).unwrap();
+            builder.insert(name, blob, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = parents
+            .iter()
+            .map(|p| repo.find_commit(*p).unwrap())
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(None, &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_merge_file_versions_both_sides_modified() {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let base = commit_tree(&repo, &[("문서.txt", b"a\nb\nc\n")], &[], "Base");
+        let ours = commit_tree(&repo, &[("문서.txt", b"A\nb\nc\n")], &[base], "Ours");
+        let theirs = commit_tree(
+            &repo,
+            &[("문서.txt", b"a\nb\nC\n"), ("logo.png", b"\x89PNG\0\0")],
+            &[base],
+            "Theirs",
+        );
+        let merge = commit_tree(
+            &repo,
+            &[
+                ("문서.txt", b"A\nb\nC\nresolved\n"),
+                ("logo.png", b"\x89PNG\0\0"),
+            ],
+            &[ours, theirs],
+            "Merge",
+        );
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+
+        let versions =
+            get_merge_file_versions(repo_path.clone(), merge.to_string(), "문서.txt".to_string())
+                .unwrap();
+        assert_eq!(versions.merge_base, Some(base.to_string()));
+        assert_eq!(versions.base.content.as_deref(), Some("a\nb\nc\n"));
+        assert_eq!(versions.ours.content.as_deref(), Some("A\nb\nc\n"));
+        assert_eq!(versions.theirs.content.as_deref(), Some("a\nb\nC\n"));
+        assert_eq!(
+            versions.result.content.as_deref(),
+            Some("A\nb\nC\nresolved\n")
+        );
+        let one_line = VersionPairStats {
+            additions: 1,
+            deletions: 1,
+        };
+        assert_eq!(versions.base_to_ours, one_line);
+        assert_eq!(versions.base_to_theirs, one_line);
+        // Result took theirs' line and added one more
+        assert_eq!(
+            versions.ours_to_result,
+            VersionPairStats {
+                additions: 2,
+                deletions: 1
+            }
+        );
+        assert_eq!(
+            versions.theirs_to_result,
+            VersionPairStats {
+                additions: 2,
+                deletions: 1
+            }
+        );
+
+        // Binary file added only on theirs: missing in base and ours
+        let image =
+            get_merge_file_versions(repo_path.clone(), merge.to_string(), "logo.png".to_string())
+                .unwrap();
+        assert!(image.base.blob_sha.is_none() && image.ours.blob_sha.is_none());
+        assert!(image.theirs.is_binary && image.result.is_binary);
+        assert!(image.result.content.is_none());
+        assert_eq!(image.theirs_to_result.additions, 0);
+
+        // Not a merge
+        let err = get_merge_file_versions(repo_path, ours.to_string(), "문서.txt".to_string())
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "not_a_merge_commit");
+    }
+
+    #[test]
+    fn test_suggest_resolution_for_whitespace_only_conflict() {
+        // Ours only re-indents the line theirs edits
+        let (temp_dir, repo_path) = setup_conflict_with(
+            "fn main() {\nlet x = 1;\n}\n",
+            "fn main() {\n    let x = 1;\n}\n",
+            "fn main() {\nlet x = 2;\n}\n",
+        );
+        let worktree_before = fs::read_to_string(temp_dir.path().join("file.txt")).unwrap();
+
+        let suggestions = suggest_conflict_resolutions(repo_path.clone()).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].path, "file.txt");
+        assert_eq!(suggestions[0].strategy, "ignore_whitespace");
+        assert_eq!(suggestions[0].resolution, "theirs");
+
+        // Suggesting never resolves anything
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo.index().unwrap().has_conflicts());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+            worktree_before
+        );
+    }
+
+    #[test]
+    fn test_suggest_union_when_both_sides_only_add() {
+        let (_temp_dir, repo_path) =
+            setup_conflict_with("use a;\n", "use a;\nuse b;\n", "use a;\nuse c;\n");
+        let suggestions = suggest_conflict_resolutions(repo_path).unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].strategy, "union");
+        assert_eq!(suggestions[0].resolution, "manual");
+        assert_eq!(
+            suggestions[0].content.as_deref(),
+            Some("use a;\nuse b;\nuse c;\n")
+        );
+    }
+
+    #[test]
+    fn test_no_suggestion_for_semantic_conflict() {
+        let (_temp_dir, repo_path) = setup_conflict();
+        assert!(suggest_conflict_resolutions(repo_path).unwrap().is_empty());
+    }
+}
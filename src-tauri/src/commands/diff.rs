@@ -1,10 +1,12 @@
-use git2::{DiffOptions, Oid, Repository};
-use std::path::Path;
+use git2::{DiffFindOptions, DiffOptions, Oid, Patch, Repository};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 use super::models::{
-    CommitFileChange, DiffHunk, DiffLine, DiffStat, ImageData, ImageDiffResult, ParsedDiff,
+    CommitFileChange, DiffHunk, DiffLine, DiffSegment, DiffStat, FileContent, FileDiff, ImageData,
+    ImageDiffResult, MergeBaseDiff, MergeDiffMode, MergeParentDiff, ParsedDiff, SubmoduleChange,
 };
-use super::utils::{normalize_unicode, open_repo};
+use super::utils::{command_error, normalize_unicode, open_repo, resolve_revision, safe_repo_path};
 
 // ============================================================================
 // Text Diff Commands
@@ -13,31 +15,82 @@ use super::utils::{normalize_unicode, open_repo};
 /// Maximum file size (10 MB) for which we will produce a text diff.
 const MAX_DIFF_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
+/// Paired lines changed by more than this fraction get no intra-line segments;
+/// highlighting nearly everything is noisier than highlighting the whole line.
+const MAX_INTRA_LINE_CHANGE_RATIO: f64 = 0.7;
+
+/// Token limit per line for the intra-line LCS (quadratic in line length).
+const MAX_INTRA_LINE_TOKENS: usize = 500;
+
 /// Get diff for a specific file (staged or unstaged).
+/// `ignore_whitespace` / `ignore_whitespace_change` behave like `git diff -w` / `-b`.
+///
+/// `max_lines` / `max_bytes` cap the returned patch at the last whole hunk that
+/// fits. Files larger than `max_file_size` bytes are diffed like binary files.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 pub async fn get_file_diff(
     repo_path: String,
     file_path: String,
     staged: bool,
     context_lines: Option<u32>,
-) -> Result<String, String> {
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+    max_lines: Option<usize>,
+    max_bytes: Option<usize>,
+    max_file_size: Option<u64>,
+) -> Result<FileDiff, String> {
     let normalized_path = normalize_unicode(&file_path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    check_diff_file_size(&full_path, staged)?;
+    let repo = open_repo(&repo_path)?;
 
-    // Guard against excessively large files
-    if !staged {
-        let full_path = std::path::Path::new(&repo_path).join(&normalized_path);
-        if full_path.exists() {
-            if let Ok(meta) = std::fs::metadata(&full_path) {
-                if meta.len() > MAX_DIFF_FILE_SIZE {
-                    return Err(format!(
-                        "파일이 너무 큽니다 ({:.1} MB). 10 MB 이하 파일만 diff를 지원합니다.",
-                        meta.len() as f64 / (1024.0 * 1024.0)
-                    ));
-                }
-            }
-        }
+    let mut opts = DiffOptions::new();
+    opts.pathspec(&normalized_path);
+    opts.context_lines(context_lines.unwrap_or(3));
+    opts.interhunk_lines(0);
+    opts.ignore_whitespace_eol(true);
+    apply_whitespace_options(&mut opts, ignore_whitespace, ignore_whitespace_change);
+    let oversized = max_file_size.is_some_and(|limit| {
+        largest_side_size(&repo, &normalized_path, &full_path, staged) > limit
+    });
+    if let Some(limit) = max_file_size.filter(|_| oversized) {
+        // Reported as "Binary files ... differ" without reading the content
+        opts.max_size(limit.min(i64::MAX as u64) as i64);
+    } else if !is_image_file(&normalized_path) {
+        // Force text diff for non-image files to avoid git2 binary misdetection
+        opts.force_text(true);
     }
 
+    let diff = working_file_diff(&repo, staged, &mut opts)?;
+
+    let hidden = whitespace_only_paths(&diff, ignore_whitespace, ignore_whitespace_change)?;
+    let mut patch_text = String::new();
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        if delta.new_file().path().is_some_and(|p| hidden.contains(p)) {
+            return true;
+        }
+        push_patch_line(&mut patch_text, &line);
+        true
+    })
+    .map_err(|e| format!("Diff 출력 실패: {}", e))?;
+
+    Ok(truncate_at_hunks(patch_text, max_lines, max_bytes))
+}
+
+/// Structured variant of `get_file_diff` + `parse_diff` in one call: the
+/// `ParsedDiff` is built from libgit2's hunks and lines directly, so line
+/// numbers come from git instead of being counted from the patch text.
+#[tauri::command]
+pub async fn get_file_diff_structured(
+    repo_path: String,
+    file_path: String,
+    staged: bool,
+    context_lines: Option<u32>,
+) -> Result<ParsedDiff, String> {
+    let normalized_path = normalize_unicode(&file_path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    check_diff_file_size(&full_path, staged)?;
     let repo = open_repo(&repo_path)?;
 
     let mut opts = DiffOptions::new();
@@ -45,11 +98,186 @@ pub async fn get_file_diff(
     opts.context_lines(context_lines.unwrap_or(3));
     opts.interhunk_lines(0);
     opts.ignore_whitespace_eol(true);
-    // Force text diff for non-image files to avoid git2 binary misdetection
     if !is_image_file(&normalized_path) {
         opts.force_text(true);
     }
+    let diff = working_file_diff(&repo, staged, &mut opts)?;
+
+    let patch = match diff.deltas().len() {
+        0 => None,
+        _ => Patch::from_diff(&diff, 0).map_err(|e| format!("Diff 생성 실패: {}", e))?,
+    };
+    match patch {
+        Some(patch) => parsed_from_patch(&repo, &patch),
+        None => Ok(ParsedDiff {
+            file_path: normalized_path.clone(),
+            old_path: normalized_path.clone(),
+            new_path: normalized_path,
+            is_binary: false,
+            old_mode: None,
+            new_mode: None,
+            mode_only: false,
+            hunks: Vec::new(),
+            additions: 0,
+            deletions: 0,
+            old_size: None,
+            new_size: None,
+            size_delta: None,
+        }),
+    }
+}
+
+/// `ParsedDiff` of a single-file patch, with the same paths, modes and line
+/// content `parse_file_diff` would read from its text.
+fn parsed_from_patch(repo: &Repository, patch: &Patch) -> Result<ParsedDiff, String> {
+    let delta = patch.delta();
+    let path_of = |file: git2::DiffFile| {
+        file.path()
+            .map(|p| normalize_unicode(&p.to_string_lossy()))
+            .unwrap_or_default()
+    };
+    let (old_path, new_path) = (path_of(delta.old_file()), path_of(delta.new_file()));
+    let mode_of = |file: git2::DiffFile| Some(u32::from(file.mode()));
+    let (old_mode, new_mode) = match delta.status() {
+        git2::Delta::Added | git2::Delta::Untracked => (None, mode_of(delta.new_file())),
+        git2::Delta::Deleted => (mode_of(delta.old_file()), None),
+        _ if delta.old_file().mode() != delta.new_file().mode() => {
+            (mode_of(delta.old_file()), mode_of(delta.new_file()))
+        }
+        _ => (None, None),
+    };
+    let is_binary = delta.flags().is_binary()
+        || delta.old_file().is_binary()
+        || delta.new_file().is_binary();
+    let (old_size, new_size, size_delta) = match is_binary {
+        true => binary_sizes(repo, &delta),
+        false => (None, None, None),
+    };
+
+    let read_err = |e: git2::Error| format!("hunk 읽기 실패: {}", e);
+    let (mut additions, mut deletions) = (0u32, 0u32);
+    let mut hunks = Vec::with_capacity(patch.num_hunks());
+    for hunk_index in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_index).map_err(read_err)?;
+        let mut lines: Vec<DiffLine> = Vec::with_capacity(line_count);
+        for line_index in 0..line_count {
+            let line = patch.line_in_hunk(hunk_index, line_index).map_err(read_err)?;
+            let line_type = match line.origin() {
+                '+' => {
+                    additions += 1;
+                    "addition"
+                }
+                '-' => {
+                    deletions += 1;
+                    "deletion"
+                }
+                ' ' => "context",
+                // "\ No newline at end of file" markers belong to the line before
+                _ => {
+                    if let Some(last) = lines.last_mut() {
+                        last.no_newline_at_eof = true;
+                    }
+                    continue;
+                }
+            };
+            let content = String::from_utf8_lossy(line.content());
+            let content = content.strip_suffix('\n').unwrap_or(&content);
+            let content = content.strip_suffix('\r').unwrap_or(content);
+            lines.push(DiffLine {
+                line_type: line_type.to_string(),
+                old_line_no: line.old_lineno(),
+                new_line_no: line.new_lineno(),
+                content: content.to_string(),
+                segments: None,
+                no_newline_at_eof: false,
+            });
+        }
+        annotate_intra_line_changes(&mut lines);
+
+        let header = String::from_utf8_lossy(hunk.header());
+        hunks.push(DiffHunk {
+            old_start: hunk.old_start(),
+            old_lines: hunk.old_lines(),
+            new_start: hunk.new_start(),
+            new_lines: hunk.new_lines(),
+            header: header.trim_end_matches(['\r', '\n']).to_string(),
+            lines,
+        });
+    }
+
+    let mode_only = is_mode_only(&old_mode, &new_mode, &old_path, &new_path, &hunks, is_binary);
+    Ok(ParsedDiff {
+        file_path: if new_path.is_empty() {
+            old_path.clone()
+        } else {
+            new_path.clone()
+        },
+        old_path,
+        new_path,
+        is_binary,
+        old_mode,
+        new_mode,
+        mode_only,
+        hunks,
+        additions,
+        deletions,
+        old_size,
+        new_size,
+        size_delta,
+    })
+}
+
+/// Old and new commit of the submodule at `submodule_path`: HEAD vs index
+/// when `staged`, index vs the submodule's checked-out HEAD otherwise.
+/// Use this instead of `get_file_diff` for entries with status "submodule".
+#[tauri::command]
+pub async fn get_submodule_change(
+    repo_path: String,
+    submodule_path: String,
+    staged: bool,
+) -> Result<SubmoduleChange, String> {
+    let normalized_path = normalize_unicode(&submodule_path);
+    safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    let repo = open_repo(&repo_path)?;
+    let submodule = repo
+        .find_submodule(&normalized_path)
+        .map_err(|_| format!("서브모듈이 아닙니다: {}", normalized_path))?;
+
+    let (old_id, new_id) = if staged {
+        (submodule.head_id(), submodule.index_id())
+    } else {
+        (submodule.index_id(), submodule.workdir_id())
+    };
+    Ok(SubmoduleChange {
+        path: normalized_path,
+        staged,
+        old_sha: old_id.map(|id| id.to_string()),
+        new_sha: new_id.map(|id| id.to_string()),
+    })
+}
+
+/// Reject unstaged diffs of files above `MAX_DIFF_FILE_SIZE`.
+fn check_diff_file_size(full_path: &Path, staged: bool) -> Result<(), String> {
+    if !staged && full_path.exists() {
+        if let Ok(meta) = std::fs::metadata(full_path) {
+            if meta.len() > MAX_DIFF_FILE_SIZE {
+                return Err(format!(
+                    "파일이 너무 큽니다 ({:.1} MB). 10 MB 이하 파일만 diff를 지원합니다.",
+                    meta.len() as f64 / (1024.0 * 1024.0)
+                ));
+            }
+        }
+    }
+    Ok(())
+}
 
+/// Staged (HEAD vs index) or unstaged (index vs working tree) diff limited
+/// by the pathspec in `opts`.
+fn working_file_diff<'r>(
+    repo: &'r Repository,
+    staged: bool,
+    opts: &mut DiffOptions,
+) -> Result<git2::Diff<'r>, String> {
     let diff = if staged {
         let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
         let head_tree = head.peel_to_tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
@@ -57,32 +285,135 @@ pub async fn get_file_diff(
         let index_tree = repo
             .find_tree(index.write_tree().map_err(|e| format!("트리 쓰기 실패: {}", e))?)
             .map_err(|e| format!("트리 찾기 실패: {}", e))?;
-        repo.diff_tree_to_tree(Some(&head_tree), Some(&index_tree), Some(&mut opts))
-            .map_err(|e| format!("Diff 생성 실패: {}", e))?
+        repo.diff_tree_to_tree(Some(&head_tree), Some(&index_tree), Some(opts))
     } else {
-        repo.diff_index_to_workdir(None, Some(&mut opts))
-            .map_err(|e| format!("Diff 생성 실패: {}", e))?
+        // Untracked files (also inside untracked directories) diff as all additions
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .show_untracked_content(true);
+        repo.diff_index_to_workdir(None, Some(opts))
     };
+    diff.map_err(|e| format!("Diff 생성 실패: {}", e))
+}
 
-    let mut patch_text = String::new();
-    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
-        let origin = line.origin();
-        // Content lines: prepend origin character (+, -, space)
-        // Header lines (F, H, etc.): output content as-is (already includes full text)
-        if matches!(origin, '+' | '-' | ' ') {
-            patch_text.push(origin);
-        }
-        let content = String::from_utf8_lossy(line.content());
-        patch_text.push_str(&content);
-        // Ensure line ends with newline for proper parsing
-        if !content.ends_with('\n') {
-            patch_text.push('\n');
+/// Size in bytes of the bigger side of `path`'s diff (HEAD vs index when
+/// staged, index vs working tree otherwise).
+fn largest_side_size(repo: &Repository, path: &str, full_path: &Path, staged: bool) -> u64 {
+    let index_size = repo
+        .index()
+        .ok()
+        .and_then(|index| index.get_path(Path::new(path), 0))
+        .map_or(0, |entry| entry.file_size as u64);
+    let other_size = if staged {
+        repo.head()
+            .and_then(|head| head.peel_to_tree())
+            .and_then(|tree| tree.get_path(Path::new(path)))
+            .and_then(|entry| repo.find_blob(entry.id()))
+            .map_or(0, |blob| blob.size() as u64)
+    } else {
+        std::fs::metadata(full_path).map_or(0, |meta| meta.len())
+    };
+    index_size.max(other_size)
+}
+
+/// Keep the file header and as many whole hunks as fit in `max_lines` lines
+/// and `max_bytes` bytes.
+fn truncate_at_hunks(
+    patch: String,
+    max_lines: Option<usize>,
+    max_bytes: Option<usize>,
+) -> FileDiff {
+    let total_lines = patch.lines().count();
+    let (max_lines, max_bytes) = (
+        max_lines.unwrap_or(usize::MAX),
+        max_bytes.unwrap_or(usize::MAX),
+    );
+    if total_lines <= max_lines && patch.len() <= max_bytes {
+        return FileDiff {
+            patch,
+            truncated: false,
+            total_lines,
+            returned_lines: total_lines,
+        };
+    }
+
+    // (end offset, line count, is a hunk) of each file header and hunk
+    let mut sections: Vec<(usize, usize, bool)> = Vec::new();
+    let mut offset = 0;
+    for line in patch.split_inclusive('\n') {
+        let is_hunk = line.starts_with("@@");
+        if is_hunk || line.starts_with("diff --git ") || sections.is_empty() {
+            sections.push((offset, 0, is_hunk));
         }
-        true
-    })
-    .map_err(|e| format!("Diff 출력 실패: {}", e))?;
+        offset += line.len();
+        if let Some(section) = sections.last_mut() {
+            section.0 = offset;
+            section.1 += 1;
+        }
+    }
 
-    Ok(patch_text)
+    // Headers are always kept so the result still parses
+    let (mut kept_end, mut kept_lines) = (0, 0);
+    for (end, line_count, is_hunk) in sections {
+        if is_hunk && (kept_lines + line_count > max_lines || end > max_bytes) {
+            break;
+        }
+        kept_end = end;
+        kept_lines += line_count;
+    }
+
+    let mut patch = patch;
+    patch.truncate(kept_end);
+    FileDiff {
+        patch,
+        truncated: kept_lines < total_lines,
+        total_lines,
+        returned_lines: kept_lines,
+    }
+}
+
+/// Optional whitespace filters shared by the diff commands; absent flags keep
+/// the default (only end-of-line whitespace ignored).
+fn apply_whitespace_options(
+    opts: &mut DiffOptions,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+) {
+    if ignore_whitespace.unwrap_or(false) {
+        opts.ignore_whitespace(true);
+    }
+    if ignore_whitespace_change.unwrap_or(false) {
+        opts.ignore_whitespace_change(true);
+    }
+}
+
+/// Files whose only changes were filtered out by the whitespace options.
+/// libgit2 still emits their headers with no hunks; like `git diff -w`, hide them.
+fn whitespace_only_paths(
+    diff: &git2::Diff,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+) -> Result<HashSet<PathBuf>, String> {
+    let mut paths = HashSet::new();
+    if !ignore_whitespace.unwrap_or(false) && !ignore_whitespace_change.unwrap_or(false) {
+        return Ok(paths);
+    }
+    for (idx, delta) in diff.deltas().enumerate() {
+        let content_only = delta.status() == git2::Delta::Modified
+            && delta.old_file().mode() == delta.new_file().mode()
+            && !delta.flags().is_binary();
+        if !content_only {
+            continue;
+        }
+        let patch =
+            Patch::from_diff(diff, idx).map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+        if patch.is_some_and(|p| p.num_hunks() == 0) {
+            if let Some(path) = delta.new_file().path() {
+                paths.insert(path.to_path_buf());
+            }
+        }
+    }
+    Ok(paths)
 }
 
 /// Get diff for a specific file at a specific commit (vs its parent).
@@ -126,15 +457,7 @@ pub async fn get_file_diff_at_commit(
 
     let mut patch_text = String::new();
     diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
-        let origin = line.origin();
-        if matches!(origin, '+' | '-' | ' ') {
-            patch_text.push(origin);
-        }
-        let content = String::from_utf8_lossy(line.content());
-        patch_text.push_str(&content);
-        if !content.ends_with('\n') {
-            patch_text.push('\n');
-        }
+        push_patch_line(&mut patch_text, &line);
         true
     })
     .map_err(|e| format!("Diff 출력 실패: {}", e))?;
@@ -144,9 +467,38 @@ pub async fn get_file_diff_at_commit(
 
 /// Get diff for a specific commit.
 #[tauri::command]
-pub async fn get_commit_diff(repo_path: String, commit_id: String) -> Result<String, String> {
+pub async fn get_commit_diff(
+    repo_path: String,
+    commit_id: String,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+) -> Result<String, String> {
     let repo = open_repo(&repo_path)?;
-    let oid = Oid::from_str(&commit_id).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
+    let (diff, hidden) =
+        commit_diff(&repo, &commit_id, ignore_whitespace, ignore_whitespace_change)?;
+
+    let mut patch_text = String::new();
+    diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+        if delta.new_file().path().is_some_and(|p| hidden.contains(p)) {
+            return true;
+        }
+        push_patch_line(&mut patch_text, &line);
+        true
+    })
+    .map_err(|e| format!("Diff 출력 실패: {}", e))?;
+
+    Ok(patch_text)
+}
+
+/// Diff of `commit_id` against its first parent as shown by `get_commit_diff`,
+/// plus the paths whose changes are hidden by the whitespace options.
+pub(super) fn commit_diff<'r>(
+    repo: &'r Repository,
+    commit_id: &str,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+) -> Result<(git2::Diff<'r>, HashSet<PathBuf>), String> {
+    let oid = Oid::from_str(commit_id).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
     let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
 
     let commit_tree = commit.tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
@@ -166,6 +518,7 @@ pub async fn get_commit_diff(repo_path: String, commit_id: String) -> Result<Str
     let mut opts = DiffOptions::new();
     opts.context_lines(3);
     opts.ignore_whitespace_eol(true);
+    apply_whitespace_options(&mut opts, ignore_whitespace, ignore_whitespace_change);
     // Force text so all non-image files produce patch output
     opts.force_text(true);
 
@@ -173,22 +526,171 @@ pub async fn get_commit_diff(repo_path: String, commit_id: String) -> Result<Str
         .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut opts))
         .map_err(|e| format!("Diff 생성 실패: {}", e))?;
 
-    let mut patch_text = String::new();
-    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
-        let origin = line.origin();
-        if matches!(origin, '+' | '-' | ' ') {
-            patch_text.push(origin);
+    let hidden = whitespace_only_paths(&diff, ignore_whitespace, ignore_whitespace_change)?;
+    Ok((diff, hidden))
+}
+
+/// Append one `Diff::print` line to patch text: the origin marker for
+/// content lines, and a newline when the content lacks one.
+pub(super) fn push_patch_line(patch: &mut String, line: &git2::DiffLine) {
+    let origin = line.origin();
+    if matches!(origin, '+' | '-' | ' ') {
+        patch.push(origin);
+    }
+    let content = String::from_utf8_lossy(line.content());
+    patch.push_str(&content);
+    if !content.ends_with('\n') {
+        patch.push('\n');
+    }
+}
+
+/// Diff `merge-base(base, head)..head` (`git diff base...head`): only the
+/// changes `head_ref` would bring in when merged into `base_ref`.
+/// Fails with `no_merge_base` for unrelated histories.
+#[tauri::command]
+pub async fn get_merge_base_diff(
+    repo_path: String,
+    base_ref: String,
+    head_ref: String,
+) -> Result<MergeBaseDiff, String> {
+    let repo = open_repo(&repo_path)?;
+    let base_ref = normalize_unicode(&base_ref);
+    let head_ref = normalize_unicode(&head_ref);
+    let base_commit = resolve_revision(&repo, &base_ref)?;
+    let head_commit = resolve_revision(&repo, &head_ref)?;
+
+    let merge_base = repo
+        .merge_base(base_commit.id(), head_commit.id())
+        .map_err(|_| {
+            command_error(
+                "no_merge_base",
+                format!(
+                    "'{}'와(과) '{}'의 공통 조상을 찾을 수 없습니다 (관련 없는 히스토리)",
+                    base_ref, head_ref
+                ),
+                serde_json::json!({ "base_ref": base_ref, "head_ref": head_ref }),
+            )
+        })?;
+    let base_tree = repo
+        .find_commit(merge_base)
+        .and_then(|c| c.tree())
+        .map_err(|e| format!("트리 접근 실패: {}", e))?;
+    let head_tree = head_commit
+        .tree()
+        .map_err(|e| format!("트리 접근 실패: {}", e))?;
+
+    let mut opts = DiffOptions::new();
+    opts.context_lines(3);
+    opts.ignore_whitespace_eol(true);
+    opts.force_text(true);
+    let mut diff = repo
+        .diff_tree_to_tree(Some(&base_tree), Some(&head_tree), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    // Also runs rename detection, so the patch shows renames too
+    let files = collect_diff_stats(&repo, &mut diff)?;
+
+    Ok(MergeBaseDiff {
+        merge_base: merge_base.to_string(),
+        patch: patch_text(&diff)?,
+        files,
+    })
+}
+
+/// Diff a merge commit against each of its parents, labelled by parent.
+/// `per_parent` returns the full diff for every parent; `combined` keeps only
+/// files that differ from all parents, i.e. what the merge itself authored
+/// (conflict resolutions), like `git show --cc`.
+#[tauri::command]
+pub async fn get_merge_commit_diff(
+    repo_path: String,
+    commit_id: String,
+    mode: MergeDiffMode,
+) -> Result<Vec<MergeParentDiff>, String> {
+    let repo = open_repo(&repo_path)?;
+    let oid = Oid::from_str(&commit_id).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
+    let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    if commit.parent_count() < 2 {
+        return Err(command_error(
+            "not_a_merge_commit",
+            format!(
+                "커밋 {}은(는) 병합 커밋이 아닙니다 (부모 {}개)",
+                &commit_id[..commit_id.len().min(7)],
+                commit.parent_count()
+            ),
+            serde_json::json!({ "sha": commit.id().to_string(), "parents": commit.parent_count() }),
+        ));
+    }
+    let commit_tree = commit.tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
+
+    let diff_against = |parent: &git2::Commit, paths: Option<&HashSet<String>>| {
+        let parent_tree = parent
+            .tree()
+            .map_err(|e| format!("부모 트리 접근 실패: {}", e))?;
+        let mut opts = DiffOptions::new();
+        opts.context_lines(3);
+        opts.force_text(true);
+        if let Some(paths) = paths {
+            opts.disable_pathspec_match(true);
+            for path in paths {
+                opts.pathspec(path);
+            }
         }
-        let content = String::from_utf8_lossy(line.content());
-        patch_text.push_str(&content);
-        if !content.ends_with('\n') {
-            patch_text.push('\n');
+        repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit_tree), Some(&mut opts))
+            .map_err(|e| format!("Diff 생성 실패: {}", e))
+    };
+    let changed_paths = |diff: &git2::Diff| -> HashSet<String> {
+        diff.deltas()
+            .filter_map(|d| d.new_file().path().or_else(|| d.old_file().path()))
+            .map(|p| p.to_string_lossy().to_string())
+            .collect()
+    };
+
+    let parents: Vec<git2::Commit> = commit.parents().collect();
+    let combined_paths = match mode {
+        MergeDiffMode::PerParent => None,
+        MergeDiffMode::Combined => {
+            let mut common: Option<HashSet<String>> = None;
+            for parent in &parents {
+                let paths = changed_paths(&diff_against(parent, None)?);
+                common = Some(match common {
+                    Some(common) => common.intersection(&paths).cloned().collect(),
+                    None => paths,
+                });
+            }
+            Some(common.unwrap_or_default())
         }
+    };
+
+    let mut diffs = Vec::new();
+    for (parent_index, parent) in parents.iter().enumerate() {
+        let (patch, mut files) = match &combined_paths {
+            Some(paths) if paths.is_empty() => (String::new(), Vec::new()),
+            paths => {
+                let diff = diff_against(parent, paths.as_ref())?;
+                let files: Vec<String> = changed_paths(&diff).into_iter().collect();
+                (patch_text(&diff)?, files)
+            }
+        };
+        files.sort();
+        diffs.push(MergeParentDiff {
+            parent_index,
+            parent_sha: parent.id().to_string(),
+            patch,
+            files: files.iter().map(|f| normalize_unicode(f)).collect(),
+        });
+    }
+    Ok(diffs)
+}
+
+/// Unified patch text of a whole diff.
+fn patch_text(diff: &git2::Diff) -> Result<String, String> {
+    let mut patch = String::new();
+    diff.print(git2::DiffFormat::Patch, |_delta, _hunk, line| {
+        push_patch_line(&mut patch, &line);
         true
     })
     .map_err(|e| format!("Diff 출력 실패: {}", e))?;
-
-    Ok(patch_text)
+    Ok(patch)
 }
 
 /// Get list of changed files for a specific commit with status and diff stats.
@@ -200,7 +702,14 @@ pub async fn get_commit_file_changes(
     let repo = open_repo(&repo_path)?;
     let oid = Oid::from_str(&commit_id).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
     let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    commit_file_changes(&repo, &commit)
+}
 
+/// Changed files of `commit` against its first parent, with rename detection.
+pub(super) fn commit_file_changes(
+    repo: &Repository,
+    commit: &git2::Commit,
+) -> Result<Vec<CommitFileChange>, String> {
     let commit_tree = commit.tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
 
     let parent_tree = if commit.parent_count() > 0 {
@@ -218,76 +727,72 @@ pub async fn get_commit_file_changes(
     let mut opts = DiffOptions::new();
     opts.force_text(true);
 
-    let diff = repo
+    // A root commit has no parent tree and diffs against the empty tree
+    let mut diff = repo
         .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut opts))
         .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("이름 변경 감지 실패: {}", e))?;
 
     let mut changes: Vec<CommitFileChange> = Vec::new();
     let mut blob_oids_to_check: Vec<(usize, Oid)> = Vec::new();
 
     // Collect file info
-    diff.foreach(
-        &mut |delta, _progress| {
-            let new_path = delta
-                .new_file()
-                .path()
-                .unwrap_or(std::path::Path::new(""))
-                .to_str()
-                .unwrap_or("")
-                .to_string();
-            let old_path = delta
-                .old_file()
-                .path()
-                .unwrap_or(std::path::Path::new(""))
-                .to_str()
-                .unwrap_or("")
-                .to_string();
-
-            let status = match delta.status() {
-                git2::Delta::Added => "added",
-                git2::Delta::Deleted => "deleted",
-                git2::Delta::Modified => "modified",
-                git2::Delta::Renamed => "renamed",
-                git2::Delta::Copied => "copied",
-                _ => "modified",
-            };
-
-            let path = if status == "deleted" {
-                old_path.clone()
-            } else {
-                new_path.clone()
-            };
-
-            let renamed_old = if status == "renamed" || status == "copied" {
-                Some(old_path)
-            } else {
-                None
-            };
+    for delta in diff.deltas() {
+        let new_path = delta
+            .new_file()
+            .path()
+            .unwrap_or(std::path::Path::new(""))
+            .to_str()
+            .unwrap_or("")
+            .to_string();
+        let old_path = delta
+            .old_file()
+            .path()
+            .unwrap_or(std::path::Path::new(""))
+            .to_str()
+            .unwrap_or("")
+            .to_string();
+
+        let status = match delta.status() {
+            git2::Delta::Added => "added",
+            git2::Delta::Deleted => "deleted",
+            git2::Delta::Modified => "modified",
+            git2::Delta::Renamed => "renamed",
+            git2::Delta::Copied => "copied",
+            _ => "modified",
+        };
+
+        let path = if status == "deleted" {
+            old_path.clone()
+        } else {
+            new_path.clone()
+        };
 
-            let git2_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
-            // Store the new file's blob OID for content-based binary check later
-            let blob_oid = delta.new_file().id();
-            changes.push(CommitFileChange {
-                path: normalize_unicode(&path),
-                status: status.to_string(),
-                additions: 0,
-                deletions: 0,
-                is_binary: is_truly_binary(&path, git2_binary),
-                old_path: renamed_old,
-            });
-            // If git2 says binary but extension-based check says text,
-            // we already override. For unknown extensions where git2 says binary,
-            // store OID for post-check.
-            if git2_binary && !is_image_file(&path) && !is_known_text_extension(&path) {
-                blob_oids_to_check.push((changes.len() - 1, blob_oid));
-            }
-            true
-        },
-        None,
-        None,
-        None,
-    )
-    .map_err(|e| format!("Diff 순회 실패: {}", e))?;
+        let renamed_old = if status == "renamed" || status == "copied" {
+            Some(normalize_unicode(&old_path))
+        } else {
+            None
+        };
+
+        let git2_binary = delta.new_file().is_binary() || delta.old_file().is_binary();
+        changes.push(CommitFileChange {
+            path: normalize_unicode(&path),
+            status: status.to_string(),
+            additions: 0,
+            deletions: 0,
+            is_binary: is_truly_binary(&path, git2_binary),
+            old_path: renamed_old,
+            old_size: None,
+            new_size: None,
+            size_delta: None,
+        });
+        // For unknown extensions where git2 says binary, check the new
+        // file's blob content afterwards
+        if git2_binary && !is_image_file(&path) && !is_known_text_extension(&path) {
+            blob_oids_to_check.push((changes.len() - 1, delta.new_file().id()));
+        }
+    }
 
     // Post-check: for files with unknown extensions that git2 flagged as binary,
     // read actual blob content to verify
@@ -299,44 +804,91 @@ pub async fn get_commit_file_changes(
         }
     }
 
-    // Count additions/deletions per file
-    if !changes.is_empty() {
-        use std::cell::Cell;
-        let idx: Cell<i32> = Cell::new(-1);
-        diff.foreach(
-            &mut |_delta, _progress| {
-                idx.set(idx.get() + 1);
-                true
-            },
-            None,
-            None,
-            Some(&mut |_delta, _hunk, line| {
-                let i = idx.get() as usize;
-                if i < changes.len() {
-                    match line.origin() {
-                        '+' => changes[i].additions += 1,
-                        '-' => changes[i].deletions += 1,
-                        _ => {}
-                    }
-                }
-                true
-            }),
-        )
-        .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+    // Count additions/deletions per file; binary files get sizes instead
+    for (idx, change) in changes.iter_mut().enumerate() {
+        if change.is_binary {
+            if let Some(delta) = diff.get_delta(idx) {
+                (change.old_size, change.new_size, change.size_delta) =
+                    binary_sizes(repo, &delta);
+            }
+            continue;
+        }
+        if let Some(patch) =
+            Patch::from_diff(&diff, idx).map_err(|e| format!("Diff 통계 수집 실패: {}", e))?
+        {
+            let (_, additions, deletions) = patch
+                .line_stats()
+                .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+            change.additions = additions as u32;
+            change.deletions = deletions as u32;
+        }
     }
 
     Ok(changes)
 }
 
 /// Parse unified diff format into structured data.
+/// Expects a single file; use `parse_multi_diff` for whole-commit patches.
 #[tauri::command]
 pub async fn parse_diff(diff_text: String) -> Result<ParsedDiff, String> {
+    Ok(parse_file_diff(&diff_text))
+}
+
+/// Parse a patch touching several files (e.g. `get_commit_diff` output) into
+/// one `ParsedDiff` per `diff --git` section. Text before the first header is
+/// ignored; a patch without any header is parsed as a single file.
+#[tauri::command]
+pub async fn parse_multi_diff(diff_text: String) -> Result<Vec<ParsedDiff>, String> {
+    let mut sections: Vec<String> = Vec::new();
+    let mut preamble = String::new();
+    for line in diff_text.lines() {
+        if line.starts_with("diff --git") {
+            sections.push(String::new());
+        }
+        let target = sections.last_mut().unwrap_or(&mut preamble);
+        target.push_str(line);
+        target.push('\n');
+    }
+
+    if sections.is_empty() {
+        if preamble.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+        sections.push(preamble);
+    }
+    Ok(sections.iter().map(|s| parse_file_diff(s)).collect())
+}
+
+/// Octal mode from a patch header such as "old mode 100644".
+fn parse_mode(mode: &str) -> Option<u32> {
+    u32::from_str_radix(mode.trim(), 8).ok()
+}
+
+/// A file whose mode changed (e.g. 100644 → 100755) in place, with no
+/// content change to show.
+fn is_mode_only(
+    old_mode: &Option<u32>,
+    new_mode: &Option<u32>,
+    old_path: &str,
+    new_path: &str,
+    hunks: &[DiffHunk],
+    is_binary: bool,
+) -> bool {
+    matches!((old_mode, new_mode), (Some(old), Some(new)) if old != new)
+        && old_path == new_path
+        && hunks.is_empty()
+        && !is_binary
+}
+
+fn parse_file_diff(diff_text: &str) -> ParsedDiff {
     let lines: Vec<&str> = diff_text.lines().collect();
 
     let mut file_path = String::new();
     let mut old_path = String::new();
     let mut new_path = String::new();
     let mut is_binary = false;
+    let mut old_mode: Option<u32> = None;
+    let mut new_mode: Option<u32> = None;
     let mut hunks: Vec<DiffHunk> = Vec::new();
     let mut additions = 0u32;
     let mut deletions = 0u32;
@@ -345,16 +897,35 @@ pub async fn parse_diff(diff_text: String) -> Result<ParsedDiff, String> {
     let mut new_line_no = 0u32;
 
     for line in lines {
+        // Inside a hunk, "--- x" / "+++ x" are a removed "-- x" / added "++ x"
+        // line, not file headers.
+        let in_hunk_body = current_hunk.as_ref().is_some_and(|h| {
+            old_line_no < h.old_start + h.old_lines || new_line_no < h.new_start + h.new_lines
+        });
+
         if line.starts_with("diff --git") {
             if let Some(parts) = line.split_whitespace().nth(2) {
-                file_path = parts.trim_start_matches("a/").to_string();
+                file_path = header_path(parts, "a/");
             }
-        } else if line.starts_with("---") {
-            old_path = line.trim_start_matches("--- a/").to_string();
-        } else if line.starts_with("+++") {
-            new_path = line.trim_start_matches("+++ b/").to_string();
-        } else if line.starts_with("Binary files") {
+        } else if !in_hunk_body && line.starts_with("---") {
+            old_path = header_path(line[3..].trim_start(), "a/");
+        } else if !in_hunk_body && line.starts_with("+++") {
+            new_path = header_path(line[3..].trim_start(), "b/");
+        } else if line.starts_with("Binary files") || line == "GIT binary patch" {
             is_binary = true;
+        } else if let Some(path) = line.strip_prefix("rename from ") {
+            old_path = header_path(path, "");
+        } else if let Some(path) = line.strip_prefix("rename to ") {
+            new_path = header_path(path, "");
+            file_path = new_path.clone();
+        } else if let Some(mode) = line.strip_prefix("old mode ") {
+            old_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("new mode ") {
+            new_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("new file mode ") {
+            new_mode = parse_mode(mode);
+        } else if let Some(mode) = line.strip_prefix("deleted file mode ") {
+            old_mode = parse_mode(mode);
         } else if line.starts_with("@@") {
             if let Some(hunk) = current_hunk.take() {
                 hunks.push(hunk);
@@ -364,23 +935,8 @@ pub async fn parse_diff(diff_text: String) -> Result<ParsedDiff, String> {
             let parts: Vec<&str> = line.split_whitespace().collect();
 
             if parts.len() >= 3 {
-                let old_range = parts[1].trim_start_matches('-');
-                let old_parts: Vec<&str> = old_range.split(',').collect();
-                let old_start = old_parts[0].parse::<u32>().unwrap_or(1);
-                let old_lines_count = if old_parts.len() > 1 {
-                    old_parts[1].parse::<u32>().unwrap_or(1)
-                } else {
-                    1
-                };
-
-                let new_range = parts[2].trim_start_matches('+');
-                let new_parts: Vec<&str> = new_range.split(',').collect();
-                let new_start = new_parts[0].parse::<u32>().unwrap_or(1);
-                let new_lines_count = if new_parts.len() > 1 {
-                    new_parts[1].parse::<u32>().unwrap_or(1)
-                } else {
-                    1
-                };
+                let (old_start, old_lines_count) = parse_hunk_range(parts[1], '-');
+                let (new_start, new_lines_count) = parse_hunk_range(parts[2], '+');
 
                 old_line_no = old_start;
                 new_line_no = new_start;
@@ -395,79 +951,374 @@ pub async fn parse_diff(diff_text: String) -> Result<ParsedDiff, String> {
                 });
             }
         } else if let Some(ref mut hunk) = current_hunk {
-            if line.starts_with('+') && !line.starts_with("+++") {
+            if let Some(content) = line.strip_prefix('+') {
                 hunk.lines.push(DiffLine {
                     line_type: "addition".to_string(),
                     old_line_no: None,
                     new_line_no: Some(new_line_no),
-                    content: line[1..].to_string(),
+                    content: content.to_string(),
+                    segments: None,
+                    no_newline_at_eof: false,
                 });
                 new_line_no += 1;
                 additions += 1;
-            } else if line.starts_with('-') && !line.starts_with("---") {
+            } else if let Some(content) = line.strip_prefix('-') {
                 hunk.lines.push(DiffLine {
                     line_type: "deletion".to_string(),
                     old_line_no: Some(old_line_no),
                     new_line_no: None,
-                    content: line[1..].to_string(),
+                    content: content.to_string(),
+                    segments: None,
+                    no_newline_at_eof: false,
                 });
                 old_line_no += 1;
                 deletions += 1;
-            } else if line.starts_with(' ') {
+            } else if let Some(content) = line.strip_prefix(' ') {
                 hunk.lines.push(DiffLine {
                     line_type: "context".to_string(),
                     old_line_no: Some(old_line_no),
                     new_line_no: Some(new_line_no),
-                    content: line[1..].to_string(),
+                    content: content.to_string(),
+                    segments: None,
+                    no_newline_at_eof: false,
                 });
                 old_line_no += 1;
                 new_line_no += 1;
+            } else if line.starts_with('\\') {
+                // "\ No newline at end of file" belongs to the line before it
+                // and is not a line of either side.
+                if let Some(last) = hunk.lines.last_mut() {
+                    last.no_newline_at_eof = true;
+                }
+            }
+        }
+    }
+
+    if let Some(hunk) = current_hunk {
+        hunks.push(hunk);
+    }
+    for hunk in &mut hunks {
+        annotate_intra_line_changes(&mut hunk.lines);
+    }
+
+    // Mode-only and binary sections have no "---"/"+++" lines
+    if old_path.is_empty() && new_path.is_empty() {
+        old_path = file_path.clone();
+        new_path = file_path.clone();
+    }
+    let mode_only = is_mode_only(&old_mode, &new_mode, &old_path, &new_path, &hunks, is_binary);
+
+    ParsedDiff {
+        file_path: normalize_unicode(&file_path),
+        old_path: normalize_unicode(&old_path),
+        new_path: normalize_unicode(&new_path),
+        is_binary,
+        old_mode,
+        new_mode,
+        mode_only,
+        hunks,
+        additions,
+        deletions,
+        old_size: None,
+        new_size: None,
+        size_delta: None,
+    }
+}
+
+/// Path from a patch header, with git's C-style quoting (`"a/\354..."`, used
+/// for non-ASCII names unless `core.quotepath` is off) undone and `prefix`
+/// ("a/" / "b/") removed.
+fn header_path(raw: &str, prefix: &str) -> String {
+    let path = match raw.strip_prefix('"').and_then(|r| r.strip_suffix('"')) {
+        Some(quoted) => {
+            let mut bytes = Vec::with_capacity(quoted.len());
+            let mut chars = quoted.chars().peekable();
+            while let Some(ch) = chars.next() {
+                if ch != '\\' {
+                    let mut buf = [0u8; 4];
+                    bytes.extend_from_slice(ch.encode_utf8(&mut buf).as_bytes());
+                    continue;
+                }
+                match chars.next() {
+                    Some('n') => bytes.push(b'\n'),
+                    Some('t') => bytes.push(b'\t'),
+                    Some(d @ '0'..='7') => {
+                        let mut value = d.to_digit(8).unwrap();
+                        for _ in 0..2 {
+                            match chars.peek().and_then(|c| c.to_digit(8)) {
+                                Some(digit) => {
+                                    value = value * 8 + digit;
+                                    chars.next();
+                                }
+                                None => break,
+                            }
+                        }
+                        bytes.push(value as u8);
+                    }
+                    Some(other) => {
+                        let mut buf = [0u8; 4];
+                        bytes.extend_from_slice(other.encode_utf8(&mut buf).as_bytes());
+                    }
+                    None => {}
+                }
             }
+            String::from_utf8_lossy(&bytes).into_owned()
+        }
+        None => raw.to_string(),
+    };
+    match path.strip_prefix(prefix) {
+        Some(stripped) => stripped.to_string(),
+        None => path,
+    }
+}
+
+/// Parse one side of a hunk header ("-12,3" / "+1"). A missing count means 1;
+/// new and deleted files use start 0 with count 0 for the empty side.
+fn parse_hunk_range(range: &str, prefix: char) -> (u32, u32) {
+    let mut parts = range.trim_start_matches(prefix).splitn(2, ',');
+    let start = parts
+        .next()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(1);
+    let count = match parts.next() {
+        Some(count) => count.parse::<u32>().unwrap_or(1),
+        None => 1,
+    };
+    (start, count)
+}
+
+/// Fill `segments` for each run of deletions directly followed by additions,
+/// pairing the n-th deleted line with the n-th added line.
+fn annotate_intra_line_changes(lines: &mut [DiffLine]) {
+    let mut i = 0;
+    while i < lines.len() {
+        let del_start = i;
+        while i < lines.len() && lines[i].line_type == "deletion" {
+            i += 1;
+        }
+        let add_start = i;
+        while i < lines.len() && lines[i].line_type == "addition" {
+            i += 1;
+        }
+        if add_start == del_start || i == add_start {
+            i = i.max(del_start + 1);
+            continue;
+        }
+
+        let pairs = (add_start - del_start).min(i - add_start);
+        for n in 0..pairs {
+            let (old, new) = (del_start + n, add_start + n);
+            if let Some((old_segments, new_segments)) =
+                intra_line_segments(&lines[old].content, &lines[new].content)
+            {
+                lines[old].segments = Some(old_segments);
+                lines[new].segments = Some(new_segments);
+            }
+        }
+    }
+}
+
+/// Split a line into diff tokens: runs of ASCII word characters stay whole so
+/// identifiers change as a unit; everything else (each Hangul syllable,
+/// whitespace, punctuation) is its own token.
+fn intra_line_tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut word_start: Option<usize> = None;
+    for (idx, ch) in line.char_indices() {
+        if ch.is_ascii_alphanumeric() || ch == '_' {
+            word_start.get_or_insert(idx);
+            continue;
+        }
+        if let Some(start) = word_start.take() {
+            tokens.push(&line[start..idx]);
+        }
+        tokens.push(&line[idx..idx + ch.len_utf8()]);
+    }
+    if let Some(start) = word_start {
+        tokens.push(&line[start..]);
+    }
+    tokens
+}
+
+/// LCS-based segments for a deleted/added line pair, or `None` when the
+/// lines are too long or changed beyond `MAX_INTRA_LINE_CHANGE_RATIO`.
+fn intra_line_segments(old: &str, new: &str) -> Option<(Vec<DiffSegment>, Vec<DiffSegment>)> {
+    let a = intra_line_tokens(old);
+    let b = intra_line_tokens(new);
+    if a.len() > MAX_INTRA_LINE_TOKENS || b.len() > MAX_INTRA_LINE_TOKENS {
+        return None;
+    }
+
+    // lcs[i][j] = LCS length of a[i..] and b[j..]
+    let mut lcs = vec![vec![0u16; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
         }
     }
 
-    if let Some(hunk) = current_hunk {
-        hunks.push(hunk);
+    let mut old_marks = Vec::with_capacity(a.len());
+    let mut new_marks = Vec::with_capacity(b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            old_marks.push((a[i], false));
+            new_marks.push((b[j], false));
+            i += 1;
+            j += 1;
+        } else if j == b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            old_marks.push((a[i], true));
+            i += 1;
+        } else {
+            new_marks.push((b[j], true));
+            j += 1;
+        }
     }
 
-    Ok(ParsedDiff {
-        file_path: normalize_unicode(&file_path),
-        old_path: normalize_unicode(&old_path),
-        new_path: normalize_unicode(&new_path),
-        is_binary,
-        hunks,
-        additions,
-        deletions,
-    })
+    if changed_ratio(&old_marks) > MAX_INTRA_LINE_CHANGE_RATIO
+        || changed_ratio(&new_marks) > MAX_INTRA_LINE_CHANGE_RATIO
+    {
+        return None;
+    }
+    Some((merge_segments(&old_marks), merge_segments(&new_marks)))
+}
+
+/// Fraction of characters marked changed; an empty line counts as fully changed.
+fn changed_ratio(marks: &[(&str, bool)]) -> f64 {
+    let total: usize = marks.iter().map(|(t, _)| t.chars().count()).sum();
+    if total == 0 {
+        return 1.0;
+    }
+    let changed: usize = marks
+        .iter()
+        .filter(|(_, changed)| *changed)
+        .map(|(t, _)| t.chars().count())
+        .sum();
+    changed as f64 / total as f64
+}
+
+fn merge_segments(marks: &[(&str, bool)]) -> Vec<DiffSegment> {
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for &(text, changed) in marks {
+        match segments.last_mut() {
+            Some(last) if last.changed == changed => last.text.push_str(text),
+            _ => segments.push(DiffSegment {
+                text: text.to_string(),
+                changed,
+            }),
+        }
+    }
+    segments
 }
 
+/// Binary contents larger than this are reported without a base64 payload.
+const MAX_BASE64_CONTENT_SIZE: usize = 10 * 1024 * 1024;
+
+/// Bytes inspected by the binary heuristic, as in libgit2.
+const BINARY_SNIFF_LEN: usize = 8000;
+
 /// Get file content at a specific commit (or current working directory).
+///
+/// Text comes back decoded (UTF-8, or UTF-16 with a BOM); anything else is
+/// flagged binary and base64-encoded up to 10 MB. Both sources go through
+/// the same detection.
 #[tauri::command]
 pub async fn get_file_content(
     repo_path: String,
     file_path: String,
     commit_id: Option<String>,
-) -> Result<String, String> {
+) -> Result<FileContent, String> {
     let normalized_path = normalize_unicode(&file_path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized_path)?;
     let repo = open_repo(&repo_path)?;
 
-    if let Some(commit_str) = commit_id {
+    let data = if let Some(commit_str) = commit_id {
         let oid = Oid::from_str(&commit_str).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
         let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
-        let tree = commit.tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
+        blob_bytes_at_commit(&repo, &commit, &normalized_path)?
+    } else {
+        std::fs::read(full_path).map_err(|e| format!("파일 읽기 실패: {}", e))?
+    };
+    Ok(file_content(&data))
+}
 
-        let entry = tree
-            .get_path(std::path::Path::new(&normalized_path))
-            .map_err(|e| format!("파일 찾기 실패: {}", e))?;
+fn file_content(data: &[u8]) -> FileContent {
+    let (text, encoding) = decode_text(data);
+    let is_binary = text.is_none();
+    let base64 = match is_binary && data.len() <= MAX_BASE64_CONTENT_SIZE {
+        true => {
+            use base64::Engine;
+            Some(base64::engine::general_purpose::STANDARD.encode(data))
+        }
+        false => None,
+    };
+    FileContent {
+        is_binary,
+        text,
+        base64,
+        size: data.len() as u64,
+        encoding_guess: encoding.to_string(),
+    }
+}
 
-        let object = entry
-            .to_object(&repo)
-            .map_err(|e| format!("오브젝트 접근 실패: {}", e))?;
-        let blob = object.as_blob().ok_or("Blob이 아닙니다")?;
-        Ok(String::from_utf8_lossy(blob.content()).to_string())
-    } else {
-        let full_path = std::path::Path::new(&repo_path).join(&normalized_path);
-        std::fs::read_to_string(full_path).map_err(|e| format!("파일 읽기 실패: {}", e))
+/// Decode file bytes as text, or `None` when they look binary.
+///
+/// A BOM decides the encoding. Otherwise a NUL byte in the first 8000 bytes
+/// means binary, as in libgit2; invalid UTF-8 is still shown (lossily) when
+/// it is mostly printable, which keeps legacy 8-bit text readable.
+fn decode_text(data: &[u8]) -> (Option<String>, &'static str) {
+    if let Some(rest) = data.strip_prefix(b"\xEF\xBB\xBF") {
+        return match std::str::from_utf8(rest) {
+            Ok(text) => (Some(text.to_string()), "utf-8-bom"),
+            Err(_) => (None, "binary"),
+        };
+    }
+    if let Some(rest) = data.strip_prefix(b"\xFF\xFE") {
+        return decode_utf16(rest, u16::from_le_bytes, "utf-16le");
+    }
+    if let Some(rest) = data.strip_prefix(b"\xFE\xFF") {
+        return decode_utf16(rest, u16::from_be_bytes, "utf-16be");
+    }
+
+    let sniff = &data[..data.len().min(BINARY_SNIFF_LEN)];
+    if sniff.contains(&0) {
+        return (None, "binary");
+    }
+    if let Ok(text) = std::str::from_utf8(data) {
+        return (Some(text.to_string()), "utf-8");
+    }
+
+    let (mut printable, mut nonprintable) = (0usize, 0usize);
+    for &byte in sniff {
+        if (byte > 0x1F && byte != 0x7F) || matches!(byte, 0x08 | 0x0C | 0x1B) {
+            printable += 1;
+        } else if !byte.is_ascii_whitespace() && byte != 0x0B {
+            nonprintable += 1;
+        }
+    }
+    if (printable >> 7) < nonprintable {
+        return (None, "binary");
+    }
+    (Some(String::from_utf8_lossy(data).to_string()), "unknown")
+}
+
+fn decode_utf16(
+    data: &[u8],
+    to_unit: fn([u8; 2]) -> u16,
+    encoding: &'static str,
+) -> (Option<String>, &'static str) {
+    let pairs = data.chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return (None, "binary");
+    }
+    let units: Vec<u16> = pairs.map(|pair| to_unit([pair[0], pair[1]])).collect();
+    match String::from_utf16(&units) {
+        Ok(text) => (Some(text), encoding),
+        Err(_) => (None, "binary"),
     }
 }
 
@@ -480,7 +1331,7 @@ pub async fn get_diff_stats(repo_path: String, staged: bool) -> Result<Vec<DiffS
     opts.ignore_whitespace_eol(true);
     opts.force_text(true);
 
-    let diff = if staged {
+    let mut diff = if staged {
         let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
         let head_tree = head.peel_to_tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
         let mut index = repo.index().map_err(|e| format!("인덱스 접근 실패: {}", e))?;
@@ -493,6 +1344,17 @@ pub async fn get_diff_stats(repo_path: String, staged: bool) -> Result<Vec<DiffS
         repo.diff_index_to_workdir(None, Some(&mut opts))
             .map_err(|e| format!("Diff 생성 실패: {}", e))?
     };
+    collect_diff_stats(&repo, &mut diff)
+}
+
+/// Per-file stats for `diff`, with renames reported once under the new path.
+/// Binary files keep zero counts.
+pub(super) fn collect_diff_stats(
+    repo: &Repository,
+    diff: &mut git2::Diff,
+) -> Result<Vec<DiffStat>, String> {
+    diff.find_similar(Some(DiffFindOptions::new().renames(true)))
+        .map_err(|e| format!("이름 변경 감지 실패: {}", e))?;
 
     let mut stats = Vec::new();
     let mut blob_oids_to_check: Vec<(usize, Oid)> = Vec::new();
@@ -508,6 +1370,9 @@ pub async fn get_diff_stats(repo_path: String, staged: bool) -> Result<Vec<DiffS
                 additions: 0,
                 deletions: 0,
                 is_binary: is_truly_binary(&path_str, git2_binary),
+                old_size: None,
+                new_size: None,
+                size_delta: None,
             });
             // For unknown extensions flagged binary, queue for content check
             if git2_binary && !is_image_file(&path_str) && !is_known_text_extension(&path_str) {
@@ -530,30 +1395,23 @@ pub async fn get_diff_stats(repo_path: String, staged: bool) -> Result<Vec<DiffS
         }
     }
 
-    // Step 2: Count per-file additions and deletions
-    if !stats.is_empty() {
-        use std::cell::Cell;
-        let current_file_idx: Cell<i32> = Cell::new(-1);
-        diff.foreach(
-            &mut |_delta, _progress| {
-                current_file_idx.set(current_file_idx.get() + 1);
-                true
-            },
-            None,
-            None,
-            Some(&mut |_delta, _hunk, line| {
-                let idx = current_file_idx.get() as usize;
-                if idx < stats.len() {
-                    match line.origin() {
-                        '+' => stats[idx].additions += 1,
-                        '-' => stats[idx].deletions += 1,
-                        _ => {}
-                    }
-                }
-                true
-            }),
-        )
-        .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+    // Step 2: Count per-file additions and deletions; binary files get sizes instead
+    for (idx, stat) in stats.iter_mut().enumerate() {
+        if stat.is_binary {
+            if let Some(delta) = diff.get_delta(idx) {
+                (stat.old_size, stat.new_size, stat.size_delta) = binary_sizes(repo, &delta);
+            }
+            continue;
+        }
+        if let Some(patch) =
+            Patch::from_diff(diff, idx).map_err(|e| format!("Diff 통계 수집 실패: {}", e))?
+        {
+            let (_, additions, deletions) = patch
+                .line_stats()
+                .map_err(|e| format!("Diff 통계 수집 실패: {}", e))?;
+            stat.additions = additions as u32;
+            stat.deletions = deletions as u32;
+        }
     }
 
     Ok(stats)
@@ -605,11 +1463,39 @@ fn is_known_text_extension(path: &str) -> bool {
 
 /// Check if raw content looks binary by scanning for NUL bytes (same heuristic as Git).
 /// Scans up to the first 8000 bytes.
-fn content_looks_binary(data: &[u8]) -> bool {
+pub(super) fn content_looks_binary(data: &[u8]) -> bool {
     let check_len = data.len().min(8000);
     data[..check_len].contains(&0)
 }
 
+/// `(old_size, new_size, size_delta)` of a binary delta. A side that does not
+/// exist (added / deleted file) has no size and counts as 0 in the delta.
+fn binary_sizes(
+    repo: &Repository,
+    delta: &git2::DiffDelta,
+) -> (Option<u64>, Option<u64>, Option<i64>) {
+    let old_size = side_size(repo, &delta.old_file());
+    let new_size = side_size(repo, &delta.new_file());
+    let size_delta = new_size.unwrap_or(0) as i64 - old_size.unwrap_or(0) as i64;
+    (old_size, new_size, Some(size_delta))
+}
+
+/// Size of one side of a delta: its blob, or the working-tree file when the
+/// content is not in the object database (unstaged changes).
+fn side_size(repo: &Repository, file: &git2::DiffFile) -> Option<u64> {
+    if !file.exists() {
+        return None;
+    }
+    if let Ok(blob) = repo.find_blob(file.id()) {
+        return Some(blob.size() as u64);
+    }
+    let on_disk = repo
+        .workdir()
+        .zip(file.path())
+        .and_then(|(workdir, path)| std::fs::metadata(workdir.join(path)).ok());
+    Some(on_disk.map_or(file.size(), |meta| meta.len()))
+}
+
 /// Override git2's binary detection. Returns true only for genuinely binary files.
 /// Priority: image extension → binary, known text extension → text,
 /// then content-based NUL scan for unknown extensions.
@@ -624,7 +1510,7 @@ fn is_truly_binary(path: &str, git2_says_binary: bool) -> bool {
 }
 
 
-fn is_image_file(path: &str) -> bool {
+pub(super) fn is_image_file(path: &str) -> bool {
     let path_lower = path.to_lowercase();
     IMAGE_EXTENSIONS
         .iter()
@@ -734,39 +1620,77 @@ fn parse_webp_dimensions(data: &[u8]) -> (u32, u32) {
     (0, 0)
 }
 
+/// Read the size of an SVG from its root `<svg>` tag.
+///
+/// Absolute `width`/`height` win; otherwise the `viewBox` size is used,
+/// keeping its aspect ratio when only one side is given. Relative sizes such
+/// as `100%` count as unknown, leaving 0 when there is no viewBox.
 fn parse_svg_dimensions(data: &[u8]) -> (u32, u32) {
     let content = String::from_utf8_lossy(data);
-    let mut width = 0u32;
-    let mut height = 0u32;
-
-    if let Some(svg_start) = content.find("<svg") {
-        let svg_tag = &content[svg_start..content[svg_start..]
-            .find('>')
-            .map(|i| svg_start + i + 1)
-            .unwrap_or(content.len())];
-
-        if let Some(w_start) = svg_tag.find("width=\"") {
-            let w_val = &svg_tag[w_start + 7..];
-            if let Some(w_end) = w_val.find('"') {
-                let numeric: String = w_val[..w_end]
-                    .chars()
-                    .take_while(|c| c.is_ascii_digit() || *c == '.')
-                    .collect();
-                width = numeric.parse::<f64>().unwrap_or(0.0) as u32;
-            }
-        }
-        if let Some(h_start) = svg_tag.find("height=\"") {
-            let h_val = &svg_tag[h_start + 8..];
-            if let Some(h_end) = h_val.find('"') {
-                let numeric: String = h_val[..h_end]
-                    .chars()
-                    .take_while(|c| c.is_ascii_digit() || *c == '.')
-                    .collect();
-                height = numeric.parse::<f64>().unwrap_or(0.0) as u32;
-            }
+    let svg_start = match content.find("<svg") {
+        Some(start) => start,
+        None => return (0, 0),
+    };
+    let svg_tag = &content[svg_start..content[svg_start..]
+        .find('>')
+        .map(|i| svg_start + i + 1)
+        .unwrap_or(content.len())];
+
+    let width = svg_attribute(svg_tag, "width").and_then(parse_svg_length);
+    let height = svg_attribute(svg_tag, "height").and_then(parse_svg_length);
+    let view_box = svg_attribute(svg_tag, "viewBox").and_then(parse_svg_view_box);
+
+    let (width, height) = match (width, height, view_box) {
+        (Some(w), Some(h), _) => (w, h),
+        (Some(w), None, Some((vw, vh))) => (w, w * vh / vw),
+        (None, Some(h), Some((vw, vh))) => (h * vw / vh, h),
+        (None, None, Some(size)) => size,
+        _ => (0.0, 0.0),
+    };
+    (width.round() as u32, height.round() as u32)
+}
+
+/// Value of `name` in an SVG tag, accepting either quote style and spaces
+/// around `=`.
+fn svg_attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let mut from = 0;
+    while let Some(found) = tag[from..].find(name) {
+        let start = from + found;
+        from = start + name.len();
+        // Skip longer names such as `stroke-width`
+        if !tag[..start].ends_with(|c: char| c.is_whitespace()) {
+            continue;
         }
+        let rest = tag[from..].trim_start();
+        let rest = match rest.strip_prefix('=') {
+            Some(rest) => rest.trim_start(),
+            None => continue,
+        };
+        let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let value = &rest[1..];
+        return value.find(quote).map(|end| &value[..end]);
+    }
+    None
+}
+
+/// Parse an absolute SVG length in user units (`120`, `120px`).
+fn parse_svg_length(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let number = value.strip_suffix("px").unwrap_or(value).trim_end();
+    number.parse::<f64>().ok().filter(|n| n.is_finite() && *n > 0.0)
+}
+
+/// Width and height of a `viewBox="min-x min-y width height"` attribute.
+fn parse_svg_view_box(value: &str) -> Option<(f64, f64)> {
+    let numbers: Vec<f64> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|part| !part.is_empty())
+        .map(|part| part.parse::<f64>().ok())
+        .collect::<Option<_>>()?;
+    match numbers[..] {
+        [_, _, width, height] if width > 0.0 && height > 0.0 => Some((width, height)),
+        _ => None,
     }
-    (width, height)
 }
 
 fn parse_image_dimensions(data: &[u8], format: &str) -> (u32, u32) {
@@ -779,17 +1703,19 @@ fn parse_image_dimensions(data: &[u8], format: &str) -> (u32, u32) {
     }
 }
 
+fn image_dimensions(data: &[u8], format: &str) -> (u32, u32) {
+    let dims = parse_image_dimensions(data, format);
+    if format == "SVG" && dims == (0, 0) {
+        return parse_svg_dimensions(data);
+    }
+    dims
+}
+
 fn build_image_data(data: &[u8], file_path: &str) -> ImageData {
     use base64::Engine;
     let mime_type = get_mime_type(file_path);
     let format = get_format_name(file_path);
-    let (mut width, mut height) = parse_image_dimensions(data, &format);
-
-    if format == "SVG" && width == 0 && height == 0 {
-        let dims = parse_svg_dimensions(data);
-        width = dims.0;
-        height = dims.1;
-    }
+    let (width, height) = image_dimensions(data, &format);
 
     let base64_data = base64::engine::general_purpose::STANDARD.encode(data);
 
@@ -800,7 +1726,84 @@ fn build_image_data(data: &[u8], file_path: &str) -> ImageData {
         width,
         height,
         format,
+        original_width: width,
+        original_height: height,
+        original_size: data.len() as u64,
+    }
+}
+
+/// Largest image (in pixels) decoded to build a thumbnail.
+const MAX_THUMBNAIL_SOURCE_PIXELS: u64 = 8192 * 8192;
+
+/// Like `build_image_data`, but PNGs wider or taller than `max_dimension` are
+/// downscaled to fit. The `original_*` fields keep the full image's metadata;
+/// formats that cannot be decoded are sent as-is.
+fn build_image_payload(data: &[u8], file_path: &str, max_dimension: Option<u32>) -> ImageData {
+    let max = match max_dimension {
+        Some(max) if max > 0 => max,
+        _ => return build_image_data(data, file_path),
+    };
+    let (width, height) = image_dimensions(data, &get_format_name(file_path));
+    if width <= max && height <= max {
+        return build_image_data(data, file_path);
+    }
+
+    match png_thumbnail(data, max) {
+        Some(encoded) => ImageData {
+            original_width: width,
+            original_height: height,
+            original_size: data.len() as u64,
+            ..build_image_data(&encoded, file_path)
+        },
+        None => build_image_data(data, file_path),
+    }
+}
+
+/// Decode a PNG and re-encode it so its longer side is `max` pixels.
+fn png_thumbnail(data: &[u8], max: u32) -> Option<Vec<u8>> {
+    // The thumbnail is sent with the source's MIME type, so it must stay a PNG
+    if !data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        return None;
+    }
+    let (width, height, pixels) = decode_rgba(data, MAX_THUMBNAIL_SOURCE_PIXELS)?;
+    let scale = max as f64 / width.max(height) as f64;
+    let new_width = ((width as f64 * scale).round() as u32).clamp(1, max);
+    let new_height = ((height as f64 * scale).round() as u32).clamp(1, max);
+    let scaled = downscale_rgba(&pixels, width, height, new_width, new_height);
+    encode_rgba_png(new_width, new_height, &scaled)
+}
+
+/// Box-filter RGBA pixels down to `new_width` x `new_height`.
+fn downscale_rgba(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    new_width: u32,
+    new_height: u32,
+) -> Vec<u8> {
+    let (w, h) = (width as usize, height as usize);
+    let (nw, nh) = (new_width as usize, new_height as usize);
+    let mut out = Vec::with_capacity(nw * nh * 4);
+    for ty in 0..nh {
+        let y0 = ty * h / nh;
+        let y1 = ((ty + 1) * h / nh).max(y0 + 1);
+        for tx in 0..nw {
+            let x0 = tx * w / nw;
+            let x1 = ((tx + 1) * w / nw).max(x0 + 1);
+            let mut sum = [0u64; 4];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let i = (y * w + x) * 4;
+                    for (acc, &v) in sum.iter_mut().zip(&pixels[i..i + 4]) {
+                        *acc += v as u64;
+                    }
+                }
+            }
+            let count = ((y1 - y0) * (x1 - x0)) as u64;
+            out.extend(sum.iter().map(|total| (total / count) as u8));
+        }
     }
+    out
 }
 
 /// Check if a file path is an image.
@@ -810,11 +1813,17 @@ pub async fn check_is_image(file_path: String) -> Result<bool, String> {
 }
 
 /// Get image data for diff comparison (old and new versions).
+///
+/// When both versions are PNGs of the same size, the result also carries the
+/// share of differing pixels and a mask highlighting them. Images that cannot
+/// be decoded keep the plain side-by-side result. With `max_dimension`, large
+/// PNGs are sent as thumbnails; `get_image_full` fetches the original.
 #[tauri::command]
 pub async fn get_image_diff(
     repo_path: String,
     file_path: String,
     staged: bool,
+    max_dimension: Option<u32>,
 ) -> Result<ImageDiffResult, String> {
     let normalized_path = normalize_unicode(&file_path);
 
@@ -824,54 +1833,166 @@ pub async fn get_image_diff(
             new_image: None,
             is_image: false,
             file_path: normalized_path,
+            diff_percentage: None,
+            diff_mask: None,
+            dimensions_changed: false,
         });
     }
 
+    safe_repo_path(Path::new(&repo_path), &normalized_path)?;
     let repo = open_repo(&repo_path)?;
-    let old_image = get_old_image_data(&repo, &normalized_path);
-    let new_image = get_new_image_data(&repo, &repo_path, &normalized_path, staged);
+    let old_bytes = get_old_image_bytes(&repo, &normalized_path);
+    let new_bytes = get_new_image_bytes(&repo, &repo_path, &normalized_path, staged);
+    let old_image = old_bytes
+        .as_deref()
+        .map(|data| build_image_payload(data, &normalized_path, max_dimension));
+    let new_image = new_bytes
+        .as_deref()
+        .map(|data| build_image_payload(data, &normalized_path, max_dimension));
+
+    let dimensions_changed = match (&old_image, &new_image) {
+        (Some(old), Some(new)) => {
+            old.original_width != 0
+                && new.original_width != 0
+                && (old.original_width != new.original_width
+                    || old.original_height != new.original_height)
+        }
+        _ => false,
+    };
+
+    let pixel_diff = match (&old_bytes, &new_bytes) {
+        (Some(old), Some(new)) if !dimensions_changed => {
+            compare_image_pixels(old, new, max_dimension)
+        }
+        _ => None,
+    };
+    let (diff_percentage, diff_mask) = match pixel_diff {
+        Some((percentage, mask)) => (Some(percentage), mask),
+        None => (None, None),
+    };
 
     Ok(ImageDiffResult {
         old_image,
         new_image,
         is_image: true,
         file_path: normalized_path,
+        diff_percentage,
+        diff_mask,
+        dimensions_changed,
     })
 }
 
-fn get_old_image_data(repo: &Repository, file_path: &str) -> Option<ImageData> {
+fn get_old_image_bytes(repo: &Repository, file_path: &str) -> Option<Vec<u8>> {
     let head = repo.head().ok()?;
     let tree = head.peel_to_tree().ok()?;
     let entry = tree.get_path(Path::new(file_path)).ok()?;
     let object = entry.to_object(repo).ok()?;
     let blob = object.as_blob()?;
-    Some(build_image_data(blob.content(), file_path))
+    Some(blob.content().to_vec())
 }
 
-fn get_new_image_data(
+fn get_new_image_bytes(
     repo: &Repository,
     repo_path: &str,
     file_path: &str,
     staged: bool,
-) -> Option<ImageData> {
+) -> Option<Vec<u8>> {
     if staged {
         let index = repo.index().ok()?;
         let entry = index.get_path(Path::new(file_path), 0)?;
         let blob = repo.find_blob(entry.id).ok()?;
-        Some(build_image_data(blob.content(), file_path))
+        Some(blob.content().to_vec())
+    } else {
+        let full_path = safe_repo_path(Path::new(repo_path), file_path).ok()?;
+        std::fs::read(&full_path).ok()
+    }
+}
+
+/// Largest image (in pixels) decoded for a pixel comparison.
+const MAX_PIXEL_DIFF_PIXELS: u64 = 4096 * 4096;
+
+/// Compare two encoded images pixel by pixel.
+///
+/// Returns the percentage of differing pixels and, when any differ, a PNG
+/// mask painting them opaque red on a transparent background. `None` when
+/// either side fails to decode or the sizes differ.
+fn compare_image_pixels(
+    old: &[u8],
+    new: &[u8],
+    max_dimension: Option<u32>,
+) -> Option<(f32, Option<ImageData>)> {
+    let (width, height, old_pixels) = decode_rgba(old, MAX_PIXEL_DIFF_PIXELS)?;
+    let (new_width, new_height, new_pixels) = decode_rgba(new, MAX_PIXEL_DIFF_PIXELS)?;
+    if width != new_width || height != new_height {
+        return None;
+    }
+
+    let total = width as usize * height as usize;
+    if total == 0 {
+        return None;
+    }
+
+    let mut mask = vec![0u8; total * 4];
+    let mut differing = 0usize;
+    for (i, (a, b)) in old_pixels
+        .chunks_exact(4)
+        .zip(new_pixels.chunks_exact(4))
+        .enumerate()
+    {
+        if a != b {
+            differing += 1;
+            mask[i * 4..i * 4 + 4].copy_from_slice(&[255, 0, 0, 255]);
+        }
+    }
+
+    let percentage = differing as f32 * 100.0 / total as f32;
+    let mask_image = if differing > 0 {
+        encode_rgba_png(width, height, &mask)
+            .map(|data| build_image_payload(&data, "diff-mask.png", max_dimension))
     } else {
-        let full_path = Path::new(repo_path).join(file_path);
-        let data = std::fs::read(&full_path).ok()?;
-        Some(build_image_data(&data, file_path))
+        None
+    };
+    Some((percentage, mask_image))
+}
+
+/// Decode a PNG, JPEG, GIF (first frame) or WebP into 8-bit RGBA pixels,
+/// giving up above `max_pixels`.
+fn decode_rgba(data: &[u8], max_pixels: u64) -> Option<(u32, u32, Vec<u8>)> {
+    let reader = || {
+        image::ImageReader::new(std::io::Cursor::new(data))
+            .with_guessed_format()
+            .ok()
+    };
+    // Read the header first so oversized images are never decoded
+    let (width, height) = reader()?.into_dimensions().ok()?;
+    if width as u64 * height as u64 > max_pixels {
+        return None;
     }
+    let pixels = reader()?.decode().ok()?.into_rgba8();
+    Some((width, height, pixels.into_raw()))
+}
+
+fn encode_rgba_png(width: u32, height: u32, pixels: &[u8]) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().ok()?;
+        writer.write_image_data(pixels).ok()?;
+    }
+    Some(out)
 }
 
 /// Get image data at a specific commit.
+///
+/// With `max_dimension`, large PNGs are downscaled as in `get_image_diff`.
 #[tauri::command]
 pub async fn get_image_at_commit(
     repo_path: String,
     file_path: String,
     commit_id: String,
+    max_dimension: Option<u32>,
 ) -> Result<Option<ImageData>, String> {
     let normalized_path = normalize_unicode(&file_path);
 
@@ -882,15 +2003,1110 @@ pub async fn get_image_at_commit(
     let repo = open_repo(&repo_path)?;
     let oid = Oid::from_str(&commit_id).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
     let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    let data = blob_bytes_at_commit(&repo, &commit, &normalized_path)?;
+
+    Ok(Some(build_image_payload(&data, &normalized_path, max_dimension)))
+}
+
+/// Get the original, full-size image after a thumbnail was shown.
+///
+/// `revision` selects a commit (e.g. `HEAD` for the old side of a working
+/// tree diff); without it the index (`staged`) or working tree copy is read.
+/// Returns `None` when the file does not exist there.
+#[tauri::command]
+pub async fn get_image_full(
+    repo_path: String,
+    file_path: String,
+    revision: Option<String>,
+    staged: bool,
+) -> Result<Option<ImageData>, String> {
+    let normalized_path = normalize_unicode(&file_path);
+
+    if !is_image_file(&normalized_path) {
+        return Ok(None);
+    }
+
+    safe_repo_path(Path::new(&repo_path), &normalized_path)?;
+    let repo = open_repo(&repo_path)?;
+    let data = match revision {
+        Some(revision) => {
+            let commit = resolve_revision(&repo, &revision)?;
+            let tree = commit
+                .tree()
+                .map_err(|e| format!("트리 접근 실패: {}", e))?;
+            match tree.get_path(Path::new(&normalized_path)) {
+                Err(e) if e.code() == git2::ErrorCode::NotFound => None,
+                _ => Some(blob_bytes_at_commit(&repo, &commit, &normalized_path)?),
+            }
+        }
+        None => get_new_image_bytes(&repo, &repo_path, &normalized_path, staged),
+    };
+
+    Ok(data.map(|data| build_image_data(&data, &normalized_path)))
+}
+
+fn blob_bytes_at_commit(
+    repo: &Repository,
+    commit: &git2::Commit,
+    file_path: &str,
+) -> Result<Vec<u8>, String> {
     let tree = commit.tree().map_err(|e| format!("트리 접근 실패: {}", e))?;
 
     let entry = tree
-        .get_path(Path::new(&normalized_path))
+        .get_path(Path::new(file_path))
         .map_err(|e| format!("파일 찾기 실패: {}", e))?;
     let object = entry
-        .to_object(&repo)
+        .to_object(repo)
         .map_err(|e| format!("오브젝트 접근 실패: {}", e))?;
     let blob = object.as_blob().ok_or("Blob이 아닙니다")?;
 
-    Ok(Some(build_image_data(blob.content(), &normalized_path)))
+    Ok(blob.content().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap();
+    }
+
+    fn stage_all(repo: &Repository) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+    }
+
+    fn find<'a>(stats: &'a [DiffStat], path: &str) -> &'a DiffStat {
+        stats.iter().find(|s| s.file_path == path).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_diff_stats_counts_lines() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("modified.txt"), "a\nb\nc\n").unwrap();
+        fs::write(root.join("deleted.txt"), "1\n2\n3\n4\n").unwrap();
+        commit_all(&repo, "Initial commit");
+
+        fs::write(root.join("modified.txt"), "a\nB\nc\nd\n").unwrap();
+        fs::remove_file(root.join("deleted.txt")).unwrap();
+        fs::write(root.join("added.txt"), "x\ny\n").unwrap();
+        stage_all(&repo);
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let stats = get_diff_stats(repo_path.clone(), true).await.unwrap();
+        assert_eq!(stats.len(), 3);
+
+        let modified = find(&stats, "modified.txt");
+        assert_eq!((modified.additions, modified.deletions), (2, 1));
+        let added = find(&stats, "added.txt");
+        assert_eq!((added.additions, added.deletions), (2, 0));
+        let deleted = find(&stats, "deleted.txt");
+        assert_eq!((deleted.additions, deleted.deletions), (0, 4));
+
+        // Unstaged side: one more line in the working tree
+        fs::write(root.join("modified.txt"), "a\nB\nc\nd\ne\n").unwrap();
+        let stats = get_diff_stats(repo_path, false).await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].additions, stats[0].deletions), (1, 0));
+    }
+
+    fn changed_text(line: &DiffLine) -> Vec<String> {
+        line.segments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .filter(|s| s.changed)
+            .map(|s| s.text.clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_diff_intra_line_korean_syllable() {
+        let diff = "diff --git a/메모.txt b/메모.txt\n--- a/메모.txt\n+++ b/메모.txt\n\
+                    @@ -1,3 +1,3 @@\n 제목\n-오늘은 날씨가 좋다\n+오늘은 날씨가 춥다\n 끝\n";
+        let parsed = parse_diff(diff.to_string()).await.unwrap();
+        let lines = &parsed.hunks[0].lines;
+
+        assert!(lines[0].segments.is_none());
+        assert_eq!(changed_text(&lines[1]), vec!["좋"]);
+        assert_eq!(changed_text(&lines[2]), vec!["춥"]);
+        assert!(lines[3].segments.is_none());
+
+        // Segments reassemble the original line
+        let rebuilt: String = lines[2]
+            .segments
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|s| s.text.as_str())
+            .collect();
+        assert_eq!(rebuilt, "오늘은 날씨가 춥다");
+    }
+
+    #[tokio::test]
+    async fn test_parse_diff_intra_line_words_and_threshold() {
+        let diff = "@@ -1,3 +1,3 @@\n\
+                    -let count = 1;\n\
+                    -완전히 다른 문장\n\
+                    -removed only\n\
+                    +let total = 1;\n\
+                    +abcdefgh\n";
+        let parsed = parse_diff(diff.to_string()).await.unwrap();
+        let lines = &parsed.hunks[0].lines;
+
+        // ASCII identifiers change as whole words
+        assert_eq!(changed_text(&lines[0]), vec!["count"]);
+        assert_eq!(changed_text(&lines[3]), vec!["total"]);
+        // Entirely rewritten pair: no segments
+        assert!(lines[1].segments.is_none());
+        assert!(lines[4].segments.is_none());
+        // Unpaired deletion
+        assert!(lines[2].segments.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_diff_new_file_without_trailing_newline() {
+        let diff = "diff --git a/new.txt b/new.txt\nnew file mode 100644\n\
+                    --- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,3 @@\n\
+                    +하나\n+++ 둘\n+셋\n\\ No newline at end of file\n";
+        let parsed = parse_diff(diff.to_string()).await.unwrap();
+        let hunk = &parsed.hunks[0];
+
+        assert_eq!((hunk.old_start, hunk.old_lines), (0, 0));
+        assert_eq!((hunk.new_start, hunk.new_lines), (1, 3));
+        assert_eq!(parsed.new_path, "new.txt");
+        assert_eq!(parsed.additions, 3);
+        assert_eq!(hunk.lines.len(), 3);
+        // "+++ 둘" inside the hunk is an added "++ 둘", not a header
+        assert_eq!(hunk.lines[1].content, "++ 둘");
+        let numbers: Vec<_> = hunk.lines.iter().map(|l| l.new_line_no).collect();
+        assert_eq!(numbers, vec![Some(1), Some(2), Some(3)]);
+        assert!(!hunk.lines[1].no_newline_at_eof);
+        assert!(hunk.lines[2].no_newline_at_eof);
+    }
+
+    #[tokio::test]
+    async fn test_parse_diff_deletion_only() {
+        let diff = "diff --git a/old.txt b/old.txt\ndeleted file mode 100644\n\
+                    --- a/old.txt\n+++ /dev/null\n@@ -1,2 +0,0 @@\n\
+                    -첫 줄\n--- 둘째 줄\n";
+        let parsed = parse_diff(diff.to_string()).await.unwrap();
+        let hunk = &parsed.hunks[0];
+
+        assert_eq!((hunk.new_start, hunk.new_lines), (0, 0));
+        assert_eq!(parsed.old_path, "old.txt");
+        assert_eq!(parsed.deletions, 2);
+        assert_eq!(hunk.lines[1].content, "-- 둘째 줄");
+        let numbers: Vec<_> = hunk.lines.iter().map(|l| l.old_line_no).collect();
+        assert_eq!(numbers, vec![Some(1), Some(2)]);
+        assert!(hunk.lines.iter().all(|l| !l.no_newline_at_eof));
+    }
+
+    #[tokio::test]
+    async fn test_parse_diff_both_sides_missing_final_newline() {
+        let diff = "@@ -1,2 +1,3 @@\n 유지\n-이전\n\\ No newline at end of file\n\
+                    +이후\n+추가\n\\ No newline at end of file\n";
+        let parsed = parse_diff(diff.to_string()).await.unwrap();
+        let lines = &parsed.hunks[0].lines;
+
+        assert_eq!(lines.len(), 4);
+        assert_eq!((parsed.additions, parsed.deletions), (2, 1));
+        let flagged: Vec<_> = lines.iter().map(|l| l.no_newline_at_eof).collect();
+        assert_eq!(flagged, vec![false, true, false, true]);
+        assert_eq!(lines[1].old_line_no, Some(2));
+        assert_eq!(lines[2].new_line_no, Some(2));
+        assert_eq!(lines[3].new_line_no, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_parse_multi_diff_commit_with_three_files() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("수정.txt"), "a\nb\nc\n").unwrap();
+        fs::write(root.join("deleted.txt"), "x\ny\n").unwrap();
+        commit_all(&repo, "Initial commit");
+
+        fs::write(root.join("수정.txt"), "a\nB\nc\nd\n").unwrap();
+        fs::remove_file(root.join("deleted.txt")).unwrap();
+        fs::write(root.join("added.txt"), "new\n").unwrap();
+        commit_all(&repo, "Three files");
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let head = repo.head().unwrap().target().unwrap().to_string();
+        let patch = get_commit_diff(repo_path, head, None, None).await.unwrap();
+        let files = parse_multi_diff(patch).await.unwrap();
+
+        let summary: Vec<_> = files
+            .iter()
+            .map(|f| (f.file_path.as_str(), f.additions, f.deletions))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![("added.txt", 1, 0), ("deleted.txt", 0, 2), ("수정.txt", 2, 1)]
+        );
+        assert_eq!(files[0].new_mode, Some(0o100644));
+        assert_eq!(files[1].old_mode, Some(0o100644));
+        assert!(files.iter().all(|f| f.hunks.len() == 1));
+    }
+
+    #[tokio::test]
+    async fn test_parse_multi_diff_binary_mode_and_rename() {
+        let diff = "diff --git a/run.sh b/run.sh\nold mode 100644\nnew mode 100755\n\
+                    diff --git a/logo.png b/logo.png\nindex 1111111..2222222 100644\n\
+                    Binary files a/logo.png and b/logo.png differ\n\
+                    diff --git a/이전.md b/새이름.md\nsimilarity index 90%\n\
+                    rename from 이전.md\nrename to 새이름.md\n\
+                    --- a/이전.md\n+++ b/새이름.md\n@@ -1 +1 @@\n-old\n+new\n";
+        let files = parse_multi_diff(diff.to_string()).await.unwrap();
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].file_path, "run.sh");
+        assert_eq!(
+            (files[0].old_mode, files[0].new_mode),
+            (Some(0o100644), Some(0o100755))
+        );
+        assert!(files[0].hunks.is_empty());
+        assert!(files[1].is_binary);
+        assert!(!files[2].is_binary);
+        assert_eq!(files[2].old_path, "이전.md");
+        assert_eq!(files[2].new_path, "새이름.md");
+        assert_eq!(files[2].file_path, "새이름.md");
+        assert_eq!((files[2].additions, files[2].deletions), (1, 1));
+
+        assert!(parse_multi_diff(String::new()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_file_changes_root_and_rename() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let body: String = (1..=20).map(|i| format!("줄 {}\n", i)).collect();
+        fs::write(root.join("이전.txt"), &body).unwrap();
+        fs::write(root.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0, 1]).unwrap();
+        commit_all(&repo, "Initial commit");
+        let repo_path = root.to_str().unwrap().to_string();
+        let first = repo.head().unwrap().target().unwrap().to_string();
+
+        // Root commit: everything is added
+        let changes = get_commit_file_changes(repo_path.clone(), first).await.unwrap();
+        assert_eq!(changes.len(), 2);
+        let text = changes.iter().find(|c| c.path == "이전.txt").unwrap();
+        assert_eq!((text.status.as_str(), text.additions, text.deletions), ("added", 20, 0));
+        let image = changes.iter().find(|c| c.path == "logo.png").unwrap();
+        assert!(image.is_binary);
+        assert_eq!((image.additions, image.deletions), (0, 0));
+
+        // Rename with one edited line
+        fs::remove_file(root.join("이전.txt")).unwrap();
+        fs::write(root.join("새이름.txt"), body.replace("줄 3\n", "셋째 줄\n")).unwrap();
+        commit_all(&repo, "Rename");
+        let second = repo.head().unwrap().target().unwrap().to_string();
+
+        let changes = get_commit_file_changes(repo_path, second).await.unwrap();
+        assert_eq!(changes.len(), 1);
+        let renamed = &changes[0];
+        assert_eq!(renamed.status, "renamed");
+        assert_eq!(renamed.path, "새이름.txt");
+        assert_eq!(renamed.old_path.as_deref(), Some("이전.txt"));
+        assert_eq!((renamed.additions, renamed.deletions), (1, 1));
+        assert!(!renamed.is_binary);
+    }
+
+    #[tokio::test]
+    async fn test_merge_base_diff_ignores_base_progress() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("공통.txt"), "base\n").unwrap();
+        commit_all(&repo, "Initial commit");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let fork = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("feature", &fork, false).unwrap();
+
+        // main moves on after the fork
+        fs::write(root.join("main-only.txt"), "main\n").unwrap();
+        commit_all(&repo, "Main work");
+
+        // feature adds its own file
+        repo.set_head("refs/heads/feature").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        fs::write(root.join("기능.txt"), "feature\n").unwrap();
+        commit_all(&repo, "Feature work");
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let result = get_merge_base_diff(repo_path.clone(), main.clone(), "feature".to_string())
+            .await
+            .unwrap();
+        assert_eq!(result.merge_base, fork.id().to_string());
+        assert_eq!(result.files.len(), 1);
+        assert_eq!(result.files[0].file_path, "기능.txt");
+        assert_eq!(result.files[0].additions, 1);
+        assert!(result.patch.contains("+feature"));
+        assert!(!result.patch.contains("main-only.txt"));
+
+        // Unrelated history
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        repo.commit(Some("refs/heads/orphan"), &sig, &sig, "Orphan", &tree, &[])
+            .unwrap();
+        let err = get_merge_base_diff(repo_path, main, "orphan".to_string())
+            .await
+            .unwrap_err();
+        let payload: crate::commands::models::CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "no_merge_base");
+    }
+
+    #[tokio::test]
+    async fn test_merge_commit_diff_per_parent_and_combined() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("충돌.txt"), "base\n").unwrap();
+        commit_all(&repo, "Base");
+        let main_ref = repo.head().unwrap().name().unwrap().to_string();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("other", &base, false).unwrap();
+
+        fs::write(root.join("충돌.txt"), "ours\n").unwrap();
+        commit_all(&repo, "Ours");
+        let ours = repo.head().unwrap().peel_to_commit().unwrap();
+
+        repo.set_head("refs/heads/other").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        fs::write(root.join("충돌.txt"), "theirs\n").unwrap();
+        fs::write(root.join("theirs-only.txt"), "new\n").unwrap();
+        commit_all(&repo, "Theirs");
+        let theirs = repo.head().unwrap().peel_to_commit().unwrap();
+
+        // Merge by hand: both files, conflict resolved to new content
+        repo.set_head(&main_ref).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        fs::write(root.join("충돌.txt"), "resolved\n").unwrap();
+        fs::write(root.join("theirs-only.txt"), "new\n").unwrap();
+        stage_all(&repo);
+        let mut index = repo.index().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let merge = repo
+            .commit(Some("HEAD"), &sig, &sig, "Merge", &tree, &[&ours, &theirs])
+            .unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        let per_parent =
+            get_merge_commit_diff(repo_path.clone(), merge.to_string(), MergeDiffMode::PerParent)
+                .await
+                .unwrap();
+        assert_eq!(per_parent.len(), 2);
+        assert_eq!(per_parent[0].parent_sha, ours.id().to_string());
+        assert_eq!(per_parent[0].files, vec!["theirs-only.txt", "충돌.txt"]);
+        assert_eq!(per_parent[1].parent_index, 1);
+        assert_eq!(per_parent[1].parent_sha, theirs.id().to_string());
+        assert_eq!(per_parent[1].files, vec!["충돌.txt"]);
+        assert!(per_parent[1].patch.contains("-theirs\n+resolved"));
+
+        let combined =
+            get_merge_commit_diff(repo_path.clone(), merge.to_string(), MergeDiffMode::Combined)
+                .await
+                .unwrap();
+        assert!(combined.iter().all(|d| d.files == vec!["충돌.txt"]));
+        assert!(combined[0].patch.contains("-ours\n+resolved"));
+        assert!(!combined[0].patch.contains("theirs-only"));
+
+        let err = get_merge_commit_diff(repo_path, ours.id().to_string(), MergeDiffMode::Combined)
+            .await
+            .unwrap_err();
+        assert!(err.contains("not_a_merge_commit"));
+    }
+
+    #[tokio::test]
+    async fn test_untracked_file_diff_is_all_additions() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("README.md"), "# 테스트\n").unwrap();
+        commit_all(&repo, "Initial commit");
+
+        fs::write(root.join("new.txt"), "첫 줄\n둘째 줄\n").unwrap();
+        fs::create_dir_all(root.join("새폴더/하위")).unwrap();
+        fs::write(root.join("새폴더/하위/메모.txt"), "내용\n").unwrap();
+
+        let repo_path = root.to_str().unwrap().to_string();
+        for (file, expected) in [
+            ("new.txt", vec!["첫 줄", "둘째 줄"]),
+            ("새폴더/하위/메모.txt", vec!["내용"]),
+        ] {
+            let diff = get_file_diff(
+                repo_path.clone(),
+                file.to_string(),
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            let parsed = parse_diff(diff.patch).await.unwrap();
+            assert_eq!(parsed.hunks.len(), 1, "{}", file);
+            let lines = &parsed.hunks[0].lines;
+            assert!(lines.iter().all(|l| l.line_type == "addition"));
+            let contents: Vec<&str> = lines.iter().map(|l| l.content.trim_end()).collect();
+            assert_eq!(contents, expected);
+            assert_eq!(parsed.deletions, 0);
+        }
+    }
+
+    /// Unstaged patch text of `file` with the given whitespace flags.
+    async fn diff_text(
+        repo_path: &str,
+        file: &str,
+        ignore_whitespace: Option<bool>,
+        ignore_whitespace_change: Option<bool>,
+    ) -> String {
+        get_file_diff(
+            repo_path.to_string(),
+            file.to_string(),
+            false,
+            None,
+            ignore_whitespace,
+            ignore_whitespace_change,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+        .patch
+    }
+
+    /// Limited unstaged diff of `file`.
+    async fn limited_diff(
+        repo_path: &str,
+        file: &str,
+        max_lines: Option<usize>,
+        max_bytes: Option<usize>,
+        max_file_size: Option<u64>,
+    ) -> FileDiff {
+        get_file_diff(
+            repo_path.to_string(),
+            file.to_string(),
+            false,
+            None,
+            None,
+            None,
+            max_lines,
+            max_bytes,
+            max_file_size,
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_file_diff_truncates_at_hunk_boundaries() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let original: String = (0..200).map(|i| format!("line {}\n", i)).collect();
+        fs::write(root.join("gen.txt"), &original).unwrap();
+        commit_all(&repo, "Initial commit");
+        // A change every 20 lines: 10 separate hunks of 7 lines + header
+        let edited: String = (0..200)
+            .map(|i| match i % 20 {
+                10 => format!("changed {}\n", i),
+                _ => format!("line {}\n", i),
+            })
+            .collect();
+        fs::write(root.join("gen.txt"), edited).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        let full = limited_diff(&repo_path, "gen.txt", None, None, None).await;
+        assert!(!full.truncated);
+        assert_eq!(full.returned_lines, full.total_lines);
+        let full_hunks = parse_diff(full.patch.clone()).await.unwrap().hunks;
+        assert_eq!(full_hunks.len(), 10);
+
+        for (max_lines, max_bytes) in [(Some(30), None), (None, Some(700)), (Some(5), None)] {
+            let cut = limited_diff(&repo_path, "gen.txt", max_lines, max_bytes, None).await;
+            assert!(cut.truncated);
+            assert_eq!(cut.total_lines, full.total_lines);
+            assert_eq!(cut.returned_lines, cut.patch.lines().count());
+            assert!(cut.returned_lines <= max_lines.unwrap_or(usize::MAX).max(4));
+            assert!(cut.patch.len() <= max_bytes.unwrap_or(usize::MAX));
+
+            // A prefix of the full patch that ends right before a hunk header
+            assert!(full.patch.starts_with(&cut.patch));
+            assert!(full.patch[cut.patch.len()..].starts_with("@@"));
+            let hunks = parse_diff(cut.patch).await.unwrap().hunks;
+            assert!(hunks.len() < full_hunks.len());
+            for (hunk, whole) in hunks.iter().zip(&full_hunks) {
+                assert_eq!(hunk.lines.len(), whole.lines.len());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_diff_skips_content_over_max_file_size() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("big.txt"), "a\n".repeat(1000)).unwrap();
+        commit_all(&repo, "Initial commit");
+        fs::write(root.join("big.txt"), "b\n".repeat(1000)).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        let skipped = limited_diff(&repo_path, "big.txt", None, None, Some(1024)).await;
+        let parsed = parse_diff(skipped.patch).await.unwrap();
+        assert!(parsed.is_binary);
+        assert!(parsed.hunks.is_empty());
+
+        let shown = limited_diff(&repo_path, "big.txt", None, None, Some(1 << 20)).await;
+        assert!(!parse_diff(shown.patch).await.unwrap().is_binary);
+    }
+
+    /// Text round-trip (`get_file_diff` + `parse_diff`) of the unstaged diff of `file`.
+    async fn text_parsed(repo_path: &str, file: &str) -> ParsedDiff {
+        parse_diff(diff_text(repo_path, file, None, None).await)
+            .await
+            .unwrap()
+    }
+
+    fn line_numbers(parsed: &ParsedDiff) -> Vec<(String, Option<u32>, Option<u32>, bool)> {
+        parsed
+            .hunks
+            .iter()
+            .flat_map(|h| &h.lines)
+            .map(|l| {
+                (
+                    l.content.clone(),
+                    l.old_line_no,
+                    l.new_line_no,
+                    l.no_newline_at_eof,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_structured_diff_matches_text_parse() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let original: String = (1..=30).map(|i| format!("줄 {}\n", i)).collect();
+        fs::write(root.join("a.txt"), &original).unwrap();
+        fs::write(root.join("gone.txt"), "x\ny\n").unwrap();
+        commit_all(&repo, "Initial commit");
+
+        // First line removed, one line changed further down, last line
+        // replaced by one without a newline
+        let edited = original
+            .replacen("줄 1\n", "", 1)
+            .replace("줄 15\n", "줄 열다섯\n")
+            .replace("줄 30\n", "끝");
+        fs::write(root.join("a.txt"), edited).unwrap();
+        fs::write(root.join("new.txt"), "하나\n둘\n").unwrap();
+        fs::write(root.join("gone.txt"), "").unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        for file in ["a.txt", "new.txt", "gone.txt"] {
+            let structured =
+                get_file_diff_structured(repo_path.clone(), file.to_string(), false, None)
+                    .await
+                    .unwrap();
+            let text = text_parsed(&repo_path, file).await;
+            assert_eq!(structured.file_path, file);
+            assert_eq!(structured.hunks.len(), text.hunks.len(), "{}", file);
+            for (a, b) in structured.hunks.iter().zip(&text.hunks) {
+                assert_eq!(a.header, b.header);
+                assert_eq!(
+                    (a.old_start, a.old_lines, a.new_start, a.new_lines),
+                    (b.old_start, b.old_lines, b.new_start, b.new_lines)
+                );
+            }
+            assert_eq!(line_numbers(&structured), line_numbers(&text), "{}", file);
+            assert_eq!(
+                (structured.additions, structured.deletions),
+                (text.additions, text.deletions)
+            );
+        }
+
+        let new_file =
+            get_file_diff_structured(repo_path.clone(), "new.txt".to_string(), false, None)
+                .await
+                .unwrap();
+        assert_eq!(new_file.hunks[0].old_start, 0);
+        assert_eq!(
+            line_numbers(&new_file),
+            vec![
+                ("하나".to_string(), None, Some(1), false),
+                ("둘".to_string(), None, Some(2), false),
+            ]
+        );
+
+        let modified = get_file_diff_structured(repo_path.clone(), "a.txt".to_string(), false, None)
+            .await
+            .unwrap();
+        let last = modified.hunks.last().unwrap().lines.last().unwrap();
+        assert_eq!((last.content.as_str(), last.new_line_no), ("끝", Some(29)));
+        assert!(last.no_newline_at_eof);
+        let changed = modified.hunks[1]
+            .lines
+            .iter()
+            .find(|l| l.line_type == "addition")
+            .unwrap();
+        assert!(changed.segments.is_some());
+
+        let unchanged = get_file_diff_structured(repo_path, "missing.txt".to_string(), true, None)
+            .await
+            .unwrap();
+        assert!(unchanged.hunks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_whitespace_options_hide_reindent() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("main.rs"), "fn main() {\nlet x = 1;\n}\n").unwrap();
+        commit_all(&repo, "Initial commit");
+        fs::write(root.join("main.rs"), "fn main() {\n    let  x = 1;\n}\n").unwrap();
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let file = "main.rs".to_string();
+        let plain = diff_text(&repo_path, &file, None, None).await;
+        assert!(plain.contains("+    let  x = 1;"));
+        let ignored = diff_text(&repo_path, &file, Some(true), None).await;
+        assert_eq!(ignored, "");
+        // -b still sees whitespace inserted where there was none
+        let change_only = diff_text(&repo_path, &file, None, Some(true)).await;
+        assert!(change_only.contains("+    let  x = 1;"));
+
+        stage_all(&repo);
+        commit_all(&repo, "Reindent");
+        let head = repo.head().unwrap().target().unwrap().to_string();
+        let commit_plain = get_commit_diff(repo_path.clone(), head.clone(), None, None)
+            .await
+            .unwrap();
+        assert!(commit_plain.contains("@@"));
+        let commit_ignored = get_commit_diff(repo_path, head, Some(true), None)
+            .await
+            .unwrap();
+        assert!(!commit_ignored.contains("@@"));
+    }
+
+    #[tokio::test]
+    async fn test_diff_stats_binary_and_rename() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let body: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+        fs::write(root.join("old_name.txt"), &body).unwrap();
+        commit_all(&repo, "Initial commit");
+
+        fs::rename(root.join("old_name.txt"), root.join("new_name.txt")).unwrap();
+        fs::write(root.join("logo.png"), [0x89, b'P', b'N', b'G', 0, 0, 1, 2]).unwrap();
+        stage_all(&repo);
+
+        let stats = get_diff_stats(root.to_str().unwrap().to_string(), true)
+            .await
+            .unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let renamed = find(&stats, "new_name.txt");
+        assert_eq!((renamed.additions, renamed.deletions), (0, 0));
+        let binary = find(&stats, "logo.png");
+        assert!(binary.is_binary);
+        assert_eq!((binary.additions, binary.deletions), (0, 0));
+        assert_eq!(
+            (binary.old_size, binary.new_size, binary.size_delta),
+            (None, Some(8), Some(8))
+        );
+        assert_eq!(renamed.size_delta, None);
+    }
+
+    #[tokio::test]
+    async fn test_binary_sizes_for_added_modified_and_deleted() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        fs::write(root.join("README.md"), "# 이미지\n").unwrap();
+        fs::write(root.join("logo.png"), vec![0u8; 1000]).unwrap();
+        fs::write(root.join("icon.png"), vec![1u8; 300]).unwrap();
+        commit_all(&repo, "Add images");
+
+        fs::write(root.join("logo.png"), vec![0u8; 1500]).unwrap();
+        fs::remove_file(root.join("icon.png")).unwrap();
+
+        // Unstaged: the new logo.png is only on disk
+        let unstaged = get_diff_stats(repo_path.clone(), false).await.unwrap();
+        let logo = find(&unstaged, "logo.png");
+        assert_eq!(
+            (logo.old_size, logo.new_size, logo.size_delta),
+            (Some(1000), Some(1500), Some(500))
+        );
+        let structured =
+            get_file_diff_structured(repo_path.clone(), "logo.png".to_string(), false, None)
+                .await
+                .unwrap();
+        assert!(structured.is_binary);
+        assert_eq!(
+            (structured.old_size, structured.new_size),
+            (Some(1000), Some(1500))
+        );
+
+        commit_all(&repo, "Grow logo, drop icon");
+        let head = repo.head().unwrap().target().unwrap().to_string();
+        let changes = get_commit_file_changes(repo_path.clone(), head).await.unwrap();
+        let logo = changes.iter().find(|c| c.path == "logo.png").unwrap();
+        assert_eq!(
+            (logo.old_size, logo.new_size, logo.size_delta),
+            (Some(1000), Some(1500), Some(500))
+        );
+        let icon = changes.iter().find(|c| c.path == "icon.png").unwrap();
+        assert_eq!(icon.status, "deleted");
+        assert_eq!(
+            (icon.old_size, icon.new_size, icon.size_delta),
+            (Some(300), None, Some(-300))
+        );
+    }
+
+    fn solid_png(width: u32, height: u32, pixels: &[[u8; 4]]) -> Vec<u8> {
+        let mut data: Vec<u8> = pixels.iter().flatten().copied().collect();
+        data.resize((width * height * 4) as usize, 255);
+        encode_rgba_png(width, height, &data).unwrap()
+    }
+
+    fn encode_rgba(
+        width: u32,
+        height: u32,
+        pixels: &[[u8; 4]],
+        format: image::ImageFormat,
+    ) -> Vec<u8> {
+        let data: Vec<u8> = pixels.iter().flatten().copied().collect();
+        let image = image::RgbaImage::from_raw(width, height, data).unwrap();
+        let mut out = std::io::Cursor::new(Vec::new());
+        image.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    #[tokio::test]
+    async fn test_image_diff_pixel_metrics() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        let white = [255, 255, 255, 255];
+        fs::write(root.join("a.png"), solid_png(2, 2, &[white; 4])).unwrap();
+        fs::write(root.join("b.png"), solid_png(2, 2, &[white; 4])).unwrap();
+        commit_all(&repo, "Add images");
+
+        // One of four pixels turns black; b.png grows instead
+        let black = [0, 0, 0, 255];
+        fs::write(
+            root.join("a.png"),
+            solid_png(2, 2, &[white, black, white, white]),
+        )
+        .unwrap();
+        fs::write(root.join("b.png"), solid_png(3, 2, &[])).unwrap();
+
+        let result = get_image_diff(repo_path.clone(), "a.png".to_string(), false, None)
+            .await
+            .unwrap();
+        assert!(!result.dimensions_changed);
+        assert_eq!(result.diff_percentage, Some(25.0));
+        let mask = result.diff_mask.unwrap();
+        assert_eq!(
+            (mask.width, mask.height, mask.format.as_str()),
+            (2, 2, "PNG")
+        );
+
+        let unchanged = get_image_diff(repo_path.clone(), "a.png".to_string(), true, None)
+            .await
+            .unwrap();
+        assert_eq!(unchanged.diff_percentage, Some(0.0));
+        assert!(unchanged.diff_mask.is_none());
+
+        let resized = get_image_diff(repo_path.clone(), "b.png".to_string(), false, None)
+            .await
+            .unwrap();
+        assert!(resized.dimensions_changed);
+        assert!(resized.diff_percentage.is_none() && resized.diff_mask.is_none());
+
+        // Undecodable data keeps the plain side-by-side result
+        fs::write(root.join("a.png"), vec![0u8; 64]).unwrap();
+        let broken = get_image_diff(repo_path, "a.png".to_string(), false, None)
+            .await
+            .unwrap();
+        assert!(broken.old_image.is_some() && broken.new_image.is_some());
+        assert!(!broken.dimensions_changed);
+        assert!(broken.diff_percentage.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_image_diff_pixel_metrics_for_gif_and_webp() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        let (white, black) = ([255, 255, 255, 255], [0, 0, 0, 255]);
+        let formats = [
+            ("a.gif", image::ImageFormat::Gif),
+            ("a.webp", image::ImageFormat::WebP),
+        ];
+        for (name, format) in formats {
+            fs::write(root.join(name), encode_rgba(2, 2, &[white; 4], format)).unwrap();
+        }
+        commit_all(&repo, "Add images");
+
+        for (name, format) in formats {
+            let changed = encode_rgba(2, 2, &[white, black, white, white], format);
+            fs::write(root.join(name), changed).unwrap();
+            let result = get_image_diff(repo_path.clone(), name.to_string(), false, None)
+                .await
+                .unwrap();
+            assert_eq!(result.diff_percentage, Some(25.0), "{}", name);
+            let mask = result.diff_mask.unwrap();
+            assert_eq!((mask.width, mask.height), (2, 2));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_image_thumbnails_are_bounded() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+        let gradient: Vec<[u8; 4]> = (0..300 * 200)
+            .map(|i| [(i % 300) as u8, (i / 300) as u8, 128, 255])
+            .collect();
+        let original = solid_png(300, 200, &gradient);
+        fs::write(root.join("큰그림.png"), &original).unwrap();
+        commit_all(&repo, "Add image");
+        let head = repo.head().unwrap().target().unwrap().to_string();
+
+        let thumb = get_image_at_commit(
+            repo_path.clone(),
+            "큰그림.png".to_string(),
+            head.clone(),
+            Some(64),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!((thumb.width, thumb.height), (64, 43));
+        assert_eq!((thumb.original_width, thumb.original_height), (300, 200));
+        assert_eq!(thumb.original_size, original.len() as u64);
+        assert!(thumb.size < thumb.original_size);
+        assert!(thumb.data.len() < original.len() * 4 / 3);
+
+        // Images already within the bound are passed through untouched
+        let small = get_image_diff(
+            repo_path.clone(),
+            "큰그림.png".to_string(),
+            false,
+            Some(512),
+        )
+        .await
+        .unwrap();
+        let new_image = small.new_image.unwrap();
+        assert_eq!(
+            (new_image.width, new_image.size),
+            (300, original.len() as u64)
+        );
+        assert_eq!(small.diff_percentage, Some(0.0));
+
+        let full = get_image_full(
+            repo_path.clone(),
+            "큰그림.png".to_string(),
+            Some("HEAD".to_string()),
+            false,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            (full.width, full.height, full.size),
+            (300, 200, original.len() as u64)
+        );
+
+        // Missing at the revision, like a missing working tree file
+        for revision in [Some("HEAD".to_string()), None] {
+            let missing = get_image_full(repo_path.clone(), "없음.png".into(), revision, false)
+                .await
+                .unwrap();
+            assert!(missing.is_none());
+        }
+    }
+
+    #[test]
+    fn test_parse_svg_dimensions() {
+        let cases: &[(&str, (u32, u32))] = &[
+            // Figma export: viewBox only
+            (
+                r#"<svg viewBox="0 0 1200 630" fill="none" xmlns="http://www.w3.org/2000/svg">"#,
+                (1200, 630),
+            ),
+            // Illustrator export
+            (
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<svg version="1.1" id="Layer_1" xmlns="http://www.w3.org/2000/svg" x="0px" y="0px"
+	 viewBox="0 0 512 512" style="enable-background:new 0 0 512 512;" xml:space="preserve">"#,
+                (512, 512),
+            ),
+            // Inkscape export: physical units fall back to the viewBox
+            (
+                "<svg\n   width=\"210mm\"\n   height=\"297mm\"\n   viewBox=\"0 0 210 297\">",
+                (210, 297),
+            ),
+            // Single quotes and spaces around `=`
+            (
+                "<svg xmlns='http://www.w3.org/2000/svg' width = '24' height= '24px'>",
+                (24, 24),
+            ),
+            // One side plus viewBox keeps the aspect ratio
+            (r#"<svg width="600" viewBox="0,0,1200,630">"#, (600, 315)),
+            // Longer attribute names are not mistaken for width/height
+            (
+                r#"<svg stroke-width="2" viewBox="0 0 16 8" data-height="99">"#,
+                (16, 8),
+            ),
+            // Relative sizes are unknown
+            (
+                r#"<svg width="100%" height="100%" xmlns="http://www.w3.org/2000/svg">"#,
+                (0, 0),
+            ),
+            (r#"<svg width="100%" height="100%" viewBox="0 0 64 48">"#, (64, 48)),
+            ("<html><body>no svg here</body></html>", (0, 0)),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(parse_svg_dimensions(header.as_bytes()), *expected, "{}", header);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_mode_only_change() {
+        use std::os::unix::fs::PermissionsExt;
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("run.sh"), "#!/bin/sh\necho hi\n").unwrap();
+        fs::write(root.join("edit.sh"), "#!/bin/sh\necho hi\n").unwrap();
+        commit_all(&repo, "Add scripts");
+        let repo_path = root.to_str().unwrap().to_string();
+
+        for script in ["run.sh", "edit.sh"] {
+            fs::set_permissions(root.join(script), fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        fs::write(root.join("edit.sh"), "#!/bin/sh\necho bye\n").unwrap();
+
+        let structured =
+            get_file_diff_structured(repo_path.clone(), "run.sh".to_string(), false, None)
+                .await
+                .unwrap();
+        let text = text_parsed(&repo_path, "run.sh").await;
+        for parsed in [&structured, &text] {
+            assert!(parsed.mode_only);
+            assert!(parsed.hunks.is_empty());
+            assert_eq!(
+                (parsed.old_mode, parsed.new_mode),
+                (Some(0o100644), Some(0o100755))
+            );
+            assert_eq!((parsed.old_path.as_str(), parsed.new_path.as_str()), ("run.sh", "run.sh"));
+        }
+
+        // A mode change alongside an edit still has hunks to show
+        let edited = text_parsed(&repo_path, "edit.sh").await;
+        assert_eq!(edited.new_mode, Some(0o100755));
+        assert!(!edited.mode_only);
+        assert_eq!(edited.hunks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_content_detects_binary_and_encodings() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let repo_path = root.to_str().unwrap().to_string();
+
+        let utf16: Vec<u8> = [0xFF, 0xFE]
+            .into_iter()
+            .chain("안녕\r\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        fs::write(root.join("utf16.txt"), &utf16).unwrap();
+        fs::write(root.join("한글.md"), "# 제목\n").unwrap();
+        fs::write(root.join("logo.png"), solid_png(2, 2, &[])).unwrap();
+        fs::write(root.join("latin1.txt"), b"caf\xE9 cr\xE8me\n").unwrap();
+        commit_all(&repo, "Add files");
+        let head = repo.head().unwrap().target().unwrap().to_string();
+
+        for (file, encoding, text) in [
+            ("utf16.txt", "utf-16le", Some("안녕\r\n")),
+            ("한글.md", "utf-8", Some("# 제목\n")),
+            ("logo.png", "binary", None),
+            ("latin1.txt", "unknown", Some("caf\u{FFFD} cr\u{FFFD}me\n")),
+        ] {
+            let on_disk = get_file_content(repo_path.clone(), file.to_string(), None)
+                .await
+                .unwrap();
+            let committed =
+                get_file_content(repo_path.clone(), file.to_string(), Some(head.clone()))
+                    .await
+                    .unwrap();
+            assert_eq!(
+                serde_json::to_value(&on_disk).unwrap(),
+                serde_json::to_value(&committed).unwrap(),
+                "{}",
+                file
+            );
+            assert_eq!(on_disk.encoding_guess, encoding, "{}", file);
+            assert_eq!(on_disk.text.as_deref(), text, "{}", file);
+            assert_eq!(on_disk.is_binary, text.is_none());
+        }
+
+        let png = get_file_content(repo_path, "logo.png".to_string(), None)
+            .await
+            .unwrap();
+        use base64::Engine;
+        let decoded = base64::engine::general_purpose::STANDARD
+            .decode(png.base64.unwrap())
+            .unwrap();
+        assert_eq!(decoded.len() as u64, png.size);
+
+        let large = file_content(&vec![0u8; MAX_BASE64_CONTENT_SIZE + 1]);
+        assert!(large.is_binary && large.base64.is_none());
+    }
 }
@@ -0,0 +1,268 @@
+//! Commit diffs streamed to the frontend as events.
+//!
+//! `get_commit_diff` builds the whole patch as one string, which for
+//! monorepo-sized commits means hundreds of MB in a single IPC response.
+//! The streaming variant walks the diff file by file on a blocking task and
+//! emits each fragment as soon as it is complete.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use super::diff::{commit_diff, push_patch_line};
+use super::models::{DiffChunk, DiffStreamComplete};
+use super::utils::{normalize_unicode, open_repo};
+
+/// Event carrying one `DiffChunk`.
+const DIFF_CHUNK_EVENT: &str = "diff-chunk";
+/// Event carrying the final `DiffStreamComplete`.
+const DIFF_COMPLETE_EVENT: &str = "diff-complete";
+/// Patch text of one file is split into chunks of roughly this size.
+const MAX_CHUNK_BYTES: usize = 256 * 1024;
+
+static NEXT_STREAM_ID: AtomicU64 = AtomicU64::new(1);
+
+// Streams asked to stop, keyed by stream id.
+lazy_static::lazy_static! {
+    static ref CANCELLED_STREAMS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+/// Start streaming the diff of `commit_id` and return its stream id.
+///
+/// The patch arrives as `diff-chunk` events followed by one `diff-complete`
+/// event; the chunks concatenated are exactly the `get_commit_diff` output.
+#[tauri::command]
+pub async fn stream_commit_diff(
+    window: tauri::Window,
+    repo_path: String,
+    commit_id: String,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+) -> Result<String, String> {
+    let stream_id = format!(
+        "diff-stream-{}",
+        NEXT_STREAM_ID.fetch_add(1, Ordering::Relaxed)
+    );
+    let id = stream_id.clone();
+    tokio::task::spawn_blocking(move || {
+        let complete = stream_diff_chunks(
+            &repo_path,
+            &commit_id,
+            ignore_whitespace,
+            ignore_whitespace_change,
+            &id,
+            |chunk| {
+                let _ = window.emit(DIFF_CHUNK_EVENT, chunk);
+            },
+        );
+        let _ = window.emit(DIFF_COMPLETE_EVENT, complete);
+    });
+    Ok(stream_id)
+}
+
+/// Stop a running `stream_commit_diff`. Its `diff-complete` event reports
+/// `cancelled`.
+#[tauri::command]
+pub async fn cancel_diff_stream(stream_id: String) -> Result<(), String> {
+    CANCELLED_STREAMS.lock().unwrap().insert(stream_id);
+    Ok(())
+}
+
+fn is_cancelled(stream_id: &str) -> bool {
+    CANCELLED_STREAMS
+        .lock()
+        .map(|cancelled| cancelled.contains(stream_id))
+        .unwrap_or(false)
+}
+
+/// Hands out chunks with increasing sequence numbers.
+struct ChunkSender<'a, F: FnMut(DiffChunk)> {
+    stream_id: &'a str,
+    sequence: usize,
+    on_chunk: F,
+}
+
+impl<F: FnMut(DiffChunk)> ChunkSender<'_, F> {
+    fn send(&mut self, file_path: &str, patch: String) {
+        if patch.is_empty() {
+            return;
+        }
+        (self.on_chunk)(DiffChunk {
+            stream_id: self.stream_id.to_string(),
+            sequence: self.sequence,
+            file_path: file_path.to_string(),
+            patch,
+        });
+        self.sequence += 1;
+    }
+}
+
+fn stream_diff_chunks<F: FnMut(DiffChunk)>(
+    repo_path: &str,
+    commit_id: &str,
+    ignore_whitespace: Option<bool>,
+    ignore_whitespace_change: Option<bool>,
+    stream_id: &str,
+    on_chunk: F,
+) -> DiffStreamComplete {
+    let mut sender = ChunkSender {
+        stream_id,
+        sequence: 0,
+        on_chunk,
+    };
+    let mut file_count = 0;
+    let mut cancelled = false;
+
+    let result = open_repo(repo_path).and_then(|repo| {
+        let (diff, hidden) = commit_diff(
+            &repo,
+            commit_id,
+            ignore_whitespace,
+            ignore_whitespace_change,
+        )?;
+        let mut fragment = String::new();
+        let mut file_path = String::new();
+        let printed = diff.print(git2::DiffFormat::Patch, |delta, _hunk, line| {
+            if delta.new_file().path().is_some_and(|p| hidden.contains(p)) {
+                return true;
+            }
+            // A file header starts the next file
+            if line.origin() == 'F' {
+                sender.send(&file_path, std::mem::take(&mut fragment));
+                if is_cancelled(stream_id) {
+                    cancelled = true;
+                    return false;
+                }
+                file_path = delta
+                    .new_file()
+                    .path()
+                    .or_else(|| delta.old_file().path())
+                    .map(|p| normalize_unicode(&p.to_string_lossy()))
+                    .unwrap_or_default();
+                file_count += 1;
+            }
+            push_patch_line(&mut fragment, &line);
+            if fragment.len() >= MAX_CHUNK_BYTES {
+                sender.send(&file_path, std::mem::take(&mut fragment));
+            }
+            true
+        });
+        match printed {
+            Err(_) if cancelled => Ok(()),
+            Err(e) => Err(format!("Diff 출력 실패: {}", e)),
+            Ok(()) => {
+                sender.send(&file_path, fragment);
+                Ok(())
+            }
+        }
+    });
+    CANCELLED_STREAMS.lock().unwrap().remove(stream_id);
+
+    DiffStreamComplete {
+        stream_id: stream_id.to_string(),
+        chunk_count: sender.sequence,
+        file_count,
+        cancelled,
+        error: result.err(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::diff::get_commit_diff;
+    use git2::{Repository, Signature};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn commit_all(repo: &Repository, message: &str) -> String {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+            .to_string()
+    }
+
+    fn setup_large_commit() -> (tempfile::TempDir, String, String) {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        fs::write(root.join("삭제될.txt"), "bye\n").unwrap();
+        commit_all(&repo, "Initial commit");
+
+        for i in 0..20 {
+            fs::write(
+                root.join(format!("file{:02}.txt", i)),
+                format!("내용 {}\n", i),
+            )
+            .unwrap();
+        }
+        let big: String = (0..12_000)
+            .map(|i| format!("line {:05} of the big file\n", i))
+            .collect();
+        fs::write(root.join("big.txt"), big).unwrap();
+        fs::remove_file(root.join("삭제될.txt")).unwrap();
+        let sha = commit_all(&repo, "Many files");
+        let repo_path = root.to_str().unwrap().to_string();
+        (temp_dir, repo_path, sha)
+    }
+
+    #[tokio::test]
+    async fn test_streamed_chunks_reassemble_commit_diff() {
+        let (_temp_dir, repo_path, sha) = setup_large_commit();
+
+        let mut chunks = Vec::new();
+        let complete = stream_diff_chunks(&repo_path, &sha, None, None, "test-stream", |c| {
+            chunks.push(c)
+        });
+        assert_eq!(complete.error, None);
+        assert!(!complete.cancelled);
+        assert_eq!(complete.file_count, 22);
+        assert_eq!(complete.chunk_count, chunks.len());
+        // big.txt is larger than one chunk
+        assert!(chunks.iter().filter(|c| c.file_path == "big.txt").count() > 1);
+        assert!(chunks.iter().any(|c| c.file_path == "삭제될.txt"));
+
+        let sequences: Vec<usize> = chunks.iter().map(|c| c.sequence).collect();
+        assert_eq!(sequences, (0..chunks.len()).collect::<Vec<_>>());
+        let streamed: String = chunks.iter().map(|c| c.patch.as_str()).collect();
+        let blocking = get_commit_diff(repo_path, sha, None, None).await.unwrap();
+        assert_eq!(streamed, blocking);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_stops_early() {
+        let (_temp_dir, repo_path, sha) = setup_large_commit();
+
+        let mut received = 0;
+        let complete = stream_diff_chunks(&repo_path, &sha, None, None, "cancel-me", |_| {
+            received += 1;
+            CANCELLED_STREAMS
+                .lock()
+                .unwrap()
+                .insert("cancel-me".to_string());
+        });
+        assert!(complete.cancelled);
+        assert_eq!(complete.error, None);
+        // The flag is checked when the next file starts
+        assert_eq!(complete.file_count, 1);
+        assert_eq!(received, complete.chunk_count);
+        assert!(!is_cancelled("cancel-me"));
+
+        let missing = stream_diff_chunks(&repo_path, "not-a-sha", None, None, "bad", |_| {});
+        assert!(missing.error.is_some());
+    }
+}
@@ -0,0 +1,337 @@
+//! Commit graph export for external tooling (Graphviz DOT or JSON Lines).
+//!
+//! The graph is streamed to disk commit by commit, so even very large
+//! histories never sit in memory as one string.
+
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::sync::Mutex;
+
+use git2::Repository;
+
+use super::git::{ref_decorations, HistoryWalker};
+use super::models::{GraphExportProgress, GraphExportResult};
+use super::utils::open_repo;
+
+/// Event emitted while `export_commit_graph` runs.
+const EXPORT_PROGRESS_EVENT: &str = "graph-export-progress";
+/// Commits written between progress events.
+const PROGRESS_INTERVAL: usize = 500;
+
+// Exports asked to stop, keyed by output path.
+lazy_static::lazy_static! {
+    static ref CANCELLED_EXPORTS: Mutex<HashSet<String>> = Mutex::new(HashSet::new());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GraphFormat {
+    Dot,
+    Jsonl,
+}
+
+impl GraphFormat {
+    fn parse(format: &str) -> Result<GraphFormat, String> {
+        match format {
+            "dot" => Ok(GraphFormat::Dot),
+            "jsonl" => Ok(GraphFormat::Jsonl),
+            other => Err(format!("지원하지 않는 내보내기 형식입니다: {}", other)),
+        }
+    }
+}
+
+/// Export the commit graph of all branches (SHA, parents, author, timestamp,
+/// refs) to `output_path` as `format` ("dot" | "jsonl").
+///
+/// Progress is emitted as `graph-export-progress` events; a cancelled or
+/// failed export removes the partial file.
+#[tauri::command]
+pub async fn export_commit_graph(
+    window: tauri::Window,
+    repo_path: String,
+    output_path: String,
+    format: String,
+    max_commits: Option<usize>,
+) -> Result<GraphExportResult, String> {
+    let repo = open_repo(&repo_path)?;
+    let format = GraphFormat::parse(&format)?;
+    export_graph_with_progress(&repo, &output_path, format, max_commits, |progress| {
+        let _ = window.emit(EXPORT_PROGRESS_EVENT, progress);
+    })
+}
+
+/// Cancel a running `export_commit_graph` writing to `output_path`.
+#[tauri::command]
+pub async fn cancel_commit_graph_export(output_path: String) -> Result<(), String> {
+    CANCELLED_EXPORTS.lock().unwrap().insert(output_path);
+    Ok(())
+}
+
+fn export_graph_with_progress<F>(
+    repo: &Repository,
+    output_path: &str,
+    format: GraphFormat,
+    max_commits: Option<usize>,
+    on_progress: F,
+) -> Result<GraphExportResult, String>
+where
+    F: Fn(GraphExportProgress),
+{
+    CANCELLED_EXPORTS.lock().unwrap().remove(output_path);
+    let file = File::create(output_path).map_err(|e| format!("파일 생성 실패: {}", e))?;
+    let mut out = BufWriter::new(file);
+
+    let result = write_graph(
+        repo,
+        &mut out,
+        output_path,
+        format,
+        max_commits,
+        &on_progress,
+    )
+    .and_then(|counts| {
+        out.flush()
+            .map_err(|e| format!("파일 쓰기 실패: {}", e))
+            .map(|_| counts)
+    });
+    let cancelled = CANCELLED_EXPORTS.lock().unwrap().remove(output_path);
+
+    match result {
+        Ok((commit_count, edge_count)) if !cancelled => {
+            on_progress(GraphExportProgress {
+                output_path: output_path.to_string(),
+                written: commit_count,
+                done: true,
+            });
+            Ok(GraphExportResult {
+                output_path: output_path.to_string(),
+                format: match format {
+                    GraphFormat::Dot => "dot".to_string(),
+                    GraphFormat::Jsonl => "jsonl".to_string(),
+                },
+                commit_count,
+                edge_count,
+            })
+        }
+        other => {
+            drop(out);
+            let _ = std::fs::remove_file(output_path);
+            match other {
+                Err(e) => Err(e),
+                Ok(_) => Err("그래프 내보내기가 취소되었습니다".to_string()),
+            }
+        }
+    }
+}
+
+/// Write every commit, returning (commits, parent links). Stops early without
+/// an error when cancelled; the caller checks the flag.
+fn write_graph<W: Write, F: Fn(GraphExportProgress)>(
+    repo: &Repository,
+    out: &mut W,
+    output_path: &str,
+    format: GraphFormat,
+    max_commits: Option<usize>,
+    on_progress: &F,
+) -> Result<(usize, usize), String> {
+    let write_err = |e: std::io::Error| format!("파일 쓰기 실패: {}", e);
+    let decorations = ref_decorations(repo)?;
+    let walker: Box<dyn Iterator<Item = _>> = if repo.is_empty().unwrap_or(false) {
+        Box::new(std::iter::empty())
+    } else {
+        Box::new(HistoryWalker::new(repo, true)?)
+    };
+
+    if format == GraphFormat::Dot {
+        writeln!(out, "digraph commits {{").map_err(write_err)?;
+        writeln!(out, "  node [shape=box, fontname=\"sans-serif\"];").map_err(write_err)?;
+    }
+
+    let (mut commit_count, mut edge_count) = (0, 0);
+    for commit in walker.take(max_commits.unwrap_or(usize::MAX)) {
+        let commit = commit?;
+        let sha = commit.id().to_string();
+        let parents: Vec<String> = commit.parent_ids().map(|p| p.to_string()).collect();
+        let refs = decorations.get(&commit.id()).cloned().unwrap_or_default();
+        let author = commit.author().name().unwrap_or("Unknown").to_string();
+        let subject = commit.summary().unwrap_or("").to_string();
+
+        match format {
+            GraphFormat::Jsonl => {
+                let record = serde_json::json!({
+                    "sha": sha,
+                    "parents": parents,
+                    "author": author,
+                    "timestamp": commit.time().seconds(),
+                    "refs": refs,
+                    "subject": subject,
+                });
+                writeln!(out, "{}", record).map_err(write_err)?;
+            }
+            GraphFormat::Dot => {
+                let mut label = format!("{} {}", &sha[..7], subject);
+                if !refs.is_empty() {
+                    label.push_str(&format!("\n({})", refs.join(", ")));
+                }
+                writeln!(out, "  \"{}\" [label={}];", sha, dot_quote(&label)).map_err(write_err)?;
+                for parent in &parents {
+                    writeln!(out, "  \"{}\" -> \"{}\";", sha, parent).map_err(write_err)?;
+                }
+            }
+        }
+
+        commit_count += 1;
+        edge_count += parents.len();
+        if commit_count % PROGRESS_INTERVAL == 0 {
+            if CANCELLED_EXPORTS.lock().unwrap().contains(output_path) {
+                return Ok((commit_count, edge_count));
+            }
+            on_progress(GraphExportProgress {
+                output_path: output_path.to_string(),
+                written: commit_count,
+                done: false,
+            });
+        }
+    }
+
+    if format == GraphFormat::Dot {
+        writeln!(out, "}}").map_err(write_err)?;
+    }
+    Ok((commit_count, edge_count))
+}
+
+/// Quote a DOT string: escapes `"` and `\`, turns newlines into `\n`.
+/// Non-ASCII text (Korean subjects) is valid as-is inside quotes.
+fn dot_quote(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => {}
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use tempfile::TempDir;
+
+    /// main: A ← B ← M (merge of B and feature C), tag v1 on B.
+    fn setup_graph_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        {
+            let sig = Signature::now("테스트", "test@example.com").unwrap();
+            let tree = repo
+                .find_tree(repo.index().unwrap().write_tree().unwrap())
+                .unwrap();
+
+            let a = repo
+                .commit(Some("HEAD"), &sig, &sig, "초기 커밋", &tree, &[])
+                .unwrap();
+            let a = repo.find_commit(a).unwrap();
+            let b = repo
+                .commit(
+                    Some("HEAD"),
+                    &sig,
+                    &sig,
+                    "기능 \"따옴표\" 추가",
+                    &tree,
+                    &[&a],
+                )
+                .unwrap();
+            let b = repo.find_commit(b).unwrap();
+            let c = repo
+                .commit(
+                    Some("refs/heads/feature"),
+                    &sig,
+                    &sig,
+                    "사이드 작업",
+                    &tree,
+                    &[&a],
+                )
+                .unwrap();
+            let c = repo.find_commit(c).unwrap();
+            repo.commit(Some("HEAD"), &sig, &sig, "병합", &tree, &[&b, &c])
+                .unwrap();
+            repo.tag_lightweight("v1", b.as_object(), false).unwrap();
+        }
+        (temp_dir, repo)
+    }
+
+    #[test]
+    fn test_export_jsonl_round_trips() {
+        let (temp_dir, repo) = setup_graph_repo();
+        let output = temp_dir.path().join("graph.jsonl");
+        let output = output.to_str().unwrap();
+
+        let result =
+            export_graph_with_progress(&repo, output, GraphFormat::Jsonl, None, |_| {}).unwrap();
+        assert_eq!((result.commit_count, result.edge_count), (4, 4));
+
+        let content = std::fs::read_to_string(output).unwrap();
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0]["subject"], "병합");
+        assert_eq!(records[0]["parents"].as_array().unwrap().len(), 2);
+        let tagged = records
+            .iter()
+            .find(|r| r["subject"] == "기능 \"따옴표\" 추가");
+        assert!(tagged.unwrap()["refs"]
+            .as_array()
+            .unwrap()
+            .contains(&serde_json::json!("v1")));
+        assert!(records.iter().all(|r| r["timestamp"].is_i64()));
+    }
+
+    #[test]
+    fn test_export_dot_edges_and_quoting() {
+        let (temp_dir, repo) = setup_graph_repo();
+        let output = temp_dir.path().join("graph.dot");
+        let output = output.to_str().unwrap();
+
+        let result =
+            export_graph_with_progress(&repo, output, GraphFormat::Dot, None, |_| {}).unwrap();
+        let content = std::fs::read_to_string(output).unwrap();
+
+        assert!(content.starts_with("digraph commits {"));
+        assert!(content.trim_end().ends_with('}'));
+        let edges = content.lines().filter(|l| l.contains("\" -> \"")).count();
+        assert_eq!(edges, result.edge_count);
+        assert_eq!(edges, 4);
+        assert!(content.contains("기능 \\\"따옴표\\\" 추가"));
+
+        // Truncated exports keep only the newest commits
+        let limited = temp_dir.path().join("limited.dot");
+        let limited = export_graph_with_progress(
+            &repo,
+            limited.to_str().unwrap(),
+            GraphFormat::Dot,
+            Some(1),
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!((limited.commit_count, limited.edge_count), (1, 2));
+    }
+
+    #[test]
+    fn test_dot_quote() {
+        assert_eq!(
+            dot_quote("한글 \"제목\"\n(main)"),
+            "\"한글 \\\"제목\\\"\\n(main)\""
+        );
+        assert_eq!(dot_quote("a\\b"), "\"a\\\\b\"");
+        assert!(GraphFormat::parse("svg").is_err());
+    }
+}
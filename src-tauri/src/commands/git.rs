@@ -1,8 +1,23 @@
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::Instant;
 use chrono::{Utc, TimeZone};
+use git2::{Repository, Signature};
 
-use super::models::{CommitInfo, FileStatus, RepositoryInfo};
-use super::utils::{normalize_unicode, open_repo, ensure_utf8_config};
+use super::branch::branch_sync_status;
+use super::hooks::{run_commit_msg, run_pre_commit};
+use super::models::{
+    CommitInfo, CommitResult, ContentSearchHit, FileStatus, FilteredHistoryPage, OperationState,
+    Page, RepositoryInfo, RepositoryStatusReport,
+};
+use super::relative_time::{format_relative_time, relative_date_locale, Locale};
+use super::signing::commit_to_head;
+use super::submodule::pointer_moved;
+use super::utils::{
+    assert_no_conflicts, collect_page, command_error, ensure_utf8_config, head_label, head_state,
+    normalize_unicode, open_repo, operation_state, page_window, repo_signature, safe_repo_path,
+    unborn_branch, MAX_PAGE_SIZE,
+};
 
 /// Open a repository and return its metadata.
 #[tauri::command]
@@ -11,24 +26,7 @@ pub async fn open_repository(path: String) -> Result<RepositoryInfo, String> {
     // Best-effort: utf8 config failure should not block opening the repo
     let _ = ensure_utf8_config(&repo);
 
-    let branch = match repo.head() {
-        Ok(head) => head.shorthand().unwrap_or("detached").to_string(),
-        Err(_) if repo.is_empty().unwrap_or(false) => {
-            // 첫 커밋 전(unborn): HEAD 파일에서 브랜치명 읽기
-            // 내용 예: "ref: refs/heads/main\n"
-            let head_file = repo.path().join("HEAD");
-            std::fs::read_to_string(&head_file)
-                .ok()
-                .and_then(|content| {
-                    content
-                        .trim()
-                        .strip_prefix("ref: refs/heads/")
-                        .map(|s| s.to_string())
-                })
-                .unwrap_or_else(|| "main".to_string())
-        }
-        Err(_) => "detached".to_string(),
-    };
+    let head = head_state(&repo)?;
 
     let remote_url = repo
         .find_remote("origin")
@@ -44,109 +42,356 @@ pub async fn open_repository(path: String) -> Result<RepositoryInfo, String> {
     Ok(RepositoryInfo {
         path: normalize_unicode(&path),
         name,
-        current_branch: branch,
+        current_branch: head_label(&repo, &head),
         remote_url,
+        is_empty: head.sha.is_empty(),
+        is_shallow: repo.is_shallow(),
+        is_detached: head.detached,
+        head_sha: head.sha,
+        // Best-effort: a broken upstream must not block opening the repo
+        sync: branch_sync_status(&repo).unwrap_or_default(),
+        state: operation_state(&repo),
     })
 }
 
+/// Merge, rebase, cherry-pick or revert in progress, with what is being
+/// applied onto what, so the UI can show e.g. "merging feature into main".
+#[tauri::command]
+pub async fn get_repository_state(repo_path: String) -> Result<OperationState, String> {
+    Ok(operation_state(&open_repo(&repo_path)?))
+}
+
 /// Get commit history (most recent first).
 /// If `all_branches` is true, includes commits reachable from ALL local branches and tags.
+/// If `with_relative_dates` is true, fills `relative_date` in the given `locale` ("ko" | "en").
+/// `start_from` (a commit sha) resumes the walk right after that commit and
+/// `skip` drops that many commits before the list starts.
+///
+/// Compatibility wrapper returning a plain list of at most `MAX_PAGE_SIZE`
+/// commits; new callers should use `get_commit_history_page`.
 #[tauri::command]
 pub async fn get_commit_history(
     repo_path: String,
     limit: usize,
     all_branches: Option<bool>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+    skip: Option<usize>,
+    start_from: Option<String>,
 ) -> Result<Vec<CommitInfo>, String> {
-    let repo = open_repo(&repo_path)?;
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    commit_history_page(
+        &repo_path,
+        start_from.as_deref(),
+        skip.unwrap_or(0),
+        limit.clamp(1, MAX_PAGE_SIZE),
+        all_branches,
+        relative_locale,
+    )
+    .map(|p| p.items)
+}
+
+/// One page of commit history; options as in `get_commit_history`.
+/// `total` is always `None`: counting would walk the whole history.
+///
+/// The cursor is the sha of the page's last commit, so commits added on top
+/// of the branch between requests do not shift later pages.
+#[tauri::command]
+pub async fn get_commit_history_page(
+    repo_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    all_branches: Option<bool>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+) -> Result<Page<CommitInfo>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let (_, limit) = page_window(None, limit)?;
+    commit_history_page(
+        &repo_path,
+        cursor.as_deref(),
+        0,
+        limit,
+        all_branches,
+        relative_locale,
+    )
+}
+
+/// `limit` commits after `skip` others, walking from the usual tips and
+/// starting after `start_after` when given. Resuming inside the same walk
+/// (rather than walking from the cursor commit) keeps sibling-branch commits
+/// that sort after the cursor but are not its ancestors.
+fn commit_history_page(
+    repo_path: &str,
+    start_after: Option<&str>,
+    skip: usize,
+    limit: usize,
+    all_branches: Option<bool>,
+    relative_locale: Option<Locale>,
+) -> Result<Page<CommitInfo>, String> {
+    let now = Utc::now().timestamp();
+    let repo = open_repo(repo_path)?;
 
     // Unborn HEAD (no commits yet): return empty list instead of error
     if repo.is_empty().unwrap_or(false) {
-        return Ok(Vec::new());
+        return collect_page(std::iter::empty(), 0, limit, Some(0));
     }
 
-    let mut revwalk = repo.revwalk().map_err(|e| format!("Revwalk 생성 실패: {}", e))?;
+    let mut walker = HistoryWalker::new(&repo, all_branches.unwrap_or(false))?;
+    if let Some(cursor) = start_after {
+        walker.skip_past(cursor)?;
+    }
 
-    if all_branches.unwrap_or(false) {
-        // Push all local branches
-        let branches = repo.branches(Some(git2::BranchType::Local))
-            .map_err(|e| format!("브랜치 목록 실패: {}", e))?;
-        for branch_result in branches {
-            if let Ok((branch, _)) = branch_result {
-                if let Ok(Some(_name)) = branch.name() {
-                    if let Ok(oid) = branch.get().peel_to_commit().map(|c| c.id()) {
-                        let _ = revwalk.push(oid);
-                    }
-                }
-            }
-        }
-        // Also push remote-tracking branches
-        let remote_branches = repo.branches(Some(git2::BranchType::Remote))
-            .map_err(|e| format!("원격 브랜치 목록 실패: {}", e))?;
-        for branch_result in remote_branches {
-            if let Ok((branch, _)) = branch_result {
-                if let Ok(oid) = branch.get().peel_to_commit().map(|c| c.id()) {
-                    let _ = revwalk.push(oid);
-                }
-            }
-        }
-    } else {
-        revwalk.push_head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+    let commits = walker.map(|commit| -> Result<CommitInfo, String> {
+        let mut info = commit_info(&commit?);
+        info.relative_date = relative_locale.map(|l| format_relative_time(info.timestamp, now, l));
+        Ok(info)
+    });
+    let mut page = collect_page(commits, skip, limit, None)?;
+    page.next_cursor = page
+        .items
+        .last()
+        .filter(|_| page.has_more)
+        .map(|c| c.sha.clone());
+    Ok(page)
+}
+
+/// Commit history narrowed by author, message, date range and/or path.
+///
+/// Text filters are case-insensitive and NFC-normalized; `author_contains`
+/// matches the author name or email. Timestamps are inclusive, in seconds,
+/// compared with the commit time like `CommitInfo.timestamp`. `path` (a file
+/// or directory) keeps commits that change it compared with their parents.
+///
+/// Paging works like `get_commit_history_page`, but a page also ends after
+/// scanning `MAX_FILTER_SCAN` commits, so a sparse search may return short or
+/// empty pages with `has_more` set; `scanned` lets the UI show progress.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub async fn get_commit_history_filtered(
+    repo_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    all_branches: Option<bool>,
+    author_contains: Option<String>,
+    message_contains: Option<String>,
+    since_timestamp: Option<i64>,
+    until_timestamp: Option<i64>,
+    path: Option<String>,
+) -> Result<FilteredHistoryPage, String> {
+    let (_, limit) = page_window(None, limit)?;
+    let repo = open_repo(&repo_path)?;
+    let mut page = FilteredHistoryPage {
+        items: Vec::new(),
+        has_more: false,
+        next_cursor: None,
+        scanned: 0,
+    };
+    if repo.is_empty().unwrap_or(false) {
+        return Ok(page);
     }
 
-    revwalk
-        .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
-        .map_err(|e| format!("정렬 설정 실패: {}", e))?;
+    let search_text = |text: Option<String>| {
+        text.map(|t| normalize_unicode(t.trim()).to_lowercase())
+            .filter(|t| !t.is_empty())
+    };
+    let filter = HistoryFilter {
+        author: search_text(author_contains),
+        message: search_text(message_contains),
+        since: since_timestamp,
+        until: until_timestamp,
+        path: path
+            .map(|p| normalize_unicode(p.trim().trim_matches('/')))
+            .filter(|p| !p.is_empty()),
+    };
 
-    let mut commits = Vec::new();
+    let mut walker = HistoryWalker::new(&repo, all_branches.unwrap_or(false))?;
+    if let Some(cursor) = cursor.as_deref() {
+        walker.skip_past(cursor)?;
+    }
 
-    for (idx, oid_result) in revwalk.enumerate() {
-        if idx >= limit {
+    let mut last_scanned = None;
+    for commit in walker.by_ref() {
+        let commit = commit?;
+        page.scanned += 1;
+        last_scanned = Some(commit.id());
+        if filter.matches(&commit) {
+            page.items.push(commit_info(&commit));
+        }
+        if page.items.len() == limit || page.scanned == MAX_FILTER_SCAN {
             break;
         }
+    }
 
-        let oid = oid_result.map_err(|e| format!("OID 읽기 실패: {}", e))?;
-        let commit = repo.find_commit(oid).map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    page.has_more = walker.next().is_some();
+    page.next_cursor = last_scanned
+        .filter(|_| page.has_more)
+        .map(|oid| oid.to_string());
+    Ok(page)
+}
 
-        let timestamp = commit.time().seconds();
-        let datetime = Utc.timestamp_opt(timestamp, 0).unwrap();
+/// Commits a single `get_commit_history_filtered` page walks at most.
+const MAX_FILTER_SCAN: usize = 5000;
 
-        let parent_ids: Vec<String> = commit.parent_ids().map(|oid| oid.to_string()).collect();
+/// Criteria of `get_commit_history_filtered`; text is lowercased NFC.
+struct HistoryFilter {
+    author: Option<String>,
+    message: Option<String>,
+    since: Option<i64>,
+    until: Option<i64>,
+    path: Option<String>,
+}
 
-        commits.push(CommitInfo {
-            sha: oid.to_string(),
-            author: commit.author().name().unwrap_or("Unknown").to_string(),
-            email: commit.author().email().unwrap_or("").to_string(),
-            message: commit.message().unwrap_or("").to_string(),
-            timestamp,
-            date: datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
-            parent_ids,
-        });
+impl HistoryFilter {
+    fn matches(&self, commit: &git2::Commit) -> bool {
+        let time = commit.time().seconds();
+        if self.since.is_some_and(|since| time < since)
+            || self.until.is_some_and(|until| time > until)
+        {
+            return false;
+        }
+        if let Some(needle) = &self.author {
+            let author = commit.author();
+            let name = normalize_unicode(author.name().unwrap_or("")).to_lowercase();
+            let email = author.email().unwrap_or("").to_lowercase();
+            if !name.contains(needle.as_str()) && !email.contains(needle.as_str()) {
+                return false;
+            }
+        }
+        if let Some(needle) = &self.message {
+            let message = normalize_unicode(commit.message().unwrap_or("")).to_lowercase();
+            if !message.contains(needle.as_str()) {
+                return false;
+            }
+        }
+        match &self.path {
+            Some(path) => commit_touches_path(commit, Path::new(path)),
+            None => true,
+        }
     }
+}
 
-    Ok(commits)
+/// Whether `path` differs between `commit` and each of its parents (for a
+/// root commit: whether it exists). Merges that keep one parent's version
+/// are skipped, like `git log -- <path>`.
+fn commit_touches_path(commit: &git2::Commit, path: &Path) -> bool {
+    let entry_id = |tree: Option<git2::Tree>| -> Option<git2::Oid> {
+        tree.and_then(|t| t.get_path(path).ok()).map(|e| e.id())
+    };
+    let own = entry_id(commit.tree().ok());
+    if commit.parent_count() == 0 {
+        return own.is_some();
+    }
+    commit
+        .parents()
+        .all(|parent| entry_id(parent.tree().ok()) != own)
 }
 
 /// Get repository status (changed files list).
+///
+/// Conflicted paths appear once with status "conflicted". Renames (staged,
+/// or a deleted file matching an untracked one) appear as "renamed" with
+/// `old_path` instead of a delete/add pair.
+///
+/// Compatibility wrapper returning a plain list; new callers should use
+/// `get_repository_status_report`.
 #[tauri::command]
 pub async fn get_repository_status(repo_path: String) -> Result<Vec<FileStatus>, String> {
-    let repo = open_repo(&repo_path)?;
-    let statuses = repo
-        .statuses(None)
-        .map_err(|e| format!("상태 조회 실패: {}", e))?;
+    get_repository_status_report(repo_path, true, false)
+        .await
+        .map(|report| report.files)
+}
+
+/// Repository status like `get_repository_status`, with how long it took.
+///
+/// For huge working trees, `include_untracked: false` skips the untracked
+/// scan entirely and `untracked_dirs_as_single_entries: true` reports an
+/// untracked directory as one "dir/" entry instead of walking into it. The
+/// refreshed stat cache is written back to the index, so the next call only
+/// re-reads files that actually changed.
+#[tauri::command]
+pub async fn get_repository_status_report(
+    repo_path: String,
+    include_untracked: bool,
+    untracked_dirs_as_single_entries: bool,
+) -> Result<RepositoryStatusReport, String> {
+    tokio::task::spawn_blocking(move || {
+        let started = Instant::now();
+        let repo = open_repo(&repo_path)?;
+        let files = collect_status(&repo, include_untracked, untracked_dirs_as_single_entries)?;
+        let elapsed_ms = started.elapsed().as_millis() as u64;
+        let fast_mode = !include_untracked || untracked_dirs_as_single_entries;
+        Ok(RepositoryStatusReport {
+            files,
+            elapsed_ms,
+            suggest_fast_mode: !fast_mode && elapsed_ms >= SLOW_STATUS_MS,
+        })
+    })
+    .await
+    .map_err(|e| format!("상태 조회 실패: {}", e))?
+}
 
+/// A full status scan slower than this suggests the fast mode to the user.
+const SLOW_STATUS_MS: u64 = 1_000;
+
+fn collect_status(
+    repo: &Repository,
+    include_untracked: bool,
+    untracked_dirs_as_single_entries: bool,
+) -> Result<Vec<FileStatus>, String> {
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(include_untracked)
+        .recurse_untracked_dirs(include_untracked && !untracked_dirs_as_single_entries)
+        .renames_head_to_index(true)
+        .renames_index_to_workdir(true)
+        .update_index(true);
+    // Writing the stat cache back fails on a locked or read-only index;
+    // the status itself is still fine without it
+    let statuses = match repo.statuses(Some(&mut opts)) {
+        Ok(statuses) => statuses,
+        Err(_) => repo
+            .statuses(Some(opts.update_index(false)))
+            .map_err(|e| format!("상태 조회 실패: {}", e))?,
+    };
+
+    let submodules = submodule_paths(repo);
     let mut files = Vec::new();
 
     for entry in statuses.iter() {
         let status = entry.status();
         let file_path = normalize_unicode(entry.path().unwrap_or(""));
+        let is_submodule = submodules.contains(&file_path);
 
-        let is_index_changed =
-            status.is_index_new() || status.is_index_modified() || status.is_index_deleted();
-        let is_wt_changed =
-            status.is_wt_new() || status.is_wt_modified() || status.is_wt_deleted();
+        if status.is_conflicted() {
+            files.push(FileStatus {
+                path: file_path,
+                status: "conflicted".to_string(),
+                staged: false,
+                old_path: None,
+            });
+            continue;
+        }
+
+        let is_index_changed = status.is_index_new()
+            || status.is_index_modified()
+            || status.is_index_deleted()
+            || status.is_index_renamed();
+        let mut is_wt_changed = status.is_wt_new()
+            || status.is_wt_modified()
+            || status.is_wt_deleted()
+            || status.is_wt_renamed();
+        // Edits inside a submodule are its own business (see `list_submodules`);
+        // only a moved checkout changes the superproject
+        if is_submodule && is_wt_changed {
+            is_wt_changed = pointer_moved(repo, &file_path);
+        }
 
         if is_index_changed {
-            let staged_status = if status.is_index_new() {
+            let staged_status = if is_submodule {
+                "submodule"
+            } else if status.is_index_renamed() {
+                "renamed"
+            } else if status.is_index_new() {
                 "added"
             } else if status.is_index_modified() {
                 "modified"
@@ -155,15 +400,21 @@ pub async fn get_repository_status(repo_path: String) -> Result<Vec<FileStatus>,
             } else {
                 "staged"
             };
+            let (path, old_path) = delta_paths(entry.head_to_index(), &file_path);
             files.push(FileStatus {
-                path: file_path.clone(),
+                path,
                 status: staged_status.to_string(),
                 staged: true,
+                old_path: old_path.filter(|_| status.is_index_renamed()),
             });
         }
 
         if is_wt_changed {
-            let unstaged_status = if status.is_wt_new() {
+            let unstaged_status = if is_submodule {
+                "submodule"
+            } else if status.is_wt_renamed() {
+                "renamed"
+            } else if status.is_wt_new() {
                 "untracked"
             } else if status.is_wt_modified() {
                 "modified"
@@ -172,10 +423,12 @@ pub async fn get_repository_status(repo_path: String) -> Result<Vec<FileStatus>,
             } else {
                 "unknown"
             };
+            let (path, old_path) = delta_paths(entry.index_to_workdir(), &file_path);
             files.push(FileStatus {
-                path: file_path,
+                path,
                 status: unstaged_status.to_string(),
                 staged: false,
+                old_path: old_path.filter(|_| status.is_wt_renamed()),
             });
         }
     }
@@ -183,17 +436,54 @@ pub async fn get_repository_status(repo_path: String) -> Result<Vec<FileStatus>,
     Ok(files)
 }
 
-/// Stage a file (add to index).
+/// New and old path of one side of a status entry; `StatusEntry::path` is
+/// the old path, which is wrong for the new side of a rename.
+fn delta_paths(delta: Option<git2::DiffDelta>, fallback: &str) -> (String, Option<String>) {
+    let path_of = |file: git2::DiffFile| {
+        file.path()
+            .map(|p| normalize_unicode(&p.to_string_lossy()))
+    };
+    match delta {
+        Some(delta) => (
+            path_of(delta.new_file()).unwrap_or_else(|| fallback.to_string()),
+            path_of(delta.old_file()),
+        ),
+        None => (fallback.to_string(), None),
+    }
+}
+
+/// Paths of the submodules registered in `.gitmodules` or the index.
+pub(super) fn submodule_paths(repo: &Repository) -> HashSet<String> {
+    repo.submodules()
+        .map(|subs| {
+            subs.iter()
+                .map(|sub| normalize_unicode(&sub.path().to_string_lossy()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Stage a file (add to index). For a submodule this stages its current
+/// HEAD commit as the new pointer.
 #[tauri::command]
 pub async fn stage_file(repo_path: String, path: String) -> Result<(), String> {
     let repo = open_repo(&repo_path)?;
+    let normalized = normalize_unicode(&path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized)?;
+
+    if full_path.is_dir() && submodule_paths(&repo).contains(&normalized) {
+        let mut submodule = repo
+            .find_submodule(&normalized)
+            .map_err(|e| format!("서브모듈 접근 실패: {}", e))?;
+        return submodule
+            .add_to_index(true)
+            .map_err(|e| format!("서브모듈 스테이징 실패: {}", e));
+    }
+
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
 
-    let normalized = normalize_unicode(&path);
-    let full_path = std::path::Path::new(&repo_path).join(&normalized);
-
     if full_path.exists() {
         index
             .add_path(Path::new(&normalized))
@@ -301,7 +591,7 @@ pub async fn stage_all(repo_path: String) -> Result<(), String> {
 pub async fn discard_file(repo_path: String, path: String) -> Result<(), String> {
     let repo = open_repo(&repo_path)?;
     let normalized = normalize_unicode(&path);
-    let abs_path = std::path::Path::new(&repo_path).join(&normalized);
+    let abs_path = safe_repo_path(Path::new(&repo_path), &normalized)?;
 
     // Check if the file is untracked (not in HEAD) → just delete it
     let in_head = repo.head().ok()
@@ -332,53 +622,394 @@ pub async fn discard_file(repo_path: String, path: String) -> Result<(), String>
     Ok(())
 }
 
+/// Throw away working tree changes to one file, restoring its index version
+/// (which is HEAD's unless the file is staged). Staged changes are kept.
+///
+/// Untracked files are deleted only with `delete_untracked`, and only what
+/// status reports as untracked: directories holding tracked files, ignored
+/// files and nested repositories are refused, as are conflicted files.
+#[tauri::command]
+pub async fn discard_file_changes(
+    repo_path: String,
+    file_path: String,
+    delete_untracked: Option<bool>,
+) -> Result<(), String> {
+    let repo = open_repo(&repo_path)?;
+    let normalized = normalize_unicode(&file_path);
+    let abs_path = safe_repo_path(Path::new(&repo_path), &normalized)?;
+    // Nested repositories' metadata too, not just the top-level `.git`
+    if normalized
+        .split(['/', '\\'])
+        .any(|part| part.eq_ignore_ascii_case(".git"))
+    {
+        return Err(command_error(
+            "invalid_path",
+            format!("잘못된 경로입니다 (저장소 메타데이터): {}", normalized),
+            serde_json::json!({ "path": normalized }),
+        ));
+    }
+
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+    let path = Path::new(&normalized);
+    if (1..=3).any(|stage| index.get_path(path, stage).is_some()) {
+        return Err(command_error(
+            "unresolved_conflicts",
+            format!("충돌이 해결되지 않은 파일은 되돌릴 수 없습니다: {}", normalized),
+            serde_json::json!({ "paths": [normalized] }),
+        ));
+    }
+
+    if index.get_path(path, 0).is_none() {
+        if abs_path.symlink_metadata().is_err() {
+            return Ok(());
+        }
+        let dir_prefix = format!("{}/", normalized.trim_end_matches('/'));
+        let tracked_below = index.iter().any(|entry| {
+            normalize_unicode(&String::from_utf8_lossy(&entry.path)).starts_with(&dir_prefix)
+        });
+        if tracked_below {
+            return Err(command_error(
+                "tracked_directory",
+                format!("추적 중인 파일이 있는 디렉토리입니다: {}", normalized),
+                serde_json::json!({ "path": normalized }),
+            ));
+        }
+        if !delete_untracked.unwrap_or(false) {
+            return Err(format!(
+                "추적되지 않는 파일입니다. 삭제하려면 delete_untracked를 지정하세요: {}",
+                normalized
+            ));
+        }
+        return delete_untracked_path(&repo, &normalized, &abs_path);
+    }
+
+    let mut checkout = git2::build::CheckoutBuilder::new();
+    checkout.path(&normalized);
+    checkout.force();
+    repo.checkout_index(Some(&mut index), Some(&mut checkout))
+        .map_err(|e| format!("파일 되돌리기 실패: {}", e))
+}
+
+/// Delete `abs_path` if status reports it as untracked: a file that is new
+/// in the working tree, or a directory listed as untracked as a whole.
+fn delete_untracked_path(repo: &Repository, path: &str, abs_path: &Path) -> Result<(), String> {
+    let is_dir = abs_path.symlink_metadata().is_ok_and(|meta| meta.is_dir());
+    let untracked = if is_dir {
+        let wanted = format!("{}/", path.trim_end_matches('/'));
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true)
+            .recurse_untracked_dirs(false)
+            .pathspec(path);
+        let statuses = repo
+            .statuses(Some(&mut opts))
+            .map_err(|e| format!("상태 조회 실패: {}", e))?;
+        let listed = statuses.iter().any(|entry| {
+            entry.status().is_wt_new()
+                && entry.path().is_some_and(|p| normalize_unicode(p) == wanted)
+        });
+        listed && !contains_git_repo(abs_path)
+    } else {
+        repo.status_file(Path::new(path))
+            .is_ok_and(|status| status.is_wt_new())
+    };
+    if !untracked {
+        return Err(format!("추적되지 않는 항목으로 보고되지 않아 삭제하지 않습니다: {}", path));
+    }
+
+    if is_dir {
+        std::fs::remove_dir_all(abs_path).map_err(|e| format!("디렉토리 삭제 실패: {}", e))
+    } else {
+        std::fs::remove_file(abs_path).map_err(|e| format!("파일 삭제 실패: {}", e))
+    }
+}
+
+/// Remove untracked files, like `git clean -f` (`-d` with
+/// `include_directories`, `-x` with `include_ignored`).
+///
+/// Returns the removed paths, directories with a trailing `/`; with
+/// `dry_run` nothing is touched and the paths that would go are returned.
+/// Directories holding a nested git repository are always kept.
+#[tauri::command]
+pub async fn clean_untracked(
+    repo_path: String,
+    dry_run: bool,
+    include_ignored: bool,
+    include_directories: bool,
+) -> Result<Vec<String>, String> {
+    let repo = open_repo(&repo_path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or("bare 저장소에서는 정리할 수 없습니다")?
+        .to_path_buf();
+
+    let mut opts = git2::StatusOptions::new();
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(false)
+        .include_ignored(include_ignored)
+        .recurse_ignored_dirs(false);
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .map_err(|e| format!("상태 조회 실패: {}", e))?;
+
+    let mut targets = Vec::new();
+    for entry in statuses.iter() {
+        let status = entry.status();
+        let removable = status.is_wt_new() || (include_ignored && status.is_ignored());
+        if !removable {
+            continue;
+        }
+        let Some(raw_path) = entry.path() else {
+            continue;
+        };
+        let is_dir = raw_path.ends_with('/');
+        let full_path = workdir.join(raw_path.trim_end_matches('/'));
+        if is_dir && (!include_directories || contains_git_repo(&full_path)) {
+            continue;
+        }
+        targets.push((normalize_unicode(raw_path), full_path, is_dir));
+    }
+    targets.sort_by(|a, b| a.0.cmp(&b.0));
+
+    if !dry_run {
+        for (path, full_path, is_dir) in &targets {
+            let removed = if *is_dir {
+                std::fs::remove_dir_all(full_path)
+            } else {
+                std::fs::remove_file(full_path)
+            };
+            removed.map_err(|e| format!("'{}' 삭제 실패: {}", path, e))?;
+        }
+    }
+    Ok(targets.into_iter().map(|(path, _, _)| path).collect())
+}
+
+/// Whether `dir` or any directory below it is a git repository.
+fn contains_git_repo(dir: &Path) -> bool {
+    if dir.join(".git").exists() {
+        return true;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return false;
+    };
+    entries.flatten().any(|entry| {
+        entry.file_type().is_ok_and(|t| t.is_dir()) && contains_git_repo(&entry.path())
+    })
+}
+
 /// Create a new commit.
+///
+/// With `paths`, only those files (or directories) are committed from the
+/// index; everything else keeps HEAD's version in the commit and stays staged.
+/// `author_name` / `author_email` commit on behalf of someone else (the
+/// committer stays the configured user), and each `co_authors` entry
+/// ("이름 <email>") is appended as a `Co-authored-by:` trailer.
+///
+/// The `pre-commit` and `commit-msg` hooks run first (see `hooks`) unless
+/// `skip_hooks` is set, like `git commit --no-verify`.
 #[tauri::command]
-pub async fn create_commit(repo_path: String, message: String) -> Result<String, String> {
+pub async fn create_commit(
+    repo_path: String,
+    message: String,
+    paths: Option<Vec<String>>,
+    author_name: Option<String>,
+    author_email: Option<String>,
+    co_authors: Option<Vec<String>>,
+    skip_hooks: Option<bool>,
+) -> Result<CommitResult, String> {
     let repo = open_repo(&repo_path)?;
     // Best-effort: config failure must not block committing
     let _ = ensure_utf8_config(&repo);
 
-    let signature = repo
-        .signature()
-        .map_err(|e| format!("Git 사용자 정보를 찾을 수 없습니다: {}", e))?;
+    let signature = repo_signature(&repo)?;
+    let author = commit_author(&signature, author_name, author_email)?;
+    let mut message = append_co_authors(&message, &co_authors.unwrap_or_default())?;
 
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
-    let tree_id = index
-        .write_tree()
-        .map_err(|e| format!("트리 쓰기 실패: {}", e))?;
-    let tree = repo
-        .find_tree(tree_id)
-        .map_err(|e| format!("트리 찾기 실패: {}", e))?;
+    assert_no_conflicts(&index)?;
+    if !skip_hooks.unwrap_or(false) {
+        run_pre_commit(&repo, &mut index, false)?;
+        message = run_commit_msg(&repo, &message)?;
+    }
 
-    let parent_commit = match repo.head() {
-        Ok(head) => Some(
-            head.peel_to_commit()
+    // Unborn HEAD: this is the first commit on the branch
+    let parent_commit = match unborn_branch(&repo) {
+        Some(_) => None,
+        None => Some(
+            repo.head()
+                .and_then(|head| head.peel_to_commit())
                 .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?,
         ),
-        Err(_) => None,
     };
 
+    let tree_id = match paths {
+        Some(paths) => partial_commit_tree(&repo, &index, parent_commit.as_ref(), &paths)?,
+        None => index
+            .write_tree()
+            .map_err(|e| format!("트리 쓰기 실패: {}", e))?,
+    };
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("트리 찾기 실패: {}", e))?;
+
     let parents = if let Some(ref parent) = parent_commit {
         vec![parent]
     } else {
         vec![]
     };
 
-    let oid = repo
-        .commit(
-            Some("HEAD"),
-            &signature,
-            &signature,
-            &message,
-            &tree,
-            &parents,
-        )
-        .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+    let created = commit_to_head(
+        &repo,
+        &author,
+        &signature,
+        &message,
+        &tree,
+        &parents,
+        "commit",
+    )?;
 
-    Ok(format!("커밋 성공: {}", oid))
+    Ok(CommitResult {
+        commit_sha: created.oid.to_string(),
+        message: format!("커밋 성공: {}", created.oid),
+        signed: created.signature_type.is_some(),
+        signature_type: created.signature_type,
+    })
+}
+
+/// Author for `create_commit`: the committer unless a name or email is given.
+/// A missing half is taken from the committer.
+fn commit_author(
+    committer: &Signature,
+    name: Option<String>,
+    email: Option<String>,
+) -> Result<Signature<'static>, String> {
+    let name = name.map(|n| normalize_unicode(n.trim())).filter(|n| !n.is_empty());
+    let email = email.map(|e| e.trim().to_string()).filter(|e| !e.is_empty());
+    if name.is_none() && email.is_none() {
+        return Ok(committer.to_owned());
+    }
+    Signature::new(
+        name.as_deref().or(committer.name()).unwrap_or(""),
+        email.as_deref().or(committer.email()).unwrap_or(""),
+        &committer.when(),
+    )
+    .map_err(|e| format!("작성자 정보가 잘못되었습니다: {}", e))
+}
+
+/// `message` with a `Co-authored-by:` trailer per co-author. Trailers join
+/// an existing trailer block, otherwise they start a new paragraph.
+fn append_co_authors(message: &str, co_authors: &[String]) -> Result<String, String> {
+    let mut trailers: Vec<String> = Vec::new();
+    for co_author in co_authors {
+        let co_author = normalize_unicode(co_author.trim());
+        if co_author.is_empty() {
+            continue;
+        }
+        let well_formed = co_author
+            .strip_suffix('>')
+            .and_then(|rest| rest.split_once(" <"))
+            .is_some_and(|(name, email)| !name.trim().is_empty() && !email.is_empty());
+        if !well_formed {
+            return Err(format!(
+                "공동 작성자는 '이름 <이메일>' 형식이어야 합니다: {}",
+                co_author
+            ));
+        }
+        let trailer = format!("Co-authored-by: {}", co_author);
+        if !trailers.contains(&trailer) && !message.lines().any(|l| l.trim() == trailer) {
+            trailers.push(trailer);
+        }
+    }
+    if trailers.is_empty() {
+        return Ok(message.to_string());
+    }
+
+    let body = message.trim_end();
+    let ends_with_trailers = body
+        .rsplit_once("\n\n")
+        .is_some_and(|(_, last)| last.lines().all(is_trailer_line));
+    let separator = if ends_with_trailers { "\n" } else { "\n\n" };
+    Ok(format!("{}{}{}", body, separator, trailers.join("\n")))
+}
+
+/// `Token: value` with an ASCII token, as in `Signed-off-by: ...`.
+fn is_trailer_line(line: &str) -> bool {
+    line.trim_end().split_once(": ").is_some_and(|(key, value)| {
+        !key.is_empty()
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+            && !value.trim().is_empty()
+    })
+}
+
+/// Tree of `parent` with the index entries under `paths` swapped in.
+///
+/// Built in a scratch index, so the repository index is never written: once
+/// HEAD moves, the committed paths match it and every other staged change is
+/// still staged.
+fn partial_commit_tree(
+    repo: &Repository,
+    index: &git2::Index,
+    parent: Option<&git2::Commit>,
+    paths: &[String],
+) -> Result<git2::Oid, String> {
+    if repo.state() != git2::RepositoryState::Clean {
+        return Err("진행 중인 병합/리베이스가 있으면 일부 파일만 커밋할 수 없습니다".to_string());
+    }
+    let paths: Vec<String> = paths
+        .iter()
+        .map(|p| normalize_unicode(p).trim_end_matches('/').to_string())
+        .collect();
+    let selected = |entry: &git2::IndexEntry| {
+        let path = normalize_unicode(&String::from_utf8_lossy(&entry.path));
+        paths.iter().any(|p| {
+            path == *p
+                || path
+                    .strip_prefix(p.as_str())
+                    .is_some_and(|rest| rest.starts_with('/'))
+        })
+    };
+
+    let index_err = |e: git2::Error| format!("인덱스 접근 실패: {}", e);
+    let mut partial = git2::Index::new().map_err(index_err)?;
+    let parent_tree = match parent {
+        Some(parent) => Some(
+            parent
+                .tree()
+                .map_err(|e| format!("트리 접근 실패: {}", e))?,
+        ),
+        None => None,
+    };
+    if let Some(tree) = &parent_tree {
+        partial.read_tree(tree).map_err(index_err)?;
+    }
+
+    let replaced: Vec<Vec<u8>> = partial
+        .iter()
+        .filter(|entry| selected(entry))
+        .map(|entry| entry.path)
+        .collect();
+    for path in replaced {
+        let path = String::from_utf8_lossy(&path).to_string();
+        partial.remove(Path::new(&path), 0).map_err(index_err)?;
+    }
+    for entry in index.iter().filter(|entry| selected(entry)) {
+        partial.add(&entry).map_err(index_err)?;
+    }
+
+    let tree_id = partial
+        .write_tree_to(repo)
+        .map_err(|e| format!("트리 쓰기 실패: {}", e))?;
+    let unchanged = match &parent_tree {
+        Some(tree) => tree.id() == tree_id,
+        None => partial.is_empty(),
+    };
+    if unchanged {
+        return Err("선택한 파일에 커밋할 변경 사항이 없습니다".to_string());
+    }
+    Ok(tree_id)
 }
 
 /// Search commits by message, author, or SHA prefix.
@@ -422,21 +1053,1166 @@ pub async fn search_commits(
             || author.contains(&query_lower);
 
         if matches {
-            let timestamp = commit.time().seconds();
-            let datetime = Utc.timestamp_opt(timestamp, 0).unwrap();
-            let parent_ids: Vec<String> = commit.parent_ids().map(|oid| oid.to_string()).collect();
-
-            results.push(CommitInfo {
-                sha,
-                author: commit.author().name().unwrap_or("Unknown").to_string(),
-                email: commit.author().email().unwrap_or("").to_string(),
-                message: commit.message().unwrap_or("").to_string(),
-                timestamp,
-                date: datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
-                parent_ids,
-            });
+            results.push(commit_info(&commit));
         }
     }
 
     Ok(results)
 }
+
+/// Commits whose diff adds or removes a line containing `needle`, newest
+/// first, like `git log -S` (and like it, merge commits are skipped).
+///
+/// With `path`, only changes under that path count. The needle and the diff
+/// lines are compared NFC-normalized, so Korean text matches whichever form
+/// it was typed or committed in.
+#[tauri::command]
+pub async fn search_commits_by_content(
+    repo_path: String,
+    needle: String,
+    path: Option<String>,
+    limit: usize,
+) -> Result<Vec<ContentSearchHit>, String> {
+    let needle = normalize_unicode(&needle);
+    if needle.is_empty() {
+        return Err("검색할 내용이 비어 있습니다".to_string());
+    }
+    tokio::task::spawn_blocking(move || {
+        let repo = open_repo(&repo_path)?;
+        let mut hits = Vec::new();
+        if limit == 0 || repo.is_empty().unwrap_or(false) {
+            return Ok(hits);
+        }
+        let pathspec = path.map(|p| normalize_unicode(&p));
+
+        for commit in HistoryWalker::new(&repo, false)? {
+            let commit = commit?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let matched = first_file_with_line(&repo, &commit, &needle, pathspec.as_deref())?;
+            if let Some(file_path) = matched {
+                hits.push(ContentSearchHit {
+                    commit: commit_info(&commit),
+                    file_path,
+                });
+                if hits.len() >= limit {
+                    break;
+                }
+            }
+        }
+        Ok(hits)
+    })
+    .await
+    .map_err(|e| format!("내용 검색 실패: {}", e))?
+}
+
+/// First file in `commit`'s diff against its parent with an added or removed
+/// line containing `needle` (already NFC).
+fn first_file_with_line(
+    repo: &Repository,
+    commit: &git2::Commit,
+    needle: &str,
+    pathspec: Option<&str>,
+) -> Result<Option<String>, String> {
+    let parent_tree = match commit.parent(0) {
+        Ok(parent) => Some(parent.tree().map_err(|e| format!("트리 읽기 실패: {}", e))?),
+        Err(_) => None,
+    };
+    let tree = commit.tree().map_err(|e| format!("트리 읽기 실패: {}", e))?;
+    let mut opts = git2::DiffOptions::new();
+    opts.context_lines(0);
+    if let Some(pathspec) = pathspec {
+        opts.pathspec(pathspec);
+    }
+    let diff = repo
+        .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+
+    for idx in 0..diff.deltas().len() {
+        let patch = match git2::Patch::from_diff(&diff, idx) {
+            Ok(Some(patch)) => patch,
+            // Binary files have no lines
+            Ok(None) => continue,
+            Err(e) => return Err(format!("Diff 생성 실패: {}", e)),
+        };
+        let mut found = false;
+        'hunks: for hunk in 0..patch.num_hunks() {
+            let lines = patch.num_lines_in_hunk(hunk).unwrap_or(0);
+            for line in 0..lines {
+                let line = match patch.line_in_hunk(hunk, line) {
+                    Ok(line) => line,
+                    Err(_) => continue,
+                };
+                if !matches!(line.origin(), '+' | '-') {
+                    continue;
+                }
+                let content = String::from_utf8_lossy(line.content());
+                let matches = if content.is_ascii() {
+                    content.contains(needle)
+                } else {
+                    normalize_unicode(&content).contains(needle)
+                };
+                if matches {
+                    found = true;
+                    break 'hunks;
+                }
+            }
+        }
+        if found {
+            let delta = patch.delta();
+            let file = delta.new_file().path().or_else(|| delta.old_file().path());
+            return Ok(file.map(|p| normalize_unicode(&p.to_string_lossy())));
+        }
+    }
+    Ok(None)
+}
+
+/// Commits in history order (topological, then newest first), starting from
+/// HEAD or from every local and remote-tracking branch.
+pub(super) struct HistoryWalker<'r> {
+    repo: &'r Repository,
+    revwalk: git2::Revwalk<'r>,
+}
+
+impl<'r> HistoryWalker<'r> {
+    pub(super) fn new(repo: &'r Repository, all_branches: bool) -> Result<Self, String> {
+        let mut revwalk = repo.revwalk().map_err(|e| format!("Revwalk 생성 실패: {}", e))?;
+
+        if all_branches {
+            for branch_type in [git2::BranchType::Local, git2::BranchType::Remote] {
+                let branches = repo
+                    .branches(Some(branch_type))
+                    .map_err(|e| format!("브랜치 목록 실패: {}", e))?;
+                for (branch, _) in branches.flatten() {
+                    if let Ok(oid) = branch.get().peel_to_commit().map(|c| c.id()) {
+                        let _ = revwalk.push(oid);
+                    }
+                }
+            }
+        } else if unborn_branch(repo).is_none() {
+            // An unborn HEAD has no history to walk
+            revwalk.push_head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+        }
+
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+            .map_err(|e| format!("정렬 설정 실패: {}", e))?;
+        Ok(HistoryWalker { repo, revwalk })
+    }
+
+    /// Advance past the commit `cursor` (a sha), so iteration continues
+    /// right after it.
+    pub(super) fn skip_past(&mut self, cursor: &str) -> Result<(), String> {
+        let cursor_oid = git2::Oid::from_str(cursor)
+            .map_err(|_| format!("잘못된 페이지 커서입니다: {}", cursor))?;
+        for oid in self.revwalk.by_ref() {
+            if oid.map_err(|e| format!("OID 읽기 실패: {}", e))? == cursor_oid {
+                return Ok(());
+            }
+        }
+        Err(format!("기록에서 커서 커밋을 찾을 수 없습니다: {}", cursor))
+    }
+}
+
+impl<'r> Iterator for HistoryWalker<'r> {
+    type Item = Result<git2::Commit<'r>, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let oid = self.revwalk.next()?;
+        Some(
+            oid.map_err(|e| format!("OID 읽기 실패: {}", e)).and_then(|oid| {
+                self.repo
+                    .find_commit(oid)
+                    .map_err(|e| format!("커밋 찾기 실패: {}", e))
+            }),
+        )
+    }
+}
+
+/// Short ref names (branches, remote-tracking branches, tags) pointing at each commit.
+pub(super) fn ref_decorations(repo: &Repository) -> Result<HashMap<git2::Oid, Vec<String>>, String> {
+    let mut decorations: HashMap<git2::Oid, Vec<String>> = HashMap::new();
+    let references = repo
+        .references()
+        .map_err(|e| format!("참조 목록 조회 실패: {}", e))?;
+    for reference in references.flatten() {
+        let is_decoration = reference.is_branch() || reference.is_remote() || reference.is_tag();
+        if !is_decoration || reference.name().is_some_and(|n| n.ends_with("/HEAD")) {
+            continue;
+        }
+        if let (Some(name), Ok(commit)) = (reference.shorthand(), reference.peel_to_commit()) {
+            decorations
+                .entry(commit.id())
+                .or_default()
+                .push(normalize_unicode(name));
+        }
+    }
+    for names in decorations.values_mut() {
+        names.sort();
+    }
+    Ok(decorations)
+}
+
+/// Build the `CommitInfo` payload for a commit (`relative_date` left empty).
+pub(super) fn commit_info(commit: &git2::Commit) -> CommitInfo {
+    let timestamp = commit.time().seconds();
+    let datetime = Utc.timestamp_opt(timestamp, 0).unwrap();
+
+    CommitInfo {
+        sha: commit.id().to_string(),
+        author: commit.author().name().unwrap_or("Unknown").to_string(),
+        email: commit.author().email().unwrap_or("").to_string(),
+        message: commit.message().unwrap_or("").to_string(),
+        timestamp,
+        date: datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+        parent_ids: commit.parent_ids().map(|oid| oid.to_string()).collect(),
+        relative_date: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::models::CommandError;
+    use git2::{BranchType, Repository, Signature};
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        let repo = Repository::init(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        (temp_dir, repo_path)
+    }
+
+    fn commit_file(repo: &Repository, name: &str, content: &str, message: &str) -> git2::Oid {
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// Leave the repository in the middle of a merge with `file.txt` conflicted.
+    fn setup_conflicted_merge(repo: &Repository) {
+        commit_file(repo, "file.txt", "base\n", "Base");
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        let default_branch = repo.head().unwrap().shorthand().unwrap().to_string();
+
+        repo.branch("other", &base, false).unwrap();
+        repo.set_head("refs/heads/other").unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(repo, "file.txt", "theirs\n", "Theirs");
+
+        repo.set_head(&format!("refs/heads/{}", default_branch)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        commit_file(repo, "file.txt", "ours\n", "Ours");
+
+        let other = repo.find_branch("other", BranchType::Local).unwrap();
+        let annotated = repo
+            .reference_to_annotated_commit(other.get())
+            .unwrap();
+        repo.merge(&[&annotated], None, None).unwrap();
+        assert!(repo.index().unwrap().has_conflicts());
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_conflicts_once() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        setup_conflicted_merge(&repo);
+
+        let status = get_repository_status(repo_path).await.unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].path, "file.txt");
+        assert_eq!(status[0].status, "conflicted");
+        assert!(!status[0].staged);
+    }
+
+    #[tokio::test]
+    async fn test_repository_state_during_merge_and_cherry_pick() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let clean = get_repository_state(repo_path.clone()).await.unwrap();
+        assert_eq!(clean.state, "clean");
+        assert!(clean.source.is_none() && clean.target.is_none());
+
+        setup_conflicted_merge(&repo);
+        let current = repo.head().unwrap().shorthand().unwrap().to_string();
+        let info = open_repository(repo_path.clone()).await.unwrap();
+        assert_eq!(info.state.state, "merge");
+        assert_eq!(info.state.source.as_deref(), Some("other"));
+        assert_eq!(info.state.target.as_deref(), Some(current.as_str()));
+        assert!(info.state.step.is_none());
+
+        repo.cleanup_state().unwrap();
+        let other = repo.revparse_single("other").unwrap();
+        std::fs::write(repo.path().join("CHERRY_PICK_HEAD"), format!("{}\n", other.id())).unwrap();
+        repo.reference("refs/remotes/origin/other", other.id(), false, "test")
+            .unwrap();
+        let state = get_repository_state(repo_path.clone()).await.unwrap();
+        assert_eq!(state.state, "cherry_pick");
+        // Local branches are preferred over remote-tracking ones
+        assert_eq!(state.source.as_deref(), Some("other"));
+
+        repo.find_reference("refs/heads/other").unwrap().delete().unwrap();
+        let state = get_repository_state(repo_path).await.unwrap();
+        assert_eq!(state.source.as_deref(), Some("origin/other"));
+    }
+
+    #[tokio::test]
+    async fn test_repository_state_during_rebase() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let onto = commit_file(&repo, "a.txt", "a\n", "Onto");
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        let rebase_dir = repo.path().join("rebase-merge");
+        std::fs::create_dir(&rebase_dir).unwrap();
+        std::fs::write(rebase_dir.join("head-name"), "refs/heads/기능/작업\n").unwrap();
+        std::fs::write(rebase_dir.join("onto"), format!("{}\n", onto)).unwrap();
+        std::fs::write(rebase_dir.join("msgnum"), "2\n").unwrap();
+        std::fs::write(rebase_dir.join("end"), "5\n").unwrap();
+        std::fs::write(rebase_dir.join("interactive"), "").unwrap();
+
+        let state = get_repository_state(repo_path.clone()).await.unwrap();
+        assert_eq!(state.state, "rebase_interactive");
+        assert_eq!(state.source.as_deref(), Some("기능/작업"));
+        assert_eq!(state.target.as_deref(), Some(main.as_str()));
+        assert_eq!((state.step, state.total_steps), (Some(2), Some(5)));
+
+        std::fs::remove_dir_all(&rebase_dir).unwrap();
+        let rebase_dir = repo.path().join("rebase-apply");
+        std::fs::create_dir(&rebase_dir).unwrap();
+        std::fs::write(rebase_dir.join("rebasing"), "").unwrap();
+        std::fs::write(rebase_dir.join("onto"), format!("{}\n", "0".repeat(40))).unwrap();
+        std::fs::write(rebase_dir.join("next"), "1\n").unwrap();
+        std::fs::write(rebase_dir.join("last"), "3\n").unwrap();
+        let state = open_repository(repo_path).await.unwrap().state;
+        assert_eq!(state.state, "rebase");
+        assert_eq!(state.target, Some("0".repeat(40)));
+        assert_eq!((state.step, state.total_steps), (Some(1), Some(3)));
+    }
+
+    #[tokio::test]
+    async fn test_status_reports_renames() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let content = "line one\nline two\nline three\n";
+        commit_file(&repo, "이전.txt", content, "Add file");
+        commit_file(&repo, "other.txt", content.repeat(2).as_str(), "Add other");
+        let root = Path::new(&repo_path);
+
+        // Staged `git mv 이전.txt 새.txt`
+        std::fs::rename(root.join("이전.txt"), root.join("새.txt")).unwrap();
+        let mut index = repo.index().unwrap();
+        index.remove_path(Path::new("이전.txt")).unwrap();
+        index.add_path(Path::new("새.txt")).unwrap();
+        index.write().unwrap();
+        // Unstaged move
+        std::fs::rename(root.join("other.txt"), root.join("moved.txt")).unwrap();
+
+        let status = get_repository_status(repo_path).await.unwrap();
+        let entries: Vec<(&str, &str, bool, Option<&str>)> = status
+            .iter()
+            .map(|f| {
+                (
+                    f.path.as_str(),
+                    f.status.as_str(),
+                    f.staged,
+                    f.old_path.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("moved.txt", "renamed", false, Some("other.txt")),
+                ("새.txt", "renamed", true, Some("이전.txt")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_report_fast_modes() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "tracked.txt", "v1\n", "Add file");
+        let root = Path::new(&repo_path);
+        std::fs::write(root.join("tracked.txt"), "v2\n").unwrap();
+        std::fs::create_dir_all(root.join("생성된/깊은")).unwrap();
+        for i in 0..3 {
+            std::fs::write(root.join(format!("생성된/깊은/{}.txt", i)), "x").unwrap();
+        }
+
+        let paths = |report: &RepositoryStatusReport| -> Vec<(String, String)> {
+            report
+                .files
+                .iter()
+                .map(|f| (f.path.clone(), f.status.clone()))
+                .collect()
+        };
+        let full = get_repository_status_report(repo_path.clone(), true, false)
+            .await
+            .unwrap();
+        assert_eq!(paths(&full).len(), 4);
+        assert!(!full.suggest_fast_mode);
+
+        let collapsed = get_repository_status_report(repo_path.clone(), true, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            paths(&collapsed),
+            vec![
+                ("tracked.txt".to_string(), "modified".to_string()),
+                ("생성된/".to_string(), "untracked".to_string()),
+            ]
+        );
+
+        let tracked_only = get_repository_status_report(repo_path, false, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            paths(&tracked_only),
+            vec![("tracked.txt".to_string(), "modified".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_status_report_writes_back_stat_cache() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "tracked.txt", "same\n", "Add file");
+        // Same content, new mtime: only the cached stat data is stale
+        let file = Path::new(&repo_path).join("tracked.txt");
+        std::fs::write(&file, "same\n").unwrap();
+        let touched = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(touched)
+            .unwrap();
+
+        let report = get_repository_status_report(repo_path.clone(), true, false)
+            .await
+            .unwrap();
+        assert!(report.files.is_empty());
+
+        // The next scan can trust the index instead of re-reading the file
+        let index = Repository::open(&repo_path).unwrap().index().unwrap();
+        let entry = index.get_path(Path::new("tracked.txt"), 0).unwrap();
+        assert_eq!(entry.mtime.seconds(), 1_000_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_search_commits_by_content() {
+        use unicode_normalization::UnicodeNormalization;
+
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "README.md", "# 프로젝트\n", "Initial commit");
+        std::fs::create_dir(Path::new(&repo_path).join("src")).unwrap();
+        let added = commit_file(&repo, "src/user.rs", "let 사용자이름 = 1;\n", "Add name");
+        commit_file(&repo, "notes.txt", "사용자이름 정리\n", "Add notes");
+        commit_file(&repo, "README.md", "# 프로젝트\n설명\n", "Describe");
+        let removed = commit_file(&repo, "src/user.rs", "let name = 1;\n", "Rename");
+
+        // Typed decomposed (as macOS input often is)
+        let needle: String = "사용자이름".nfd().collect();
+        let hits = search_commits_by_content(repo_path.clone(), needle.clone(), None, 10)
+            .await
+            .unwrap();
+        let found: Vec<(String, &str)> = hits
+            .iter()
+            .map(|h| (h.commit.sha.clone(), h.file_path.as_str()))
+            .collect();
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0], (removed.to_string(), "src/user.rs"));
+        assert_eq!(found[1].1, "notes.txt");
+        assert_eq!(found[2], (added.to_string(), "src/user.rs"));
+
+        let in_src =
+            search_commits_by_content(repo_path.clone(), needle.clone(), Some("src".into()), 10)
+                .await
+                .unwrap();
+        assert_eq!(in_src.len(), 2);
+        let limited = search_commits_by_content(repo_path.clone(), needle, None, 1)
+            .await
+            .unwrap();
+        assert_eq!(limited.len(), 1);
+        assert!(search_commits_by_content(repo_path.clone(), "없는내용".into(), None, 10)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(search_commits_by_content(repo_path, String::new(), None, 10)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_commit_refuses_conflicted_index() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        setup_conflicted_merge(&repo);
+        let head_before = repo.head().unwrap().target().unwrap();
+
+        let err = create_commit(repo_path, "Merge".to_string(), None, None, None, None, None)
+            .await
+            .unwrap_err();
+        let payload: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "unresolved_conflicts");
+        assert_eq!(payload.details["paths"], serde_json::json!(["file.txt"]));
+        assert_eq!(repo.head().unwrap().target().unwrap(), head_before);
+    }
+
+    #[tokio::test]
+    async fn test_commit_selected_paths_keeps_rest_staged() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "old.txt", "old\n", "Initial");
+        let root = temp_dir.path();
+        std::fs::write(root.join("가.txt"), "first\n").unwrap();
+        std::fs::write(root.join("other.txt"), "second\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("가.txt")).unwrap();
+        index.add_path(Path::new("other.txt")).unwrap();
+        index.remove_path(Path::new("old.txt")).unwrap();
+        index.write().unwrap();
+
+        // NFD input selects the NFC path in the index
+        let nfd: String = {
+            use unicode_normalization::UnicodeNormalization;
+            "가.txt".nfd().collect()
+        };
+        create_commit(
+            repo_path.clone(),
+            "일부 커밋".to_string(),
+            Some(vec![nfd]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("일부 커밋"));
+        let tree = head.tree().unwrap();
+        assert!(tree.get_path(Path::new("가.txt")).is_ok());
+        assert!(tree.get_path(Path::new("other.txt")).is_err());
+        assert!(tree.get_path(Path::new("old.txt")).is_ok());
+
+        let status = get_repository_status(repo_path.clone()).await.unwrap();
+        let staged: Vec<(&str, &str)> = status
+            .iter()
+            .filter(|f| f.staged)
+            .map(|f| (f.path.as_str(), f.status.as_str()))
+            .collect();
+        assert_eq!(staged.len(), 2, "{:?}", staged);
+        assert!(staged.contains(&("other.txt", "added")));
+        assert!(staged.contains(&("old.txt", "deleted")));
+
+        // Committing the staged deletion by path, then nothing is left for it
+        create_commit(
+            repo_path.clone(),
+            "삭제".to_string(),
+            Some(vec!["old.txt".to_string()]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        assert!(tree.get_path(Path::new("old.txt")).is_err());
+        assert!(tree.get_path(Path::new("other.txt")).is_err());
+        let err = create_commit(
+            repo_path,
+            "없음".to_string(),
+            Some(vec!["old.txt".to_string()]),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("변경 사항이 없습니다"));
+    }
+
+    #[tokio::test]
+    async fn test_discard_file_changes() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let root = temp_dir.path();
+        commit_file(&repo, "한글.txt", "원본\n", "Initial");
+        commit_file(&repo, "staged.txt", "v1\n", "Second");
+
+        // Modified tracked file, addressed by its NFD spelling
+        std::fs::write(root.join("한글.txt"), "수정됨\n").unwrap();
+        let nfd: String = {
+            use unicode_normalization::UnicodeNormalization;
+            "한글.txt".nfd().collect()
+        };
+        discard_file_changes(repo_path.clone(), nfd, None)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("한글.txt")).unwrap(), "원본\n");
+
+        // Staged + modified: the staged version survives
+        std::fs::write(root.join("staged.txt"), "v2\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("staged.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(root.join("staged.txt"), "v3\n").unwrap();
+        discard_file_changes(repo_path.clone(), "staged.txt".to_string(), None)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(root.join("staged.txt")).unwrap(), "v2\n");
+        let status = get_repository_status(repo_path.clone()).await.unwrap();
+        assert!(status
+            .iter()
+            .any(|f| f.path == "staged.txt" && f.staged && f.status == "modified"));
+
+        // Untracked file is only deleted with the flag
+        std::fs::write(root.join("새파일.txt"), "new\n").unwrap();
+        let err = discard_file_changes(repo_path.clone(), "새파일.txt".to_string(), None)
+            .await
+            .unwrap_err();
+        assert!(err.contains("delete_untracked"));
+        assert!(root.join("새파일.txt").exists());
+        discard_file_changes(repo_path, "새파일.txt".to_string(), Some(true))
+            .await
+            .unwrap();
+        assert!(!root.join("새파일.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_discard_deletes_only_untracked_content() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir(root.join("src")).unwrap();
+        commit_file(&repo, "src/main.rs", "fn main() {}\n", "Initial");
+        commit_file(&repo, ".gitignore", "*.log\n", "Ignore logs");
+        let discard = |path: &str| discard_file_changes(repo_path.clone(), path.into(), Some(true));
+
+        // A tracked directory has no index entry of its own
+        let err = discard("src").await.unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "tracked_directory");
+        assert!(root.join("src/main.rs").exists());
+
+        for path in [".git", "vendor/.git"] {
+            std::fs::create_dir_all(root.join(path)).unwrap();
+            let err = discard(path).await.unwrap_err();
+            let err: CommandError = serde_json::from_str(&err).unwrap();
+            assert_eq!(err.code, "invalid_path", "{}", path);
+        }
+        assert!(repo.head().is_ok());
+
+        // Ignored files and nested repositories are not untracked content
+        std::fs::write(root.join("debug.log"), "log\n").unwrap();
+        assert!(discard("debug.log").await.is_err());
+        assert!(root.join("debug.log").exists());
+        std::fs::write(root.join("vendor/lib.rs"), "x\n").unwrap();
+        assert!(discard("vendor").await.is_err());
+        assert!(root.join("vendor/lib.rs").exists());
+
+        std::fs::create_dir_all(root.join("build/out")).unwrap();
+        std::fs::write(root.join("build/out/app.o"), "obj").unwrap();
+        discard("build").await.unwrap();
+        assert!(!root.join("build").exists());
+    }
+
+    #[tokio::test]
+    async fn test_discard_file_changes_refuses_conflicts() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        setup_conflicted_merge(&repo);
+
+        let err = discard_file_changes(repo_path.clone(), "file.txt".to_string(), Some(true))
+            .await
+            .unwrap_err();
+        let payload: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(payload.code, "unresolved_conflicts");
+        assert!(std::fs::read_to_string(std::path::Path::new(&repo_path).join("file.txt"))
+            .unwrap()
+            .contains("<<<<<<<"));
+    }
+
+    #[tokio::test]
+    async fn test_clean_untracked() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let root = temp_dir.path();
+        commit_file(&repo, ".gitignore", "*.log\n", "Initial");
+        std::fs::write(root.join("top.txt"), "top\n").unwrap();
+        std::fs::write(root.join("한글무시.log"), "ignored\n").unwrap();
+        std::fs::create_dir_all(root.join("새폴더/하위")).unwrap();
+        std::fs::write(root.join("새폴더/하위/b.txt"), "b\n").unwrap();
+        Repository::init(root.join("vendor/lib")).unwrap();
+        std::fs::write(root.join("vendor/lib/c.txt"), "c\n").unwrap();
+
+        let preview = clean_untracked(repo_path.clone(), true, true, true)
+            .await
+            .unwrap();
+        assert_eq!(preview, vec!["top.txt", "새폴더/", "한글무시.log"]);
+        assert!(root.join("top.txt").exists() && root.join("새폴더").exists());
+
+        let files_only = clean_untracked(repo_path.clone(), true, false, false)
+            .await
+            .unwrap();
+        assert_eq!(files_only, vec!["top.txt"]);
+
+        let removed = clean_untracked(repo_path.clone(), false, false, true)
+            .await
+            .unwrap();
+        assert_eq!(removed, vec!["top.txt", "새폴더/"]);
+        assert!(!root.join("top.txt").exists());
+        assert!(!root.join("새폴더").exists());
+        assert!(root.join("한글무시.log").exists());
+        assert!(root.join("vendor/lib/.git").exists());
+
+        let removed = clean_untracked(repo_path, false, true, true).await.unwrap();
+        assert_eq!(removed, vec!["한글무시.log"]);
+        assert!(!root.join("한글무시.log").exists());
+        assert!(root.join(".gitignore").exists());
+    }
+
+    #[tokio::test]
+    async fn test_commit_with_author_and_co_authors() {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "a.txt", "a\n", "Initial");
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Committer").unwrap();
+        config.set_str("user.email", "committer@example.com").unwrap();
+
+        std::fs::write(temp_dir.path().join("a.txt"), "b\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let message = "기능: 페어 작업\n\n첫 문단입니다.\n\n둘째 문단입니다.\n";
+        create_commit(
+            repo_path.clone(),
+            message.to_string(),
+            None,
+            Some("김철수".to_string()),
+            Some("chulsoo@example.com".to_string()),
+            Some(vec![
+                "이영희 <younghee@example.com>".to_string(),
+                "Pair Bot <bot@example.com>".to_string(),
+            ]),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.author().name(), Some("김철수"));
+        assert_eq!(head.author().email(), Some("chulsoo@example.com"));
+        assert_eq!(head.committer().name(), Some("Committer"));
+
+        let history = get_commit_history(repo_path.clone(), 1, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(history[0].author, "김철수");
+        let committed = &history[0].message;
+        assert!(committed.contains("둘째 문단입니다.\n\nCo-authored-by: 이영희"));
+        let trailers = git2::message_trailers_strs(committed).unwrap();
+        let co_authors: Vec<(&str, &str)> = trailers.iter().collect();
+        assert_eq!(
+            co_authors,
+            vec![
+                ("Co-authored-by", "이영희 <younghee@example.com>"),
+                ("Co-authored-by", "Pair Bot <bot@example.com>"),
+            ]
+        );
+
+        // An existing trailer block is extended rather than split
+        let extended = append_co_authors(
+            "수정\n\nSigned-off-by: A <a@example.com>",
+            &["B <b@example.com>".to_string()],
+        )
+        .unwrap();
+        assert_eq!(
+            extended,
+            "수정\n\nSigned-off-by: A <a@example.com>\nCo-authored-by: B <b@example.com>"
+        );
+        assert!(append_co_authors("수정", &["B".to_string()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_first_commit_on_unborn_head() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.set_head("refs/heads/trunk").unwrap();
+
+        let info = open_repository(repo_path.clone()).await.unwrap();
+        assert!(info.is_empty);
+        assert_eq!(info.current_branch, "trunk");
+        assert!(!info.sync.has_upstream);
+        assert_eq!(
+            crate::commands::branch::get_current_branch(repo_path.clone())
+                .await
+                .unwrap(),
+            "trunk"
+        );
+        let history = get_commit_history(repo_path.clone(), 50, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert!(history.is_empty());
+
+        std::fs::write(Path::new(&repo_path).join("README.md"), "# 첫 커밋\n").unwrap();
+        let status = get_repository_status(repo_path.clone()).await.unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].status, "untracked");
+        stage_file(repo_path.clone(), "README.md".to_string())
+            .await
+            .unwrap();
+        let result = create_commit(
+            repo_path.clone(),
+            "첫 커밋".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let info = open_repository(repo_path.clone()).await.unwrap();
+        assert!(!info.is_empty);
+        assert_eq!(info.current_branch, "trunk");
+        let history = get_commit_history(repo_path.clone(), 50, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].sha, result.commit_sha);
+        assert!(history[0].parent_ids.is_empty());
+        assert!(get_repository_status(repo_path).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_history_pages_follow_cursor() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        for i in 0..5 {
+            commit_file(&repo, "a.txt", &format!("{}\n", i), &format!("커밋 {}", i));
+        }
+
+        let mut messages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page =
+                get_commit_history_page(repo_path.clone(), cursor, Some(2), None, None, None)
+                    .await
+                    .unwrap();
+            assert!(page.items.len() <= 2);
+            assert_eq!(page.total, None);
+            messages.extend(page.items.into_iter().map(|c| c.message));
+            if !page.has_more {
+                assert_eq!(page.next_cursor, None);
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(messages, vec!["커밋 4", "커밋 3", "커밋 2", "커밋 1", "커밋 0"]);
+
+        // The compatibility wrapper keeps its plain-list shape
+        let legacy = get_commit_history(repo_path, 3, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(legacy.len(), 3);
+    }
+
+    fn commit_by(repo: &Repository, author: &str, name: &str, message: &str, time: i64) {
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        if let Some(dir) = Path::new(name).parent() {
+            std::fs::create_dir_all(workdir.join(dir)).unwrap();
+        }
+        std::fs::write(workdir.join(name), message).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let when = git2::Time::new(time, 540);
+        let sig = Signature::new(author, "dev@example.com", &when).unwrap();
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_commit_history_filtered() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let nfd_kim: String = {
+            use unicode_normalization::UnicodeNormalization;
+            "김철수".nfd().collect()
+        };
+        commit_by(&repo, &nfd_kim, "src/a.rs", "Add parser", 1_000);
+        commit_by(&repo, "Lee", "docs/README.md", "문서 추가", 2_000);
+        commit_by(&repo, "김철수", "src/b.rs", "FIX parser bug", 3_000);
+        commit_by(&repo, "Lee", "src/a.rs", "Tweak parser", 4_000);
+
+        let filtered = |author: Option<&str>,
+                        message: Option<&str>,
+                        since: Option<i64>,
+                        until: Option<i64>,
+                        path: Option<&str>| {
+            get_commit_history_filtered(
+                repo_path.clone(),
+                None,
+                None,
+                None,
+                author.map(str::to_string),
+                message.map(str::to_string),
+                since,
+                until,
+                path.map(str::to_string),
+            )
+        };
+        let messages = |page: &FilteredHistoryPage| -> Vec<String> {
+            page.items.iter().map(|c| c.message.clone()).collect()
+        };
+
+        // An NFC query finds the NFD-stored author name too
+        let page = filtered(Some("김철수"), None, None, None, None).await.unwrap();
+        assert_eq!(messages(&page), vec!["FIX parser bug", "Add parser"]);
+        assert_eq!(page.scanned, 4);
+        assert!(!page.has_more && page.next_cursor.is_none());
+
+        let page = filtered(None, Some("PARSER"), Some(2_000), Some(4_000), None)
+            .await
+            .unwrap();
+        assert_eq!(messages(&page), vec!["Tweak parser", "FIX parser bug"]);
+
+        let page = filtered(None, None, None, None, Some("src/a.rs")).await.unwrap();
+        assert_eq!(messages(&page), vec!["Tweak parser", "Add parser"]);
+        let page = filtered(Some("lee"), None, None, None, Some("src/")).await.unwrap();
+        assert_eq!(messages(&page), vec!["Tweak parser"]);
+
+        // With a limit, the cursor continues where the page stopped
+        let first = get_commit_history_filtered(
+            repo_path.clone(),
+            None,
+            Some(1),
+            None,
+            None,
+            Some("parser".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(messages(&first), vec!["Tweak parser"]);
+        assert!(first.has_more);
+        let rest = get_commit_history_filtered(
+            repo_path.clone(),
+            first.next_cursor,
+            Some(10),
+            None,
+            None,
+            Some("parser".to_string()),
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(messages(&rest), vec!["FIX parser bug", "Add parser"]);
+        assert_eq!(rest.scanned, 3);
+    }
+
+    #[tokio::test]
+    async fn test_commit_history_limit_is_clamped() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        for i in 0..MAX_PAGE_SIZE + 5 {
+            commit_file(&repo, "a.txt", &format!("{}\n", i), &format!("커밋 {}", i));
+        }
+        let history =
+            get_commit_history(repo_path.clone(), usize::MAX, None, None, None, None, None)
+                .await
+                .unwrap();
+        assert_eq!(history.len(), MAX_PAGE_SIZE);
+        let history = get_commit_history(repo_path, 0, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_commit_history_pages_through_250_commits() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        for i in 0..250 {
+            commit_file(&repo, "a.txt", &format!("{}\n", i), &format!("커밋 {}", i));
+        }
+        let full = get_commit_history(repo_path.clone(), 1000, None, None, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(full.len(), 250);
+
+        let mut shas = Vec::new();
+        let mut page_sizes = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page =
+                get_commit_history_page(repo_path.clone(), cursor, Some(100), None, None, None)
+                    .await
+                    .unwrap();
+            page_sizes.push(page.items.len());
+            shas.extend(page.items.iter().map(|c| c.sha.clone()));
+            if !page.has_more {
+                break;
+            }
+            assert_eq!(page.next_cursor, shas.last().cloned());
+            cursor = page.next_cursor;
+            // A commit made between requests does not shift the next page
+            if page_sizes.len() == 1 {
+                commit_file(&repo, "b.txt", "new\n", "새 커밋");
+            }
+        }
+        assert_eq!(page_sizes, vec![100, 100, 50]);
+        let expected: Vec<String> = full.iter().map(|c| c.sha.clone()).collect();
+        assert_eq!(shas, expected);
+
+        // skip and start_from on the plain-list command
+        let after = get_commit_history(
+            repo_path.clone(),
+            10,
+            None,
+            None,
+            None,
+            Some(5),
+            Some(expected[99].clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(after[0].sha, expected[105]);
+        assert_eq!(after.len(), 10);
+        let err = get_commit_history_page(
+            repo_path,
+            Some("0".repeat(40)),
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("커서"));
+    }
+
+    /// Main repo with submodule "libs/core" pinned at the library's first
+    /// commit, whose checkout was then moved to the second commit.
+    /// Returns (temp dir, main repo path, first commit, second commit).
+    fn setup_repo_with_moved_submodule() -> (TempDir, String, git2::Oid, git2::Oid) {
+        let (temp_dir, repo_path) = setup_test_repo();
+        let lib_path = temp_dir.path().join("lib-origin");
+        std::fs::create_dir(&lib_path).unwrap();
+        let lib = Repository::init(&lib_path).unwrap();
+        let first = commit_file(&lib, "lib.txt", "v1\n", "Lib v1");
+        let second = commit_file(&lib, "lib.txt", "v2\n", "Lib v2");
+        lib.set_head_detached(first).unwrap();
+
+        let repo = Repository::open(&repo_path).unwrap();
+        commit_file(&repo, "README.md", "# main\n", "Initial commit");
+        let mut submodule = repo
+            .submodule(lib_path.to_str().unwrap(), Path::new("libs/core"), true)
+            .unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+        let mut index = repo.index().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Add submodule", &tree, &[&head])
+            .unwrap();
+
+        let checkout = submodule.open().unwrap();
+        checkout.set_head_detached(second).unwrap();
+        checkout
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        (temp_dir, repo_path, first, second)
+    }
+
+    #[tokio::test]
+    async fn test_submodule_pointer_status_and_staging() {
+        use crate::commands::diff::get_submodule_change;
+
+        let (_temp_dir, repo_path, first, second) = setup_repo_with_moved_submodule();
+
+        let status = get_repository_status(repo_path.clone()).await.unwrap();
+        let entries: Vec<_> = status.iter().filter(|f| f.path == "libs/core").collect();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].status, "submodule");
+        assert!(!entries[0].staged);
+
+        let change = get_submodule_change(repo_path.clone(), "libs/core".into(), false)
+            .await
+            .unwrap();
+        assert_eq!(change.old_sha, Some(first.to_string()));
+        assert_eq!(change.new_sha, Some(second.to_string()));
+
+        stage_file(repo_path.clone(), "libs/core".into()).await.unwrap();
+        let status = get_repository_status(repo_path.clone()).await.unwrap();
+        let entries: Vec<_> = status.iter().filter(|f| f.path == "libs/core").collect();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].staged);
+        assert_eq!(entries[0].status, "submodule");
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let entry = repo
+            .index()
+            .unwrap()
+            .get_path(Path::new("libs/core"), 0)
+            .unwrap();
+        assert_eq!(entry.id, second);
+        assert_eq!(entry.mode, 0o160000);
+
+        let staged = get_submodule_change(repo_path.clone(), "libs/core".into(), true)
+            .await
+            .unwrap();
+        assert_eq!(staged.old_sha, Some(first.to_string()));
+        assert_eq!(staged.new_sha, Some(second.to_string()));
+        assert!(get_submodule_change(repo_path, "README.md".into(), false)
+            .await
+            .is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stage_symlink_pointing_outside_repo() {
+        let (_temp_dir, repo_path) = setup_test_repo();
+        let outside = TempDir::new().unwrap();
+        let target = outside.path().join("shared.txt");
+        std::fs::write(&target, "공유\n").unwrap();
+        std::os::unix::fs::symlink(&target, Path::new(&repo_path).join("shared")).unwrap();
+
+        stage_file(repo_path.clone(), "shared".into()).await.unwrap();
+        let repo = Repository::open(&repo_path).unwrap();
+        let entry = repo.index().unwrap().get_path(Path::new("shared"), 0).unwrap();
+        assert_eq!(entry.mode, 0o120000);
+    }
+}
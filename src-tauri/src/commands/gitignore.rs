@@ -0,0 +1,216 @@
+//! `.gitignore` helpers: checking paths against the ignore rules, adding
+//! patterns and listing what is currently ignored.
+
+use std::path::Path;
+
+use super::git::get_repository_status;
+use super::models::FileStatus;
+use super::utils::{normalize_unicode, open_repo, safe_repo_path};
+
+/// Whether `path` (relative to the repository root) is matched by the
+/// ignore rules, whether or not it exists.
+#[tauri::command]
+pub async fn is_path_ignored(repo_path: String, path: String) -> Result<bool, String> {
+    let repo = open_repo(&repo_path)?;
+    let normalized = normalize_unicode(&path);
+    safe_repo_path(Path::new(&repo_path), &normalized)?;
+    repo.is_path_ignored(Path::new(&normalized))
+        .map_err(|e| format!("무시 규칙 확인 실패: {}", e))
+}
+
+/// Append `pattern` to the root `.gitignore` (or `gitignore_path`, relative
+/// to the repository root), creating the file if needed, and return the
+/// updated repository status.
+///
+/// The file's existing bytes and line endings are kept; a pattern that is
+/// already listed is not added twice. Trailing spaces in the pattern are
+/// escaped, since git would otherwise drop them.
+#[tauri::command]
+pub async fn add_to_gitignore(
+    repo_path: String,
+    pattern: String,
+    gitignore_path: Option<String>,
+) -> Result<Vec<FileStatus>, String> {
+    let pattern = ignore_line(&normalize_unicode(pattern.trim_start()));
+    if pattern.is_empty() {
+        return Err("무시할 패턴이 비어 있습니다".to_string());
+    }
+    let repo = open_repo(&repo_path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or("bare 저장소에는 .gitignore가 없습니다")?
+        .to_path_buf();
+    let target = gitignore_path
+        .map(|p| normalize_unicode(&p))
+        .unwrap_or_else(|| ".gitignore".to_string());
+    let full_path = safe_repo_path(&workdir, &target)?;
+
+    let existing = match std::fs::read(&full_path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => return Err(format!("'{}' 읽기 실패: {}", target, e)),
+    };
+    let updated = append_pattern(existing, &pattern);
+    if let Some(updated) = updated {
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("폴더 생성 실패: {}", e))?;
+        }
+        std::fs::write(&full_path, updated)
+            .map_err(|e| format!("'{}' 쓰기 실패: {}", target, e))?;
+    }
+    get_repository_status(repo_path).await
+}
+
+/// Ignored files and directories (directories with a trailing `/`, not
+/// descended into), sorted.
+#[tauri::command]
+pub async fn list_ignored_files(repo_path: String) -> Result<Vec<String>, String> {
+    let repo = open_repo(&repo_path)?;
+    let mut opts = git2::StatusOptions::new();
+    opts.include_ignored(true)
+        .recurse_ignored_dirs(false)
+        .include_untracked(false);
+    let statuses = repo
+        .statuses(Some(&mut opts))
+        .map_err(|e| format!("상태 조회 실패: {}", e))?;
+
+    let mut ignored: Vec<String> = statuses
+        .iter()
+        .filter(|entry| entry.status().is_ignored())
+        .filter_map(|entry| entry.path().map(normalize_unicode))
+        .collect();
+    ignored.sort();
+    Ok(ignored)
+}
+
+/// `pattern` as a `.gitignore` line: trailing spaces escaped with `\`.
+fn ignore_line(pattern: &str) -> String {
+    let trimmed = pattern.trim_end_matches([' ', '\t', '\r', '\n']);
+    let significant = trimmed.len();
+    let trailing_spaces = pattern[significant..].chars().filter(|c| *c == ' ').count();
+    let mut line = trimmed.to_string();
+    for _ in 0..trailing_spaces {
+        line.push_str("\\ ");
+    }
+    line
+}
+
+/// `existing` with `line` appended on its own line, or None when the file
+/// already lists it. Uses CRLF when the file does.
+fn append_pattern(mut existing: Vec<u8>, line: &str) -> Option<Vec<u8>> {
+    let text = String::from_utf8_lossy(&existing);
+    if text.lines().any(|l| l.trim_end_matches('\r') == line) {
+        return None;
+    }
+    let newline: &[u8] = if text.contains("\r\n") {
+        b"\r\n"
+    } else {
+        b"\n"
+    };
+    if !existing.is_empty() && !existing.ends_with(b"\n") {
+        existing.extend_from_slice(newline);
+    }
+    existing.extend_from_slice(line.as_bytes());
+    existing.extend_from_slice(newline);
+    Some(existing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        Repository::init(temp_dir.path()).unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, repo_path)
+    }
+
+    #[tokio::test]
+    async fn test_add_korean_pattern_with_spaces() {
+        let (temp_dir, repo_path) = setup_repo();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("빌드 결과")).unwrap();
+        fs::write(root.join("빌드 결과/app.bin"), "bin").unwrap();
+        fs::write(root.join("메모.txt"), "memo").unwrap();
+        // Existing file without a trailing newline, CRLF endings
+        fs::write(root.join(".gitignore"), "*.log\r\ntarget/").unwrap();
+
+        assert!(
+            !is_path_ignored(repo_path.clone(), "빌드 결과/app.bin".into())
+                .await
+                .unwrap()
+        );
+        let status = add_to_gitignore(repo_path.clone(), "빌드 결과/".into(), None)
+            .await
+            .unwrap();
+        assert!(
+            is_path_ignored(repo_path.clone(), "빌드 결과/app.bin".into())
+                .await
+                .unwrap()
+        );
+        let paths: Vec<&str> = status.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec![".gitignore", "메모.txt"]);
+        assert_eq!(
+            fs::read_to_string(root.join(".gitignore")).unwrap(),
+            "*.log\r\ntarget/\r\n빌드 결과/\r\n"
+        );
+
+        // Adding it again changes nothing
+        add_to_gitignore(repo_path.clone(), "빌드 결과/".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join(".gitignore")).unwrap(),
+            "*.log\r\ntarget/\r\n빌드 결과/\r\n"
+        );
+        assert_eq!(
+            list_ignored_files(repo_path).await.unwrap(),
+            vec!["빌드 결과/".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_add_to_nested_gitignore() {
+        let (temp_dir, repo_path) = setup_repo();
+        let root = temp_dir.path();
+        fs::create_dir(root.join("docs")).unwrap();
+        fs::write(root.join("docs/draft .md"), "draft").unwrap();
+
+        add_to_gitignore(
+            repo_path.clone(),
+            "draft .md".into(),
+            Some("docs/.gitignore".into()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            fs::read_to_string(root.join("docs/.gitignore")).unwrap(),
+            "draft .md\n"
+        );
+        assert!(is_path_ignored(repo_path.clone(), "docs/draft .md".into())
+            .await
+            .unwrap());
+        assert!(!is_path_ignored(repo_path.clone(), "draft .md".into())
+            .await
+            .unwrap());
+        assert!(
+            add_to_gitignore(repo_path.clone(), "x".into(), Some("../.gitignore".into()))
+                .await
+                .is_err()
+        );
+        assert!(add_to_gitignore(repo_path, "  ".into(), None)
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_ignore_line_escapes_trailing_spaces() {
+        assert_eq!(ignore_line("a b"), "a b");
+        assert_eq!(ignore_line("name  "), "name\\ \\ ");
+        assert_eq!(ignore_line("dir/\n"), "dir/");
+    }
+}
@@ -0,0 +1,304 @@
+//! Lane layout of the commit graph for the history view.
+//!
+//! Lanes are assigned straight down the history: a commit takes the lane
+//! that was waiting for it (or the first free one), its first parent
+//! continues in that lane and further parents open new lanes. Lanes never
+//! move sideways, so every line is a straight or diagonal segment between
+//! two neighbouring rows.
+
+use git2::Oid;
+
+use super::git::{commit_info, HistoryWalker};
+use super::models::{GraphCommit, GraphEdge, Page};
+use super::utils::{open_repo, page_window};
+
+/// Commit history with graph lanes, newest first, in pages like
+/// `get_commit_history_page`.
+///
+/// Lanes are computed from the top of the history on every call, so a page
+/// fetched with `cursor` lines up exactly with the page before it.
+#[tauri::command]
+pub async fn get_commit_graph(
+    repo_path: String,
+    limit: Option<usize>,
+    cursor: Option<String>,
+    all_branches: Option<bool>,
+) -> Result<Page<GraphCommit>, String> {
+    let (_, limit) = page_window(None, limit)?;
+    let repo = open_repo(&repo_path)?;
+    let mut page = Page {
+        items: Vec::new(),
+        total: None,
+        has_more: false,
+        next_cursor: None,
+    };
+    if repo.is_empty().unwrap_or(false) {
+        page.total = Some(0);
+        return Ok(page);
+    }
+
+    let cursor_oid = cursor
+        .as_deref()
+        .map(|c| Oid::from_str(c).map_err(|_| format!("잘못된 페이지 커서입니다: {}", c)))
+        .transpose()?;
+    let mut lanes = LaneAssigner::default();
+    let mut walker = HistoryWalker::new(&repo, all_branches.unwrap_or(false))?;
+
+    // Replay the rows above the cursor to rebuild the lane state
+    if let Some(cursor_oid) = cursor_oid {
+        let mut found = false;
+        for commit in walker.by_ref() {
+            let commit = commit?;
+            let parents: Vec<Oid> = commit.parent_ids().collect();
+            lanes.place(commit.id(), &parents);
+            if commit.id() == cursor_oid {
+                found = true;
+                break;
+            }
+        }
+        if !found {
+            return Err(format!(
+                "기록에서 커서 커밋을 찾을 수 없습니다: {}",
+                cursor.unwrap_or_default()
+            ));
+        }
+    }
+
+    for commit in walker.by_ref() {
+        let commit = commit?;
+        let parents: Vec<Oid> = commit.parent_ids().collect();
+        let (lane, edges) = lanes.place(commit.id(), &parents);
+        page.items.push(GraphCommit {
+            commit: commit_info(&commit),
+            lane,
+            edges,
+        });
+        if page.items.len() == limit {
+            break;
+        }
+    }
+    page.has_more = page.items.len() == limit && walker.next().is_some();
+    page.next_cursor = page
+        .items
+        .last()
+        .filter(|_| page.has_more)
+        .map(|c| c.commit.sha.clone());
+    Ok(page)
+}
+
+/// Lanes still open below the current row, each waiting for a commit.
+#[derive(Default)]
+struct LaneAssigner {
+    lanes: Vec<Option<Oid>>,
+}
+
+impl LaneAssigner {
+    /// Put the next commit (in history order) on a lane and return the lane
+    /// with the edges leaving its row.
+    fn place(&mut self, commit: Oid, parents: &[Oid]) -> (u32, Vec<GraphEdge>) {
+        let lane = match self.lane_of(commit) {
+            Some(lane) => lane,
+            None => self.free_lane(),
+        };
+        self.lanes[lane] = None;
+        let before = self.lanes.clone();
+
+        let mut edges = Vec::new();
+        for (index, &parent) in parents.iter().enumerate() {
+            let target = match self.lane_of(parent) {
+                Some(existing) => existing,
+                None => {
+                    let target = if index == 0 { lane } else { self.free_lane() };
+                    self.lanes[target] = Some(parent);
+                    target
+                }
+            };
+            edges.push(GraphEdge {
+                from_lane: lane as u32,
+                to_lane: target as u32,
+                target_sha: parent.to_string(),
+                from_commit: true,
+            });
+        }
+        for (index, waiting) in before.iter().enumerate() {
+            if let Some(target) = waiting {
+                edges.push(GraphEdge {
+                    from_lane: index as u32,
+                    to_lane: index as u32,
+                    target_sha: target.to_string(),
+                    from_commit: false,
+                });
+            }
+        }
+
+        while self.lanes.last() == Some(&None) {
+            self.lanes.pop();
+        }
+        (lane as u32, edges)
+    }
+
+    fn lane_of(&self, commit: Oid) -> Option<usize> {
+        self.lanes.iter().position(|l| *l == Some(commit))
+    }
+
+    fn free_lane(&mut self) -> usize {
+        match self.lanes.iter().position(Option::is_none) {
+            Some(lane) => lane,
+            None => {
+                self.lanes.push(None);
+                self.lanes.len() - 1
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature, Time};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, file: &str, message: &str, time: i64) -> Oid {
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join(file), message).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::new("Test User", "test@example.com", &Time::new(time, 0)).unwrap();
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    fn checkout(repo: &Repository, branch: &str) {
+        repo.set_head(&format!("refs/heads/{}", branch)).unwrap();
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+    }
+
+    /// main: A - B - M (merges feature) - D; feature: A - F1 - F2;
+    /// topic (diverging, unmerged): B - T1.
+    fn setup_graph_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let a = commit(&repo, "a.txt", "A", 1_000);
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        repo.branch("feature", &repo.find_commit(a).unwrap(), false)
+            .unwrap();
+        let b = commit(&repo, "b.txt", "B", 2_000);
+        repo.branch("topic", &repo.find_commit(b).unwrap(), false)
+            .unwrap();
+
+        checkout(&repo, "feature");
+        commit(&repo, "f.txt", "F1", 3_000);
+        let f2 = commit(&repo, "f.txt", "F2", 4_000);
+        checkout(&repo, "topic");
+        commit(&repo, "t.txt", "T1", 5_000);
+
+        checkout(&repo, &main);
+        let mut index = repo
+            .merge_commits(
+                &repo.head().unwrap().peel_to_commit().unwrap(),
+                &repo.find_commit(f2).unwrap(),
+                None,
+            )
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree_to(&repo).unwrap()).unwrap();
+        let sig = Signature::new("Test User", "test@example.com", &Time::new(6_000, 0)).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "M",
+            &tree,
+            &[&head, &repo.find_commit(f2).unwrap()],
+        )
+        .unwrap();
+        checkout(&repo, &main);
+        commit(&repo, "d.txt", "D", 7_000);
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, repo_path)
+    }
+
+    #[tokio::test]
+    async fn test_commit_graph_lanes() {
+        let (_temp_dir, repo_path) = setup_graph_repo();
+        let graph = get_commit_graph(repo_path, None, None, Some(true))
+            .await
+            .unwrap();
+        let rows: Vec<(&str, u32)> = graph
+            .items
+            .iter()
+            .map(|c| (c.commit.message.as_str(), c.lane))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("D", 0),
+                ("M", 0),
+                ("T1", 2),
+                ("F2", 1),
+                ("F1", 1),
+                ("B", 0),
+                ("A", 1)
+            ]
+        );
+
+        // The merge opens lane 1 for its second parent
+        let merge = &graph.items[1];
+        let from_merge: Vec<(u32, u32)> = merge
+            .edges
+            .iter()
+            .filter(|e| e.from_commit)
+            .map(|e| (e.from_lane, e.to_lane))
+            .collect();
+        assert_eq!(from_merge, vec![(0, 0), (0, 1)]);
+        // T1 joins B's lane while the feature lane passes through
+        let topic = &graph.items[2];
+        assert!(topic
+            .edges
+            .iter()
+            .any(|e| e.from_commit && e.from_lane == 2 && e.to_lane == 0));
+        assert!(topic
+            .edges
+            .iter()
+            .any(|e| !e.from_commit && e.from_lane == 1 && e.to_lane == 1));
+        // B's first parent was already waited for in the feature lane
+        assert_eq!(graph.items[5].edges[0].to_lane, 1);
+        // The root commit ends every line
+        assert!(graph.items[6].edges.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_commit_graph_pages_match_single_walk() {
+        let (_temp_dir, repo_path) = setup_graph_repo();
+        let full = get_commit_graph(repo_path.clone(), None, None, Some(true))
+            .await
+            .unwrap();
+        assert!(!full.has_more);
+
+        let mut paged = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = get_commit_graph(repo_path.clone(), Some(3), cursor, Some(true))
+                .await
+                .unwrap();
+            assert!(page.items.len() <= 3);
+            paged.extend(page.items);
+            if !page.has_more {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        assert_eq!(paged.len(), full.items.len());
+        for (paged, full) in paged.iter().zip(&full.items) {
+            assert_eq!(paged.commit.sha, full.commit.sha);
+            assert_eq!(paged.lane, full.lane);
+            assert_eq!(paged.edges, full.edges);
+        }
+    }
+}
@@ -1,96 +1,311 @@
-use git2::{DiffOptions, Oid};
+use git2::{DiffFindOptions, DiffOptions, Oid, Repository};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use super::models::FileHistoryEntry;
-use super::utils::{normalize_unicode, open_repo};
+use super::models::{FileHistoryEntry, Page, PathIdentity};
+use super::utils::{collect_page, normalize_unicode, open_repo, page_window, MAX_PAGE_SIZE};
 
-/// Get file history (commits that modified a specific file).
-#[tauri::command]
-pub fn get_file_history(
-    repo_path: String,
-    file_path: String,
-    limit: Option<usize>,
-) -> Result<Vec<FileHistoryEntry>, String> {
-    let normalized_path = normalize_unicode(&file_path);
-    let repo = open_repo(&repo_path)?;
+/// Cached trackers per repository before the cache for that repo is reset.
+const MAX_CACHED_TRACKERS: usize = 64;
 
-    let mut revwalk = repo
-        .revwalk()
-        .map_err(|e| format!("Revwalk 생성 실패: {}", e))?;
-    revwalk
-        .push_head()
-        .map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+/// (start commit, path) → tracked history for one repository.
+type TrackerCache = HashMap<(Oid, String), Arc<RenameTracker>>;
+
+lazy_static::lazy_static! {
+    /// Git dir → trackers. Commits are immutable, so entries never go stale;
+    /// new commits simply produce new keys.
+    static ref RENAME_CACHE: Mutex<HashMap<String, TrackerCache>> = Mutex::new(HashMap::new());
+}
 
-    let max_commits = limit.unwrap_or(100);
-    let mut history = Vec::new();
+/// A commit that touched the tracked file, with the name it had there.
+#[derive(Debug, Clone)]
+pub struct PathChange {
+    pub oid: Oid,
+    /// Path in this commit (the new name for renames)
+    pub path: String,
+    /// "added", "deleted", "modified", "renamed"
+    pub status: &'static str,
+    /// Previous name when `status` is "renamed"
+    pub old_path: Option<String>,
+    first_parent: Option<Oid>,
+}
+
+/// Rename-aware history of one path, shared by every feature that needs to
+/// know what a file was called at an older commit.
+///
+/// Walks back from a start commit like `git log --follow`: whenever the file
+/// appears as added, similarity detection looks for its previous name and the
+/// walk continues under that name. An addition with no previous name is the
+/// file's origin and ends the walk.
+#[derive(Debug)]
+pub struct RenameTracker {
+    from: Oid,
+    path: String,
+    changes: Vec<PathChange>,
+}
 
-    for oid in revwalk {
-        if history.len() >= max_commits {
-            break;
+impl RenameTracker {
+    /// Tracker for `path` as of `from`, built once per repository and cached.
+    pub fn for_path(
+        repo: &Repository,
+        path: &str,
+        from: Oid,
+    ) -> Result<Arc<RenameTracker>, String> {
+        let repo_key = repo.path().to_string_lossy().to_string();
+        let key = (from, path.to_string());
+        if let Some(tracker) = RENAME_CACHE
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(&repo_key)?.get(&key).cloned())
+        {
+            return Ok(tracker);
         }
 
-        let oid = oid.map_err(|e| format!("OID 읽기 실패: {}", e))?;
-        let commit = repo
-            .find_commit(oid)
-            .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+        let tracker = Arc::new(Self::build(repo, path, from)?);
+        if let Ok(mut cache) = RENAME_CACHE.lock() {
+            let entries = cache.entry(repo_key).or_default();
+            if entries.len() >= MAX_CACHED_TRACKERS {
+                entries.clear();
+            }
+            entries.insert(key, tracker.clone());
+        }
+        Ok(tracker)
+    }
+
+    fn build(repo: &Repository, path: &str, from: Oid) -> Result<RenameTracker, String> {
+        let mut revwalk = repo
+            .revwalk()
+            .map_err(|e| format!("Revwalk 생성 실패: {}", e))?;
+        revwalk
+            .push(from)
+            .map_err(|e| format!("Revwalk push 실패: {}", e))?;
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)
+            .map_err(|e| format!("정렬 설정 실패: {}", e))?;
+
+        let mut current_path = path.to_string();
+        let mut changes = Vec::new();
+
+        for oid in revwalk {
+            let oid = oid.map_err(|e| format!("OID 읽기 실패: {}", e))?;
+            let commit = repo
+                .find_commit(oid)
+                .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+            let tree = commit
+                .tree()
+                .map_err(|e| format!("트리 접근 실패: {}", e))?;
+            let parent = commit.parent(0).ok();
+            let parent_tree = match &parent {
+                Some(p) => Some(
+                    p.tree()
+                        .map_err(|e| format!("부모 트리 접근 실패: {}", e))?,
+                ),
+                None => None,
+            };
+
+            let mut opts = DiffOptions::new();
+            opts.pathspec(&current_path);
+            let diff = repo
+                .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
+                .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+            let Some(delta) = diff.deltas().next() else {
+                continue;
+            };
 
-        let tree = commit
-            .tree()
-            .map_err(|e| format!("트리 접근 실패: {}", e))?;
-
-        let parent_tree = if commit.parent_count() > 0 {
-            Some(
-                commit
-                    .parent(0)
-                    .and_then(|p| p.tree())
-                    .map_err(|e| format!("부모 트리 접근 실패: {}", e))?,
-            )
-        } else {
-            None
-        };
-
-        let mut opts = DiffOptions::new();
-        opts.pathspec(&normalized_path);
-
-        let diff = repo
-            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), Some(&mut opts))
-            .map_err(|e| format!("Diff 생성 실패: {}", e))?;
-
-        if diff.deltas().len() > 0 {
-            let delta = diff.deltas().next().unwrap();
-            let status = match delta.status() {
+            let mut status = match delta.status() {
                 git2::Delta::Added => "added",
                 git2::Delta::Deleted => "deleted",
                 git2::Delta::Modified => "modified",
                 git2::Delta::Renamed => "renamed",
                 _ => "unknown",
             };
+            let mut old_path = None;
+            if status == "added" && parent_tree.is_some() {
+                // The pathspec hides the delete side; rediff the whole tree to find a source
+                old_path = find_rename_source(repo, parent_tree.as_ref(), &tree, &current_path)?;
+                if old_path.is_some() {
+                    status = "renamed";
+                }
+            }
 
-            let old_path = if status == "renamed" {
-                delta
-                    .old_file()
-                    .path()
-                    .and_then(|p| p.to_str())
-                    .map(normalize_unicode)
-            } else {
-                None
-            };
-
-            history.push(FileHistoryEntry {
-                commit_sha: oid.to_string(),
-                message: commit.message().unwrap_or("No message").to_string(),
-                author: format!(
-                    "{} <{}>",
-                    commit.author().name().unwrap_or("Unknown"),
-                    commit.author().email().unwrap_or("unknown@example.com")
-                ),
-                date: commit.time().seconds(),
-                changes: status.to_string(),
-                old_path,
+            changes.push(PathChange {
+                oid,
+                path: normalize_unicode(&current_path),
+                status,
+                old_path: old_path.as_deref().map(normalize_unicode),
+                first_parent: parent.map(|p| p.id()),
             });
+            match old_path {
+                Some(old) => current_path = old,
+                None if status == "added" => break,
+                None => {}
+            }
+        }
+
+        Ok(RenameTracker {
+            from,
+            path: path.to_string(),
+            changes,
+        })
+    }
+
+    /// Commits that touched the file, newest first.
+    pub fn changes(&self) -> &[PathChange] {
+        &self.changes
+    }
+
+    /// Name of the file in `oid`, if that commit touched it.
+    pub fn path_in(&self, oid: Oid) -> Option<&str> {
+        self.changes
+            .iter()
+            .find(|change| change.oid == oid)
+            .map(|change| change.path.as_str())
+    }
+
+    /// Names the file had, newest first, each with the newest commit carrying it.
+    pub fn identity_chain(&self) -> Vec<PathIdentity> {
+        let mut chain = vec![PathIdentity {
+            path: normalize_unicode(&self.path),
+            commit_sha: self.from.to_string(),
+            introduced_sha: None,
+        }];
+        for change in &self.changes {
+            let Some(current) = chain.last_mut() else {
+                break;
+            };
+            match (change.status, &change.old_path) {
+                ("renamed", Some(old_path)) => {
+                    current.introduced_sha = Some(change.oid.to_string());
+                    if let Some(parent) = change.first_parent {
+                        chain.push(PathIdentity {
+                            path: old_path.clone(),
+                            commit_sha: parent.to_string(),
+                            introduced_sha: None,
+                        });
+                    }
+                }
+                ("added", _) if current.introduced_sha.is_none() => {
+                    current.introduced_sha = Some(change.oid.to_string());
+                }
+                _ => {}
+            }
         }
+        chain
     }
+}
+
+/// Old path of the file that became `new_path` between the two trees, if any.
+fn find_rename_source(
+    repo: &Repository,
+    old_tree: Option<&git2::Tree>,
+    new_tree: &git2::Tree,
+    new_path: &str,
+) -> Result<Option<String>, String> {
+    let mut diff = repo
+        .diff_tree_to_tree(old_tree, Some(new_tree), None)
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let mut find_opts = DiffFindOptions::new();
+    find_opts.renames(true);
+    diff.find_similar(Some(&mut find_opts))
+        .map_err(|e| format!("이름 변경 감지 실패: {}", e))?;
+
+    Ok(diff
+        .deltas()
+        .find(|d| {
+            d.status() == git2::Delta::Renamed
+                && d.new_file().path().and_then(|p| p.to_str()) == Some(new_path)
+        })
+        .and_then(|d| {
+            d.old_file()
+                .path()
+                .and_then(|p| p.to_str())
+                .map(str::to_string)
+        }))
+}
+
+/// Get file history (commits that modified a specific file), following renames.
+///
+/// Compatibility wrapper returning a plain list (default 100 entries, at most
+/// `MAX_PAGE_SIZE`); new callers should use `get_file_history_page`.
+#[tauri::command]
+pub fn get_file_history(
+    repo_path: String,
+    file_path: String,
+    limit: Option<usize>,
+) -> Result<Vec<FileHistoryEntry>, String> {
+    let limit = limit.unwrap_or(100).clamp(1, MAX_PAGE_SIZE);
+    file_history_page(&repo_path, &file_path, 0, limit).map(|p| p.items)
+}
+
+/// One page of `get_file_history`.
+#[tauri::command]
+pub fn get_file_history_page(
+    repo_path: String,
+    file_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<Page<FileHistoryEntry>, String> {
+    let (offset, limit) = page_window(cursor.as_deref(), limit)?;
+    file_history_page(&repo_path, &file_path, offset, limit)
+}
+
+fn file_history_page(
+    repo_path: &str,
+    file_path: &str,
+    offset: usize,
+    limit: usize,
+) -> Result<Page<FileHistoryEntry>, String> {
+    let normalized_path = normalize_unicode(file_path);
+    let repo = open_repo(repo_path)?;
+
+    let head = repo
+        .head()
+        .and_then(|h| h.peel_to_commit())
+        .map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+    let tracker = RenameTracker::for_path(&repo, &normalized_path, head.id())?;
+
+    let entries = tracker.changes().iter().map(|change| {
+        let commit = repo
+            .find_commit(change.oid)
+            .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+
+        Ok(FileHistoryEntry {
+            commit_sha: change.oid.to_string(),
+            message: commit.message().unwrap_or("No message").to_string(),
+            author: format!(
+                "{} <{}>",
+                commit.author().name().unwrap_or("Unknown"),
+                commit.author().email().unwrap_or("unknown@example.com")
+            ),
+            date: commit.time().seconds(),
+            changes: change.status.to_string(),
+            old_path: change.old_path.clone(),
+        })
+    });
+    let total = tracker.changes().len() as u64;
+    collect_page(entries, offset, limit, Some(total))
+}
+
+/// Names a file had going back from `from_sha` (default HEAD), newest first.
+#[tauri::command]
+pub fn get_path_identity_chain(
+    repo_path: String,
+    path: String,
+    from_sha: Option<String>,
+) -> Result<Vec<PathIdentity>, String> {
+    let normalized_path = normalize_unicode(&path);
+    let repo = open_repo(&repo_path)?;
 
-    Ok(history)
+    let from = match from_sha {
+        Some(sha) => Oid::from_str(&sha).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?,
+        None => repo
+            .head()
+            .and_then(|h| h.peel_to_commit())
+            .map_err(|e| format!("HEAD 접근 실패: {}", e))?
+            .id(),
+    };
+    let tracker = RenameTracker::for_path(&repo, &normalized_path, from)?;
+    Ok(tracker.identity_chain())
 }
 
 /// Get file content at specific commit.
@@ -120,3 +335,129 @@ pub fn get_file_at_commit(
 
     String::from_utf8(blob.content().to_vec()).map_err(|e| format!("UTF-8 변환 실패: {}", e))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Signature;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn commit_all(repo: &Repository, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.update_all(["*"].iter(), None).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|h| h.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parent_refs)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_file_renamed_twice() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let body: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+
+        fs::write(root.join("원본.txt"), &body).unwrap();
+        let added = commit_all(&repo, "Add file");
+        fs::write(root.join("원본.txt"), format!("{}extra\n", body)).unwrap();
+        commit_all(&repo, "Edit file");
+        fs::rename(root.join("원본.txt"), root.join("middle.txt")).unwrap();
+        let first_rename = commit_all(&repo, "First rename");
+        fs::rename(root.join("middle.txt"), root.join("final.txt")).unwrap();
+        let second_rename = commit_all(&repo, "Second rename");
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let chain =
+            get_path_identity_chain(repo_path.clone(), "final.txt".to_string(), None).unwrap();
+        let names: Vec<&str> = chain.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(names, vec!["final.txt", "middle.txt", "원본.txt"]);
+        assert_eq!(chain[0].commit_sha, second_rename.to_string());
+        assert_eq!(chain[0].introduced_sha, Some(second_rename.to_string()));
+        assert_eq!(chain[1].introduced_sha, Some(first_rename.to_string()));
+        assert_eq!(chain[2].introduced_sha, Some(added.to_string()));
+
+        // File history follows the same chain back to the original name
+        let history = get_file_history(repo_path, "final.txt".to_string(), None).unwrap();
+        let changes: Vec<&str> = history.iter().map(|h| h.changes.as_str()).collect();
+        assert_eq!(changes, vec!["renamed", "renamed", "modified", "added"]);
+        assert_eq!(history[0].old_path.as_deref(), Some("middle.txt"));
+        assert_eq!(history[1].old_path.as_deref(), Some("원본.txt"));
+        assert_eq!(history[1].old_path.as_deref(), Some(chain[2].path.as_str()));
+    }
+
+    #[test]
+    fn test_identity_chain_from_older_commit() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        let body: String = (0..20).map(|i| format!("line {}\n", i)).collect();
+
+        fs::write(root.join("a.txt"), &body).unwrap();
+        commit_all(&repo, "Add a");
+        fs::rename(root.join("a.txt"), root.join("b.txt")).unwrap();
+        let renamed = commit_all(&repo, "Rename");
+        fs::write(root.join("b.txt"), format!("{}more\n", body)).unwrap();
+        commit_all(&repo, "Edit b");
+
+        let chain = get_path_identity_chain(
+            root.to_str().unwrap().to_string(),
+            "b.txt".to_string(),
+            Some(renamed.to_string()),
+        )
+        .unwrap();
+        let names: Vec<&str> = chain.iter().map(|c| c.path.as_str()).collect();
+        assert_eq!(names, vec!["b.txt", "a.txt"]);
+        assert_eq!(chain[0].commit_sha, renamed.to_string());
+    }
+
+    #[test]
+    fn test_file_history_limit_is_clamped() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+        for i in 0..MAX_PAGE_SIZE + 5 {
+            fs::write(root.join("a.txt"), format!("{}\n", i)).unwrap();
+            commit_all(&repo, "Edit a");
+        }
+
+        let repo_path = root.to_str().unwrap().to_string();
+        let history = get_file_history(repo_path, "a.txt".to_string(), Some(usize::MAX)).unwrap();
+        assert_eq!(history.len(), MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_tracker_stops_at_file_origin() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        let repo = Repository::init(root).unwrap();
+
+        fs::write(root.join("keep.txt"), "keep\n").unwrap();
+        fs::write(root.join("a.txt"), "first life\n").unwrap();
+        commit_all(&repo, "Add a");
+        fs::remove_file(root.join("a.txt")).unwrap();
+        commit_all(&repo, "Remove a");
+        fs::write(root.join("a.txt"), "unrelated\n").unwrap();
+        let recreated = commit_all(&repo, "Recreate a");
+        fs::write(root.join("a.txt"), "unrelated, edited\n").unwrap();
+        let edited = commit_all(&repo, "Edit a");
+
+        // The walk ends at the re-creation instead of scanning older history
+        let tracker = RenameTracker::for_path(&repo, "a.txt", edited).unwrap();
+        let oids: Vec<Oid> = tracker.changes().iter().map(|c| c.oid).collect();
+        assert_eq!(oids, vec![edited, recreated]);
+        assert_eq!(tracker.changes()[1].status, "added");
+    }
+}
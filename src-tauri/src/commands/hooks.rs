@@ -0,0 +1,334 @@
+//! Client-side commit hooks for commits made through the app.
+//!
+//! `create_commit` and `amend_commit` run `pre-commit` and `commit-msg` the
+//! way `git commit` does: from the working tree root, with `GIT_INDEX_FILE`
+//! pointing at the index being committed, looked up in `core.hooksPath` or
+//! the repository's `hooks` directory. A hook that exits non-zero aborts the
+//! commit with its output in a `hook_failed` error.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use git2::{Index, Repository};
+
+use super::cli::{git_cli, run_with_timeout, sanitized_command, GIT_TIMEOUT};
+use super::utils::command_error;
+
+/// Directories a GUI launch on macOS often lacks, added to the hooks' PATH so
+/// hooks can find Homebrew / system tools like they do from a terminal.
+#[cfg(not(windows))]
+const EXTRA_PATH_DIRS: &[&str] = &[
+    "/opt/homebrew/bin",
+    "/usr/local/bin",
+    "/usr/bin",
+    "/bin",
+    "/usr/sbin",
+    "/sbin",
+];
+#[cfg(windows)]
+const EXTRA_PATH_DIRS: &[&str] = &[];
+
+/// Run `pre-commit` against the staged content of `index`, then reload
+/// `index` so files the hook restaged are committed.
+///
+/// With `unsaved`, `index` holds changes not written to disk (amend with
+/// `include_worktree`); the hook then sees them through a temporary index
+/// file, like `git commit -a`, and the on-disk index is left alone.
+pub fn run_pre_commit(repo: &Repository, index: &mut Index, unsaved: bool) -> Result<(), String> {
+    let Some(hook) = find_hook(repo, "pre-commit")? else {
+        return Ok(());
+    };
+    if !unsaved {
+        run_hook(repo, "pre-commit", &hook, &[], &repo.path().join("index"))?;
+        return index
+            .read(true)
+            .map_err(|e| format!("인덱스 다시 읽기 실패: {}", e));
+    }
+
+    let temp_path = repo.path().join("gitmul-hook-index");
+    let result = (|| {
+        let mut temp =
+            Index::open(&temp_path).map_err(|e| format!("임시 인덱스 생성 실패: {}", e))?;
+        copy_entries(index, &mut temp)?;
+        temp.write()
+            .map_err(|e| format!("임시 인덱스 쓰기 실패: {}", e))?;
+        run_hook(repo, "pre-commit", &hook, &[], &temp_path)?;
+        temp.read(true)
+            .map_err(|e| format!("임시 인덱스 읽기 실패: {}", e))?;
+        index
+            .clear()
+            .map_err(|e| format!("인덱스 갱신 실패: {}", e))?;
+        copy_entries(&temp, index)
+    })();
+    let _ = std::fs::remove_file(&temp_path);
+    result
+}
+
+/// Run `commit-msg` on `message` and return the message it leaves behind.
+/// The message goes through `COMMIT_EDITMSG` in the git directory, as with
+/// `git commit`.
+pub fn run_commit_msg(repo: &Repository, message: &str) -> Result<String, String> {
+    let Some(hook) = find_hook(repo, "commit-msg")? else {
+        return Ok(message.to_string());
+    };
+    let message_file = repo.path().join("COMMIT_EDITMSG");
+    std::fs::write(&message_file, message)
+        .map_err(|e| format!("커밋 메시지 파일 쓰기 실패: {}", e))?;
+    run_hook(
+        repo,
+        "commit-msg",
+        &hook,
+        &[message_file.as_os_str()],
+        &repo.path().join("index"),
+    )?;
+    std::fs::read_to_string(&message_file).map_err(|e| format!("커밋 메시지 파일 읽기 실패: {}", e))
+}
+
+/// Hooks directory: `core.hooksPath` (relative to the working tree root, `~`
+/// expanded) or `hooks` in the common git directory.
+fn hooks_dir(repo: &Repository) -> Result<PathBuf, String> {
+    let config = repo
+        .config()
+        .map_err(|e| format!("설정 읽기 실패: {}", e))?;
+    match config.get_path("core.hooksPath") {
+        Ok(path) if path.is_relative() => {
+            let base = repo.workdir().unwrap_or_else(|| repo.path());
+            Ok(base.join(path))
+        }
+        Ok(path) => Ok(path),
+        Err(_) => Ok(common_dir(repo).join("hooks")),
+    }
+}
+
+/// The main repository's git directory; a linked worktree's git directory
+/// names it in its `commondir` file.
+fn common_dir(repo: &Repository) -> PathBuf {
+    let git_dir = repo.path();
+    match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => git_dir.join(common.trim()),
+        Err(_) => git_dir.to_path_buf(),
+    }
+}
+
+/// The hook named `name` if it exists and is executable.
+fn find_hook(repo: &Repository, name: &str) -> Result<Option<PathBuf>, String> {
+    let hook = hooks_dir(repo)?.join(name);
+    Ok(Some(hook).filter(|hook| is_executable(hook)))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+fn run_hook(
+    repo: &Repository,
+    name: &str,
+    hook: &Path,
+    args: &[&std::ffi::OsStr],
+    index_file: &Path,
+) -> Result<(), String> {
+    // Hooks are shell scripts; Windows has no shebang handling
+    let mut cmd = if cfg!(windows) {
+        let mut cmd = sanitized_command(Path::new("sh"));
+        cmd.arg(hook);
+        cmd
+    } else {
+        sanitized_command(hook)
+    };
+    cmd.args(args)
+        .current_dir(repo.workdir().unwrap_or_else(|| repo.path()))
+        .env("PATH", hook_path_var())
+        .env("GIT_INDEX_FILE", index_file)
+        .env("GIT_EDITOR", ":");
+
+    let output = run_with_timeout(&mut cmd, GIT_TIMEOUT)?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let exit_code = output.status.code();
+    let combined = [stdout.as_str(), stderr.as_str()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n");
+    Err(command_error(
+        "hook_failed",
+        format!("'{}' 훅이 커밋을 중단했습니다:\n{}", name, combined),
+        serde_json::json!({
+            "hook": name,
+            "exit_code": exit_code,
+            "stdout": stdout,
+            "stderr": stderr,
+        }),
+    ))
+}
+
+/// PATH for hooks: the inherited one, the directory of the git executable in
+/// use, then `EXTRA_PATH_DIRS` not already present.
+fn hook_path_var() -> OsString {
+    let mut dirs: Vec<PathBuf> = std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default();
+    let git_dir = git_cli()
+        .ok()
+        .and_then(|info| Path::new(&info.path).parent().map(Path::to_path_buf));
+    let extra = git_dir
+        .into_iter()
+        .chain(EXTRA_PATH_DIRS.iter().map(PathBuf::from));
+    for dir in extra {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    std::env::join_paths(dirs).unwrap_or_default()
+}
+
+fn copy_entries(from: &Index, to: &mut Index) -> Result<(), String> {
+    for entry in from.iter() {
+        to.add(&entry)
+            .map_err(|e| format!("인덱스 갱신 실패: {}", e))?;
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::commands::amend::amend_commit;
+    use crate::commands::git::create_commit;
+    use crate::commands::models::CommandError;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, repo_path)
+    }
+
+    fn write_hook(dir: &Path, name: &str, script: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        let path = dir.join(name);
+        std::fs::write(&path, script).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn stage(repo_path: &str, name: &str, content: &str) {
+        let repo = Repository::open(repo_path).unwrap();
+        std::fs::write(Path::new(repo_path).join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failing_pre_commit_aborts_commit() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let hooks = Path::new(&repo_path).join(".git/hooks");
+        write_hook(
+            &hooks,
+            "pre-commit",
+            "#!/bin/sh\necho \"린트 실패: $GIT_INDEX_FILE\" >&2\nexit 1\n",
+        );
+        stage(&repo_path, "a.txt", "a\n");
+
+        let err = create_commit(
+            repo_path.clone(),
+            "첫 커밋".into(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "hook_failed");
+        assert_eq!(err.details["hook"], "pre-commit");
+        assert_eq!(err.details["exit_code"], 1);
+        assert!(err.message.contains("린트 실패"));
+        assert!(err.details["stderr"]
+            .as_str()
+            .unwrap()
+            .ends_with(".git/index"));
+        let repo = Repository::open(&repo_path).unwrap();
+        assert!(repo.head().is_err());
+
+        // The escape hatch commits anyway
+        create_commit(
+            repo_path,
+            "첫 커밋".into(),
+            None,
+            None,
+            None,
+            None,
+            Some(true),
+        )
+        .await
+        .unwrap();
+        assert!(repo.head().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_commit_msg_rewrites_message_from_hooks_path() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", "githooks")
+            .unwrap();
+        let hooks = Path::new(&repo_path).join("githooks");
+        write_hook(
+            &hooks,
+            "commit-msg",
+            "#!/bin/sh\nprintf '\\n\\nReviewed-by: 검토자\\n' >> \"$1\"\n",
+        );
+        // Not executable: ignored
+        std::fs::write(hooks.join("pre-commit"), "#!/bin/sh\nexit 1\n").unwrap();
+        stage(&repo_path, "a.txt", "a\n");
+
+        create_commit(
+            repo_path.clone(),
+            "기능 추가".into(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("기능 추가\n\nReviewed-by: 검토자\n"));
+
+        // Amend runs the hooks too, and sees auto-staged changes
+        write_hook(
+            &hooks,
+            "pre-commit",
+            "#!/bin/sh\ngit diff --cached --name-only > staged.txt\n",
+        );
+        std::fs::write(Path::new(&repo_path).join("a.txt"), "b\n").unwrap();
+        amend_commit(repo_path.clone(), "고친 기능".into(), Some(true), None).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.message(), Some("고친 기능\n\nReviewed-by: 검토자\n"));
+        let staged = std::fs::read_to_string(Path::new(&repo_path).join("staged.txt")).unwrap();
+        assert_eq!(staged, "a.txt\n");
+    }
+}
@@ -1,6 +1,8 @@
-use git2::BranchType;
+use git2::{BranchType, Commit, Repository};
 
-use super::utils::open_repo;
+use super::models::MergeResult;
+use super::signing::{commit_to_head, CreatedCommit};
+use super::utils::{conflicted_paths, open_repo, repo_signature};
 
 /// Merge a branch into the current branch.
 #[tauri::command]
@@ -8,7 +10,7 @@ pub fn merge_branch(
     repo_path: String,
     source_branch: String,
     no_fast_forward: bool,
-) -> Result<String, String> {
+) -> Result<MergeResult, String> {
     let repo = open_repo(&repo_path)?;
 
     let source = repo
@@ -38,63 +40,103 @@ pub fn merge_branch(
         repo.checkout_head(None)
             .map_err(|e| format!("체크아웃 실패: {}", e))?;
 
-        Ok("Fast-forward 병합 완료".to_string())
+        Ok(merge_result("Fast-forward 병합 완료"))
     } else if analysis.is_normal() {
-        let mut merge_options = git2::MergeOptions::new();
-        let mut checkout_options = git2::build::CheckoutBuilder::new();
+        let message = format!("Merge branch '{}'", source_branch);
+        match merge_into_head(&repo, &source_commit, &message)? {
+            MergeOutcome::Committed(created) => Ok(MergeResult {
+                message: "병합 완료".to_string(),
+                commit_sha: Some(created.oid.to_string()),
+                signed: created.signature_type.is_some(),
+                signature_type: created.signature_type,
+            }),
+            MergeOutcome::Conflicts(_) => Ok(merge_result("병합 완료 (충돌 발생)")),
+        }
+    } else if analysis.is_up_to_date() {
+        Ok(merge_result("이미 최신 상태입니다"))
+    } else {
+        Err("병합 불가: 처리할 수 없는 병합 상태입니다".to_string())
+    }
+}
 
-        repo.merge(
-            &[&annotated_commit],
-            Some(&mut merge_options),
-            Some(&mut checkout_options),
-        )
-        .map_err(|e| format!("병합 실패: {}", e))?;
+/// How a merge into HEAD ended.
+pub(super) enum MergeOutcome {
+    /// The merge commit
+    Committed(CreatedCommit),
+    /// Paths left conflicted
+    Conflicts(Vec<String>),
+}
 
-        let mut index = repo
-            .index()
-            .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+/// Merge `source_commit` into HEAD and commit the result as `message`.
+///
+/// On conflicts nothing is committed: the merge state (MERGE_HEAD, MERGE_MSG
+/// holding `message`, the conflicted index and working tree) stays behind for
+/// `get_conflicts`, `resolve_conflict` and `abort_merge`.
+pub(super) fn merge_into_head(
+    repo: &Repository,
+    source_commit: &Commit,
+    message: &str,
+) -> Result<MergeOutcome, String> {
+    let annotated_commit = repo
+        .find_annotated_commit(source_commit.id())
+        .map_err(|e| format!("Annotated 커밋 생성 실패: {}", e))?;
+    let mut merge_options = git2::MergeOptions::new();
+    let mut checkout_options = git2::build::CheckoutBuilder::new();
 
-        if index.has_conflicts() {
-            return Ok("병합 완료 (충돌 발생)".to_string());
-        }
+    repo.merge(
+        &[&annotated_commit],
+        Some(&mut merge_options),
+        Some(&mut checkout_options),
+    )
+    .map_err(|e| format!("병합 실패: {}", e))?;
 
-        // Create merge commit
-        let sig = repo
-            .signature()
-            .map_err(|e| format!("서명 생성 실패: {}", e))?;
-        let tree_id = index
-            .write_tree()
-            .map_err(|e| format!("트리 쓰기 실패: {}", e))?;
-        let tree = repo
-            .find_tree(tree_id)
-            .map_err(|e| format!("트리 찾기 실패: {}", e))?;
-        let head = repo
-            .head()
-            .map_err(|e| format!("HEAD 접근 실패: {}", e))?;
-        let head_commit = head
-            .peel_to_commit()
-            .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?;
-
-        let message = format!("Merge branch '{}'", source_branch);
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
 
-        repo.commit(
-            Some("HEAD"),
-            &sig,
-            &sig,
-            &message,
-            &tree,
-            &[&head_commit, &source_commit],
-        )
-        .map_err(|e| format!("병합 커밋 생성 실패: {}", e))?;
+    if index.has_conflicts() {
+        std::fs::write(repo.path().join("MERGE_MSG"), format!("{}\n", message))
+            .map_err(|e| format!("병합 메시지 쓰기 실패: {}", e))?;
+        return Ok(MergeOutcome::Conflicts(conflicted_paths(&index)));
+    }
 
-        repo.cleanup_state()
-            .map_err(|e| format!("상태 정리 실패: {}", e))?;
+    // Create merge commit
+    let sig = repo_signature(repo)?;
+    let tree_id = index
+        .write_tree()
+        .map_err(|e| format!("트리 쓰기 실패: {}", e))?;
+    let tree = repo
+        .find_tree(tree_id)
+        .map_err(|e| format!("트리 찾기 실패: {}", e))?;
+    let head_commit = repo
+        .head()
+        .map_err(|e| format!("HEAD 접근 실패: {}", e))?
+        .peel_to_commit()
+        .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?;
+
+    let created = commit_to_head(
+        repo,
+        &sig,
+        &sig,
+        message,
+        &tree,
+        &[&head_commit, source_commit],
+        "commit (merge)",
+    )
+    .map_err(|e| format!("병합 커밋 생성 실패: {}", e))?;
+
+    repo.cleanup_state()
+        .map_err(|e| format!("상태 정리 실패: {}", e))?;
+    Ok(MergeOutcome::Committed(created))
+}
 
-        Ok("병합 완료".to_string())
-    } else if analysis.is_up_to_date() {
-        Ok("이미 최신 상태입니다".to_string())
-    } else {
-        Err("병합 불가: 처리할 수 없는 병합 상태입니다".to_string())
+/// Result of a merge that created no commit.
+fn merge_result(message: &str) -> MergeResult {
+    MergeResult {
+        message: message.to_string(),
+        commit_sha: None,
+        signed: false,
+        signature_type: None,
     }
 }
 
@@ -233,6 +275,6 @@ mod tests {
             false,
         );
         assert!(result.is_ok());
-        assert!(result.unwrap().contains("최신"));
+        assert!(result.unwrap().message.contains("최신"));
     }
 }
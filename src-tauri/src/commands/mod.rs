@@ -1,12 +1,24 @@
 // Shared infrastructure
 pub mod models;
 pub mod utils;
+pub mod app_data;
+pub mod relative_time;
+pub mod cli;
+pub mod credentials;
 
 // Command modules
 pub mod git;
 pub mod repos;
 pub mod branch;
 pub mod diff;
+pub mod diff_stream;
+pub mod commit_details;
+pub mod signing;
+pub mod hooks;
+pub mod commit_lint;
+pub mod staging;
+pub mod gitignore;
+pub mod watcher;
 pub mod remote;
 pub mod amend;
 pub mod stash;
@@ -15,10 +27,17 @@ pub mod conflict;
 pub mod cherrypick;
 pub mod revert;
 pub mod tags;
+pub mod submodule;
 pub mod history;
+pub mod blame;
+pub mod graph;
+pub mod export;
+pub mod patch;
 pub mod rebase;
 pub mod reflog;
+pub mod reset;
 pub mod bundle;
 pub mod clone;
 pub mod shell;
 pub mod config;
+pub mod presets;
@@ -5,6 +5,42 @@
 
 use serde::{Deserialize, Serialize};
 
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Machine-readable command error.
+///
+/// Commands return `Result<T, String>`; when the UI has to react to a specific
+/// failure (confirmation dialog, conflict view, ...) the `Err` string carries
+/// this struct serialized as JSON. Build it with `utils::command_error`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommandError {
+    /// Stable error code, e.g. "branch_not_merged"
+    pub code: String,
+    /// Human-readable message
+    pub message: String,
+    /// Code-specific payload
+    pub details: serde_json::Value,
+}
+
+// ============================================================================
+// Pagination
+// ============================================================================
+
+/// One page of a list command (`*_page` commands).
+///
+/// Pass `next_cursor` back as `cursor` to get the following page; it is opaque
+/// to the frontend. Build pages with `utils::collect_page`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Size of the whole list when known cheaply (`None` for history walks)
+    pub total: Option<u64>,
+    pub has_more: bool,
+    pub next_cursor: Option<String>,
+}
+
 // ============================================================================
 // Repository & Core
 // ============================================================================
@@ -16,6 +52,39 @@ pub struct RepositoryInfo {
     pub name: String,
     pub current_branch: String,
     pub remote_url: Option<String>,
+    /// HEAD is unborn: no commit on `current_branch` yet
+    pub is_empty: bool,
+    /// History is cut off by a shallow clone or fetch (see `unshallow`)
+    pub is_shallow: bool,
+    /// HEAD points at a commit rather than a branch; `current_branch` is
+    /// then "HEAD detached at <short sha>"
+    pub is_detached: bool,
+    /// Commit HEAD points at (empty when `is_empty`)
+    pub head_sha: String,
+    /// Divergence of the current branch from its upstream
+    #[serde(flatten)]
+    pub sync: BranchSyncStatus,
+    /// Merge, rebase, ... left in progress, so the app can reopen its screen
+    pub state: OperationState,
+}
+
+/// Operation in progress in a repository, e.g. merging `source` into
+/// `target`.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OperationState {
+    /// "clean", "merge", "rebase", "rebase_interactive", "rebase_merge",
+    /// "apply_mailbox", "apply_mailbox_or_rebase", "cherry_pick",
+    /// "cherry_pick_sequence", "revert", "revert_sequence" or "bisect"
+    pub state: String,
+    /// What is brought in: the merged / picked / reverted commit, or the
+    /// branch being rebased. A branch name when one points at the commit,
+    /// its sha otherwise
+    pub source: Option<String>,
+    /// Where it goes: the current branch, or the commit a rebase is onto
+    pub target: Option<String>,
+    /// Rebase progress: the step being applied (1-based) out of `total_steps`
+    pub step: Option<usize>,
+    pub total_steps: Option<usize>,
 }
 
 /// A single commit from the repository history.
@@ -28,14 +97,81 @@ pub struct CommitInfo {
     pub timestamp: i64,
     pub date: String,
     pub parent_ids: Vec<String>,
+    /// "3일 전" style date, only when requested via `with_relative_dates`
+    pub relative_date: Option<String>,
+}
+
+/// A page of `get_commit_history_filtered` matches.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FilteredHistoryPage {
+    pub items: Vec<CommitInfo>,
+    pub has_more: bool,
+    pub next_cursor: Option<String>,
+    /// Commits walked for this page, matching or not
+    pub scanned: usize,
+}
+
+/// A `search_commits_by_content` match.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ContentSearchHit {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    /// First file (in diff order) whose added or removed lines contain the needle
+    pub file_path: String,
 }
 
 /// Working tree / index file status.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct FileStatus {
     pub path: String,
+    /// "added" | "modified" | "deleted" | "renamed" | "untracked" |
+    /// "conflicted" | "submodule" | ...
     pub status: String,
     pub staged: bool,
+    /// Previous path of a "renamed" entry
+    pub old_path: Option<String>,
+}
+
+/// `get_repository_status_report` result.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepositoryStatusReport {
+    pub files: Vec<FileStatus>,
+    /// Time spent scanning the working tree
+    pub elapsed_ms: u64,
+    /// The full scan was slow enough that skipping or collapsing untracked
+    /// files is worth offering
+    pub suggest_fast_mode: bool,
+}
+
+/// `repo-status-changed` event: something in a watched repository changed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepoStatusChangedEvent {
+    pub repo_path: String,
+    /// "workdir" | "index" | "refs" (the most significant change in the batch)
+    pub reason: String,
+}
+
+/// Result of creating a commit.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommitResult {
+    pub commit_sha: String,
+    pub message: String,
+    /// Signed per `commit.gpgsign` / `gpg.format=ssh`
+    pub signed: bool,
+    /// "ssh" when signed
+    pub signature_type: Option<String>,
+}
+
+/// Result of merging a branch.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeResult {
+    pub message: String,
+    /// The merge commit, when one was created
+    pub commit_sha: Option<String>,
+    /// Signed per `commit.gpgsign` / `gpg.format=ssh`
+    pub signed: bool,
+    /// "ssh" when signed
+    pub signature_type: Option<String>,
 }
 
 /// Recently opened repository entry (persisted to disk).
@@ -56,10 +192,167 @@ pub struct BranchInfo {
     pub name: String,
     pub is_current: bool,
     pub is_remote: bool,
+    /// Remote a remote-tracking branch belongs to ("origin"); None for local ones
+    pub remote_name: Option<String>,
     pub commit_sha: String,
     pub commit_message: String,
     pub author: String,
     pub timestamp: i64,
+    /// "3일 전" style date, only when requested via `with_relative_dates`
+    pub relative_date: Option<String>,
+    /// Configured upstream of a local branch ("origin/main"), kept even when
+    /// it is gone
+    pub upstream: Option<String>,
+    /// Commits not on the upstream / only on the upstream; only computed
+    /// when requested via `include_tracking`, 0 otherwise
+    pub ahead: u32,
+    pub behind: u32,
+    /// Local branch whose configured upstream no longer exists (e.g. pruned)
+    pub upstream_gone: bool,
+    /// Working directory of another worktree that has this branch checked out
+    pub checked_out_in: Option<String>,
+    /// Tip is HEAD or an ancestor of it; only computed when requested via
+    /// `include_merge_status`, false otherwise
+    pub is_merged: bool,
+    /// `branch.<name>.description` of a local branch, when it has one
+    pub description: Option<String>,
+}
+
+/// How the current branch relates to its upstream.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BranchSyncStatus {
+    /// Configured upstream ("origin/main"), kept even when it is gone
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    /// The upstream is configured and its ref exists
+    pub has_upstream: bool,
+    /// Configured, but the remote-tracking ref was deleted (e.g. pruned)
+    pub upstream_gone: bool,
+    /// HEAD is detached, so there is no current branch to track anything
+    pub detached: bool,
+}
+
+/// Where HEAD points after a checkout-like operation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeadState {
+    /// Branch name, `None` when detached
+    pub name: Option<String>,
+    pub detached: bool,
+    pub sha: String,
+}
+
+/// Outcome of `switch_branch`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchSwitchResult {
+    pub head: HeadState,
+    /// Stash (index in `stash_list`) holding the changes set aside with
+    /// `stash_changes`; None when nothing was stashed
+    pub stash_index: Option<usize>,
+}
+
+/// Commits and file changes of `head_branch` relative to `base_branch`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchComparison {
+    pub base_branch: String,
+    pub head_branch: String,
+    pub merge_base: String,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Commits on head but not on base (most recent first)
+    pub commits: Vec<CommitInfo>,
+    /// File-level diff from the base tip to the head tip
+    pub files: Vec<DiffStat>,
+}
+
+/// Commits unique to each of two branches, for comparing them before a
+/// merge or rebase.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchDivergence {
+    pub branch_a: String,
+    pub branch_b: String,
+    pub merge_base: String,
+    /// Commits on A but not on B (most recent first)
+    pub only_in_a: Vec<CommitInfo>,
+    /// More commits are only on A than `only_in_a` holds
+    pub only_in_a_truncated: bool,
+    /// Commits on B but not on A (most recent first)
+    pub only_in_b: Vec<CommitInfo>,
+    pub only_in_b_truncated: bool,
+}
+
+/// Whether a branch tip is preserved by some other ref.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchBackupInfo {
+    pub branch: String,
+    pub commit_sha: String,
+    pub is_backed_up: bool,
+    /// Full ref names (remote-tracking, tags, other local branches) containing the tip
+    pub containing_refs: Vec<String>,
+}
+
+/// A local branch's upstream as configured by `set_upstream`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchTracking {
+    pub branch: String,
+    /// "origin/main"; None when the branch tracks nothing
+    pub upstream: Option<String>,
+    /// `branch.<name>.remote`, e.g. "origin"
+    pub remote: Option<String>,
+    /// `branch.<name>.merge`, e.g. "refs/heads/main"
+    pub merge: Option<String>,
+}
+
+/// A branch removed by `delete_branch`; recreating `name` at `tip_sha`
+/// undoes the delete.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DeletedBranch {
+    pub name: String,
+    pub tip_sha: String,
+}
+
+/// Result of `archive_branch`. The remote steps are reported separately:
+/// the tag can reach the remote while deleting the branch there fails.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArchivedBranch {
+    pub branch: String,
+    /// `archive/<branch>`
+    pub tag_name: String,
+    pub tip_sha: String,
+    /// Remote of the upstream, when remote steps were attempted
+    pub remote: Option<String>,
+    pub tag_pushed: bool,
+    pub tag_push_error: Option<String>,
+    pub remote_branch_deleted: bool,
+    pub remote_delete_error: Option<String>,
+}
+
+/// Result of `rename_branch`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RenamedBranch {
+    pub old_name: String,
+    pub new_name: String,
+    /// Upstream carried over from the old name ("origin/old")
+    pub upstream: Option<String>,
+    /// The upstream exists and is still named after the old branch name
+    pub pushed_under_old_name: bool,
+}
+
+/// Outcome for one branch in `prune_gone_branches`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GoneBranchResult {
+    pub name: String,
+    pub tip_sha: String,
+    /// The upstream that no longer exists, e.g. "origin/feature"
+    pub upstream: Option<String>,
+    /// False on a dry run or when deleting failed
+    pub deleted: bool,
+    /// Why the branch was kept; may be a structured `branch_not_merged` error
+    pub error: Option<String>,
+    /// The tip is reachable from another ref, as in `is_branch_backed_up`
+    pub backed_up: bool,
+    /// Full ref names containing the tip, checked before deleting
+    pub containing_refs: Vec<String>,
 }
 
 // ============================================================================
@@ -74,6 +367,19 @@ pub struct DiffLine {
     pub old_line_no: Option<u32>,
     pub new_line_no: Option<u32>,
     pub content: String,
+    /// Intra-line changes for a paired deletion/addition; `None` for context
+    /// lines and for pairs that changed almost entirely
+    pub segments: Option<Vec<DiffSegment>>,
+    /// Set when the line is followed by "\ No newline at end of file"
+    #[serde(default)]
+    pub no_newline_at_eof: bool,
+}
+
+/// A run of text within a changed line, marked if it differs from the paired line.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DiffSegment {
+    pub text: String,
+    pub changed: bool,
 }
 
 /// A diff hunk containing one or more lines.
@@ -87,6 +393,33 @@ pub struct DiffHunk {
     pub lines: Vec<DiffLine>,
 }
 
+/// Patch text of `get_file_diff`, cut at a hunk boundary once it exceeds the
+/// requested `max_lines` / `max_bytes`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileDiff {
+    pub patch: String,
+    pub truncated: bool,
+    /// Lines of the full patch
+    pub total_lines: usize,
+    /// Lines in `patch`
+    pub returned_lines: usize,
+}
+
+/// Contents of a file as returned by `get_file_content`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileContent {
+    pub is_binary: bool,
+    /// Decoded text (None for binary files)
+    pub text: Option<String>,
+    /// Base64 of binary files up to the size cap (None for text or larger files)
+    pub base64: Option<String>,
+    /// Size in bytes
+    pub size: u64,
+    /// "utf-8", "utf-8-bom", "utf-16le", "utf-16be", "unknown" (lossy
+    /// non-UTF-8 text) or "binary"
+    pub encoding_guess: String,
+}
+
 /// Fully parsed diff for a single file.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ParsedDiff {
@@ -94,9 +427,20 @@ pub struct ParsedDiff {
     pub old_path: String,
     pub new_path: String,
     pub is_binary: bool,
+    /// File modes from "old mode"/"new mode" or "new/deleted file mode" headers,
+    /// as numbers (0o100644 is serialized as 33188)
+    pub old_mode: Option<u32>,
+    pub new_mode: Option<u32>,
+    /// Only the mode changed (e.g. 100644 → 100755); `hunks` is empty
+    pub mode_only: bool,
     pub hunks: Vec<DiffHunk>,
     pub additions: u32,
     pub deletions: u32,
+    /// Binary files only: sizes in bytes (`None` for a missing side) and
+    /// `new_size - old_size`. Not known when parsed from patch text.
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+    pub size_delta: Option<i64>,
 }
 
 /// Per-file diff statistics.
@@ -106,18 +450,115 @@ pub struct DiffStat {
     pub additions: u32,
     pub deletions: u32,
     pub is_binary: bool,
+    /// Binary files only: sizes in bytes (`None` for a missing side) and
+    /// `new_size - old_size`
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+    pub size_delta: Option<i64>,
+}
+
+/// A submodule pointer change: the gitlink commit on each side of the diff
+/// (`None` when the submodule is added or removed).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmoduleChange {
+    pub path: String,
+    pub staged: bool,
+    pub old_sha: Option<String>,
+    pub new_sha: Option<String>,
+}
+
+/// A submodule of the repository as listed by `list_submodules`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmoduleInfo {
+    pub path: String,
+    pub url: Option<String>,
+    /// Commit recorded in the superproject index
+    pub index_sha: Option<String>,
+    /// Commit checked out in the submodule, `None` until it is initialized
+    pub workdir_sha: Option<String>,
+    /// "uninitialized" | "clean" | "modified" | "ahead"
+    pub status: String,
+}
+
+/// Outcome of updating one submodule in `update_submodule` /
+/// `init_all_submodules`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SubmoduleUpdateResult {
+    pub path: String,
+    pub success: bool,
+    pub error: Option<String>,
+    /// Commit checked out after the update
+    pub workdir_sha: Option<String>,
+}
+
+/// One `diff-chunk` event of a streamed commit diff. Concatenating the
+/// `patch` of every chunk in `sequence` order gives the `get_commit_diff`
+/// text.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffChunk {
+    pub stream_id: String,
+    /// 0-based, increasing by one per chunk
+    pub sequence: usize,
+    /// File the fragment belongs to (a large file spans several chunks)
+    pub file_path: String,
+    pub patch: String,
+}
+
+/// Final `diff-complete` event of a streamed commit diff.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffStreamComplete {
+    pub stream_id: String,
+    pub chunk_count: usize,
+    pub file_count: usize,
+    pub cancelled: bool,
+    /// Set when the diff could not be produced; earlier chunks may still
+    /// have been sent
+    pub error: Option<String>,
+}
+
+/// Three-dot diff: what `head` changed since it forked from `base`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeBaseDiff {
+    pub merge_base: String,
+    pub patch: String,
+    pub files: Vec<DiffStat>,
+}
+
+/// How `get_merge_commit_diff` compares a merge commit with its parents.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeDiffMode {
+    /// Full diff against every parent
+    PerParent,
+    /// Only files that differ from all parents (hand-resolved or evil changes)
+    Combined,
+}
+
+/// Diff of a merge commit against one of its parents.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeParentDiff {
+    /// 0 for the first parent (the branch merged into)
+    pub parent_index: usize,
+    pub parent_sha: String,
+    pub patch: String,
+    pub files: Vec<String>,
 }
 
 /// A single changed file in a commit with status and diff statistics.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct CommitFileChange {
     pub path: String,
-    /// "added", "modified", "deleted", "renamed"
+    /// "added", "modified", "deleted", "renamed", "copied"
     pub status: String,
     pub additions: u32,
     pub deletions: u32,
     pub is_binary: bool,
+    /// Previous path for renames and copies
     pub old_path: Option<String>,
+    /// Binary files only, as in `DiffStat`
+    pub old_size: Option<u64>,
+    pub new_size: Option<u64>,
+    pub size_delta: Option<i64>,
 }
 
 // ============================================================================
@@ -139,6 +580,12 @@ pub struct ImageData {
     pub height: u32,
     /// File format (e.g., "PNG", "JPEG", "GIF", "SVG", "WebP")
     pub format: String,
+    /// Width of the full image (differs from `width` for thumbnails)
+    pub original_width: u32,
+    /// Height of the full image (differs from `height` for thumbnails)
+    pub original_height: u32,
+    /// Size of the full image in bytes (differs from `size` for thumbnails)
+    pub original_size: u64,
 }
 
 /// Result of comparing two image versions.
@@ -152,6 +599,13 @@ pub struct ImageDiffResult {
     pub is_image: bool,
     /// File path
     pub file_path: String,
+    /// Percentage (0-100) of pixels that differ, when both versions decode
+    /// to the same dimensions
+    pub diff_percentage: Option<f32>,
+    /// PNG mask marking the differing pixels (None when nothing differs)
+    pub diff_mask: Option<ImageData>,
+    /// Whether the two versions have different dimensions
+    pub dimensions_changed: bool,
 }
 
 // ============================================================================
@@ -164,7 +618,10 @@ pub struct RemoteInfo {
     pub name: String,
     pub url: String,
     pub fetch_url: String,
+    /// Where pushes go: `pushurl` when set, else `url`
     pub push_url: String,
+    /// `remote.<name>.pushurl` is set
+    pub separate_push_url: bool,
 }
 
 /// A branch on a remote.
@@ -177,10 +634,21 @@ pub struct RemoteBranchInfo {
     pub is_head: bool,
 }
 
-/// Progress of a sync operation (fetch / pull / push).
+/// Local branches left tracking remote refs that were just deleted or pruned.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrphanedTrackingReport {
+    /// Remote-tracking refs that were removed, e.g. `refs/remotes/origin/feature`
+    pub removed_refs: Vec<String>,
+    /// Local branches whose upstream was one of `removed_refs`
+    pub orphaned_branches: Vec<String>,
+    /// Whether `branch.<name>.remote/merge` was cleared for `orphaned_branches`
+    pub tracking_config_removed: bool,
+}
+
+/// Progress of a sync operation (fetch / pull / push / submodule update).
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SyncProgress {
-    /// "idle", "fetching", "pulling", "pushing"
+    /// "idle", "fetching", "pulling", "pushing", "updating_submodules"
     pub phase: String,
     pub current: u32,
     pub total: u32,
@@ -188,6 +656,102 @@ pub struct SyncProgress {
     pub message: String,
 }
 
+/// Payload of `sync-progress` events.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncProgressEvent {
+    pub repo_path: String,
+    /// Increases with every fetch, pull or push started with a window
+    pub operation_id: u64,
+    pub progress: SyncProgress,
+}
+
+/// Payload of the `sync-complete` and `sync-error` events ending an operation.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SyncFinishedEvent {
+    pub repo_path: String,
+    pub operation_id: u64,
+    /// What the command returned (`sync-complete`)
+    pub result: Option<serde_json::Value>,
+    /// The command's error (`sync-error`)
+    pub error: Option<String>,
+}
+
+/// Result of `fetch_remote`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FetchResult {
+    pub message: String,
+    /// Remote-tracking refs deleted with `prune`, e.g. "refs/remotes/origin/old"
+    pub pruned_refs: Vec<String>,
+}
+
+/// Result of `pull_changes`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PullResult {
+    /// "fast_forward", "merged", "conflicts" or "up_to_date"
+    pub kind: String,
+    pub message: String,
+    /// The merge commit when `kind` is "merged"
+    pub commit_sha: Option<String>,
+    /// Paths left to resolve when `kind` is "conflicts"
+    pub conflicted_files: Vec<String>,
+}
+
+/// Result of `push_changes`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PushResult {
+    pub message: String,
+    /// The branch's upstream after the push, e.g. "origin/feature"; None when
+    /// it has none
+    pub upstream: Option<String>,
+    /// What the remote made of each ref sent: the branch and any tags
+    pub refs: Vec<PushRefResult>,
+}
+
+/// One ref of a push, as the remote reported it.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PushRefResult {
+    /// e.g. "refs/tags/v1.0"
+    pub refname: String,
+    pub accepted: bool,
+    /// The remote's reason when it was rejected
+    pub message: Option<String>,
+}
+
+/// Outcome for one branch of `push_branches`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BranchPushStatus {
+    pub branch: String,
+    /// "pushed", "up_to_date" or "rejected"
+    pub status: String,
+    /// Why it was rejected
+    pub message: Option<String>,
+}
+
+/// Outcome for one remote of `fetch_all_remotes`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteFetchResult {
+    pub remote: String,
+    /// Refs the fetch created, moved or pruned, e.g. "refs/remotes/origin/main"
+    pub updated_refs: Vec<String>,
+    /// Why the fetch failed; the other remotes are fetched regardless
+    pub error: Option<String>,
+}
+
+/// A credential for `retry_with_credentials`, offered for one attempt only.
+/// No `Debug`: it holds secrets.
+#[derive(Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteCredential {
+    /// HTTPS user name and password (or access token)
+    UserPass { username: String, password: String },
+    /// An SSH private key file; `username` defaults to the one in the URL
+    SshKey {
+        username: Option<String>,
+        key_path: String,
+        passphrase: Option<String>,
+    },
+}
+
 // ============================================================================
 // Conflict Resolution
 // ============================================================================
@@ -209,6 +773,73 @@ pub struct ConflictInfo {
     pub merge_msg: Option<String>,
 }
 
+/// What a conflict resolution would produce, computed without writing anything.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConflictResolutionPreview {
+    pub path: String,
+    pub resolution: String,
+    pub is_binary: bool,
+    /// Resulting text (None for binary)
+    pub content: Option<String>,
+    pub size: u64,
+    /// Blob hash of the resulting content
+    pub sha: String,
+    pub line_count: u32,
+    /// Lines added / removed relative to the current working-tree file
+    pub additions: u32,
+    pub deletions: u32,
+    /// Unified diff from the working-tree file to the result (empty for binary)
+    pub diff: String,
+}
+
+/// A safe one-click resolution for a trivially conflicted file.
+/// `resolution`/`content` are ready to pass to `resolve_conflict`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConflictSuggestion {
+    pub path: String,
+    /// How the clean result was found: "ignore_whitespace" or "union"
+    pub strategy: String,
+    /// "ours", "theirs", or "manual" (with `content`)
+    pub resolution: String,
+    pub content: Option<String>,
+}
+
+/// One side of a historical merge for a single file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeFileVersion {
+    /// Blob SHA, `None` when the file doesn't exist on this side
+    pub blob_sha: Option<String>,
+    pub is_binary: bool,
+    /// Text content (None when missing or binary)
+    pub content: Option<String>,
+}
+
+/// Lines added / removed going from one version to another.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct VersionPairStats {
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+/// Base, ours (parent 1), theirs (parent 2) and result of a file in a past merge.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MergeFileVersions {
+    pub path: String,
+    pub merge_commit: String,
+    /// `None` when the parents share no history
+    pub merge_base: Option<String>,
+    pub base: MergeFileVersion,
+    pub ours: MergeFileVersion,
+    pub theirs: MergeFileVersion,
+    pub result: MergeFileVersion,
+    /// What each parent changed relative to the base
+    pub base_to_ours: VersionPairStats,
+    pub base_to_theirs: VersionPairStats,
+    /// What the merge result changed relative to each parent
+    pub ours_to_result: VersionPairStats,
+    pub theirs_to_result: VersionPairStats,
+}
+
 // ============================================================================
 // Cherry-pick / Revert / Rebase
 // ============================================================================
@@ -219,6 +850,10 @@ pub struct CherryPickResult {
     pub success: bool,
     pub conflicts: Vec<String>,
     pub message: String,
+    /// Signed per `commit.gpgsign` / `gpg.format=ssh`
+    pub signed: bool,
+    /// "ssh" when signed
+    pub signature_type: Option<String>,
 }
 
 /// Result of a revert operation.
@@ -227,6 +862,10 @@ pub struct RevertResult {
     pub success: bool,
     pub conflicts: Vec<String>,
     pub message: String,
+    /// Signed per `commit.gpgsign` / `gpg.format=ssh`
+    pub signed: bool,
+    /// "ssh" when signed
+    pub signature_type: Option<String>,
 }
 
 /// Current rebase status information.
@@ -245,6 +884,23 @@ pub struct RebaseResult {
     pub message: String,
 }
 
+// ============================================================================
+// Amend
+// ============================================================================
+
+/// Result of amending HEAD.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AmendResult {
+    pub commit_sha: String,
+    pub message: String,
+    /// Tracked paths staged automatically by `include_worktree`
+    pub auto_staged_paths: Vec<String>,
+    /// Signed per `commit.gpgsign` / `gpg.format=ssh`
+    pub signed: bool,
+    /// "ssh" when signed
+    pub signature_type: Option<String>,
+}
+
 // ============================================================================
 // Stash
 // ============================================================================
@@ -255,6 +911,8 @@ pub struct StashInfo {
     pub index: usize,
     pub message: String,
     pub oid: String,
+    /// "3일 전" style date, only when requested via `with_relative_dates`
+    pub relative_date: Option<String>,
 }
 
 // ============================================================================
@@ -287,6 +945,53 @@ pub struct FileHistoryEntry {
     pub old_path: Option<String>,
 }
 
+/// One name a file had in its history (see `history::RenameTracker`).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PathIdentity {
+    pub path: String,
+    /// Newest commit in which the file has this name
+    pub commit_sha: String,
+    /// Commit that introduced this name (rename or addition), if found
+    pub introduced_sha: Option<String>,
+}
+
+// ============================================================================
+// Blame
+// ============================================================================
+
+/// Who last changed one line of a file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BlameLine {
+    /// 1-based line number in the blamed version
+    pub line_number: usize,
+    /// Line text without its line ending
+    pub content: String,
+    /// Commit that last changed the line (all zeros when uncommitted)
+    pub commit_sha: String,
+    pub author: String,
+    pub author_email: String,
+    pub timestamp: i64,
+    /// 1-based line number in `commit_sha`
+    pub original_line_number: usize,
+    /// Path of the file in `commit_sha`, which differs after a rename
+    pub original_path: String,
+    /// First line of the commit message
+    pub summary: String,
+    pub is_uncommitted: bool,
+}
+
+/// Blame for a whole file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileBlame {
+    pub file_path: String,
+    /// Commit that was blamed (None for the working tree)
+    pub commit_sha: Option<String>,
+    pub total_lines: usize,
+    /// True when the file exceeded the line limit and was not blamed
+    pub skipped: bool,
+    pub lines: Vec<BlameLine>,
+}
+
 // ============================================================================
 // Reflog
 // ============================================================================
@@ -300,4 +1005,224 @@ pub struct ReflogEntry {
     pub message: String,
     pub committer: String,
     pub timestamp: i64,
+    /// "3일 전" style date, only when requested via `with_relative_dates`
+    pub relative_date: Option<String>,
+}
+
+/// A branch visited recently, from `get_recent_branches`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RecentBranch {
+    /// Branch name, or for a detached checkout the commit (or tag) checked out
+    pub name: String,
+    /// A detached checkout rather than a branch
+    pub detached: bool,
+    /// When the branch was last checked out or left
+    pub timestamp: i64,
+}
+
+// ============================================================================
+// Reset
+// ============================================================================
+
+/// HEAD before and after `reset_to_commit`; `previous_head` is what an undo
+/// (reset via the reflog) goes back to.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResetResult {
+    /// Empty when HEAD had no commit yet
+    pub previous_head: String,
+    pub new_head: String,
+    /// "soft" | "mixed" | "hard"
+    pub mode: String,
+}
+
+// ============================================================================
+// Git CLI
+// ============================================================================
+
+/// The `git` executable used for operations libgit2 cannot do (bundles, ...).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GitCliInfo {
+    pub path: String,
+    /// e.g. "git version 2.43.0"
+    pub version: String,
+    /// "override", "path", or "fallback" (well-known install location)
+    pub source: String,
+}
+
+// ============================================================================
+// Commit Graph
+// ============================================================================
+
+/// A commit of the history graph with its lane layout.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphCommit {
+    #[serde(flatten)]
+    pub commit: CommitInfo,
+    /// Column the commit's dot is drawn in
+    pub lane: u32,
+    /// Lines from this row to the next one
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A line segment of the graph between a row and the row below it. The
+/// incoming lines of a row are the previous row's edges.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GraphEdge {
+    pub from_lane: u32,
+    pub to_lane: u32,
+    /// Commit the line leads to
+    pub target_sha: String,
+    /// false for a lane that only passes through this row
+    pub from_commit: bool,
+}
+
+// ============================================================================
+// Graph Export
+// ============================================================================
+
+/// Progress of a running `export_commit_graph`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphExportProgress {
+    pub output_path: String,
+    /// Commits written so far
+    pub written: usize,
+    pub done: bool,
+}
+
+/// Summary of a finished commit graph export.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GraphExportResult {
+    pub output_path: String,
+    /// "dot" or "jsonl"
+    pub format: String,
+    pub commit_count: usize,
+    /// Parent links written (one DOT edge each)
+    pub edge_count: usize,
+}
+
+// ============================================================================
+// Patch Export
+// ============================================================================
+
+/// A `.patch` file written by `export_patch`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PatchExportResult {
+    pub output_path: String,
+    /// Exported commit, or `None` for uncommitted changes
+    pub commit_sha: Option<String>,
+    /// Written file size in bytes
+    pub file_size: u64,
+}
+
+/// A hunk of a patch that does not apply to the current files.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RejectedHunk {
+    pub path: String,
+    /// `@@ ... @@` header, or `None` when the file as a whole is rejected
+    /// (e.g. a new file that already exists, or a binary change)
+    pub header: Option<String>,
+}
+
+/// Outcome of `apply_patch`. Nothing is written unless every hunk applies.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PatchApplyResult {
+    /// `true` once the patch was written (always `false` for a dry run)
+    pub applied: bool,
+    /// Files touched by the patch
+    pub files: Vec<String>,
+    pub rejected: Vec<RejectedHunk>,
+}
+
+// ============================================================================
+// Command Presets
+// ============================================================================
+
+/// One step of a preset: an allowlisted backend command with fixed parameters.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PresetStep {
+    /// Backend command name, e.g. "fetch_remote"
+    pub command: String,
+    /// Parameters by name (JSON object), e.g. `{"remote_name": "origin"}`
+    #[serde(default)]
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A named sequence of steps persisted in the app data directory.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CommandPreset {
+    pub name: String,
+    pub steps: Vec<PresetStep>,
+}
+
+/// Outcome of one executed preset step.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PresetStepResult {
+    pub command: String,
+    pub success: bool,
+    pub message: String,
+}
+
+/// Outcome of `run_command_preset`. Steps after a failure are not run and
+/// not listed.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PresetRunResult {
+    pub preset: String,
+    /// True when every step succeeded
+    pub completed: bool,
+    pub steps: Vec<PresetStepResult>,
+    /// Non-fatal notices from successful steps (e.g. orphaned branches after a prune)
+    pub warnings: Vec<String>,
+}
+
+// ============================================================================
+// Commit Details
+// ============================================================================
+
+/// Everything the commit detail panel shows for one commit.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CommitDetails {
+    /// Author identity; `commit.timestamp` is the committer time
+    pub commit: CommitInfo,
+    pub author_timestamp: i64,
+    /// May differ from the author after a rebase, cherry-pick or amend
+    pub committer: String,
+    pub committer_email: String,
+    /// Whether the commit carries a `gpgsig` header
+    pub signed: bool,
+    /// "gpg", "ssh" or "x509", from the signature armor
+    pub signature_type: Option<String>,
+    pub files: Vec<CommitFileChange>,
+    /// Totals over `files`
+    pub additions: u32,
+    pub deletions: u32,
+    /// Branches and tags pointing at the commit
+    pub refs: Vec<String>,
+}
+
+// ============================================================================
+// Commit Message Lint
+// ============================================================================
+
+/// Options for `validate_commit_message`; every field has a default.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct CommitLintConfig {
+    /// Check the `type(scope): description` format (default true)
+    pub conventional: Option<bool>,
+    /// Allowed types (default: feat, fix, docs, style, refactor, perf, test,
+    /// build, ci, chore, revert)
+    pub allowed_types: Option<Vec<String>>,
+    /// Longest subject in user-perceived characters (default 72)
+    pub max_subject_length: Option<usize>,
+}
+
+/// One problem found in a commit message.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LintFinding {
+    /// Rule id, e.g. "header-max-length", "type-enum"
+    pub rule: String,
+    /// "error" | "warning"
+    pub severity: String,
+    pub message: String,
+    /// 1-based line of the message
+    pub line: usize,
 }
@@ -0,0 +1,473 @@
+//! `.patch` file export for sharing changes without access to a remote.
+//!
+//! Commits are written in `git format-patch` (mbox) form so they can be
+//! applied with `git am`; uncommitted changes become a plain unified diff
+//! for `git apply`. `apply_patch` reads either form back.
+
+use std::cell::Cell;
+
+use git2::{
+    ApplyLocation, ApplyOptions, Diff, DiffFormat, DiffOptions, EmailCreateOptions, Oid, Patch,
+    Repository,
+};
+
+use super::models::{PatchApplyResult, PatchExportResult, RejectedHunk};
+use super::utils::open_repo;
+
+/// Export `commit_sha` (or, when `None`, the uncommitted changes against HEAD)
+/// to `output_path`. Binary files are included as git binary patches.
+#[tauri::command]
+pub async fn export_patch(
+    repo_path: String,
+    commit_sha: Option<String>,
+    output_path: String,
+) -> Result<PatchExportResult, String> {
+    let repo = open_repo(&repo_path)?;
+    let content = match &commit_sha {
+        Some(sha) => commit_patch(&repo, sha)?,
+        None => working_tree_patch(&repo)?,
+    };
+    std::fs::write(&output_path, &content).map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+    let file_size = std::fs::metadata(&output_path)
+        .map(|m| m.len())
+        .map_err(|e| format!("파일 정보 읽기 실패: {}", e))?;
+
+    Ok(PatchExportResult {
+        output_path,
+        commit_sha,
+        file_size,
+    })
+}
+
+/// `git format-patch`-style mail for one non-merge commit.
+fn commit_patch(repo: &Repository, sha: &str) -> Result<Vec<u8>, String> {
+    let oid = Oid::from_str(sha).map_err(|e| format!("잘못된 커밋 SHA: {}", e))?;
+    let commit = repo
+        .find_commit(oid)
+        .map_err(|e| format!("커밋 찾기 실패: {}", e))?;
+    if commit.parent_count() > 1 {
+        return Err("병합 커밋은 패치로 내보낼 수 없습니다".to_string());
+    }
+
+    let mut opts = EmailCreateOptions::new();
+    opts.diff_options().show_binary(true);
+    let email = git2::Email::from_commit(&commit, &mut opts)
+        .map_err(|e| format!("패치 생성 실패: {}", e))?;
+    Ok(with_charset_headers(email.as_slice()))
+}
+
+/// Declare the UTF-8 body once the headers hold non-ASCII text (Korean
+/// subjects or author names), as `git format-patch` does, so `git am` and
+/// mail clients don't guess the encoding.
+fn with_charset_headers(mail: &[u8]) -> Vec<u8> {
+    if mail.is_ascii() {
+        return mail.to_vec();
+    }
+    let header_end = match mail.windows(2).position(|w| w == b"\n\n") {
+        Some(pos) => pos + 1,
+        None => return mail.to_vec(),
+    };
+    let mut out = Vec::with_capacity(mail.len() + 96);
+    out.extend_from_slice(&mail[..header_end]);
+    out.extend_from_slice(
+        b"MIME-Version: 1.0\nContent-Type: text/plain; charset=UTF-8\nContent-Transfer-Encoding: 8bit\n",
+    );
+    out.extend_from_slice(&mail[header_end..]);
+    out
+}
+
+/// Unified diff of staged and unstaged changes to tracked files (`git diff HEAD`).
+fn working_tree_patch(repo: &Repository) -> Result<Vec<u8>, String> {
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let mut opts = DiffOptions::new();
+    opts.show_binary(true);
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    if diff.deltas().len() == 0 {
+        return Err("내보낼 변경 사항이 없습니다".to_string());
+    }
+
+    // Raw bytes, so non-UTF-8 content and missing final newlines survive
+    let mut patch = Vec::new();
+    diff.print(DiffFormat::Patch, |_delta, _hunk, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+            patch.push(line.origin() as u8);
+        }
+        patch.extend_from_slice(line.content());
+        true
+    })
+    .map_err(|e| format!("Diff 출력 실패: {}", e))?;
+    Ok(patch)
+}
+
+/// Apply the patch file at `patch_path` to the working tree, or with
+/// `location` "index" / "both" to the index as well.
+///
+/// The patch is applied all at once: if any hunk fails, nothing is written
+/// and the failing hunks are listed in `rejected`. `check_only` performs the
+/// same check without touching anything.
+#[tauri::command]
+pub async fn apply_patch(
+    repo_path: String,
+    patch_path: String,
+    check_only: bool,
+    location: Option<String>,
+) -> Result<PatchApplyResult, String> {
+    let repo = open_repo(&repo_path)?;
+    let location = match location.as_deref().unwrap_or("workdir") {
+        "workdir" => ApplyLocation::WorkDir,
+        "index" => ApplyLocation::Index,
+        "both" => ApplyLocation::Both,
+        other => return Err(format!("지원하지 않는 적용 위치입니다: {}", other)),
+    };
+    let buffer = std::fs::read(&patch_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+    let diff = Diff::from_buffer(&buffer).map_err(|e| format!("패치 파싱 실패: {}", e))?;
+    let files: Vec<String> = diff
+        .deltas()
+        .map(|delta| {
+            delta
+                .new_file()
+                .path()
+                .or_else(|| delta.old_file().path())
+                .map(|p| p.to_string_lossy().to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    if files.is_empty() {
+        return Err("패치에 변경 사항이 없습니다".to_string());
+    }
+
+    let mut rejected = existing_new_files(&repo, &diff, location, &files);
+    if !applies(&repo, &diff, location, None, None) {
+        rejected.extend(rejected_hunks(&repo, &diff, location, &files)?);
+    }
+    if !rejected.is_empty() {
+        return Ok(PatchApplyResult {
+            applied: false,
+            files,
+            rejected,
+        });
+    }
+    if !check_only {
+        repo.apply(&diff, location, None)
+            .map_err(|e| format!("패치 적용 실패: {}", e))?;
+    }
+    Ok(PatchApplyResult {
+        applied: !check_only,
+        files,
+        rejected: Vec::new(),
+    })
+}
+
+/// Files the patch creates that already exist in the working tree.
+/// libgit2 would silently overwrite them, while `git apply` refuses.
+fn existing_new_files(
+    repo: &Repository,
+    diff: &Diff,
+    location: ApplyLocation,
+    files: &[String],
+) -> Vec<RejectedHunk> {
+    let workdir = match (location, repo.workdir()) {
+        (ApplyLocation::WorkDir | ApplyLocation::Both, Some(workdir)) => workdir,
+        _ => return Vec::new(),
+    };
+    diff.deltas()
+        .zip(files)
+        .filter(|(delta, path)| {
+            delta.status() == git2::Delta::Added && workdir.join(path).symlink_metadata().is_ok()
+        })
+        .map(|(_, path)| RejectedHunk {
+            path: path.clone(),
+            header: None,
+        })
+        .collect()
+}
+
+/// Dry-run `diff`, optionally limited to delta `only_delta` and, within it,
+/// hunk `only_hunk`.
+fn applies(
+    repo: &Repository,
+    diff: &Diff,
+    location: ApplyLocation,
+    only_delta: Option<usize>,
+    only_hunk: Option<usize>,
+) -> bool {
+    let (delta_pos, hunk_pos) = (Cell::new(0), Cell::new(0));
+    let mut opts = ApplyOptions::new();
+    opts.check(true);
+    opts.delta_callback(|_| {
+        let pos = delta_pos.replace(delta_pos.get() + 1);
+        only_delta.is_none() || only_delta == Some(pos)
+    });
+    opts.hunk_callback(|_| {
+        let pos = hunk_pos.replace(hunk_pos.get() + 1);
+        only_hunk.is_none() || only_hunk == Some(pos)
+    });
+    repo.apply(diff, location, Some(&mut opts)).is_ok()
+}
+
+/// Narrow a failed apply down to the hunks (or whole files) that fail on
+/// their own.
+fn rejected_hunks(
+    repo: &Repository,
+    diff: &Diff,
+    location: ApplyLocation,
+    files: &[String],
+) -> Result<Vec<RejectedHunk>, String> {
+    let mut rejected = Vec::new();
+    for (delta_index, path) in files.iter().enumerate() {
+        if applies(repo, diff, location, Some(delta_index), None) {
+            continue;
+        }
+        let headers: Vec<String> = match Patch::from_diff(diff, delta_index)
+            .map_err(|e| format!("패치 파싱 실패: {}", e))?
+        {
+            Some(patch) => (0..patch.num_hunks())
+                .filter_map(|i| patch.hunk(i).ok())
+                .map(|(hunk, _)| {
+                    String::from_utf8_lossy(hunk.header())
+                        .trim_end()
+                        .to_string()
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let failing_hunks: Vec<String> = headers
+            .into_iter()
+            .enumerate()
+            .filter(|(hunk_index, _)| {
+                !applies(repo, diff, location, Some(delta_index), Some(*hunk_index))
+            })
+            .map(|(_, header)| header)
+            .collect();
+        if failing_hunks.is_empty() {
+            rejected.push(RejectedHunk {
+                path: path.clone(),
+                header: None,
+            });
+        }
+        rejected.extend(failing_hunks.into_iter().map(|header| RejectedHunk {
+            path: path.clone(),
+            header: Some(header),
+        }));
+    }
+    Ok(rejected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::cli::{git_command, run_with_timeout, GIT_TIMEOUT};
+    use git2::Signature;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_all(repo: &Repository, name: &str, message: &str) -> Oid {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"].iter(), git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now(name, "dev@example.com").unwrap();
+        let parents: Vec<_> = repo
+            .head()
+            .ok()
+            .map(|h| h.peel_to_commit().unwrap())
+            .into_iter()
+            .collect();
+        let parents: Vec<_> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+            .unwrap()
+    }
+
+    /// Origin with one base commit, and a fresh clone of it.
+    fn setup_origin_and_clone() -> (TempDir, Repository, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let origin_path = temp_dir.path().join("origin");
+        let repo = Repository::init(&origin_path).unwrap();
+        std::fs::write(origin_path.join("README.md"), "# 패치\n").unwrap();
+        commit_all(&repo, "Base", "Initial commit");
+
+        let clone_path = temp_dir.path().join("clone");
+        Repository::clone(origin_path.to_str().unwrap(), &clone_path).unwrap();
+        let clone_path = clone_path.to_str().unwrap().to_string();
+        (temp_dir, repo, clone_path)
+    }
+
+    fn run_git(dir: &str, args: &[&str]) -> std::process::Output {
+        let mut cmd = git_command().unwrap();
+        cmd.current_dir(dir)
+            .args(["-c", "user.name=Tester", "-c", "user.email=t@example.com"])
+            .args(args);
+        run_with_timeout(&mut cmd, GIT_TIMEOUT).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_export_commit_patch_round_trips_with_am() {
+        let (temp_dir, repo, clone_path) = setup_origin_and_clone();
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join("README.md"), "# 패치\n\n한글 설명\n").unwrap();
+        std::fs::write(workdir.join("note.txt"), "끝 줄에 개행 없음").unwrap();
+        let oid = commit_all(&repo, "홍길동", "문서 업데이트\n\n본문도 한글입니다.\n");
+
+        let output = temp_dir.path().join("0001.patch");
+        let result = export_patch(
+            workdir.to_str().unwrap().to_string(),
+            Some(oid.to_string()),
+            output.to_str().unwrap().to_string(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.file_size, std::fs::metadata(&output).unwrap().len());
+        let text = std::fs::read_to_string(&output).unwrap();
+        assert!(text.contains("Subject: [PATCH] 문서 업데이트"));
+        assert!(text.contains("charset=UTF-8"));
+
+        let am = run_git(&clone_path, &["am", output.to_str().unwrap()]);
+        assert!(
+            am.status.success(),
+            "{}",
+            String::from_utf8_lossy(&am.stderr)
+        );
+
+        let clone = Repository::open(&clone_path).unwrap();
+        let applied = clone.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(applied.author().name(), Some("홍길동"));
+        assert_eq!(applied.summary(), Some("문서 업데이트"));
+        assert_eq!(applied.body(), Some("본문도 한글입니다."));
+        assert_eq!(applied.tree_id(), repo.find_commit(oid).unwrap().tree_id());
+    }
+
+    #[tokio::test]
+    async fn test_export_working_tree_patch_applies_in_clone() {
+        let (temp_dir, repo, clone_path) = setup_origin_and_clone();
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        let repo_path = workdir.to_str().unwrap().to_string();
+        let output = temp_dir.path().join("wip.patch");
+        let output_path = output.to_str().unwrap().to_string();
+
+        assert!(export_patch(repo_path.clone(), None, output_path.clone())
+            .await
+            .is_err());
+
+        // One staged new file, one unstaged edit
+        std::fs::write(workdir.join("new.txt"), "새 파일\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("new.txt")).unwrap();
+        index.write().unwrap();
+        std::fs::write(workdir.join("README.md"), "# 패치 수정\n").unwrap();
+
+        let result = export_patch(repo_path, None, output_path.clone())
+            .await
+            .unwrap();
+        assert!(result.commit_sha.is_none());
+        assert!(result.file_size > 0);
+
+        let apply = run_git(&clone_path, &["apply", &output_path]);
+        assert!(
+            apply.status.success(),
+            "{}",
+            String::from_utf8_lossy(&apply.stderr)
+        );
+        let clone_dir = Path::new(&clone_path);
+        assert_eq!(
+            std::fs::read_to_string(clone_dir.join("README.md")).unwrap(),
+            "# 패치 수정\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(clone_dir.join("new.txt")).unwrap(),
+            "새 파일\n"
+        );
+    }
+
+    /// Export the uncommitted changes of `repo` to `name`.
+    async fn export_changes(repo: &Repository, temp_dir: &TempDir, name: &str) -> String {
+        let output = temp_dir.path().join(name).to_str().unwrap().to_string();
+        let repo_path = repo.workdir().unwrap().to_str().unwrap().to_string();
+        export_patch(repo_path, None, output.clone()).await.unwrap();
+        output
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_clean_and_dry_run() {
+        let (temp_dir, repo, clone_path) = setup_origin_and_clone();
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join("README.md"), "# 패치\n추가 줄\n").unwrap();
+        let patch_path = export_changes(&repo, &temp_dir, "clean.patch").await;
+        let readme = Path::new(&clone_path).join("README.md");
+
+        let check = apply_patch(clone_path.clone(), patch_path.clone(), true, None)
+            .await
+            .unwrap();
+        assert!(!check.applied);
+        assert!(check.rejected.is_empty());
+        assert_eq!(check.files, vec!["README.md"]);
+        assert_eq!(std::fs::read_to_string(&readme).unwrap(), "# 패치\n");
+
+        let result = apply_patch(
+            clone_path.clone(),
+            patch_path,
+            false,
+            Some("both".to_string()),
+        )
+        .await
+        .unwrap();
+        assert!(result.applied);
+        assert_eq!(std::fs::read_to_string(&readme).unwrap(), "# 패치\n추가 줄\n");
+        let clone = Repository::open(&clone_path).unwrap();
+        let statuses = clone.statuses(None).unwrap();
+        assert!(statuses
+            .iter()
+            .all(|s| s.status() == git2::Status::INDEX_MODIFIED));
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_reports_conflicting_hunk() {
+        let (temp_dir, repo, clone_path) = setup_origin_and_clone();
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join("README.md"), "# 바뀐 제목\n").unwrap();
+        let patch_path = export_changes(&repo, &temp_dir, "conflict.patch").await;
+
+        // The clone changed the same line differently
+        let readme = Path::new(&clone_path).join("README.md");
+        std::fs::write(&readme, "# 다른 제목\n").unwrap();
+
+        let result = apply_patch(clone_path.clone(), patch_path, false, None)
+            .await
+            .unwrap();
+        assert!(!result.applied);
+        assert_eq!(result.rejected.len(), 1);
+        assert_eq!(result.rejected[0].path, "README.md");
+        assert_eq!(result.rejected[0].header.as_deref(), Some("@@ -1 +1 @@"));
+        assert_eq!(std::fs::read_to_string(&readme).unwrap(), "# 다른 제목\n");
+    }
+
+    #[tokio::test]
+    async fn test_apply_patch_with_korean_new_file() {
+        let (temp_dir, repo, clone_path) = setup_origin_and_clone();
+        let workdir = repo.workdir().unwrap().to_path_buf();
+        std::fs::write(workdir.join("문서.txt"), "내용\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("문서.txt")).unwrap();
+        index.write().unwrap();
+        let patch_path = export_changes(&repo, &temp_dir, "korean.patch").await;
+
+        let result = apply_patch(clone_path.clone(), patch_path.clone(), false, None)
+            .await
+            .unwrap();
+        assert!(result.applied);
+        assert_eq!(result.files, vec!["문서.txt"]);
+        let new_file = Path::new(&clone_path).join("문서.txt");
+        assert_eq!(std::fs::read_to_string(&new_file).unwrap(), "내용\n");
+
+        // Applying again fails: the file already exists
+        let again = apply_patch(clone_path, patch_path, true, None)
+            .await
+            .unwrap();
+        assert!(!again.applied);
+        assert_eq!(again.rejected.len(), 1);
+        assert_eq!(again.rejected[0].path, "문서.txt");
+    }
+}
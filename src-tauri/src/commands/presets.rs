@@ -0,0 +1,489 @@
+//! User-defined command presets ("fetch + pull main", ...).
+//!
+//! A preset only chains existing backend commands from `STEP_SPECS` with
+//! fixed, type-checked parameters; it never runs arbitrary shell commands.
+
+use serde_json::Value;
+
+use super::app_data::{self, AppData};
+use super::branch::switch_branch;
+use super::git::stage_all;
+use super::models::{CommandPreset, PresetRunResult, PresetStep, PresetStepResult};
+use super::remote::{
+    fetch_remote_with_events, prune_remote, pull_changes_with_events, push_changes_with_events,
+};
+use super::stash::{stash_pop, stash_save};
+use super::utils::command_error;
+
+impl AppData for Vec<CommandPreset> {
+    const FILE_NAME: &'static str = "command_presets.json";
+    const SCHEMA_VERSION: u32 = 1;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ParamKind {
+    Str,
+    Bool,
+    Uint,
+}
+
+/// An allowlisted command and the parameters a step may pass to it.
+struct StepSpec {
+    command: &'static str,
+    /// (name, kind, required)
+    params: &'static [(&'static str, ParamKind, bool)],
+}
+
+const STEP_SPECS: &[StepSpec] = &[
+    StepSpec {
+        command: "fetch_remote",
+        params: &[("remote_name", ParamKind::Str, true)],
+    },
+    StepSpec {
+        command: "prune_remote",
+        params: &[
+            ("remote_name", ParamKind::Str, true),
+            ("also_remove_tracking_config", ParamKind::Bool, false),
+        ],
+    },
+    StepSpec {
+        command: "pull_changes",
+        params: &[
+            ("remote_name", ParamKind::Str, true),
+            ("branch_name", ParamKind::Str, true),
+        ],
+    },
+    StepSpec {
+        command: "push_changes",
+        params: &[
+            ("remote_name", ParamKind::Str, true),
+            ("branch_name", ParamKind::Str, true),
+            ("force", ParamKind::Bool, false),
+        ],
+    },
+    StepSpec {
+        command: "switch_branch",
+        params: &[("branch_name", ParamKind::Str, true)],
+    },
+    StepSpec {
+        command: "stage_all",
+        params: &[],
+    },
+    StepSpec {
+        command: "stash_save",
+        params: &[
+            ("message", ParamKind::Str, false),
+            ("include_untracked", ParamKind::Bool, false),
+        ],
+    },
+    StepSpec {
+        command: "stash_pop",
+        params: &[("index", ParamKind::Uint, false)],
+    },
+];
+
+/// Save (or replace) a preset after validating every step.
+#[tauri::command]
+pub async fn save_command_preset(name: String, steps: Vec<PresetStep>) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("프리셋 이름을 입력하세요".to_string());
+    }
+    validate_steps(&steps)?;
+
+    let mut presets: Vec<CommandPreset> = app_data::load()?;
+    presets.retain(|p| p.name != name);
+    presets.push(CommandPreset { name, steps });
+    app_data::save(&presets)
+}
+
+/// List saved presets.
+#[tauri::command]
+pub async fn list_command_presets() -> Result<Vec<CommandPreset>, String> {
+    app_data::load()
+}
+
+/// Run a saved preset's steps in order, stopping at the first failure.
+///
+/// Presets with a force push are refused unless this run passes
+/// `confirm_force`; the confirmation is never stored with the preset.
+#[tauri::command]
+pub async fn run_command_preset(
+    repo_path: String,
+    name: String,
+    confirm_force: Option<bool>,
+) -> Result<PresetRunResult, String> {
+    let presets: Vec<CommandPreset> = app_data::load()?;
+    let preset = presets
+        .into_iter()
+        .find(|p| p.name == name)
+        .ok_or_else(|| format!("프리셋 '{}'을(를) 찾을 수 없습니다", name))?;
+    // The file may have been edited by hand since it was saved
+    validate_steps(&preset.steps)?;
+    if !confirm_force.unwrap_or(false) {
+        if let Some(idx) = preset.steps.iter().position(|s| bool_param(s, "force")) {
+            return Err(command_error(
+                "force_push_unconfirmed",
+                format!("{}번째 단계가 강제 푸시합니다. 실행 전에 확인이 필요합니다", idx + 1),
+                serde_json::json!({ "step": idx }),
+            ));
+        }
+    }
+
+    let mut result = PresetRunResult {
+        preset: preset.name,
+        completed: true,
+        steps: Vec::new(),
+        warnings: Vec::new(),
+    };
+    for step in &preset.steps {
+        let outcome = run_step(&repo_path, step, &mut result.warnings).await;
+        let success = outcome.is_ok();
+        result.steps.push(PresetStepResult {
+            command: step.command.clone(),
+            success,
+            message: outcome.unwrap_or_else(|e| e),
+        });
+        if !success {
+            result.completed = false;
+            break;
+        }
+    }
+    Ok(result)
+}
+
+/// Reject unknown commands and unknown or mistyped parameters.
+fn validate_steps(steps: &[PresetStep]) -> Result<(), String> {
+    let invalid = |idx: usize, step: &PresetStep, reason: String| {
+        command_error(
+            "invalid_preset",
+            format!("{}번째 단계({}): {}", idx + 1, step.command, reason),
+            serde_json::json!({ "step": idx, "command": step.command }),
+        )
+    };
+    if steps.is_empty() {
+        return Err(command_error(
+            "invalid_preset",
+            "프리셋에 단계가 없습니다",
+            serde_json::json!({}),
+        ));
+    }
+
+    for (idx, step) in steps.iter().enumerate() {
+        let spec = STEP_SPECS
+            .iter()
+            .find(|s| s.command == step.command)
+            .ok_or_else(|| invalid(idx, step, "허용되지 않은 명령입니다".to_string()))?;
+
+        for key in step.params.keys() {
+            if !spec.params.iter().any(|(name, _, _)| name == key) {
+                return Err(invalid(idx, step, format!("알 수 없는 매개변수 '{}'", key)));
+            }
+        }
+        for &(name, kind, required) in spec.params {
+            let matches_kind = match step.params.get(name) {
+                None => !required,
+                Some(Value::String(s)) => kind == ParamKind::Str && !s.is_empty(),
+                Some(Value::Bool(_)) => kind == ParamKind::Bool,
+                Some(Value::Number(n)) => kind == ParamKind::Uint && n.is_u64(),
+                Some(_) => false,
+            };
+            if !matches_kind {
+                return Err(invalid(
+                    idx,
+                    step,
+                    format!("매개변수 '{}' 값이 잘못되었습니다", name),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Execute one validated step, returning its success message.
+async fn run_step(
+    repo_path: &str,
+    step: &PresetStep,
+    warnings: &mut Vec<String>,
+) -> Result<String, String> {
+    let repo_path = repo_path.to_string();
+    match step.command.as_str() {
+        "fetch_remote" => {
+            fetch_remote_with_events(None, repo_path, str_param(step, "remote_name"), None, false)
+                .await
+                .map(|result| result.message)
+        }
+        "prune_remote" => {
+            let remote = str_param(step, "remote_name");
+            let also_remove = Some(bool_param(step, "also_remove_tracking_config"));
+            let report = prune_remote(repo_path, remote.clone(), also_remove).await?;
+            if !report.orphaned_branches.is_empty() {
+                warnings.push(format!(
+                    "원격 브랜치가 사라진 로컬 브랜치: {}",
+                    report.orphaned_branches.join(", ")
+                ));
+            }
+            Ok(format!(
+                "'{}' 정리 완료: {}개 참조 삭제",
+                remote,
+                report.removed_refs.len()
+            ))
+        }
+        "pull_changes" => {
+            let result = pull_changes_with_events(
+                None,
+                repo_path,
+                str_param(step, "remote_name"),
+                str_param(step, "branch_name"),
+            )
+            .await?;
+            // Later steps must not run on top of an unfinished merge
+            if result.kind == "conflicts" {
+                return Err(result.message);
+            }
+            Ok(result.message)
+        }
+        "push_changes" => {
+            let force = bool_param(step, "force");
+            if force {
+                warnings.push(format!(
+                    "'{}' 브랜치를 강제 푸시했습니다",
+                    str_param(step, "branch_name")
+                ));
+            }
+            push_changes_with_events(
+                None,
+                repo_path,
+                str_param(step, "remote_name"),
+                str_param(step, "branch_name"),
+                force,
+                None,
+                None,
+                None,
+            )
+            .await
+            .map(|result| result.message)
+        }
+        "switch_branch" => {
+            let branch_name = str_param(step, "branch_name");
+            switch_branch(repo_path, branch_name.clone(), None, None)
+                .await
+                .map(|_| format!("'{}' 브랜치로 전환 완료", branch_name))
+        }
+        "stage_all" => stage_all(repo_path)
+            .await
+            .map(|_| "모든 변경사항 스테이징 완료".to_string()),
+        "stash_save" => {
+            let message = step
+                .params
+                .get("message")
+                .and_then(Value::as_str)
+                .map(str::to_string);
+            stash_save(repo_path, message, bool_param(step, "include_untracked"))
+        }
+        "stash_pop" => {
+            let index = step
+                .params
+                .get("index")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            stash_pop(repo_path, index as usize)
+        }
+        other => Err(format!("허용되지 않은 명령입니다: {}", other)),
+    }
+}
+
+fn str_param(step: &PresetStep, name: &str) -> String {
+    step.params
+        .get(name)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn bool_param(step: &PresetStep, name: &str) -> bool {
+    step.params
+        .get(name)
+        .and_then(Value::as_bool)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::models::CommandError;
+    use crate::commands::utils::ScopedConfigHome;
+    use git2::{Repository, Signature};
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    fn step(command: &str, params: Value) -> PresetStep {
+        PresetStep {
+            command: command.to_string(),
+            params: params.as_object().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Repo with one commit plus an uncommitted change, and an isolated config dir.
+    fn setup() -> (TempDir, ScopedConfigHome, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let config_home = ScopedConfigHome::set(&temp_dir.path().join("config"));
+        let repo_path = temp_dir.path().join("repo");
+        let repo = Repository::init(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        std::fs::write(repo_path.join("a.txt"), "one\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(std::path::Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        std::fs::write(repo_path.join("a.txt"), "two\n").unwrap();
+        (temp_dir, config_home, repo_path.to_str().unwrap().to_string())
+    }
+
+    fn error_code(err: &str) -> String {
+        serde_json::from_str::<CommandError>(err).unwrap().code
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_two_step_preset_runs_in_order_and_persists() {
+        let (_temp_dir, _config_home, repo_path) = setup();
+        let steps = vec![
+            step("stage_all", Value::Null),
+            step(
+                "stash_save",
+                serde_json::json!({ "message": "프리셋 스태시" }),
+            ),
+        ];
+        save_command_preset("정리".to_string(), steps.clone())
+            .await
+            .unwrap();
+        let presets = list_command_presets().await.unwrap();
+        assert_eq!(
+            presets,
+            vec![CommandPreset {
+                name: "정리".to_string(),
+                steps
+            }]
+        );
+
+        let result = run_command_preset(repo_path.clone(), "정리".to_string(), None)
+            .await
+            .unwrap();
+        assert!(result.completed);
+        let commands: Vec<&str> = result.steps.iter().map(|s| s.command.as_str()).collect();
+        assert_eq!(commands, vec!["stage_all", "stash_save"]);
+
+        // The stash captured the change staged by the first step
+        let mut repo = Repository::open(&repo_path).unwrap();
+        let mut messages = Vec::new();
+        repo.stash_foreach(|_, msg, _| {
+            messages.push(msg.to_string());
+            true
+        })
+        .unwrap();
+        assert!(messages[0].contains("프리셋 스태시"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_preset_stops_at_failed_step() {
+        let (_temp_dir, _config_home, repo_path) = setup();
+        let steps = vec![
+            step(
+                "switch_branch",
+                serde_json::json!({ "branch_name": "없는-브랜치" }),
+            ),
+            step("stage_all", Value::Null),
+        ];
+        save_command_preset("broken".to_string(), steps)
+            .await
+            .unwrap();
+
+        let result = run_command_preset(repo_path.clone(), "broken".to_string(), None)
+            .await
+            .unwrap();
+        assert!(!result.completed);
+        assert_eq!(result.steps.len(), 1);
+        assert!(!result.steps[0].success);
+
+        // The second step never ran: nothing is staged
+        let repo = Repository::open(&repo_path).unwrap();
+        let statuses = repo.statuses(None).unwrap();
+        assert!(statuses
+            .iter()
+            .all(|s| !s.status().contains(git2::Status::INDEX_MODIFIED)));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_validation_rejects_unknown_and_dangerous_steps() {
+        let (_temp_dir, _config_home, _repo_path) = setup();
+        let push = |params: Value| vec![step("push_changes", params)];
+
+        for steps in [
+            vec![step("rm_rf", Value::Null)],
+            vec![step("fetch_remote", Value::Null)],
+            vec![step(
+                "fetch_remote",
+                serde_json::json!({ "remote_name": 1 }),
+            )],
+            vec![step("stage_all", serde_json::json!({ "path": "/" }))],
+            // Confirmation belongs to the run, not the saved preset
+            push(serde_json::json!({
+                "remote_name": "origin",
+                "branch_name": "main",
+                "force": true,
+                "confirm_force": true,
+            })),
+            vec![],
+        ] {
+            let err = save_command_preset("bad".to_string(), steps)
+                .await
+                .unwrap_err();
+            assert_eq!(error_code(&err), "invalid_preset");
+        }
+        assert!(list_command_presets().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_force_push_preset_needs_confirmation_per_run() {
+        let (_temp_dir, _config_home, repo_path) = setup();
+        let steps = vec![
+            step("stage_all", Value::Null),
+            step(
+                "push_changes",
+                serde_json::json!({ "remote_name": "origin", "branch_name": "main", "force": true }),
+            ),
+        ];
+        save_command_preset("force".to_string(), steps)
+            .await
+            .unwrap();
+
+        for confirm_force in [None, Some(false)] {
+            let err = run_command_preset(repo_path.clone(), "force".to_string(), confirm_force)
+                .await
+                .unwrap_err();
+            assert_eq!(error_code(&err), "force_push_unconfirmed");
+        }
+        // Refused before any step ran: nothing is staged
+        let repo = Repository::open(&repo_path).unwrap();
+        let statuses = repo.statuses(None).unwrap();
+        assert!(statuses
+            .iter()
+            .all(|s| !s.status().contains(git2::Status::INDEX_MODIFIED)));
+
+        // Confirmed, the steps run (the push fails: there is no remote)
+        let result = run_command_preset(repo_path, "force".to_string(), Some(true))
+            .await
+            .unwrap();
+        assert_eq!(result.steps.len(), 2);
+        assert!(result.steps[0].success && !result.steps[1].success);
+    }
+}
@@ -1,7 +1,7 @@
 use git2::RebaseOptions;
 
 use super::models::{RebaseInfo, RebaseResult};
-use super::utils::open_repo;
+use super::utils::{assert_no_conflicts, open_repo, repo_signature};
 
 /// Start rebase.
 #[tauri::command]
@@ -25,6 +25,8 @@ pub fn start_rebase(repo_path: String, onto: String) -> Result<RebaseResult, Str
         .find_annotated_commit(onto_commit.id())
         .map_err(|e| format!("Annotated 커밋 생성 실패: {}", e))?;
 
+    // Before starting, so a missing identity leaves no rebase in progress
+    let sig = repo_signature(&repo)?;
     let mut opts = RebaseOptions::new();
     let mut rebase = repo
         .rebase(
@@ -39,7 +41,7 @@ pub fn start_rebase(repo_path: String, onto: String) -> Result<RebaseResult, Str
     while let Some(op) = rebase.next() {
         match op {
             Ok(_) => {
-                if let Err(e) = rebase.commit(None, &repo.signature().unwrap(), None) {
+                if let Err(e) = rebase.commit(None, &sig, None) {
                     conflicts.push(format!("커밋 중 오류: {}", e));
                 }
             }
@@ -73,13 +75,16 @@ pub fn start_rebase(repo_path: String, onto: String) -> Result<RebaseResult, Str
 pub fn rebase_continue(repo_path: String) -> Result<(), String> {
     let repo = open_repo(&repo_path)?;
 
+    let index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+    assert_no_conflicts(&index)?;
+
     let mut rebase = repo
         .open_rebase(None)
         .map_err(|e| format!("Rebase 상태 열기 실패: {}", e))?;
 
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let sig = repo_signature(&repo)?;
 
     rebase
         .commit(None, &sig, None)
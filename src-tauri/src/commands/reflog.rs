@@ -1,25 +1,62 @@
-use super::models::ReflogEntry;
-use super::utils::open_repo;
+use git2::BranchType;
+
+use super::models::{Page, RecentBranch, ReflogEntry};
+use super::relative_time::{format_relative_time, relative_date_locale, Locale};
+use super::utils::{collect_page, normalize_unicode, open_repo, page_window, MAX_PAGE_SIZE};
+
+/// Entries returned by `get_recent_branches` unless told otherwise.
+const DEFAULT_RECENT_BRANCHES: usize = 10;
 
 /// Get reflog entries.
+/// If `with_relative_dates` is true, fills `relative_date` in the given `locale` ("ko" | "en").
+///
+/// Compatibility wrapper returning a plain list (default 100 entries, at most
+/// `MAX_PAGE_SIZE`); new callers should use `get_reflog_page`.
 #[tauri::command]
 pub fn get_reflog(
     repo_path: String,
     ref_name: Option<String>,
     limit: Option<usize>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
 ) -> Result<Vec<ReflogEntry>, String> {
-    let repo = open_repo(&repo_path)?;
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let limit = limit.unwrap_or(100).clamp(1, MAX_PAGE_SIZE);
+    reflog_page(&repo_path, ref_name, 0, limit, relative_locale).map(|p| p.items)
+}
+
+/// One page of `get_reflog`.
+#[tauri::command]
+pub fn get_reflog_page(
+    repo_path: String,
+    ref_name: Option<String>,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+) -> Result<Page<ReflogEntry>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let (offset, limit) = page_window(cursor.as_deref(), limit)?;
+    reflog_page(&repo_path, ref_name, offset, limit, relative_locale)
+}
+
+fn reflog_page(
+    repo_path: &str,
+    ref_name: Option<String>,
+    offset: usize,
+    limit: usize,
+    relative_locale: Option<Locale>,
+) -> Result<Page<ReflogEntry>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let repo = open_repo(repo_path)?;
     let reference = ref_name.unwrap_or_else(|| "HEAD".to_string());
 
     let reflog = repo
         .reflog(&reference)
         .map_err(|e| format!("Reflog 접근 실패: {}", e))?;
 
-    let max_entries = limit.unwrap_or(100);
-    let mut entries = Vec::new();
-
-    for (index, entry) in reflog.iter().enumerate().take(max_entries) {
-        entries.push(ReflogEntry {
+    let entries = reflog.iter().enumerate().map(|(index, entry)| {
+        Ok::<_, String>(ReflogEntry {
             index,
             old_oid: entry.id_old().to_string(),
             new_oid: entry.id_new().to_string(),
@@ -30,10 +67,77 @@ pub fn get_reflog(
                 entry.committer().email().unwrap_or("unknown@example.com")
             ),
             timestamp: entry.committer().when().seconds(),
-        });
-    }
+            relative_date: relative_locale
+                .map(|l| format_relative_time(entry.committer().when().seconds(), now, l)),
+        })
+    });
+    collect_page(entries, offset, limit, Some(reflog.len() as u64))
+}
 
-    Ok(entries)
+/// Branches checked out recently, most recent first, from the
+/// "checkout: moving from X to Y" entries of the HEAD reflog; the first one
+/// is where `git checkout -` would go. The current branch and branches that
+/// no longer exist are left out. Detached checkouts (a commit or tag) are
+/// only listed with `include_commits`.
+#[tauri::command]
+pub fn get_recent_branches(
+    repo_path: String,
+    limit: Option<usize>,
+    include_commits: Option<bool>,
+) -> Result<Vec<RecentBranch>, String> {
+    let repo = open_repo(&repo_path)?;
+    let limit = limit.unwrap_or(DEFAULT_RECENT_BRANCHES);
+    let include_commits = include_commits.unwrap_or(false);
+    let current = repo.head().ok().and_then(|head| {
+        if head.is_branch() {
+            head.shorthand().map(normalize_unicode)
+        } else {
+            head.target().map(|id| id.to_string())
+        }
+    });
+    let reflog = repo
+        .reflog("HEAD")
+        .map_err(|e| format!("Reflog 접근 실패: {}", e))?;
+
+    let mut recent: Vec<RecentBranch> = Vec::new();
+    for entry in reflog.iter() {
+        let Some((from, to)) = entry
+            .message()
+            .and_then(|m| m.strip_prefix("checkout: moving from "))
+            .and_then(|m| m.split_once(" to "))
+        else {
+            continue;
+        };
+        let timestamp = entry.committer().when().seconds();
+        // Y was being visited and X was left at the time of the entry
+        for name in [to.trim(), from.trim()].map(normalize_unicode) {
+            if recent.len() >= limit
+                || current.as_deref() == Some(name.as_str())
+                || recent.iter().any(|r| r.name == name)
+            {
+                continue;
+            }
+            let detached = repo.find_branch(&name, BranchType::Local).is_err();
+            if detached
+                && !(include_commits
+                    && repo
+                        .revparse_single(&name)
+                        .and_then(|object| object.peel_to_commit())
+                        .is_ok())
+            {
+                continue;
+            }
+            recent.push(RecentBranch {
+                name,
+                detached,
+                timestamp,
+            });
+        }
+        if recent.len() >= limit {
+            break;
+        }
+    }
+    Ok(recent)
 }
 
 /// Reset to a reflog entry.
@@ -60,3 +164,87 @@ pub fn reset_to_reflog(
         .map_err(|e| format!("리셋 실패: {}", e))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::branch::{checkout_commit, switch_branch};
+    use git2::{Repository, Signature};
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        let commit = repo.find_commit(commit).unwrap();
+        for branch in ["기능", "수정", "삭제될"] {
+            repo.branch(branch, &commit, false).unwrap();
+        }
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, repo_path)
+    }
+
+    async fn switch(repo_path: &str, branch: &str) {
+        switch_branch(repo_path.to_string(), branch.to_string(), None, None)
+            .await
+            .unwrap();
+    }
+
+    fn names(recent: &[RecentBranch]) -> Vec<&str> {
+        recent.iter().map(|r| r.name.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_recent_branches_most_recent_first() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let main = repo.head().unwrap().shorthand().unwrap().to_string();
+        assert!(get_recent_branches(repo_path.clone(), None, None)
+            .unwrap()
+            .is_empty());
+
+        for branch in ["기능", "삭제될", "수정", "기능"] {
+            switch(&repo_path, branch).await;
+        }
+        repo.find_branch("삭제될", BranchType::Local)
+            .unwrap()
+            .delete()
+            .unwrap();
+        let recent = get_recent_branches(repo_path.clone(), None, None).unwrap();
+        assert_eq!(names(&recent), vec!["수정", main.as_str()]);
+        assert!(recent.iter().all(|r| !r.detached && r.timestamp > 0));
+
+        let head = repo.head().unwrap().target().unwrap().to_string();
+        checkout_commit(repo_path.clone(), head.clone(), None)
+            .await
+            .unwrap();
+        switch(&repo_path, &main).await;
+        let recent = get_recent_branches(repo_path.clone(), None, None).unwrap();
+        assert_eq!(names(&recent), vec!["기능", "수정"]);
+
+        let recent = get_recent_branches(repo_path.clone(), Some(2), Some(true)).unwrap();
+        assert_eq!(names(&recent), vec![head.as_str(), "기능"]);
+        assert!(recent[0].detached);
+    }
+
+    #[test]
+    fn test_reflog_limit_is_clamped() {
+        let (_temp_dir, repo_path) = setup_repo();
+        let repo = Repository::open(&repo_path).unwrap();
+        let head = repo.head().unwrap().target().unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let mut reflog = repo.reflog("HEAD").unwrap();
+        for i in 0..MAX_PAGE_SIZE + 5 {
+            reflog.append(head, &sig, Some(&format!("move {}", i))).unwrap();
+        }
+        reflog.write().unwrap();
+
+        let entries = get_reflog(repo_path, None, Some(usize::MAX), None, None).unwrap();
+        assert_eq!(entries.len(), MAX_PAGE_SIZE);
+    }
+}
@@ -0,0 +1,158 @@
+//! Relative timestamp formatting ("3일 전" / "3 days ago").
+//!
+//! Every panel formats through these thresholds so the same commit reads
+//! identically in the history, branch, stash, and reflog views.
+
+use chrono::{TimeZone, Utc};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// Anything newer than this is "just now".
+const JUST_NOW_SECS: i64 = MINUTE;
+/// Older than this falls back to an absolute date.
+const DATE_FALLBACK_SECS: i64 = 4 * WEEK;
+/// Future timestamps within this window (clock skew) still read "just now";
+/// further in the future we show the absolute date instead of nonsense.
+const FUTURE_SKEW_TOLERANCE_SECS: i64 = 5 * MINUTE;
+
+/// Output language for relative timestamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    Ko,
+    En,
+}
+
+impl Locale {
+    /// Parse a locale code ("ko" | "en"). `None` defaults to Korean.
+    pub fn parse(code: Option<&str>) -> Result<Locale, String> {
+        match code.unwrap_or("ko") {
+            "ko" => Ok(Locale::Ko),
+            "en" => Ok(Locale::En),
+            other => Err(format!("지원하지 않는 로케일입니다: {}", other)),
+        }
+    }
+}
+
+/// Resolve the listing-command options into the locale to format with,
+/// or `None` when relative dates were not requested.
+pub fn relative_date_locale(
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+) -> Result<Option<Locale>, String> {
+    if !with_relative_dates.unwrap_or(false) {
+        return Ok(None);
+    }
+    Locale::parse(locale.as_deref()).map(Some)
+}
+
+/// Format `timestamp` relative to `now` (both Unix seconds).
+pub fn format_relative_time(timestamp: i64, now: i64, locale: Locale) -> String {
+    let elapsed = now - timestamp;
+
+    if !(-FUTURE_SKEW_TOLERANCE_SECS..DATE_FALLBACK_SECS).contains(&elapsed) {
+        return format_date(timestamp);
+    }
+    if elapsed < JUST_NOW_SECS {
+        return match locale {
+            Locale::Ko => "방금 전".to_string(),
+            Locale::En => "just now".to_string(),
+        };
+    }
+
+    let (count, ko_unit, en_unit) = if elapsed < HOUR {
+        (elapsed / MINUTE, "분", "minute")
+    } else if elapsed < DAY {
+        (elapsed / HOUR, "시간", "hour")
+    } else if elapsed < WEEK {
+        (elapsed / DAY, "일", "day")
+    } else {
+        (elapsed / WEEK, "주", "week")
+    };
+
+    match locale {
+        Locale::Ko => format!("{}{} 전", count, ko_unit),
+        Locale::En if count == 1 => format!("1 {} ago", en_unit),
+        Locale::En => format!("{} {}s ago", count, en_unit),
+    }
+}
+
+fn format_date(timestamp: i64) -> String {
+    Utc.timestamp_opt(timestamp, 0)
+        .single()
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// Format a batch of timestamps relative to the current time.
+#[tauri::command]
+pub async fn format_relative_times(
+    timestamps: Vec<i64>,
+    locale: String,
+) -> Result<Vec<String>, String> {
+    let locale = Locale::parse(Some(&locale))?;
+    let now = Utc::now().timestamp();
+    Ok(timestamps
+        .into_iter()
+        .map(|ts| format_relative_time(ts, now, locale))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000; // 2023-11-14 22:13:20 UTC
+
+    #[test]
+    fn test_korean_thresholds() {
+        let ko = |ago| format_relative_time(NOW - ago, NOW, Locale::Ko);
+        assert_eq!(ko(0), "방금 전");
+        assert_eq!(ko(59), "방금 전");
+        assert_eq!(ko(60), "1분 전");
+        assert_eq!(ko(59 * MINUTE), "59분 전");
+        assert_eq!(ko(HOUR), "1시간 전");
+        assert_eq!(ko(23 * HOUR), "23시간 전");
+        assert_eq!(ko(3 * DAY), "3일 전");
+        assert_eq!(ko(2 * WEEK), "2주 전");
+        assert_eq!(ko(DATE_FALLBACK_SECS), "2023-10-17");
+    }
+
+    #[test]
+    fn test_english_thresholds_and_plurals() {
+        let en = |ago| format_relative_time(NOW - ago, NOW, Locale::En);
+        assert_eq!(en(10), "just now");
+        assert_eq!(en(MINUTE), "1 minute ago");
+        assert_eq!(en(5 * MINUTE), "5 minutes ago");
+        assert_eq!(en(HOUR), "1 hour ago");
+        assert_eq!(en(DAY), "1 day ago");
+        assert_eq!(en(6 * DAY), "6 days ago");
+        assert_eq!(en(WEEK), "1 week ago");
+        assert_eq!(en(3 * WEEK), "3 weeks ago");
+        assert_eq!(en(60 * DAY), "2023-09-15");
+    }
+
+    #[test]
+    fn test_future_timestamps_from_clock_skew() {
+        // Slightly ahead: treat as just now
+        assert_eq!(format_relative_time(NOW + 90, NOW, Locale::Ko), "방금 전");
+        assert_eq!(format_relative_time(NOW + 90, NOW, Locale::En), "just now");
+        // Far ahead: absolute date rather than a negative duration
+        assert_eq!(format_relative_time(NOW + 2 * DAY, NOW, Locale::Ko), "2023-11-16");
+        assert_eq!(format_relative_time(NOW + 2 * DAY, NOW, Locale::En), "2023-11-16");
+    }
+
+    #[test]
+    fn test_locale_parsing() {
+        assert_eq!(Locale::parse(None).unwrap(), Locale::Ko);
+        assert_eq!(Locale::parse(Some("en")).unwrap(), Locale::En);
+        assert!(Locale::parse(Some("fr")).is_err());
+        assert_eq!(relative_date_locale(None, Some("en".to_string())).unwrap(), None);
+        assert_eq!(
+            relative_date_locale(Some(true), None).unwrap(),
+            Some(Locale::Ko)
+        );
+    }
+}
@@ -1,75 +1,169 @@
 use git2::{
-    AutotagOption, BranchType, Cred, Direction, FetchOptions, PushOptions,
-    RemoteCallbacks,
+    AutotagOption, BranchType, Direction, FetchOptions, FetchPrune, Oid, PushOptions,
+    ReferenceType, Remote, RemoteCallbacks, Repository,
 };
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::cli::{git_command, run_with_timeout, GIT_TIMEOUT};
+use super::credentials::{credential_callbacks, find_remote, remote_error};
+use super::merge::{merge_into_head, MergeOutcome};
+use super::models::{
+    BranchPushStatus, CommandError, FetchResult, OrphanedTrackingReport, PullResult, PushRefResult,
+    PushResult, RemoteBranchInfo, RemoteCredential, RemoteFetchResult, RemoteInfo,
+    SyncFinishedEvent, SyncProgress, SyncProgressEvent,
+};
+use super::utils::{command_error, configured_upstream, normalize_unicode, open_repo};
 
-use super::models::{RemoteBranchInfo, RemoteInfo, SyncProgress};
-use super::utils::{normalize_unicode, open_repo};
-
-// Global progress state
 lazy_static::lazy_static! {
-    static ref SYNC_PROGRESS: Arc<Mutex<SyncProgress>> = Arc::new(Mutex::new(SyncProgress {
-        phase: "idle".to_string(),
-        current: 0,
-        total: 0,
-        bytes: 0,
-        message: String::new(),
-    }));
+    /// Progress of each repository's fetch, pull or push, by `progress_key`
+    static ref SYNC_PROGRESS: Mutex<HashMap<String, SyncProgress>> = Mutex::new(HashMap::new());
+    /// Operations that failed with `auth_required`, by operation id
+    static ref PENDING_OPERATIONS: Mutex<HashMap<String, RemoteOperation>> =
+        Mutex::new(HashMap::new());
+    /// Where each repository's running operation reports events, by `progress_key`
+    static ref SYNC_LISTENERS: Mutex<HashMap<String, SyncListener>> = Mutex::new(HashMap::new());
 }
 
-/// Build credential callbacks that try SSH key files (multiple), then credential helper.
-/// On Windows, ssh-agent integration via libgit2 is unreliable, so we prefer key files.
-fn build_credentials_callbacks() -> RemoteCallbacks<'static> {
-    let mut callbacks = RemoteCallbacks::new();
-    let tried_count = std::cell::Cell::new(0u32);
+static NEXT_OPERATION_ID: AtomicU64 = AtomicU64::new(1);
+static NEXT_SYNC_ID: AtomicU64 = AtomicU64::new(1);
 
-    callbacks.credentials(move |_url, username_from_url, allowed_types| {
-        let username = username_from_url.unwrap_or("git");
-        let attempt = tried_count.get();
-        tried_count.set(attempt + 1);
+/// Event carrying a `SyncProgressEvent`.
+const SYNC_PROGRESS_EVENT: &str = "sync-progress";
+/// Event carrying the `SyncFinishedEvent` of a successful operation.
+const SYNC_COMPLETE_EVENT: &str = "sync-complete";
+/// Event carrying the `SyncFinishedEvent` of a failed operation.
+const SYNC_ERROR_EVENT: &str = "sync-error";
+/// Transfer progress is emitted at most this often (about 10 Hz).
+const PROGRESS_EVENT_INTERVAL: Duration = Duration::from_millis(100);
 
-        // SSH key authentication: try multiple key files in priority order
-        if allowed_types.is_ssh_key() {
-            let home = dirs::home_dir().unwrap_or_default();
-            // Priority order: ed25519 > ecdsa > rsa (newest → oldest algorithm)
-            let key_files = ["id_ed25519", "id_ecdsa", "id_rsa", "id_dsa"];
+/// Sends one event (name, payload) of a sync operation.
+type SyncEmitter = Arc<dyn Fn(&str, serde_json::Value) + Send + Sync>;
 
-            // Find the Nth existing key file based on attempt count
-            let existing_keys: Vec<_> = key_files
-                .iter()
-                .map(|k| home.join(".ssh").join(k))
-                .filter(|p| p.exists())
-                .collect();
+/// The operation whose progress events a repository's updates go to.
+struct SyncListener {
+    operation_id: u64,
+    repo_path: String,
+    emit: SyncEmitter,
+    last_transfer_event: Option<Instant>,
+}
 
-            if let Some(key_path) = existing_keys.get(attempt as usize) {
-                return Cred::ssh_key(username, None, key_path, None);
-            }
+/// Whether `push_changes` may overwrite the remote branch.
+#[derive(Clone, Copy, PartialEq)]
+enum ForcePush {
+    No,
+    Yes,
+    /// Only while the remote branch is where its remote-tracking ref says
+    WithLease,
+}
 
-            // All key files exhausted, try ssh-agent as last resort
-            if attempt as usize >= existing_keys.len() {
-                return Cred::ssh_key_from_agent(username);
-            }
-        }
+/// A fetch, pull or push, kept when it fails with `auth_required` so that
+/// `retry_with_credentials` can run it again.
+enum RemoteOperation {
+    Fetch {
+        repo_path: String,
+        remote_name: String,
+        depth: Option<u32>,
+        prune: bool,
+    },
+    Pull {
+        repo_path: String,
+        remote_name: String,
+        branch_name: String,
+    },
+    Push {
+        repo_path: String,
+        remote_name: String,
+        branch_name: String,
+        force: ForcePush,
+        set_upstream: Option<bool>,
+        push_mode_tags: Option<String>,
+    },
+    PushBranches {
+        repo_path: String,
+        remote_name: String,
+        branches: Vec<String>,
+        force: bool,
+    },
+}
 
-        // HTTPS: try git credential helper (Windows Credential Manager, macOS Keychain, etc.)
-        if allowed_types.is_user_pass_plaintext() {
-            return Cred::credential_helper(
-                &git2::Config::open_default().unwrap_or_else(|_| git2::Config::new().unwrap()),
-                _url,
-                username_from_url,
-            );
+impl RemoteOperation {
+    /// Run with `credential` offered first, returning what the operation's
+    /// own command returns.
+    fn run(&self, credential: Option<RemoteCredential>) -> Result<serde_json::Value, String> {
+        match self {
+            RemoteOperation::Fetch {
+                repo_path,
+                remote_name,
+                depth,
+                prune,
+            } => fetch_with(repo_path, remote_name, *depth, *prune, credential)
+                .and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string())),
+            RemoteOperation::Pull {
+                repo_path,
+                remote_name,
+                branch_name,
+            } => pull_with(repo_path, remote_name, branch_name, credential)
+                .and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string())),
+            RemoteOperation::Push {
+                repo_path,
+                remote_name,
+                branch_name,
+                force,
+                set_upstream,
+                push_mode_tags,
+            } => push_with(
+                repo_path,
+                remote_name,
+                branch_name,
+                *force,
+                *set_upstream,
+                push_mode_tags.as_deref(),
+                credential,
+            )
+            .and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string())),
+            RemoteOperation::PushBranches {
+                repo_path,
+                remote_name,
+                branches,
+                force,
+            } => push_branches_with(repo_path, remote_name, branches, *force, credential)
+                .and_then(|result| serde_json::to_value(result).map_err(|e| e.to_string())),
         }
+    }
 
-        // Default (Kerberos, etc.)
-        if allowed_types.is_default() {
-            return Cred::default();
+    fn repo_path(&self) -> &str {
+        match self {
+            RemoteOperation::Fetch { repo_path, .. }
+            | RemoteOperation::Pull { repo_path, .. }
+            | RemoteOperation::Push { repo_path, .. }
+            | RemoteOperation::PushBranches { repo_path, .. } => repo_path,
         }
+    }
 
-        Err(git2::Error::from_str("인증 방법을 찾을 수 없습니다. SSH 키를 ~/.ssh/에 설치하거나 Git Credential Manager를 설정하세요."))
-    });
-
-    callbacks
+    /// `error` as it is, unless it is `auth_required`: the operation is then
+    /// kept for `retry_with_credentials` and its id added as `operation_id`.
+    fn keep_for_retry(self, error: String) -> String {
+        match serde_json::from_str::<CommandError>(&error) {
+            Ok(mut error) if error.code == "auth_required" => {
+                let id = format!("op-{}", NEXT_OPERATION_ID.fetch_add(1, Ordering::Relaxed));
+                if let Some(details) = error.details.as_object_mut() {
+                    details.insert("operation_id".to_string(), id.clone().into());
+                }
+                update_progress(
+                    &progress_key(Path::new(self.repo_path())),
+                    "idle",
+                    "인증 정보가 필요합니다",
+                );
+                PENDING_OPERATIONS.lock().unwrap().insert(id, self);
+                command_error(&error.code, error.message, error.details)
+            }
+            _ => error,
+        }
+    }
 }
 
 /// List all remotes.
@@ -80,26 +174,31 @@ pub async fn list_remotes(repo_path: String) -> Result<Vec<RemoteInfo>, String>
 
     for remote_name in repo.remotes().map_err(|e| format!("원격 목록 조회 실패: {}", e))?.iter() {
         if let Some(name) = remote_name {
-            let remote = repo
-                .find_remote(name)
-                .map_err(|e| format!("원격 '{}' 찾기 실패: {}", name, e))?;
-            let url = remote.url().unwrap_or("").to_string();
-            let push_url = remote
-                .pushurl()
-                .unwrap_or(remote.url().unwrap_or(""))
-                .to_string();
-
-            remotes.push(RemoteInfo {
-                name: normalize_unicode(name),
-                url: url.clone(),
-                fetch_url: url,
-                push_url,
-            });
+            remotes.push(remote_info(&repo, name)?);
         }
     }
     Ok(remotes)
 }
 
+fn remote_info(repo: &Repository, name: &str) -> Result<RemoteInfo, String> {
+    let remote = repo
+        .find_remote(name)
+        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", name, e))?;
+    let url = remote.url().unwrap_or("").to_string();
+    let push_url = remote
+        .pushurl()
+        .unwrap_or(remote.url().unwrap_or(""))
+        .to_string();
+
+    Ok(RemoteInfo {
+        name: normalize_unicode(name),
+        url: url.clone(),
+        fetch_url: url,
+        push_url,
+        separate_push_url: remote.pushurl().is_some(),
+    })
+}
+
 /// Add a new remote.
 #[tauri::command]
 pub async fn add_remote(repo_path: String, name: String, url: String) -> Result<String, String> {
@@ -110,6 +209,105 @@ pub async fn add_remote(repo_path: String, name: String, url: String) -> Result<
     Ok(format!("원격 '{}' 추가 완료", normalized_name))
 }
 
+/// Change a remote's URL, keeping its refspecs and remote-tracking branches.
+/// `push_url` sets a separate URL for pushes (`remote.<name>.pushurl`); an
+/// empty one removes it so pushes use `url` again, and None leaves it as is.
+#[tauri::command]
+pub async fn set_remote_url(
+    repo_path: String,
+    name: String,
+    url: String,
+    push_url: Option<String>,
+) -> Result<RemoteInfo, String> {
+    let normalized_name = normalize_unicode(&name);
+    let repo = open_repo(&repo_path)?;
+    // Fail on an unknown remote rather than creating its config section
+    repo.find_remote(&normalized_name)
+        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", normalized_name, e))?;
+
+    repo.remote_set_url(&normalized_name, &url)
+        .map_err(|e| format!("원격 URL 변경 실패: {}", e))?;
+    if let Some(push_url) = push_url {
+        let push_url = Some(push_url.as_str()).filter(|url| !url.is_empty());
+        repo.remote_set_pushurl(&normalized_name, push_url)
+            .map_err(|e| format!("원격 푸시 URL 변경 실패: {}", e))?;
+    }
+    remote_info(&repo, &normalized_name)
+}
+
+/// Rename a remote. Its remote-tracking branches, the upstream config of
+/// branches tracking it and its default fetch refspec follow the new name.
+/// Returns the non-default fetch refspecs that could not be rewritten and
+/// were left as they were.
+#[tauri::command]
+pub async fn rename_remote(
+    repo_path: String,
+    old_name: String,
+    new_name: String,
+) -> Result<Vec<String>, String> {
+    let normalized_old = normalize_unicode(&old_name);
+    let normalized_new = normalize_unicode(&new_name);
+    let repo = open_repo(&repo_path)?;
+    let refspecs: Vec<String> = repo
+        .find_remote(&normalized_old)
+        .and_then(|remote| remote.fetch_refspecs())
+        .map(|refspecs| refspecs.iter().flatten().map(str::to_string).collect())
+        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", normalized_old, e))?;
+    // Checked up front: the refspecs below are removed before libgit2 would
+    // notice either problem
+    if !git2::Remote::is_valid_name(&normalized_new) {
+        return Err(format!("잘못된 원격 이름입니다: {}", normalized_new));
+    }
+    if repo.find_remote(&normalized_new).is_ok() {
+        return Err(command_error(
+            "remote_exists",
+            format!("원격 '{}'가 이미 존재합니다", normalized_new),
+            serde_json::json!({ "remote": normalized_new }),
+        ));
+    }
+
+    // libgit2 fails halfway through renaming a remote with several fetch
+    // refspecs (a multivar), so those are moved over here the way it would
+    let several = refspecs.len() > 1;
+    if several {
+        repo.config()
+            .and_then(|mut config| {
+                config.remove_multivar(&format!("remote.{}.fetch", normalized_old), ".*")
+            })
+            .map_err(|e| format!("원격 이름 변경 실패: {}", e))?;
+    }
+    let renamed = match repo.remote_rename(&normalized_old, &normalized_new) {
+        Ok(renamed) => renamed,
+        Err(e) => {
+            if several {
+                for refspec in &refspecs {
+                    let _ = repo.remote_add_fetch(&normalized_old, refspec);
+                }
+            }
+            return Err(format!("원격 이름 변경 실패: {}", e));
+        }
+    };
+    let mut problems: Vec<String> = renamed
+        .iter()
+        .flatten()
+        .map(normalize_unicode)
+        .collect();
+    if several {
+        let default = format!("+refs/heads/*:refs/remotes/{}/*", normalized_old);
+        for refspec in refspecs {
+            let renamed = if refspec == default {
+                format!("+refs/heads/*:refs/remotes/{}/*", normalized_new)
+            } else {
+                problems.push(normalize_unicode(&refspec));
+                refspec
+            };
+            repo.remote_add_fetch(&normalized_new, &renamed)
+                .map_err(|e| format!("페치 refspec 추가 실패: {}", e))?;
+        }
+    }
+    Ok(problems)
+}
+
 /// Remove a remote.
 #[tauri::command]
 pub async fn remove_remote(repo_path: String, name: String) -> Result<String, String> {
@@ -121,57 +319,316 @@ pub async fn remove_remote(repo_path: String, name: String) -> Result<String, St
 }
 
 /// Fetch from remote.
+/// With `depth`, only that many commits of each fetched branch are downloaded
+/// (like `git fetch --depth`), leaving or making the repository shallow.
+/// With `prune`, remote-tracking refs whose branch is gone from the remote are
+/// deleted (`git fetch --prune`) and listed in the result.
+///
+/// Progress is emitted as `sync-progress` events, and the outcome as a
+/// `sync-complete` or `sync-error` event.
+#[tauri::command]
+pub async fn fetch_remote(
+    window: tauri::Window,
+    repo_path: String,
+    remote_name: String,
+    depth: Option<u32>,
+    prune: bool,
+) -> Result<FetchResult, String> {
+    fetch_remote_with_events(Some(window), repo_path, remote_name, depth, prune).await
+}
+
+/// `fetch_remote`, emitting its events to `window` when there is one.
+pub async fn fetch_remote_with_events(
+    window: Option<tauri::Window>,
+    repo_path: String,
+    remote_name: String,
+    depth: Option<u32>,
+    prune: bool,
+) -> Result<FetchResult, String> {
+    with_sync_events(window.map(window_emitter), &repo_path.clone(), || {
+        fetch_with(&repo_path, &remote_name, depth, prune, None).map_err(|e| {
+            RemoteOperation::Fetch {
+                repo_path,
+                remote_name,
+                depth,
+                prune,
+            }
+            .keep_for_retry(e)
+        })
+    })
+}
+
+/// Run a fetch, pull or push again after it failed with an `auth_required`
+/// error, offering `credential` first, and return what that command returns.
+/// `operation_id` comes from the error's details and is good for one retry;
+/// failing again returns a new one.
+#[tauri::command]
+pub async fn retry_with_credentials(
+    operation_id: String,
+    credential: RemoteCredential,
+) -> Result<serde_json::Value, String> {
+    let operation = PENDING_OPERATIONS
+        .lock()
+        .unwrap()
+        .remove(&operation_id)
+        .ok_or_else(|| format!("재시도할 작업을 찾을 수 없습니다: {}", operation_id))?;
+    operation
+        .run(Some(credential))
+        .map_err(|e| operation.keep_for_retry(e))
+}
+
+fn fetch_with(
+    repo_path: &str,
+    remote_name: &str,
+    depth: Option<u32>,
+    prune: bool,
+    credential: Option<RemoteCredential>,
+) -> Result<FetchResult, String> {
+    let normalized_name = normalize_unicode(remote_name);
+    let repo = open_repo(repo_path)?;
+
+    let fetched = fetch_one(
+        &repo,
+        &normalized_name,
+        prune,
+        depth.map(fetch_depth),
+        credential,
+    )?;
+
+    update_progress(
+        &repo_progress_key(&repo),
+        "idle",
+        &format!("'{}'에서 페치 완료", normalized_name),
+    );
+    Ok(FetchResult {
+        message: format!("'{}' 페치 완료", normalized_name),
+        pruned_refs: fetched.pruned,
+    })
+}
+
+/// Fetch every remote of the repository, one after the other. A remote that
+/// fails (unreachable, rejected credentials, ...) is reported in its result
+/// and does not stop the others.
 #[tauri::command]
-pub async fn fetch_remote(repo_path: String, remote_name: String) -> Result<String, String> {
+pub async fn fetch_all_remotes(
+    repo_path: String,
+    prune: bool,
+) -> Result<Vec<RemoteFetchResult>, String> {
+    let repo = open_repo(&repo_path)?;
+    let names = repo
+        .remotes()
+        .map_err(|e| format!("원격 목록 조회 실패: {}", e))?;
+
+    let mut results = Vec::new();
+    for name in names.iter().flatten() {
+        let name = normalize_unicode(name);
+        let (updated_refs, error) = match fetch_one(&repo, &name, prune, None, None) {
+            Ok(fetched) => {
+                let mut refs = [fetched.updated, fetched.pruned].concat();
+                refs.sort();
+                (refs, None)
+            }
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        results.push(RemoteFetchResult {
+            remote: name,
+            updated_refs,
+            error,
+        });
+    }
+
+    update_progress(
+        &repo_progress_key(&repo),
+        "idle",
+        &format!("원격 {}개 페치 완료", results.len()),
+    );
+    Ok(results)
+}
+
+/// Fetch one remote into `repo`, returning the refs the fetch changed. The
+/// progress names the remote and stays "fetching" for the caller to end.
+fn fetch_one(
+    repo: &Repository,
+    remote_name: &str,
+    prune: bool,
+    depth: Option<i32>,
+    credential: Option<RemoteCredential>,
+) -> Result<FetchedRefs, String> {
+    update_progress(
+        &repo_progress_key(repo),
+        "fetching",
+        &format!(
+            "'{}'에서 페치{} 중...",
+            remote_name,
+            if prune { "(prune)" } else { "" }
+        ),
+    );
+    run_fetch(repo, remote_name, prune, depth, credential)
+}
+
+/// Fetch the full history of a shallow repository (`git fetch --unshallow`).
+///
+/// Runs the git CLI: libgit2's local transport cannot deepen a shallow clone
+/// and would drop the shallow marker with the history still missing.
+#[tauri::command]
+pub async fn unshallow(repo_path: String, remote_name: String) -> Result<String, String> {
     let normalized_name = normalize_unicode(&remote_name);
     let repo = open_repo(&repo_path)?;
+    if !repo.is_shallow() {
+        return Err("얕은 저장소가 아닙니다".to_string());
+    }
+    let workdir = repo.workdir().unwrap_or_else(|| repo.path()).to_path_buf();
+    let progress = repo_progress_key(&repo);
+
+    update_progress(&progress, "fetching", &format!("'{}'에서 전체 기록 페치 중...", normalized_name));
+
+    let mut cmd = git_command()?;
+    cmd.current_dir(&workdir)
+        .args(["fetch", "--unshallow", "--tags", &normalized_name]);
+    let output = run_with_timeout(&mut cmd, GIT_TIMEOUT);
+    update_progress(&progress, "idle", &format!("'{}'에서 전체 기록 페치 완료", normalized_name));
+    let output = output?;
+    if !output.status.success() {
+        return Err(format!(
+            "전체 기록 페치 실패: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(format!("'{}'에서 전체 기록을 가져왔습니다", normalized_name))
+}
 
-    update_progress("fetching", &format!("'{}'에서 페치 중...", normalized_name));
+/// Prune without fetching: remote-tracking refs whose branch was deleted on the remote
+/// are removed, and only the remote's ref list is downloaded.
+/// Reports the local branches that tracked them; with `also_remove_tracking_config`
+/// their `branch.<name>.remote/merge` keys are cleared (the branches themselves are kept).
+#[tauri::command]
+pub async fn prune_remote(
+    repo_path: String,
+    remote_name: String,
+    also_remove_tracking_config: Option<bool>,
+) -> Result<OrphanedTrackingReport, String> {
+    let normalized_name = normalize_unicode(&remote_name);
+    let repo = open_repo(&repo_path)?;
 
-    let mut remote = repo
-        .find_remote(&normalized_name)
-        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", normalized_name, e))?;
+    let progress = repo_progress_key(&repo);
+    update_progress(&progress, "fetching", &format!("'{}' 정리 중...", normalized_name));
+    let removed_refs = prune_tracking_refs(&repo, &normalized_name);
+    update_progress(&progress, "idle", &format!("'{}' 정리 완료", normalized_name));
+    let removed_refs = removed_refs?;
 
-    let mut callbacks = build_credentials_callbacks();
-    callbacks.transfer_progress(|progress| {
-        let mut sync_progress = SYNC_PROGRESS.lock().unwrap();
-        sync_progress.current = progress.received_objects() as u32;
-        sync_progress.total = progress.total_objects() as u32;
-        sync_progress.bytes = progress.received_bytes() as u64;
-        true
+    report_orphaned_tracking(&repo, removed_refs, also_remove_tracking_config.unwrap_or(false))
+}
+
+/// Delete a branch on the remote (`git push <remote> :<branch>`) and its remote-tracking ref.
+/// Reports local branches that tracked it; see `prune_remote` for `also_remove_tracking_config`.
+#[tauri::command]
+pub async fn delete_remote_branch(
+    repo_path: String,
+    remote_name: String,
+    branch_name: String,
+    also_remove_tracking_config: Option<bool>,
+) -> Result<OrphanedTrackingReport, String> {
+    let normalized_remote = normalize_unicode(&remote_name);
+    let normalized_branch = normalize_unicode(&branch_name);
+    let repo = open_repo(&repo_path)?;
+
+    let mut remote = find_remote(&repo, &normalized_remote)?;
+
+    let rejection = Arc::new(Mutex::new(None::<String>));
+    let rejection_cb = Arc::clone(&rejection);
+    let mut callbacks = credential_callbacks(Some(&repo), None);
+    callbacks.push_update_reference(move |_refname, status| {
+        if let Some(msg) = status {
+            *rejection_cb.lock().unwrap() = Some(msg.to_string());
+        }
+        Ok(())
     });
 
-    let mut fetch_options = FetchOptions::new();
-    fetch_options.remote_callbacks(callbacks);
-    fetch_options.download_tags(AutotagOption::All);
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(callbacks);
 
+    let refspec = format!(":refs/heads/{}", normalized_branch);
     remote
-        .fetch(&[] as &[&str], Some(&mut fetch_options), None)
-        .map_err(|e| format!("페치 실패: {}", e))?;
+        .push(&[&refspec], Some(&mut push_options))
+        .map_err(|e| remote_error("원격 브랜치 삭제 실패", e))?;
+
+    if let Some(msg) = rejection.lock().unwrap().take() {
+        return Err(format!("원격 브랜치 삭제 거부됨: {}", msg));
+    }
+
+    // libgit2 normally drops the tracking ref while updating tips; make sure it is gone
+    let tracking_ref = format!("refs/remotes/{}/{}", normalized_remote, normalized_branch);
+    if let Ok(mut reference) = repo.find_reference(&tracking_ref) {
+        reference
+            .delete()
+            .map_err(|e| format!("원격 추적 브랜치 삭제 실패: {}", e))?;
+    }
 
-    update_progress("idle", &format!("'{}'에서 페치 완료", normalized_name));
-    Ok(format!("'{}' 페치 완료", normalized_name))
+    report_orphaned_tracking(
+        &repo,
+        vec![tracking_ref],
+        also_remove_tracking_config.unwrap_or(false),
+    )
 }
 
 /// Pull changes from remote.
+///
+/// Fetches, then fast-forwards the branch or, when both sides have moved,
+/// merges `<remote>/<branch>` into HEAD as "Merge branch '<branch>' of
+/// <url>". A conflicted merge is not an error: the result lists the
+/// conflicted files and the merge is left in progress, to be resolved with
+/// `resolve_conflict` or given up with `abort_merge`.
+///
+/// Progress and outcome are emitted as with `fetch_remote`.
 #[tauri::command]
 pub async fn pull_changes(
+    window: tauri::Window,
     repo_path: String,
     remote_name: String,
     branch_name: String,
-) -> Result<String, String> {
-    let normalized_remote = normalize_unicode(&remote_name);
-    let normalized_branch = normalize_unicode(&branch_name);
+) -> Result<PullResult, String> {
+    pull_changes_with_events(Some(window), repo_path, remote_name, branch_name).await
+}
+
+/// `pull_changes`, emitting its events to `window` when there is one.
+pub async fn pull_changes_with_events(
+    window: Option<tauri::Window>,
+    repo_path: String,
+    remote_name: String,
+    branch_name: String,
+) -> Result<PullResult, String> {
+    with_sync_events(window.map(window_emitter), &repo_path.clone(), || {
+        pull_with(&repo_path, &remote_name, &branch_name, None).map_err(|e| {
+            RemoteOperation::Pull {
+                repo_path,
+                remote_name,
+                branch_name,
+            }
+            .keep_for_retry(e)
+        })
+    })
+}
+
+fn pull_with(
+    repo_path: &str,
+    remote_name: &str,
+    branch_name: &str,
+    credential: Option<RemoteCredential>,
+) -> Result<PullResult, String> {
+    let normalized_remote = normalize_unicode(remote_name);
+    let normalized_branch = normalize_unicode(branch_name);
+    let progress = progress_key(Path::new(repo_path));
 
     update_progress(
+        &progress,
         "pulling",
         &format!("'{}/{}'에서 풀 중...", normalized_remote, normalized_branch),
     );
 
     // Fetch first
-    fetch_remote(repo_path.clone(), normalized_remote.clone()).await?;
+    fetch_with(repo_path, &normalized_remote, None, false, credential)?;
 
-    let repo = open_repo(&repo_path)?;
+    let repo = open_repo(repo_path)?;
 
     let remote_branch_name = format!("{}/{}", normalized_remote, normalized_branch);
     let remote_branch = repo
@@ -191,8 +648,8 @@ pub async fn pull_changes(
         .map_err(|e| format!("병합 분석 실패: {}", e))?;
 
     if merge_analysis.is_up_to_date() {
-        update_progress("idle", "이미 최신 상태입니다");
-        return Ok("이미 최신 상태입니다".to_string());
+        update_progress(&progress, "idle", "이미 최신 상태입니다");
+        return Ok(pull_result("up_to_date", "이미 최신 상태입니다"));
     }
 
     let refname = format!("refs/heads/{}", normalized_branch);
@@ -210,123 +667,540 @@ pub async fn pull_changes(
         repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
             .map_err(|e| format!("체크아웃 실패: {}", e))?;
 
-        update_progress("idle", &format!("Fast-forward 완료: {}", remote_commit.id()));
-        Ok("풀 성공 (fast-forward)".to_string())
+        update_progress(
+            &progress,
+            "idle",
+            &format!("Fast-forward 완료: {}", remote_commit.id()),
+        );
+        Ok(pull_result("fast_forward", "풀 성공 (fast-forward)"))
     } else if merge_analysis.is_normal() {
-        // Non-fast-forward: perform a merge commit
-        let head_commit = repo
-            .head()
-            .map_err(|e| format!("HEAD 접근 실패: {}", e))?
-            .peel_to_commit()
-            .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?;
-
-        let mut index = repo
-            .merge_commits(&head_commit, &remote_commit, None)
-            .map_err(|e| format!("병합 실패: {}", e))?;
-
-        if index.has_conflicts() {
-            // Write the index with conflicts so the user can resolve them
-            index
-                .write_tree_to(&repo)
-                .map_err(|e| format!("충돌 인덱스 쓰기 실패: {}", e))?;
-            update_progress("idle", "충돌 발생");
-            return Err(
-                "풀 실패: 충돌이 발생했습니다. 충돌을 해결한 후 커밋하세요.".to_string(),
-            );
+        // Both sides moved: merge the remote-tracking commit into HEAD
+        let remote_url = repo
+            .find_remote(&normalized_remote)
+            .ok()
+            .and_then(|remote| remote.url().map(str::to_string))
+            .unwrap_or_else(|| normalized_remote.clone());
+        let merge_msg = format!("Merge branch '{}' of {}", normalized_branch, remote_url);
+
+        match merge_into_head(&repo, &remote_commit, &merge_msg)? {
+            MergeOutcome::Committed(created) => {
+                update_progress(&progress, "idle", "병합 완료");
+                Ok(PullResult {
+                    commit_sha: Some(created.oid.to_string()),
+                    ..pull_result("merged", "풀 성공 (merge commit)")
+                })
+            }
+            MergeOutcome::Conflicts(paths) => {
+                update_progress(&progress, "idle", "충돌 발생");
+                Ok(PullResult {
+                    conflicted_files: paths,
+                    ..pull_result(
+                        "conflicts",
+                        "충돌이 발생했습니다. 충돌을 해결한 후 커밋하세요.",
+                    )
+                })
+            }
         }
-
-        let tree_id = index
-            .write_tree_to(&repo)
-            .map_err(|e| format!("병합 트리 쓰기 실패: {}", e))?;
-        let tree = repo
-            .find_tree(tree_id)
-            .map_err(|e| format!("트리 찾기 실패: {}", e))?;
-
-        let sig = repo
-            .signature()
-            .map_err(|e| format!("서명 가져오기 실패: {}", e))?;
-
-        let merge_msg = format!(
-            "Merge remote-tracking branch '{}/{}'",
-            normalized_remote, normalized_branch
-        );
-        repo.commit(
-            Some("HEAD"),
-            &sig,
-            &sig,
-            &merge_msg,
-            &tree,
-            &[&head_commit, &remote_commit],
-        )
-        .map_err(|e| format!("병합 커밋 생성 실패: {}", e))?;
-
-        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))
-            .map_err(|e| format!("체크아웃 실패: {}", e))?;
-
-        update_progress("idle", "병합 완료");
-        Ok("풀 성공 (merge commit)".to_string())
     } else {
-        update_progress("idle", "병합 불가");
+        update_progress(&progress, "idle", "병합 불가");
         Err("풀 실패: 병합을 진행할 수 없는 상태입니다.".to_string())
     }
 }
 
+fn pull_result(kind: &str, message: &str) -> PullResult {
+    PullResult {
+        kind: kind.to_string(),
+        message: message.to_string(),
+        commit_sha: None,
+        conflicted_files: Vec::new(),
+    }
+}
+
 /// Push changes to remote.
+/// With `set_upstream` (by default when the branch has no upstream yet), an
+/// accepted push makes `<remote>/<branch>` the branch's upstream, like
+/// `git push -u`.
+///
+/// `push_mode_tags` adds tags to the push: "follow" the annotated tags on
+/// the pushed history (`--follow-tags`), "all" every local tag (`--tags`);
+/// "none" by default. Tags are never forced. The branch being rejected fails
+/// the push; a rejected tag only shows in the result's `refs`.
+///
+/// `force_with_lease` (which wins over `force`) overwrites the remote branch
+/// only if it is still where `refs/remotes/<remote>/<branch>` says, or
+/// absent when there is no such ref. Otherwise the push fails with a
+/// `lease_failed` error carrying `remote_sha` and `tracking_sha` (null when
+/// absent); fetching updates the lease.
+///
+/// Progress and outcome are emitted as with `fetch_remote`.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn push_changes(
+    window: tauri::Window,
     repo_path: String,
     remote_name: String,
     branch_name: String,
     force: bool,
-) -> Result<String, String> {
-    let normalized_remote = normalize_unicode(&remote_name);
-    let normalized_branch = normalize_unicode(&branch_name);
+    set_upstream: Option<bool>,
+    push_mode_tags: Option<String>,
+    force_with_lease: Option<bool>,
+) -> Result<PushResult, String> {
+    push_changes_with_events(
+        Some(window),
+        repo_path,
+        remote_name,
+        branch_name,
+        force,
+        set_upstream,
+        push_mode_tags,
+        force_with_lease,
+    )
+    .await
+}
+
+/// `push_changes`, emitting its events to `window` when there is one.
+#[allow(clippy::too_many_arguments)]
+pub async fn push_changes_with_events(
+    window: Option<tauri::Window>,
+    repo_path: String,
+    remote_name: String,
+    branch_name: String,
+    force: bool,
+    set_upstream: Option<bool>,
+    push_mode_tags: Option<String>,
+    force_with_lease: Option<bool>,
+) -> Result<PushResult, String> {
+    let force = match (force_with_lease.unwrap_or(false), force) {
+        (true, _) => ForcePush::WithLease,
+        (false, true) => ForcePush::Yes,
+        (false, false) => ForcePush::No,
+    };
+    with_sync_events(window.map(window_emitter), &repo_path.clone(), || {
+        push_with(
+            &repo_path,
+            &remote_name,
+            &branch_name,
+            force,
+            set_upstream,
+            push_mode_tags.as_deref(),
+            None,
+        )
+        .map_err(|e| {
+            RemoteOperation::Push {
+                repo_path,
+                remote_name,
+                branch_name,
+                force,
+                set_upstream,
+                push_mode_tags,
+            }
+            .keep_for_retry(e)
+        })
+    })
+}
+
+fn push_with(
+    repo_path: &str,
+    remote_name: &str,
+    branch_name: &str,
+    force: ForcePush,
+    set_upstream: Option<bool>,
+    push_mode_tags: Option<&str>,
+    credential: Option<RemoteCredential>,
+) -> Result<PushResult, String> {
+    let normalized_remote = normalize_unicode(remote_name);
+    let normalized_branch = normalize_unicode(branch_name);
+    let branch_ref = format!("refs/heads/{}", normalized_branch);
+    let progress = progress_key(Path::new(repo_path));
 
     update_progress(
+        &progress,
         "pushing",
         &format!("'{}/{}'에 푸시 중...", normalized_remote, normalized_branch),
     );
 
-    let repo = open_repo(&repo_path)?;
-    let mut remote = repo
-        .find_remote(&normalized_remote)
-        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", normalized_remote, e))?;
+    let repo = open_repo(repo_path)?;
+    let tip = repo
+        .refname_to_id(&branch_ref)
+        .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", normalized_branch, e))?;
+    let tracking_ref = format!("refs/remotes/{}/{}", normalized_remote, normalized_branch);
+    let mut refspecs = vec![format!(
+        "{}{}:{}",
+        if force == ForcePush::No { "" } else { "+" },
+        branch_ref,
+        branch_ref
+    )];
+    let tags = tags_to_push(&repo, push_mode_tags.unwrap_or("none"), tip)?;
+    let mut remote = find_remote(&repo, &normalized_remote)?;
+
+    // libgit2 fails the whole push on a tag the remote has with another
+    // target, so those are left out and reported here
+    let mut rejected_tags = Vec::new();
+    if !tags.is_empty() {
+        let advertised = advertised_refs(&repo, &mut remote, Direction::Push, credential.clone())?;
+        for (refname, id) in tags {
+            match advertised.get(&refname) {
+                None => refspecs.push(format!("{}:{}", refname, refname)),
+                Some(remote_id) if *remote_id == id => {}
+                Some(_) => rejected_tags.push(PushRefResult {
+                    refname: normalize_unicode(&refname),
+                    accepted: false,
+                    message: Some("원격에 대상이 다른 같은 이름의 태그가 있습니다".to_string()),
+                }),
+            }
+        }
+    }
 
-    let mut callbacks = build_credentials_callbacks();
-    callbacks.push_transfer_progress(|current, total, bytes| {
-        let mut progress = SYNC_PROGRESS.lock().unwrap();
-        progress.current = current as u32;
-        progress.total = total as u32;
-        progress.bytes = bytes as u64;
+    let updates = Arc::new(Mutex::new(Vec::<PushRefResult>::new()));
+    let updates_cb = Arc::clone(&updates);
+    let mut callbacks = credential_callbacks(Some(&repo), credential);
+    let progress_cb = progress.clone();
+    callbacks.push_transfer_progress(move |current, total, bytes| {
+        edit_progress(&progress_cb, |progress| {
+            progress.current = current as u32;
+            progress.total = total as u32;
+            progress.bytes = bytes as u64;
+        });
     });
+    callbacks.push_update_reference(move |refname, status| {
+        updates_cb.lock().unwrap().push(PushRefResult {
+            refname: normalize_unicode(refname),
+            accepted: status.is_none(),
+            message: status.map(str::to_string),
+        });
+        Ok(())
+    });
+
+    // Checked against what the remote advertises on the push connection itself
+    let lease_failure = Arc::new(Mutex::new(None::<String>));
+    if force == ForcePush::WithLease {
+        let expected = repo.refname_to_id(&tracking_ref).ok();
+        let lease_failure_cb = Arc::clone(&lease_failure);
+        let branch_ref = branch_ref.clone();
+        let (remote_label, branch_label) = (normalized_remote.clone(), normalized_branch.clone());
+        callbacks.push_negotiation(move |updates| {
+            for update in updates {
+                if update.dst_refname() != Some(branch_ref.as_str()) {
+                    continue;
+                }
+                let remote_tip = Some(update.src()).filter(|id| !id.is_zero());
+                if remote_tip != expected {
+                    *lease_failure_cb.lock().unwrap() = Some(command_error(
+                        "lease_failed",
+                        format!(
+                            "'{}/{}'이(가) 마지막 페치 이후 바뀌었습니다. 페치해서 확인한 뒤 다시 푸시하세요.",
+                            remote_label, branch_label
+                        ),
+                        serde_json::json!({
+                            "remote": remote_label,
+                            "branch": branch_label,
+                            "remote_sha": remote_tip.map(|id| id.to_string()),
+                            "tracking_sha": expected.map(|id| id.to_string()),
+                        }),
+                    ));
+                    return Err(git2::Error::from_str("lease failed"));
+                }
+            }
+            Ok(())
+        });
+    }
 
     let mut push_options = PushOptions::new();
     push_options.remote_callbacks(callbacks);
 
-    let refspec = if force {
-        format!(
-            "+refs/heads/{}:refs/heads/{}",
-            normalized_branch, normalized_branch
-        )
-    } else {
-        format!(
-            "refs/heads/{}:refs/heads/{}",
-            normalized_branch, normalized_branch
-        )
-    };
+    // git2 drops a push_negotiation error's message, hence `lease_failure`
+    if let Err(e) = remote.push(&refspecs, Some(&mut push_options)) {
+        update_progress(&progress, "idle", "푸시 실패");
+        return Err(lease_failure
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or_else(|| remote_error("푸시 실패", e)));
+    }
 
-    remote
-        .push(&[&refspec], Some(&mut push_options))
-        .map_err(|e| format!("푸시 실패: {}", e))?;
+    let mut refs = std::mem::take(&mut *updates.lock().unwrap());
+    let branch_update = refs.iter().find(|update| update.refname == branch_ref);
+    if let Some(msg) = branch_update.and_then(|update| update.message.as_deref()) {
+        update_progress(&progress, "idle", "푸시 거부됨");
+        return Err(format!("푸시 거부됨: {}", msg));
+    }
 
-    update_progress(
-        "idle",
-        &format!("'{}/{}' 푸시 완료", normalized_remote, normalized_branch),
-    );
-    Ok(format!(
-        "'{}/{}' 푸시 완료",
-        normalized_remote, normalized_branch
-    ))
+    let mut upstream = configured_upstream(&repo, &normalized_branch);
+    if branch_update.is_some() {
+        repo.reference(&tracking_ref, tip, true, "update by push")
+            .map_err(|e| format!("원격 추적 브랜치 갱신 실패: {}", e))?;
+
+        if set_upstream.unwrap_or(upstream.is_none()) {
+            let mut config = repo
+                .config()
+                .map_err(|e| format!("설정 열기 실패: {}", e))?;
+            config
+                .set_str(
+                    &format!("branch.{}.remote", normalized_branch),
+                    &normalized_remote,
+                )
+                .and_then(|_| {
+                    config.set_str(
+                        &format!("branch.{}.merge", normalized_branch),
+                        &format!("refs/heads/{}", normalized_branch),
+                    )
+                })
+                .map_err(|e| format!("업스트림 설정 실패: {}", e))?;
+            upstream = Some(tracking_ref);
+        }
+    }
+
+    refs.extend(rejected_tags);
+    let message = format!("'{}/{}' 푸시 완료", normalized_remote, normalized_branch);
+    update_progress(&progress, "idle", &message);
+    Ok(PushResult {
+        message,
+        upstream: upstream
+            .map(|u| normalize_unicode(u.strip_prefix("refs/remotes/").unwrap_or(&u))),
+        refs,
+    })
+}
+
+/// Tags `mode` adds to a push of `tip`, as (ref name, target): "all" local
+/// tags, or with "follow" the annotated tags pointing at `tip` or its history.
+fn tags_to_push(repo: &Repository, mode: &str, tip: Oid) -> Result<Vec<(String, Oid)>, String> {
+    if mode == "none" {
+        return Ok(Vec::new());
+    }
+    if mode != "follow" && mode != "all" {
+        return Err(format!("알 수 없는 태그 푸시 방식: {}", mode));
+    }
+    let names = repo
+        .tag_names(None)
+        .map_err(|e| format!("태그 목록 조회 실패: {}", e))?;
+    let mut tags = Vec::new();
+    for name in names.iter().flatten() {
+        let refname = format!("refs/tags/{}", name);
+        let Ok(id) = repo.refname_to_id(&refname) else {
+            continue;
+        };
+        if mode == "follow" {
+            // Lightweight tags do not peel to a tag object and are left out
+            let target = repo
+                .find_reference(&refname)
+                .and_then(|reference| reference.peel_to_tag())
+                .and_then(|tag| tag.target())
+                .and_then(|target| target.peel_to_commit());
+            let on_history = target.is_ok_and(|commit| {
+                commit.id() == tip || repo.graph_descendant_of(tip, commit.id()).unwrap_or(false)
+            });
+            if !on_history {
+                continue;
+            }
+        }
+        tags.push((refname, id));
+    }
+    Ok(tags)
+}
+
+/// The refs `remote` advertises when connecting in `direction`, by name.
+fn advertised_refs(
+    repo: &Repository,
+    remote: &mut Remote,
+    direction: Direction,
+    credential: Option<RemoteCredential>,
+) -> Result<HashMap<String, Oid>, String> {
+    let callbacks = credential_callbacks(Some(repo), credential);
+    let connection = remote
+        .connect_auth(direction, Some(callbacks), None)
+        .map_err(|e| remote_error("원격 연결 실패", e))?;
+    let heads = connection
+        .list()
+        .map_err(|e| format!("원격 참조 목록 조회 실패: {}", e))?;
+    Ok(heads
+        .iter()
+        .map(|head| (head.name().to_string(), head.oid()))
+        .collect())
+}
+
+/// Push several branches to `remote_name` in one go, each to the branch of
+/// the same name. A branch the remote has moved past is reported "rejected"
+/// without being sent (unless `force`), so the others still go through.
+#[tauri::command]
+pub async fn push_branches(
+    repo_path: String,
+    remote_name: String,
+    branches: Vec<String>,
+    force: bool,
+) -> Result<Vec<BranchPushStatus>, String> {
+    push_branches_with(&repo_path, &remote_name, &branches, force, None).map_err(|e| {
+        RemoteOperation::PushBranches {
+            repo_path,
+            remote_name,
+            branches,
+            force,
+        }
+        .keep_for_retry(e)
+    })
+}
+
+/// `push_branches` for every local branch whose upstream is on
+/// `remote_name`, e.g. to mirror work to a backup remote.
+#[tauri::command]
+pub async fn push_all_branches(
+    repo_path: String,
+    remote_name: String,
+    force: bool,
+) -> Result<Vec<BranchPushStatus>, String> {
+    let normalized_remote = normalize_unicode(&remote_name);
+    let repo = open_repo(&repo_path)?;
+    let config = repo
+        .config()
+        .and_then(|mut config| config.snapshot())
+        .map_err(|e| format!("설정 열기 실패: {}", e))?;
+    let mut branches = Vec::new();
+    let local = repo
+        .branches(Some(BranchType::Local))
+        .map_err(|e| format!("브랜치 목록 조회 실패: {}", e))?;
+    for (branch, _) in local.flatten() {
+        if let Ok(Some(name)) = branch.name() {
+            let remote = config.get_string(&format!("branch.{}.remote", name));
+            if remote.is_ok_and(|remote| normalize_unicode(&remote) == normalized_remote) {
+                branches.push(normalize_unicode(name));
+            }
+        }
+    }
+    push_branches(repo_path, normalized_remote, branches, force).await
+}
+
+fn push_branches_with(
+    repo_path: &str,
+    remote_name: &str,
+    branches: &[String],
+    force: bool,
+    credential: Option<RemoteCredential>,
+) -> Result<Vec<BranchPushStatus>, String> {
+    let normalized_remote = normalize_unicode(remote_name);
+    let repo = open_repo(repo_path)?;
+    let mut tips = Vec::new();
+    for branch in branches {
+        let branch = normalize_unicode(branch);
+        let tip = repo
+            .refname_to_id(&format!("refs/heads/{}", branch))
+            .map_err(|e| format!("브랜치 '{}' 찾기 실패: {}", branch, e))?;
+        tips.push((branch, tip));
+    }
+    if tips.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let progress = repo_progress_key(&repo);
+    update_progress(&progress, "pushing", &format!("'{}'에 브랜치 푸시 중...", normalized_remote));
+    let mut remote = find_remote(&repo, &normalized_remote)?;
+    let advertised = advertised_refs(&repo, &mut remote, Direction::Push, credential.clone())?;
+
+    // What is decided before sending; the rest waits for the remote's answer
+    let mut statuses: HashMap<String, BranchPushStatus> = HashMap::new();
+    let mut refspecs = Vec::new();
+    let force_prefix = if force { "+" } else { "" };
+    for (branch, tip) in &tips {
+        let refname = format!("refs/heads/{}", branch);
+        let decided = match advertised.get(&refname) {
+            Some(remote_id) if remote_id == tip => Some(("up_to_date", None)),
+            // libgit2 would fail the whole push on it
+            Some(remote_id)
+                if !force && !repo.graph_descendant_of(*tip, *remote_id).unwrap_or(false) =>
+            {
+                Some((
+                    "rejected",
+                    Some("원격에 로컬에 없는 커밋이 있습니다 (fast-forward 불가)".to_string()),
+                ))
+            }
+            _ => None,
+        };
+        match decided {
+            Some((status, message)) => {
+                statuses.insert(
+                    refname,
+                    BranchPushStatus {
+                        branch: branch.clone(),
+                        status: status.to_string(),
+                        message,
+                    },
+                );
+            }
+            None => refspecs.push(format!("{}{}:{}", force_prefix, refname, refname)),
+        }
+    }
+
+    edit_progress(&progress, |progress| {
+        progress.current = statuses.len() as u32;
+        progress.total = tips.len() as u32;
+    });
+    if !refspecs.is_empty() {
+        let updates = Arc::new(Mutex::new(Vec::<(String, Option<String>)>::new()));
+        let updates_cb = Arc::clone(&updates);
+        let mut callbacks = credential_callbacks(Some(&repo), credential);
+        let progress_cb = progress.clone();
+        callbacks.push_update_reference(move |refname, status| {
+            updates_cb
+                .lock()
+                .unwrap()
+                .push((refname.to_string(), status.map(str::to_string)));
+            edit_progress(&progress_cb, |progress| {
+                progress.current += 1;
+                progress.message = format!(
+                    "{} 푸시 완료 ({}/{})",
+                    refname.trim_start_matches("refs/heads/"),
+                    progress.current,
+                    progress.total
+                );
+            });
+            Ok(())
+        });
+        let mut push_options = PushOptions::new();
+        push_options.remote_callbacks(callbacks);
+        remote
+            .push(&refspecs, Some(&mut push_options))
+            .map_err(|e| remote_error("푸시 실패", e))?;
+
+        for (refname, rejection) in updates.lock().unwrap().drain(..) {
+            let refname = normalize_unicode(&refname);
+            let Some((branch, tip)) = tips
+                .iter()
+                .find(|(branch, _)| refname == format!("refs/heads/{}", branch))
+            else {
+                continue;
+            };
+            if rejection.is_none() {
+                let tracking_ref = format!("refs/remotes/{}/{}", normalized_remote, branch);
+                repo.reference(&tracking_ref, *tip, true, "update by push")
+                    .map_err(|e| format!("원격 추적 브랜치 갱신 실패: {}", e))?;
+            }
+            let status = match rejection {
+                Some(_) => "rejected",
+                None => "pushed",
+            };
+            statuses.insert(
+                refname,
+                BranchPushStatus {
+                    branch: branch.clone(),
+                    status: status.to_string(),
+                    message: rejection,
+                },
+            );
+        }
+    }
+
+    update_progress(&progress, "idle", &format!("'{}'에 브랜치 푸시 완료", normalized_remote));
+    Ok(tips
+        .iter()
+        .map(|(branch, _)| {
+            let refname = format!("refs/heads/{}", branch);
+            statuses
+                .remove(&refname)
+                .unwrap_or_else(|| BranchPushStatus {
+                    branch: branch.clone(),
+                    status: "rejected".to_string(),
+                    message: Some("원격이 결과를 알리지 않았습니다".to_string()),
+                })
+        })
+        .collect())
 }
 
 /// Get remote branches.
@@ -378,11 +1252,12 @@ pub async fn get_remote_branches(
     Ok(branches)
 }
 
-/// Get sync progress.
+/// Get the sync progress of `repo_path`; idle when nothing ran there yet.
 #[tauri::command]
-pub async fn get_sync_progress(_repo_path: String) -> Result<SyncProgress, String> {
-    let progress = SYNC_PROGRESS.lock().unwrap().clone();
-    Ok(progress)
+pub async fn get_sync_progress(repo_path: String) -> Result<SyncProgress, String> {
+    let key = progress_key(Path::new(&repo_path));
+    let progress = SYNC_PROGRESS.lock().unwrap().get(&key).cloned();
+    Ok(progress.unwrap_or_else(idle_progress))
 }
 
 /// Check remote connection.
@@ -394,30 +1269,1342 @@ pub async fn check_remote_connection(
     let normalized_name = normalize_unicode(&remote_name);
     let repo = open_repo(&repo_path)?;
 
-    let mut remote = repo
-        .find_remote(&normalized_name)
-        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", normalized_name, e))?;
+    let mut remote = find_remote(&repo, &normalized_name)?;
 
-    let callbacks = build_credentials_callbacks();
-    remote
+    let callbacks = credential_callbacks(Some(&repo), None);
+    // The connection disconnects when dropped, so ask it before then
+    let mut connection = remote
         .connect_auth(Direction::Fetch, Some(callbacks), None)
-        .map_err(|e| format!("원격 연결 실패: {}", e))?;
-
-    let connected = remote.connected();
-    remote.disconnect().ok();
-    Ok(connected)
+        .map_err(|e| remote_error("원격 연결 실패", e))?;
+    Ok(connection.connected())
 }
 
 // ============================================================================
 // Helpers
 // ============================================================================
 
-fn update_progress(phase: &str, message: &str) {
-    let mut progress = SYNC_PROGRESS.lock().unwrap();
-    progress.phase = phase.to_string();
-    progress.message = message.to_string();
-    if phase == "idle" {
-        progress.current = 0;
-        progress.total = 0;
+/// Fetch the remote's configured refspecs, reporting progress into `SYNC_PROGRESS`.
+/// `FetchOptions::depth` for a requested depth; 0 means no limit there and
+/// `i32::MAX` is libgit2's "unshallow", so larger depths are capped below it.
+pub(super) fn fetch_depth(depth: u32) -> i32 {
+    depth.min(i32::MAX as u32 - 1) as i32
+}
+
+/// Credential callbacks (see `credential_callbacks`) that also report
+/// transfer progress to `get_sync_progress`, under `progress_key`.
+pub(super) fn fetch_progress_callbacks(
+    progress_key: String,
+    repo: Option<&Repository>,
+    credential: Option<RemoteCredential>,
+) -> RemoteCallbacks<'static> {
+    let mut callbacks = credential_callbacks(repo, credential);
+    callbacks.transfer_progress(move |progress| {
+        edit_progress(&progress_key, |sync_progress| {
+            sync_progress.current = progress.received_objects() as u32;
+            sync_progress.total = progress.total_objects() as u32;
+            sync_progress.bytes = progress.received_bytes() as u64;
+        });
+        true
+    });
+    callbacks
+}
+
+fn run_fetch(
+    repo: &Repository,
+    remote_name: &str,
+    prune: bool,
+    depth: Option<i32>,
+    credential: Option<RemoteCredential>,
+) -> Result<FetchedRefs, String> {
+    let mut remote = find_remote(repo, remote_name)?;
+    // An anonymous remote for an SSH alias has no refspecs of its own
+    let refspecs = match remote.name() {
+        Some(_) => Vec::new(),
+        None => repo
+            .find_remote(remote_name)
+            .and_then(|named| named.fetch_refspecs())
+            .map(|refspecs| refspecs.iter().flatten().map(str::to_string).collect())
+            .map_err(|e| format!("원격 '{}' 찾기 실패: {}", remote_name, e))?,
+    };
+
+    let fetched = Arc::new(Mutex::new(FetchedRefs::default()));
+    let fetched_cb = Arc::clone(&fetched);
+    let mut callbacks = fetch_progress_callbacks(repo_progress_key(repo), Some(repo), credential);
+    callbacks.update_tips(move |refname, _old, new| {
+        let mut fetched = fetched_cb.lock().unwrap();
+        // Pruned refs are reported with a zero new id
+        if new.is_zero() {
+            fetched.pruned.push(normalize_unicode(refname));
+        } else {
+            fetched.updated.push(normalize_unicode(refname));
+        }
+        true
+    });
+
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(callbacks);
+    fetch_options.download_tags(AutotagOption::All);
+    if prune {
+        fetch_options.prune(FetchPrune::On);
+    }
+    if let Some(depth) = depth {
+        fetch_options.depth(depth);
+    }
+
+    remote
+        .fetch(&refspecs, Some(&mut fetch_options), None)
+        .map_err(|e| remote_error("페치 실패", e))?;
+    let mut fetched = std::mem::take(&mut *fetched.lock().unwrap());
+    fetched.updated.sort();
+    fetched.pruned.sort();
+    Ok(fetched)
+}
+
+/// Refs a fetch changed, as its `update_tips` callback reported them.
+#[derive(Default)]
+struct FetchedRefs {
+    /// Created or moved
+    updated: Vec<String>,
+    /// Deleted by pruning
+    pruned: Vec<String>,
+}
+
+/// Delete the remote-tracking refs of `remote_name` whose remote branch is
+/// gone, like `git remote prune`: only the remote's ref list is downloaded.
+/// Each ref is mapped back through the remote's fetch refspecs. Returns the
+/// deleted refs, sorted.
+fn prune_tracking_refs(repo: &Repository, remote_name: &str) -> Result<Vec<String>, String> {
+    let named = repo
+        .find_remote(remote_name)
+        .map_err(|e| format!("원격 '{}' 찾기 실패: {}", remote_name, e))?;
+    let mut remote = find_remote(repo, remote_name)?;
+    let advertised = advertised_refs(repo, &mut remote, Direction::Fetch, None)?;
+
+    let mut pruned = Vec::new();
+    for refname in remote_tracking_refs(repo, remote_name)? {
+        let mut reference = match repo.find_reference(&refname) {
+            Ok(reference) if reference.kind() == Some(ReferenceType::Direct) => reference,
+            // `<remote>/HEAD` is symbolic and follows the remote's default branch
+            _ => continue,
+        };
+        let source = named
+            .refspecs()
+            .filter(|spec| spec.direction() == Direction::Fetch && spec.dst_matches(&refname))
+            .find_map(|spec| spec.rtransform(&refname).ok())
+            .and_then(|buf| buf.as_str().map(str::to_string));
+        let Some(source) = source else {
+            continue;
+        };
+        if !advertised.contains_key(&source) {
+            reference
+                .delete()
+                .map_err(|e| format!("원격 추적 브랜치 삭제 실패: {}", e))?;
+            pruned.push(normalize_unicode(&refname));
+        }
+    }
+    pruned.sort();
+    Ok(pruned)
+}
+
+/// Full names of the remote-tracking refs under `refs/remotes/<remote>/`.
+fn remote_tracking_refs(repo: &Repository, remote_name: &str) -> Result<HashSet<String>, String> {
+    let refs = repo
+        .references_glob(&format!("refs/remotes/{}/*", remote_name))
+        .map_err(|e| format!("원격 추적 브랜치 조회 실패: {}", e))?;
+    Ok(refs
+        .flatten()
+        .filter_map(|r| r.name().map(str::to_string))
+        .collect())
+}
+
+/// Find local branches whose upstream is one of `removed_refs`, optionally
+/// clearing their tracking config.
+fn report_orphaned_tracking(
+    repo: &Repository,
+    removed_refs: Vec<String>,
+    remove_tracking_config: bool,
+) -> Result<OrphanedTrackingReport, String> {
+    let mut orphaned_branches = Vec::new();
+    if !removed_refs.is_empty() {
+        let branches = repo
+            .branches(Some(BranchType::Local))
+            .map_err(|e| format!("브랜치 목록 조회 실패: {}", e))?;
+        for (branch, _) in branches.flatten() {
+            if let Ok(Some(name)) = branch.name() {
+                if configured_upstream(repo, name).is_some_and(|u| removed_refs.contains(&u)) {
+                    orphaned_branches.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    let tracking_config_removed = remove_tracking_config && !orphaned_branches.is_empty();
+    if tracking_config_removed {
+        let mut config = repo
+            .config()
+            .map_err(|e| format!("설정 열기 실패: {}", e))?;
+        for name in &orphaned_branches {
+            for key in ["remote", "merge"] {
+                match config.remove(&format!("branch.{}.{}", name, key)) {
+                    Ok(()) => {}
+                    Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+                    Err(e) => return Err(format!("추적 설정 삭제 실패: {}", e)),
+                }
+            }
+        }
+    }
+
+    Ok(OrphanedTrackingReport {
+        removed_refs: removed_refs.iter().map(|r| normalize_unicode(r)).collect(),
+        orphaned_branches: orphaned_branches.iter().map(|b| normalize_unicode(b)).collect(),
+        tracking_config_removed,
+    })
+}
+
+/// Key of a repository in `SYNC_PROGRESS`: its canonicalized path, so every
+/// spelling of the path shares one entry.
+pub(super) fn progress_key(repo_path: &Path) -> String {
+    let path = repo_path
+        .canonicalize()
+        .unwrap_or_else(|_| repo_path.to_path_buf());
+    normalize_unicode(&path.to_string_lossy())
+}
+
+/// `progress_key` of an open repository: its working tree, or the git
+/// directory when bare.
+pub(super) fn repo_progress_key(repo: &Repository) -> String {
+    progress_key(repo.workdir().unwrap_or_else(|| repo.path()))
+}
+
+fn idle_progress() -> SyncProgress {
+    SyncProgress {
+        phase: "idle".to_string(),
+        current: 0,
+        total: 0,
+        bytes: 0,
+        message: String::new(),
+    }
+}
+
+/// Update the transfer counters of `key`; the listening operation hears of
+/// it at most every `PROGRESS_EVENT_INTERVAL`.
+fn edit_progress(key: &str, edit: impl FnOnce(&mut SyncProgress)) {
+    record_progress(key, edit, true);
+}
+
+/// Set the phase and message of `key`, always emitted to its listener.
+pub(super) fn update_progress(key: &str, phase: &str, message: &str) {
+    record_progress(
+        key,
+        |progress| {
+            progress.phase = phase.to_string();
+            progress.message = message.to_string();
+            if phase == "idle" {
+                progress.current = 0;
+                progress.total = 0;
+            }
+        },
+        false,
+    );
+}
+
+fn record_progress(key: &str, edit: impl FnOnce(&mut SyncProgress), throttled: bool) {
+    let snapshot = {
+        let mut progress = SYNC_PROGRESS.lock().unwrap();
+        let entry = progress.entry(key.to_string()).or_insert_with(idle_progress);
+        edit(entry);
+        entry.clone()
+    };
+
+    let mut listeners = SYNC_LISTENERS.lock().unwrap();
+    let Some(listener) = listeners.get_mut(key) else {
+        return;
+    };
+    if throttled {
+        let now = Instant::now();
+        if listener
+            .last_transfer_event
+            .is_some_and(|last| now.duration_since(last) < PROGRESS_EVENT_INTERVAL)
+        {
+            return;
+        }
+        listener.last_transfer_event = Some(now);
+    }
+    let event = SyncProgressEvent {
+        repo_path: listener.repo_path.clone(),
+        operation_id: listener.operation_id,
+        progress: snapshot,
+    };
+    if let Ok(payload) = serde_json::to_value(event) {
+        (listener.emit)(SYNC_PROGRESS_EVENT, payload);
+    }
+}
+
+fn window_emitter(window: tauri::Window) -> SyncEmitter {
+    Arc::new(move |event, payload| {
+        let _ = window.emit(event, payload);
+    })
+}
+
+/// Run `operation` on `repo_path`; with an `emit`ter, its progress there is
+/// sent as `sync-progress` events under a new operation id, followed by a
+/// `sync-complete` or `sync-error` event with the outcome. `SYNC_PROGRESS`
+/// is updated either way.
+fn with_sync_events<T: serde::Serialize>(
+    emit: Option<SyncEmitter>,
+    repo_path: &str,
+    operation: impl FnOnce() -> Result<T, String>,
+) -> Result<T, String> {
+    let Some(emit) = emit else {
+        return operation();
+    };
+    let key = progress_key(Path::new(repo_path));
+    let operation_id = NEXT_SYNC_ID.fetch_add(1, Ordering::Relaxed);
+    // A later operation on the same repository takes the events over
+    SYNC_LISTENERS.lock().unwrap().insert(
+        key.clone(),
+        SyncListener {
+            operation_id,
+            repo_path: repo_path.to_string(),
+            emit: Arc::clone(&emit),
+            last_transfer_event: None,
+        },
+    );
+
+    let result = operation();
+
+    {
+        let mut listeners = SYNC_LISTENERS.lock().unwrap();
+        if listeners.get(&key).map(|l| l.operation_id) == Some(operation_id) {
+            listeners.remove(&key);
+        }
+    }
+    let (event, result_value, error) = match &result {
+        Ok(value) => (SYNC_COMPLETE_EVENT, serde_json::to_value(value).ok(), None),
+        Err(e) => (SYNC_ERROR_EVENT, None, Some(e.clone())),
+    };
+    let finished = SyncFinishedEvent {
+        repo_path: repo_path.to_string(),
+        operation_id,
+        result: result_value,
+        error,
+    };
+    if let Ok(payload) = serde_json::to_value(finished) {
+        emit(event, payload);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::branch::list_branches;
+    use git2::Signature;
+    use tempfile::tempdir;
+
+    /// Local repo with `main` and `feature` pushed to a bare `origin`, `feature` tracking it.
+    async fn setup_tracked_feature() -> (tempfile::TempDir, String, String) {
+        let temp_dir = tempdir().unwrap();
+        let origin_path = temp_dir.path().join("origin.git");
+        let repo_path = temp_dir.path().join("local");
+        Repository::init_bare(&origin_path).unwrap();
+        let repo = Repository::init(&repo_path).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let head = repo
+            .commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        repo.branch("feature", &repo.find_commit(head).unwrap(), false)
+            .unwrap();
+        repo.remote("origin", origin_path.to_str().unwrap()).unwrap();
+
+        let repo_path = repo_path.to_str().unwrap().to_string();
+        for branch in ["main", "feature"] {
+            push_changes_with_events(
+                None,
+                repo_path.clone(),
+                "origin".into(),
+                branch.into(),
+                false,
+                None,
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, false)
+            .await
+            .unwrap();
+        repo.find_branch("feature", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/feature"))
+            .unwrap();
+
+        let origin_path = origin_path.to_str().unwrap().to_string();
+        (temp_dir, repo_path, origin_path)
+    }
+
+    fn feature_upstream_gone(branches: &[crate::commands::models::BranchInfo]) -> bool {
+        branches
+            .iter()
+            .find(|b| b.name == "feature" && !b.is_remote)
+            .unwrap()
+            .upstream_gone
+    }
+
+    #[tokio::test]
+    async fn test_set_remote_url_with_separate_push_url() {
+        let (temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let mirror_path = temp_dir.path().join("mirror.git");
+        let mirror_url = mirror_path.to_str().unwrap().to_string();
+
+        let info = set_remote_url(
+            repo_path.clone(),
+            "origin".into(),
+            origin_path.clone(),
+            Some(mirror_url.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.url, origin_path);
+        assert_eq!(info.push_url, mirror_url);
+        assert!(info.separate_push_url);
+
+        let repo = Repository::open(&repo_path).unwrap();
+        let remote = repo.find_remote("origin").unwrap();
+        assert_eq!(remote.pushurl(), Some(mirror_url.as_str()));
+        assert_eq!(
+            remote.fetch_refspecs().unwrap().get(0),
+            Some("+refs/heads/*:refs/remotes/origin/*")
+        );
+
+        // None keeps the push URL, an empty one removes it
+        let info = set_remote_url(
+            repo_path.clone(),
+            "origin".into(),
+            origin_path.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.push_url, mirror_url);
+        let info = set_remote_url(
+            repo_path.clone(),
+            "origin".into(),
+            origin_path.clone(),
+            Some(String::new()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.push_url, origin_path);
+        assert!(!info.separate_push_url);
+        let remotes = list_remotes(repo_path.clone()).await.unwrap();
+        assert_eq!(remotes.len(), 1);
+        assert!(!remotes[0].separate_push_url);
+
+        assert!(set_remote_url(repo_path, "없음".into(), origin_path, None)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_remote_moves_tracking_config() {
+        let (_temp_dir, repo_path, _origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.remote_add_fetch("origin", "+refs/pull/*:refs/pull-requests/*")
+            .unwrap();
+
+        let problems = rename_remote(repo_path.clone(), "origin".into(), "upstream".into())
+            .await
+            .unwrap();
+        assert_eq!(problems, vec!["+refs/pull/*:refs/pull-requests/*"]);
+        assert!(repo.find_remote("origin").is_err());
+        let remote = repo.find_remote("upstream").unwrap();
+        let refspecs = remote.fetch_refspecs().unwrap();
+        assert_eq!(
+            refspecs.iter().flatten().collect::<Vec<_>>(),
+            vec![
+                "+refs/heads/*:refs/remotes/upstream/*",
+                "+refs/pull/*:refs/pull-requests/*"
+            ]
+        );
+        assert!(repo.find_reference("refs/remotes/upstream/main").is_ok());
+        assert!(repo.find_reference("refs/remotes/origin/main").is_err());
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(config.get_str("branch.feature.remote").unwrap(), "upstream");
+        assert_eq!(
+            configured_upstream(&repo, "feature").as_deref(),
+            Some("refs/remotes/upstream/feature")
+        );
+
+        assert!(rename_remote(repo_path, "origin".into(), "다른".into())
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_remote_refusal_keeps_refspecs() {
+        let (_temp_dir, repo_path, _origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.remote_add_fetch("origin", "+refs/pull/*:refs/pull-requests/*")
+            .unwrap();
+        repo.remote("backup", "https://example.com/backup.git")
+            .unwrap();
+        let fetch_refspecs = |name: &str| -> Vec<String> {
+            let remote = repo.find_remote(name).unwrap();
+            let refspecs = remote.fetch_refspecs().unwrap();
+            refspecs.iter().flatten().map(str::to_string).collect()
+        };
+        let before = fetch_refspecs("origin");
+        assert_eq!(before.len(), 2);
+
+        let err = rename_remote(repo_path.clone(), "origin".into(), "backup".into())
+            .await
+            .unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "remote_exists");
+        assert!(rename_remote(repo_path, "origin".into(), "bad name..x".into())
+            .await
+            .is_err());
+        assert_eq!(fetch_refspecs("origin"), before);
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_prune_reports_orphaned_tracking_branch() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+
+        let branches = list_branches(repo_path.clone(), None, None, None, None, None, None).await.unwrap();
+        assert!(!feature_upstream_gone(&branches));
+
+        // Someone else deletes the branch on the remote
+        Repository::open_bare(&origin_path)
+            .unwrap()
+            .find_reference("refs/heads/feature")
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        let report = prune_remote(repo_path.clone(), "origin".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(report.removed_refs, vec!["refs/remotes/origin/feature"]);
+        assert_eq!(report.orphaned_branches, vec!["feature"]);
+        assert!(!report.tracking_config_removed);
+
+        let branches = list_branches(repo_path.clone(), None, None, None, None, None, None).await.unwrap();
+        assert!(feature_upstream_gone(&branches));
+        assert!(branches.iter().any(|b| b.name == "feature" && !b.is_remote));
+    }
+
+    #[tokio::test]
+    async fn test_delete_remote_branch_can_clear_tracking_config() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+
+        let report = delete_remote_branch(
+            repo_path.clone(),
+            "origin".into(),
+            "feature".into(),
+            Some(true),
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.orphaned_branches, vec!["feature"]);
+        assert!(report.tracking_config_removed);
+
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        assert!(origin.find_reference("refs/heads/feature").is_err());
+
+        // Branch kept, tracking config cleared, so it is no longer "gone"
+        let repo = Repository::open(&repo_path).unwrap();
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert!(config.get_str("branch.feature.remote").is_err());
+        assert!(config.get_str("branch.feature.merge").is_err());
+        let branches = list_branches(repo_path, None, None, None, None, None, None).await.unwrap();
+        assert!(!feature_upstream_gone(&branches));
+    }
+
+    /// Bare `origin.git` with `count` linear commits on main.
+    fn setup_origin(base: &std::path::Path, count: usize) -> std::path::PathBuf {
+        let origin_path = base.join("origin.git");
+        let repo = Repository::init_bare(&origin_path).unwrap();
+        repo.set_head("refs/heads/main").unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let mut parent: Option<git2::Oid> = None;
+        for i in 0..count {
+            let mut builder = repo.treebuilder(None).unwrap();
+            let blob = repo.blob(format!("{}\n", i).as_bytes()).unwrap();
+            builder.insert("file.txt", blob, 0o100644).unwrap();
+            let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+            let parents: Vec<git2::Commit> =
+                parent.iter().map(|p| repo.find_commit(*p).unwrap()).collect();
+            let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+            let message = format!("commit {}", i);
+            let oid = repo
+                .commit(Some("refs/heads/main"), &sig, &sig, &message, &tree, &parent_refs)
+                .unwrap();
+            parent = Some(oid);
+        }
+        origin_path
+    }
+
+    fn head_history_len(repo_path: &str) -> usize {
+        let repo = Repository::open(repo_path).unwrap();
+        let mut walk = repo.revwalk().unwrap();
+        walk.push_head().unwrap();
+        walk.count()
+    }
+
+    #[tokio::test]
+    async fn test_push_sets_upstream_of_new_branch() {
+        let (_temp_dir, repo_path, _origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let upstream = |branch: &str| configured_upstream(&repo, branch);
+        assert_eq!(upstream("main").as_deref(), Some("refs/remotes/origin/main"));
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("기능", &head, false).unwrap();
+        repo.branch("실험", &head, false).unwrap();
+        let push = |branch: &str, set_upstream: Option<bool>| {
+            push_changes_with_events(
+                None,
+                repo_path.clone(),
+                "origin".into(),
+                branch.into(),
+                false,
+                set_upstream,
+                None,
+                None,
+            )
+        };
+
+        let result = push("기능", None).await.unwrap();
+        assert_eq!(result.upstream.as_deref(), Some("origin/기능"));
+        let config = repo.config().unwrap().snapshot().unwrap();
+        assert_eq!(config.get_str("branch.기능.remote").unwrap(), "origin");
+        assert_eq!(config.get_str("branch.기능.merge").unwrap(), "refs/heads/기능");
+        assert_eq!(
+            repo.refname_to_id("refs/remotes/origin/기능").unwrap(),
+            head.id()
+        );
+
+        // An existing upstream is kept unless asked for
+        repo.find_branch("feature", BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/main"))
+            .unwrap();
+        let result = push("feature", None).await.unwrap();
+        assert_eq!(result.upstream.as_deref(), Some("origin/main"));
+        let result = push("feature", Some(true)).await.unwrap();
+        assert_eq!(result.upstream.as_deref(), Some("origin/feature"));
+
+        let result = push("실험", Some(false)).await.unwrap();
+        assert_eq!(result.upstream, None);
+        assert_eq!(upstream("실험"), None);
+        assert!(repo.find_reference("refs/remotes/origin/실험").is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_push_with_follow_and_all_tags() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        let tree = base.tree().unwrap();
+        let tip = repo
+            .commit(Some("HEAD"), &sig, &sig, "Release", &tree, &[&base])
+            .unwrap();
+        let tip = repo.find_object(tip, None).unwrap();
+        let side = repo
+            .commit(None, &sig, &sig, "Side", &tree, &[&base])
+            .unwrap();
+        let side = repo.find_object(side, None).unwrap();
+        repo.tag("v1.0", &tip, &sig, "릴리스 1.0", false).unwrap();
+        let base = repo.find_object(base.id(), None).unwrap();
+        repo.tag("v0.9", &base, &sig, "0.9", false).unwrap();
+        repo.tag("side", &side, &sig, "곁가지", false).unwrap();
+        repo.tag_lightweight("가벼운", &tip, false).unwrap();
+        // Already on the remote with another target
+        origin
+            .tag_lightweight("v0.9", &origin.revparse_single("feature").unwrap(), false)
+            .unwrap();
+        let push = |mode: &str| {
+            push_changes_with_events(
+                None,
+                repo_path.clone(),
+                "origin".into(),
+                "main".into(),
+                false,
+                None,
+                Some(mode.into()),
+                None,
+            )
+        };
+        let on_origin = |tag: &str| origin.find_reference(&format!("refs/tags/{}", tag)).is_ok();
+        let refs = |result: PushResult| -> Vec<(String, bool)> {
+            result
+                .refs
+                .into_iter()
+                .map(|update| (update.refname, update.accepted))
+                .collect()
+        };
+
+        let result = push("follow").await.unwrap();
+        assert_eq!(
+            refs(result),
+            vec![
+                ("refs/heads/main".to_string(), true),
+                ("refs/tags/v1.0".to_string(), true),
+                ("refs/tags/v0.9".to_string(), false),
+            ]
+        );
+        assert!(on_origin("v1.0"));
+        assert!(!on_origin("side") && !on_origin("가벼운"));
+
+        // v1.0 is there already and not sent again
+        let result = push("all").await.unwrap();
+        assert_eq!(
+            refs(result),
+            vec![
+                ("refs/heads/main".to_string(), true),
+                ("refs/tags/side".to_string(), true),
+                ("refs/tags/가벼운".to_string(), true),
+                ("refs/tags/v0.9".to_string(), false),
+            ]
+        );
+        assert!(on_origin("side") && on_origin("가벼운"));
+        assert_ne!(
+            origin.refname_to_id("refs/tags/v0.9").unwrap(),
+            repo.refname_to_id("refs/tags/v0.9").unwrap()
+        );
+        assert!(push("some").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_push_all_branches_reports_each_branch() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let commit_on = |repo: &Repository, refname: &str, message: &str| {
+            let parent = repo.find_reference(refname).unwrap().peel_to_commit().unwrap();
+            let tree = parent.tree().unwrap();
+            repo.commit(Some(refname), &sig, &sig, message, &tree, &[&parent])
+                .unwrap()
+        };
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("실험", &head, false).unwrap();
+        repo.branch("로컬", &head, false).unwrap();
+        push_changes_with_events(
+            None,
+            repo_path.clone(),
+            "origin".into(),
+            "실험".into(),
+            false,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        // 실험 moves on both sides, main only locally
+        commit_on(&origin, "refs/heads/실험", "원격 커밋");
+        commit_on(&repo, "refs/heads/실험", "로컬 커밋");
+        let main_tip = commit_on(&repo, "refs/heads/main", "새 기능");
+
+        let statuses = push_all_branches(repo_path.clone(), "origin".into(), false)
+            .await
+            .unwrap();
+        let summary: Vec<(&str, &str)> = statuses
+            .iter()
+            .map(|s| (s.branch.as_str(), s.status.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("feature", "up_to_date"),
+                ("main", "pushed"),
+                ("실험", "rejected"),
+            ]
+        );
+        assert!(statuses[2].message.is_some());
+        assert_eq!(origin.refname_to_id("refs/heads/main").unwrap(), main_tip);
+        assert_eq!(
+            repo.refname_to_id("refs/remotes/origin/main").unwrap(),
+            main_tip
+        );
+
+        let statuses = push_branches(repo_path.clone(), "origin".into(), vec!["실험".into()], true)
+            .await
+            .unwrap();
+        assert_eq!(statuses[0].status, "pushed");
+        assert_eq!(
+            origin.refname_to_id("refs/heads/실험").unwrap(),
+            repo.refname_to_id("refs/heads/실험").unwrap()
+        );
+        assert!(push_branches(repo_path, "origin".into(), vec!["없음".into()], false)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_force_with_lease_checks_remote_tip() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let commit_on = |repo: &Repository, refname: &str, message: &str| {
+            let parent = repo.find_reference(refname).unwrap().peel_to_commit().unwrap();
+            let tree = parent.tree().unwrap();
+            repo.commit(Some(refname), &sig, &sig, message, &tree, &[&parent])
+                .unwrap()
+        };
+        let lease_push = |branch: &str| {
+            push_changes_with_events(
+                None,
+                repo_path.clone(),
+                "origin".into(),
+                branch.into(),
+                false,
+                None,
+                None,
+                Some(true),
+            )
+        };
+
+        // A teammate pushed after our last fetch
+        let known = repo.refname_to_id("refs/remotes/origin/main").unwrap();
+        let theirs = commit_on(&origin, "refs/heads/main", "동료 커밋");
+        let ours = commit_on(&repo, "refs/heads/main", "내 커밋");
+        let err = lease_push("main").await.unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).expect(&err);
+        assert_eq!(err.code, "lease_failed");
+        assert_eq!(err.details["remote_sha"], theirs.to_string());
+        assert_eq!(err.details["tracking_sha"], known.to_string());
+        assert_eq!(origin.refname_to_id("refs/heads/main").unwrap(), theirs);
+
+        // Seen it: the lease now allows overwriting
+        fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, false)
+            .await
+            .unwrap();
+        lease_push("main").await.unwrap();
+        assert_eq!(origin.refname_to_id("refs/heads/main").unwrap(), ours);
+
+        // No remote-tracking ref: the remote branch must not exist
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.branch("새", &head, false).unwrap();
+        origin.reference("refs/heads/새", theirs, false, "").unwrap();
+        let err = lease_push("새").await.unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).expect(&err);
+        assert_eq!(err.code, "lease_failed");
+        assert_eq!(err.details["remote_sha"], theirs.to_string());
+        assert!(err.details["tracking_sha"].is_null());
+
+        origin.find_reference("refs/heads/새").unwrap().delete().unwrap();
+        lease_push("새").await.unwrap();
+        assert_eq!(origin.refname_to_id("refs/heads/새").unwrap(), ours);
+    }
+
+    #[tokio::test]
+    async fn test_pull_merges_diverged_branch() {
+        use crate::commands::conflict::{abort_merge, get_conflicts};
+
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let commit_on = |repo: &Repository, name: &str, content: &str| {
+            let head = repo.find_reference("refs/heads/main").unwrap();
+            let parent = head.peel_to_commit().unwrap();
+            let mut builder = repo.treebuilder(Some(&parent.tree().unwrap())).unwrap();
+            let blob = repo.blob(content.as_bytes()).unwrap();
+            builder.insert(name, blob, 0o100644).unwrap();
+            let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+            repo.commit(Some("refs/heads/main"), &sig, &sig, content, &tree, &[&parent])
+                .unwrap()
+        };
+        let pull =
+            || pull_changes_with_events(None, repo_path.clone(), "origin".into(), "main".into());
+
+        // Both sides moved, touching different files
+        commit_on(&origin, "theirs.txt", "그쪽\n");
+        commit_on(&repo, "ours.txt", "이쪽\n");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let result = pull().await.unwrap();
+        assert_eq!(result.kind, "merged");
+        let merge = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(result.commit_sha, Some(merge.id().to_string()));
+        assert_eq!(merge.parent_count(), 2);
+        assert_eq!(
+            merge.message(),
+            Some(format!("Merge branch 'main' of {}", origin_path).as_str())
+        );
+        assert!(std::path::Path::new(&repo_path).join("theirs.txt").exists());
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(pull().await.unwrap().kind, "up_to_date");
+
+        // Now the same file: the merge stops and stays in progress
+        commit_on(&origin, "ours.txt", "그쪽도\n");
+        commit_on(&repo, "ours.txt", "이쪽도\n");
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let before = repo.head().unwrap().target().unwrap();
+        let result = pull().await.unwrap();
+        assert_eq!(result.kind, "conflicts");
+        assert_eq!(result.conflicted_files, vec!["ours.txt".to_string()]);
+        assert_eq!(repo.state(), git2::RepositoryState::Merge);
+        let conflicts = get_conflicts(repo_path.clone()).unwrap();
+        assert_eq!(conflicts.files.len(), 1);
+        assert_eq!(
+            conflicts.merge_msg,
+            Some(format!("Merge branch 'main' of {}", origin_path))
+        );
+
+        abort_merge(repo_path.clone()).unwrap();
+        assert_eq!(repo.state(), git2::RepositoryState::Clean);
+        assert_eq!(repo.head().unwrap().target(), Some(before));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_prune_and_prune_without_fetch() {
+        let (_temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let head = origin.find_reference("refs/heads/main").unwrap();
+        let head = head.peel_to_commit().unwrap();
+        origin.branch("임시", &head, false).unwrap();
+        fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, false)
+            .await
+            .unwrap();
+        assert!(repo.find_reference("refs/remotes/origin/임시").is_ok());
+
+        origin
+            .find_reference("refs/heads/feature")
+            .unwrap()
+            .delete()
+            .unwrap();
+        let result = fetch_remote_with_events(None, repo_path.clone(), "origin".into(), None, true)
+            .await
+            .unwrap();
+        assert_eq!(result.pruned_refs, vec!["refs/remotes/origin/feature"]);
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_err());
+
+        // Pruning alone removes `임시` but leaves the new commit on `main` unfetched
+        origin
+            .find_reference("refs/heads/임시")
+            .unwrap()
+            .delete()
+            .unwrap();
+        let tree = head.tree().unwrap();
+        let moved = origin
+            .commit(Some("refs/heads/main"), &sig, &sig, "원격 커밋", &tree, &[&head])
+            .unwrap();
+        let report = prune_remote(repo_path.clone(), "origin".into(), None)
+            .await
+            .unwrap();
+        assert_eq!(report.removed_refs, vec!["refs/remotes/origin/임시"]);
+        assert_eq!(
+            repo.refname_to_id("refs/remotes/origin/main").unwrap(),
+            head.id()
+        );
+        assert!(repo.find_commit(moved).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_remotes_reports_each_remote() {
+        let (temp_dir, repo_path, origin_path) = setup_tracked_feature().await;
+        let repo = Repository::open(&repo_path).unwrap();
+        let upstream_path = setup_origin(&temp_dir.path().join("upstream"), 2);
+        repo.remote("upstream", upstream_path.to_str().unwrap())
+            .unwrap();
+        let missing = temp_dir.path().join("없음.git");
+        repo.remote("broken", missing.to_str().unwrap()).unwrap();
+        let origin = Repository::open_bare(&origin_path).unwrap();
+        origin
+            .find_reference("refs/heads/feature")
+            .unwrap()
+            .delete()
+            .unwrap();
+
+        let results = fetch_all_remotes(repo_path.clone(), true).await.unwrap();
+        let result = |name: &str| results.iter().find(|r| r.remote == name).unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(result("broken").error.is_some());
+        assert!(result("origin").error.is_none());
+        assert_eq!(
+            result("origin").updated_refs,
+            vec!["refs/remotes/origin/feature".to_string()]
+        );
+        assert!(repo.find_reference("refs/remotes/origin/feature").is_err());
+        assert_eq!(
+            result("upstream").updated_refs,
+            vec!["refs/remotes/upstream/main".to_string()]
+        );
+        assert!(repo.find_reference("refs/remotes/upstream/main").is_ok());
+
+        // Nothing new the second time
+        let results = fetch_all_remotes(repo_path, false).await.unwrap();
+        assert!(results
+            .iter()
+            .filter(|r| r.error.is_none())
+            .all(|r| r.updated_refs.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_sync_progress_is_tracked_per_repository() {
+        let temp_dir = tempdir().unwrap();
+        let setup = |name: &str, remote: &str, commits: usize| {
+            let base = temp_dir.path().join(name);
+            let origin_path = setup_origin(&base, commits);
+            let repo = Repository::init(base.join("local")).unwrap();
+            repo.remote(remote, origin_path.to_str().unwrap()).unwrap();
+            base.join("local").to_str().unwrap().to_string()
+        };
+        let first = setup("a", "origin", 3);
+        let second = setup("b", "upstream", 20);
+
+        std::thread::scope(|scope| {
+            let fetches = [(&first, "origin"), (&second, "upstream")].map(|(path, remote)| {
+                scope.spawn(move || fetch_with(path, remote, None, false, None).unwrap())
+            });
+            for fetch in fetches {
+                fetch.join().unwrap();
+            }
+        });
+
+        let progress = get_sync_progress(first.clone()).await.unwrap();
+        assert_eq!(progress.phase, "idle");
+        assert_eq!(progress.message, "'origin'에서 페치 완료");
+        // Another spelling of the same path finds the same entry
+        let other = get_sync_progress(format!("{}/", second)).await.unwrap();
+        assert_eq!(other.message, "'upstream'에서 페치 완료");
+        // Each transfer counted only its own bytes
+        assert!(progress.bytes > 0 && other.bytes > progress.bytes);
+
+        // Progress of one repository does not leak into another
+        update_progress(&progress_key(Path::new(&first)), "fetching", "페치 중");
+        assert_eq!(get_sync_progress(first).await.unwrap().phase, "fetching");
+        assert_eq!(get_sync_progress(second).await.unwrap().phase, "idle");
+        let untouched = temp_dir.path().to_str().unwrap().to_string();
+        let progress = get_sync_progress(untouched).await.unwrap();
+        assert_eq!(progress.phase, "idle");
+        assert!(progress.message.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sync_events_carry_repo_and_operation_id() {
+        let temp_dir = tempdir().unwrap();
+        let origin_path = setup_origin(temp_dir.path(), 5);
+        let local = temp_dir.path().join("local");
+        let repo = Repository::init(&local).unwrap();
+        repo.remote("origin", origin_path.to_str().unwrap()).unwrap();
+        let repo_path = local.to_str().unwrap().to_string();
+        let key = progress_key(&local);
+
+        let events = Arc::new(Mutex::new(Vec::<(String, serde_json::Value)>::new()));
+        let events_cb = Arc::clone(&events);
+        let emit: SyncEmitter = Arc::new(move |event, payload| {
+            events_cb.lock().unwrap().push((event.to_string(), payload));
+        });
+        let fetch = |remote: &str| {
+            with_sync_events(Some(Arc::clone(&emit)), &repo_path, || {
+                fetch_with(&repo_path, remote, None, false, None)
+            })
+        };
+        fetch("origin").unwrap();
+        assert!(fetch("missing").is_err());
+
+        let taken = std::mem::take(&mut *events.lock().unwrap());
+        let finished: Vec<&(String, serde_json::Value)> = taken
+            .iter()
+            .filter(|(event, _)| event != SYNC_PROGRESS_EVENT)
+            .collect();
+        assert_eq!(finished.len(), 2);
+        assert_eq!(finished[0].0, SYNC_COMPLETE_EVENT);
+        assert_eq!(finished[0].1["result"]["message"], "'origin' 페치 완료");
+        assert_eq!(finished[1].0, SYNC_ERROR_EVENT);
+        assert!(finished[1].1["error"].is_string());
+        let first_id = finished[0].1["operation_id"].as_u64().unwrap();
+        assert!(finished[1].1["operation_id"].as_u64().unwrap() > first_id);
+        assert!(taken.iter().all(|(_, payload)| payload["repo_path"] == repo_path.as_str()));
+        let phases: Vec<&str> = taken
+            .iter()
+            .filter(|(event, payload)| {
+                event == SYNC_PROGRESS_EVENT && payload["operation_id"] == first_id
+            })
+            .map(|(_, payload)| payload["progress"]["phase"].as_str().unwrap())
+            .collect();
+        assert_eq!(phases.first(), Some(&"fetching"));
+        assert_eq!(phases.last(), Some(&"idle"));
+
+        // Transfer updates are throttled, phase changes are not
+        with_sync_events(Some(Arc::clone(&emit)), &repo_path, || {
+            for i in 0..50 {
+                edit_progress(&key, |progress| progress.current = i);
+            }
+            update_progress(&key, "idle", "끝");
+            Ok(())
+        })
+        .unwrap();
+        let names: Vec<String> = events.lock().unwrap().iter().map(|(e, _)| e.clone()).collect();
+        assert_eq!(names, [SYNC_PROGRESS_EVENT, SYNC_PROGRESS_EVENT, SYNC_COMPLETE_EVENT]);
+
+        // Once finished, updates only reach the shared state
+        update_progress(&key, "fetching", "페치 중");
+        assert_eq!(events.lock().unwrap().len(), 3);
+        assert_eq!(get_sync_progress(repo_path).await.unwrap().phase, "fetching");
+    }
+
+    #[tokio::test]
+    async fn test_unshallow_fetches_full_history() {
+        let temp_dir = tempdir().unwrap();
+        let origin_path = setup_origin(temp_dir.path(), 5);
+        // libgit2's local transport ignores depth, so the shallow clone is
+        // made with git itself
+        let shallow_path = temp_dir.path().join("shallow");
+        let cloned = std::process::Command::new("git")
+            .args(["clone", "-q", "--depth", "2"])
+            .arg(format!("file://{}", origin_path.display()))
+            .arg(&shallow_path)
+            .status();
+        if !cloned.is_ok_and(|status| status.success()) {
+            return;
+        }
+        let shallow_path = shallow_path.to_str().unwrap().to_string();
+        assert_eq!(head_history_len(&shallow_path), 2);
+        let info = crate::commands::git::open_repository(shallow_path.clone())
+            .await
+            .unwrap();
+        assert!(info.is_shallow);
+
+        unshallow(shallow_path.clone(), "origin".into())
+            .await
+            .unwrap();
+        assert!(!Repository::open(&shallow_path).unwrap().is_shallow());
+        assert_eq!(head_history_len(&shallow_path), 5);
+        assert!(unshallow(shallow_path, "origin".into()).await.is_err());
+
+        // A depth-limited clone of a full repository
+        let target = temp_dir.path().join("clone");
+        let cloned = crate::commands::clone::clone_repository(
+            origin_path.to_str().unwrap().to_string(),
+            target.to_str().unwrap().to_string(),
+            Some(1),
+        )
+        .await
+        .unwrap();
+        fetch_remote_with_events(None, cloned.clone(), "origin".into(), Some(1), false)
+            .await
+            .unwrap();
+        assert!(Repository::open(&cloned).unwrap().head().is_ok());
+    }
+
+    #[test]
+    fn test_fetch_depth_never_requests_unshallow() {
+        assert_eq!(fetch_depth(3), 3);
+        assert_eq!(fetch_depth(u32::MAX), i32::MAX - 1);
+    }
+
+    /// Smart HTTP on localhost through `git http-backend`, serving the
+    /// repositories under `root` to requests with Basic auth `user_pass`.
+    fn serve_git_http(root: &std::path::Path, user_pass: &str) -> String {
+        use base64::Engine;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let root = root.to_path_buf();
+        let expected = format!(
+            "Basic {}",
+            base64::engine::general_purpose::STANDARD.encode(user_pass)
+        );
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = handle_git_http(stream, &root, &expected);
+            }
+        });
+        url
+    }
+
+    fn handle_git_http(
+        mut stream: std::net::TcpStream,
+        root: &std::path::Path,
+        expected_auth: &str,
+    ) -> std::io::Result<()> {
+        use std::io::{BufRead, Read, Write};
+
+        let mut reader = std::io::BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or("").to_string();
+        let target = parts.next().unwrap_or("").to_string();
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let Some((name, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+            headers.insert(name.to_ascii_lowercase(), value.trim().to_string());
+        }
+
+        let mut body = Vec::new();
+        if let Some(length) = headers.get("content-length") {
+            body.resize(length.parse().unwrap_or(0), 0);
+            reader.read_exact(&mut body)?;
+        } else if headers.contains_key("transfer-encoding") {
+            loop {
+                let mut size = String::new();
+                reader.read_line(&mut size)?;
+                let size = usize::from_str_radix(size.trim(), 16).unwrap_or(0);
+                let mut chunk = vec![0; size + 2];
+                reader.read_exact(&mut chunk)?;
+                if size == 0 {
+                    break;
+                }
+                body.extend_from_slice(&chunk[..size]);
+            }
+        }
+
+        if headers.get("authorization").map(String::as_str) != Some(expected_auth) {
+            return stream.write_all(
+                b"HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"git\"\r\n\
+                  Content-Length: 0\r\nConnection: close\r\n\r\n",
+            );
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+        let mut child = std::process::Command::new("git")
+            .arg("http-backend")
+            .env("GIT_PROJECT_ROOT", root)
+            .env("GIT_HTTP_EXPORT_ALL", "1")
+            .env("REMOTE_USER", "user")
+            .env("REQUEST_METHOD", &method)
+            .env("PATH_INFO", path)
+            .env("QUERY_STRING", query)
+            .env(
+                "CONTENT_TYPE",
+                headers.get("content-type").cloned().unwrap_or_default(),
+            )
+            .env("CONTENT_LENGTH", body.len().to_string())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .spawn()?;
+        child.stdin.take().unwrap().write_all(&body)?;
+        let output = child.wait_with_output()?;
+
+        // CGI headers, then the body
+        let end = output
+            .stdout
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .unwrap_or(output.stdout.len());
+        let cgi_headers = String::from_utf8_lossy(&output.stdout[..end]).to_string();
+        let body = output.stdout.get(end + 4..).unwrap_or_default();
+        let mut status = "200 OK".to_string();
+        let mut response_headers = String::new();
+        for line in cgi_headers.lines() {
+            match line.strip_prefix("Status: ") {
+                Some(code) => status = code.to_string(),
+                None => response_headers.push_str(&format!("{}\r\n", line)),
+            }
+        }
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            response_headers,
+            body.len()
+        )?;
+        stream.write_all(body)
+    }
+
+    fn auth_required(err: &str) -> CommandError {
+        let err: CommandError = serde_json::from_str(err).expect(err);
+        assert_eq!(err.code, "auth_required");
+        err
+    }
+
+    #[tokio::test]
+    async fn test_retry_with_credentials_after_rejection() {
+        let temp_dir = tempdir().unwrap();
+        let origin = Repository::init_bare(temp_dir.path().join("origin.git")).unwrap();
+        let repo_path = temp_dir.path().join("local");
+        let repo = Repository::init(&repo_path).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        repo.commit(Some("refs/heads/main"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        let url = format!("{}/origin.git", serve_git_http(temp_dir.path(), "user:secret"));
+        repo.remote("origin", &url).unwrap();
+        let repo_path = repo_path.to_str().unwrap().to_string();
+        let credential = |password: &str| RemoteCredential::UserPass {
+            username: "user".into(),
+            password: password.into(),
+        };
+
+        // No credential helper: the push asks for a password
+        let err = push_changes_with_events(
+            None,
+            repo_path.clone(),
+            "origin".into(),
+            "main".into(),
+            false,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        let err = auth_required(&err);
+        assert_eq!(err.details["kind"], "password");
+        assert_eq!(err.details["url"], url);
+        assert_eq!(err.details["allowed_types"], serde_json::json!(["user_pass"]));
+        let first_id = err.details["operation_id"].as_str().unwrap().to_string();
+
+        // A rejected password is asked for again, under a new id
+        let err = retry_with_credentials(first_id.clone(), credential("wrong"))
+            .await
+            .unwrap_err();
+        let retry_id = auth_required(&err).details["operation_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        assert_ne!(retry_id, first_id);
+        assert!(origin.find_reference("refs/heads/main").is_err());
+
+        retry_with_credentials(retry_id.clone(), credential("secret"))
+            .await
+            .unwrap();
+        assert!(origin.find_reference("refs/heads/main").is_ok());
+        assert!(retry_with_credentials(retry_id, credential("secret"))
+            .await
+            .is_err());
+
+        // Pull (and so fetch) retries the same way
+        let err = pull_changes_with_events(None, repo_path.clone(), "origin".into(), "main".into())
+            .await
+            .unwrap_err();
+        let operation_id = auth_required(&err).details["operation_id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        retry_with_credentials(operation_id, credential("secret"))
+            .await
+            .unwrap();
+        assert!(repo.find_reference("refs/remotes/origin/main").is_ok());
     }
 }
@@ -0,0 +1,163 @@
+//! Moving the current branch to another commit, like `git reset`.
+
+use git2::{Repository, ResetType};
+
+use super::models::ResetResult;
+use super::utils::{command_error, normalize_unicode, open_repo, resolve_revision};
+
+/// Point the current branch (or a detached HEAD) at `commit_sha`.
+///
+/// `mode` is "soft" (index and working tree kept), "mixed" (index reset) or
+/// "hard" (index and working tree reset). A hard reset that would throw away
+/// changes to tracked files fails with a `reset_would_discard` error listing
+/// them, unless `force` is set. The move is recorded in the reflog, so
+/// `reset_to_reflog` can undo it.
+#[tauri::command]
+pub fn reset_to_commit(
+    repo_path: String,
+    commit_sha: String,
+    mode: String,
+    force: Option<bool>,
+) -> Result<ResetResult, String> {
+    let repo = open_repo(&repo_path)?;
+    let reset_type = match mode.as_str() {
+        "soft" => ResetType::Soft,
+        "mixed" => ResetType::Mixed,
+        "hard" => ResetType::Hard,
+        _ => return Err(format!("알 수 없는 리셋 타입: {}", mode)),
+    };
+    let target = resolve_revision(&repo, &commit_sha)?;
+    let previous_head = repo
+        .head()
+        .ok()
+        .and_then(|head| head.target())
+        .map(|id| id.to_string())
+        .unwrap_or_default();
+
+    if mode == "hard" && !force.unwrap_or(false) {
+        let discarded = changed_tracked_paths(&repo)?;
+        if !discarded.is_empty() {
+            return Err(command_error(
+                "reset_would_discard",
+                format!(
+                    "하드 리셋하면 {} 개 파일의 변경사항이 사라집니다",
+                    discarded.len()
+                ),
+                serde_json::json!({ "paths": discarded }),
+            ));
+        }
+    }
+
+    repo.reset(target.as_object(), reset_type, None)
+        .map_err(|e| format!("리셋 실패: {}", e))?;
+    Ok(ResetResult {
+        previous_head,
+        new_head: target.id().to_string(),
+        mode,
+    })
+}
+
+/// Tracked files with staged or unstaged changes, sorted. Untracked files
+/// survive a hard reset and are not listed.
+fn changed_tracked_paths(repo: &Repository) -> Result<Vec<String>, String> {
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let diff = repo
+        .diff_tree_to_workdir_with_index(head_tree.as_ref(), None)
+        .map_err(|e| format!("변경사항 확인 실패: {}", e))?;
+    let mut paths: Vec<String> = diff
+        .deltas()
+        .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+        .map(|path| normalize_unicode(&path.to_string_lossy()))
+        .collect();
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::models::CommandError;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        (temp_dir, repo)
+    }
+
+    fn commit_file(repo: &Repository, name: &str, content: &str) -> git2::Oid {
+        let root = repo.workdir().unwrap();
+        std::fs::write(root.join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = repo.signature().unwrap();
+        let parents: Vec<git2::Commit> = repo
+            .head()
+            .ok()
+            .and_then(|head| head.peel_to_commit().ok())
+            .into_iter()
+            .collect();
+        let parents: Vec<&git2::Commit> = parents.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &parents)
+            .unwrap()
+    }
+
+    fn status_of(repo: &Repository, name: &str) -> git2::Status {
+        repo.status_file(Path::new(name)).unwrap()
+    }
+
+    #[test]
+    fn test_soft_and_mixed_keep_changes() {
+        let (temp_dir, repo) = setup_repo();
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        let first = commit_file(&repo, "파일.txt", "하나\n");
+        let second = commit_file(&repo, "파일.txt", "둘\n");
+
+        let result = reset_to_commit(path.clone(), first.to_string(), "soft".into(), None).unwrap();
+        assert_eq!(result.previous_head, second.to_string());
+        assert_eq!(result.new_head, first.to_string());
+        assert_eq!(repo.head().unwrap().target(), Some(first));
+        assert_eq!(status_of(&repo, "파일.txt"), git2::Status::INDEX_MODIFIED);
+
+        reset_to_commit(path.clone(), second.to_string(), "soft".into(), None).unwrap();
+        reset_to_commit(path.clone(), "HEAD~1".into(), "mixed".into(), None).unwrap();
+        assert_eq!(status_of(&repo, "파일.txt"), git2::Status::WT_MODIFIED);
+
+        assert!(reset_to_commit(path, first.to_string(), "keep".into(), None).is_err());
+    }
+
+    #[test]
+    fn test_hard_reset_reports_discarded_files() {
+        let (temp_dir, repo) = setup_repo();
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        let first = commit_file(&repo, "a.txt", "a\n");
+        let second = commit_file(&repo, "b.txt", "b\n");
+        std::fs::write(temp_dir.path().join("a.txt"), "수정\n").unwrap();
+        std::fs::write(temp_dir.path().join("새 파일.txt"), "untracked\n").unwrap();
+
+        let err =
+            reset_to_commit(path.clone(), first.to_string(), "hard".into(), None).unwrap_err();
+        let err: CommandError = serde_json::from_str(&err).unwrap();
+        assert_eq!(err.code, "reset_would_discard");
+        assert_eq!(err.details["paths"], serde_json::json!(["a.txt"]));
+        assert_eq!(repo.head().unwrap().target(), Some(second));
+
+        let result = reset_to_commit(path, first.to_string(), "hard".into(), Some(true)).unwrap();
+        assert_eq!(result.previous_head, second.to_string());
+        assert_eq!(
+            std::fs::read_to_string(temp_dir.path().join("a.txt")).unwrap(),
+            "a\n"
+        );
+        assert!(!temp_dir.path().join("b.txt").exists());
+        assert!(temp_dir.path().join("새 파일.txt").exists());
+        let reflog = repo.reflog("HEAD").unwrap();
+        assert_eq!(reflog.get(0).unwrap().id_old(), second);
+    }
+}
@@ -1,7 +1,8 @@
 use git2::Oid;
 
 use super::models::RevertResult;
-use super::utils::open_repo;
+use super::signing::commit_to_head;
+use super::utils::{open_repo, repo_signature};
 
 /// Revert a commit.
 #[tauri::command]
@@ -34,13 +35,13 @@ pub fn revert_commit(repo_path: String, commit_sha: String) -> Result<RevertResu
             success: false,
             conflicts,
             message: format!("리버트 중 충돌이 발생했습니다: {} 개 파일", num_conflicts),
+            signed: false,
+            signature_type: None,
         });
     }
 
     // Auto-commit
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let sig = repo_signature(&repo)?;
     let mut index = repo
         .index()
         .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
@@ -63,12 +64,13 @@ pub fn revert_commit(repo_path: String, commit_sha: String) -> Result<RevertResu
         original_msg, commit_sha
     );
 
-    repo.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])
-        .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+    let created = commit_to_head(&repo, &sig, &sig, &message, &tree, &[&parent], "commit")?;
 
     Ok(RevertResult {
         success: true,
         conflicts: vec![],
         message: "리버트가 성공적으로 완료되었습니다".to_string(),
+        signed: created.signature_type.is_some(),
+        signature_type: created.signature_type,
     })
 }
@@ -0,0 +1,320 @@
+//! Commit signing driven by the repository's git config.
+//!
+//! When `commit.gpgsign` is on and `gpg.format` is `ssh`, commits are signed
+//! like git does it: the raw commit buffer is signed with
+//! `ssh-keygen -Y sign -n git` using the key from `user.signingkey`, and the
+//! signature is stored in the `gpgsig` header. Other formats are not
+//! supported yet and commits stay unsigned, as before.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use git2::{Commit, Oid, Repository, Signature, Tree};
+
+use super::cli::{run_with_timeout, sanitized_command};
+
+/// `ssh-keygen` may wait on an agent or a hardware key; give it a minute.
+const SIGN_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A commit written by this module, with how it was signed.
+pub struct CreatedCommit {
+    pub oid: Oid,
+    /// "ssh" when signed, None otherwise
+    pub signature_type: Option<String>,
+}
+
+/// Signing setup read from the repository config.
+struct SshSigner {
+    program: String,
+    key: String,
+}
+
+fn configured_signer(repo: &Repository) -> Result<Option<SshSigner>, String> {
+    let config = repo
+        .config()
+        .map_err(|e| format!("설정 읽기 실패: {}", e))?;
+    if !config.get_bool("commit.gpgsign").unwrap_or(false) {
+        return Ok(None);
+    }
+    if config.get_string("gpg.format").ok().as_deref() != Some("ssh") {
+        return Ok(None);
+    }
+    let key = config
+        .get_string("user.signingkey")
+        .map_err(|_| "SSH 서명에 사용할 user.signingkey가 설정되지 않았습니다".to_string())?;
+    let program = config
+        .get_string("gpg.ssh.program")
+        .unwrap_or_else(|_| "ssh-keygen".to_string());
+    Ok(Some(SshSigner { program, key }))
+}
+
+/// Commit onto HEAD like `Repository::commit(Some("HEAD"), ..)`, signing the
+/// commit when the config asks for SSH signatures.
+pub fn commit_to_head(
+    repo: &Repository,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+    operation: &str,
+) -> Result<CreatedCommit, String> {
+    match create_signed_commit(repo, author, committer, message, tree, parents)? {
+        Some(created) => {
+            move_head(repo, created.oid, operation, message)?;
+            Ok(created)
+        }
+        None => {
+            let oid = repo
+                .commit(Some("HEAD"), author, committer, message, tree, parents)
+                .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+            Ok(CreatedCommit {
+                oid,
+                signature_type: None,
+            })
+        }
+    }
+}
+
+/// Write a signed commit object without moving any ref. `None` when signing
+/// is not configured, leaving the caller to commit as usual.
+pub fn create_signed_commit(
+    repo: &Repository,
+    author: &Signature,
+    committer: &Signature,
+    message: &str,
+    tree: &Tree,
+    parents: &[&Commit],
+) -> Result<Option<CreatedCommit>, String> {
+    let signer = match configured_signer(repo)? {
+        Some(signer) => signer,
+        None => return Ok(None),
+    };
+    let buffer = repo
+        .commit_create_buffer(author, committer, message, tree, parents)
+        .map_err(|e| format!("커밋 생성 실패: {}", e))?;
+    let signature = ssh_sign(&signer, &buffer)?;
+    let content = std::str::from_utf8(&buffer)
+        .map_err(|_| "커밋 내용이 UTF-8이 아니어서 서명할 수 없습니다".to_string())?;
+    let oid = repo
+        .commit_signed(content, &signature, Some("gpgsig"))
+        .map_err(|e| format!("서명된 커밋 생성 실패: {}", e))?;
+    Ok(Some(CreatedCommit {
+        oid,
+        signature_type: Some("ssh".to_string()),
+    }))
+}
+
+/// Point HEAD (or the branch it names) at `oid`, logging
+/// "`operation`: <summary>" like git.
+pub fn move_head(
+    repo: &Repository,
+    oid: Oid,
+    operation: &str,
+    message: &str,
+) -> Result<(), String> {
+    let summary = message.lines().next().unwrap_or("");
+    let log_message = format!("{}: {}", operation, summary);
+    let head = repo
+        .find_reference("HEAD")
+        .map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+    match head.symbolic_target() {
+        Some(branch) => repo.reference(branch, oid, true, &log_message).map(|_| ()),
+        None => repo.set_head_detached(oid),
+    }
+    .map_err(|e| format!("HEAD 갱신 실패: {}", e))
+}
+
+/// Sign `buffer` with `ssh-keygen -Y sign`, returning the armored signature.
+fn ssh_sign(signer: &SshSigner, buffer: &[u8]) -> Result<String, String> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let work_dir =
+        std::env::temp_dir().join(format!("gitmul-sign-{}-{}", std::process::id(), nanos));
+    std::fs::create_dir_all(&work_dir).map_err(|e| format!("임시 폴더 생성 실패: {}", e))?;
+    let result = run_ssh_keygen(signer, buffer, &work_dir);
+    let _ = std::fs::remove_dir_all(&work_dir);
+    result
+}
+
+fn run_ssh_keygen(signer: &SshSigner, buffer: &[u8], work_dir: &Path) -> Result<String, String> {
+    let payload = work_dir.join("commit");
+    std::fs::write(&payload, buffer).map_err(|e| format!("임시 파일 쓰기 실패: {}", e))?;
+
+    let mut cmd = sanitized_command(Path::new(&signer.program));
+    cmd.args(["-Y", "sign", "-n", "git", "-f"]);
+    match literal_public_key(&signer.key) {
+        // A public key means the private half lives in ssh-agent
+        Some(public_key) => {
+            let key_file = work_dir.join("signingkey.pub");
+            std::fs::write(&key_file, public_key)
+                .map_err(|e| format!("임시 파일 쓰기 실패: {}", e))?;
+            cmd.arg(key_file).arg("-U");
+        }
+        None => {
+            cmd.arg(expand_home(&signer.key));
+        }
+    }
+    cmd.arg(&payload);
+
+    let output = run_with_timeout(&mut cmd, SIGN_TIMEOUT)?;
+    if !output.status.success() {
+        return Err(format!(
+            "SSH 커밋 서명 실패: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    std::fs::read_to_string(work_dir.join("commit.sig"))
+        .map_err(|e| format!("서명 파일 읽기 실패: {}", e))
+}
+
+/// `user.signingkey` given as a key (`key::ssh-ed25519 AAAA...` or a bare
+/// `ssh-...` line) rather than a path.
+fn literal_public_key(key: &str) -> Option<&str> {
+    if let Some(literal) = key.strip_prefix("key::") {
+        return Some(literal);
+    }
+    let is_key_line =
+        key.starts_with("ssh-") || key.starts_with("ecdsa-") || key.starts_with("sk-");
+    is_key_line.then_some(key)
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/").zip(dirs::home_dir()) {
+        Some((rest, home)) => home.join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::amend::amend_commit;
+    use crate::commands::git::create_commit;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    /// Repository configured for SSH signing with a fresh ed25519 key, or
+    /// None when `ssh-keygen` is not installed.
+    fn setup_signing_repo() -> Option<(TempDir, String)> {
+        let temp_dir = TempDir::new().unwrap();
+        let key = temp_dir.path().join("id_ed25519");
+        let generated = Command::new("ssh-keygen")
+            .args(["-q", "-t", "ed25519", "-N", "", "-C", "test", "-f"])
+            .arg(&key)
+            .status()
+            .ok()?;
+        if !generated.success() {
+            return None;
+        }
+
+        let repo_dir = temp_dir.path().join("repo");
+        let repo = Repository::init(&repo_dir).unwrap();
+        let mut config = repo.config().unwrap();
+        config.set_str("user.name", "Test User").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        config.set_bool("commit.gpgsign", true).unwrap();
+        config.set_str("gpg.format", "ssh").unwrap();
+        config
+            .set_str("user.signingkey", key.to_str().unwrap())
+            .unwrap();
+        Some((temp_dir, repo_dir.to_str().unwrap().to_string()))
+    }
+
+    fn assert_valid_ssh_signature(repo: &Repository, oid: Oid) {
+        let (signature, signed_data) = repo.extract_signature(&oid, None).unwrap();
+        let signature = signature.as_str().unwrap().to_string();
+        assert!(signature.starts_with("-----BEGIN SSH SIGNATURE-----"));
+
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("sig"), signature).unwrap();
+        std::fs::write(dir.path().join("data"), &*signed_data).unwrap();
+        let status = Command::new("ssh-keygen")
+            .args(["-Y", "check-novalidate", "-n", "git", "-s"])
+            .arg(dir.path().join("sig"))
+            .stdin(std::fs::File::open(dir.path().join("data")).unwrap())
+            .stdout(std::process::Stdio::null())
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[tokio::test]
+    async fn test_commit_and_amend_are_ssh_signed() {
+        let Some((_temp_dir, repo_path)) = setup_signing_repo() else {
+            return;
+        };
+        let repo = Repository::open(&repo_path).unwrap();
+        std::fs::write(Path::new(&repo_path).join("a.txt"), "a\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+
+        let result = create_commit(
+            repo_path.clone(),
+            "서명된 커밋".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(result.signed);
+        assert_eq!(result.signature_type.as_deref(), Some("ssh"));
+        let head = repo.head().unwrap();
+        assert_eq!(head.target().unwrap().to_string(), result.commit_sha);
+        assert_valid_ssh_signature(&repo, head.target().unwrap());
+        let reflog = repo.reflog("HEAD").unwrap();
+        assert_eq!(
+            reflog.get(0).unwrap().message(),
+            Some("commit: 서명된 커밋")
+        );
+
+        let amended =
+            amend_commit(repo_path.clone(), "고친 메시지".to_string(), None, None).unwrap();
+        assert!(amended.signed);
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert_eq!(head.id().to_string(), amended.commit_sha);
+        assert_eq!(head.message(), Some("고친 메시지"));
+        assert_eq!(head.parent_count(), 0);
+        assert_valid_ssh_signature(&repo, head.id());
+
+        // Without commit.gpgsign nothing is signed
+        repo.config()
+            .unwrap()
+            .set_bool("commit.gpgsign", false)
+            .unwrap();
+        let unsigned = create_commit(
+            repo_path,
+            "unsigned".to_string(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        assert!(!unsigned.signed && unsigned.signature_type.is_none());
+        assert!(repo
+            .extract_signature(&Oid::from_str(&unsigned.commit_sha).unwrap(), None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_literal_signing_keys() {
+        assert_eq!(
+            literal_public_key("key::ssh-ed25519 AAAA test"),
+            Some("ssh-ed25519 AAAA test")
+        );
+        assert_eq!(
+            literal_public_key("ssh-ed25519 AAAA test"),
+            Some("ssh-ed25519 AAAA test")
+        );
+        assert_eq!(literal_public_key("~/.ssh/id_ed25519"), None);
+    }
+}
@@ -0,0 +1,833 @@
+//! Hunk- and line-level staging (`git add -p` / `git reset -p`), plus
+//! discarding a single workdir hunk (`git checkout -p`).
+//!
+//! Hunk indices match the `hunks` that `parse_diff` returns for `get_file_diff`,
+//! so the diff is regenerated here with the same options before a hunk is picked.
+
+use git2::{Diff, DiffOptions, Patch};
+use std::path::Path;
+
+use super::diff::is_image_file;
+use super::utils::{normalize_unicode, open_repo, safe_repo_path};
+
+/// One hunk extracted from a regenerated diff.
+struct HunkLines {
+    header: String,
+    old_start: usize,
+    old_lines: usize,
+    new_start: usize,
+    new_lines: usize,
+    /// (origin, content) with origin one of ' ', '+', '-'
+    lines: Vec<(char, Vec<u8>)>,
+}
+
+/// Stage a single hunk of the working-tree changes of `file_path`.
+///
+/// `hunk_index` refers to the unstaged diff as shown by `get_file_diff`
+/// (pass the same `context_lines`). If `expected_header` is given and the hunk at
+/// that index no longer has this header, the file changed since the diff was shown.
+#[tauri::command]
+pub async fn stage_hunk(
+    repo_path: String,
+    file_path: String,
+    hunk_index: usize,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    stage_selection(
+        &repo_path,
+        &file_path,
+        hunk_index,
+        None,
+        expected_header.as_deref(),
+        context_lines,
+    )
+}
+
+/// Unstage a single hunk of the staged changes of `file_path`.
+///
+/// `hunk_index` refers to the staged diff (`get_file_diff` with `staged: true`).
+#[tauri::command]
+pub async fn unstage_hunk(
+    repo_path: String,
+    file_path: String,
+    hunk_index: usize,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    unstage_selection(
+        &repo_path,
+        &file_path,
+        hunk_index,
+        None,
+        expected_header.as_deref(),
+        context_lines,
+    )
+}
+
+/// Stage only some lines of a hunk. `line_indices` index into the hunk's
+/// `DiffLine`s as returned by `parse_diff`; unselected changes stay unstaged.
+#[tauri::command]
+pub async fn stage_lines(
+    repo_path: String,
+    file_path: String,
+    hunk_index: usize,
+    line_indices: Vec<usize>,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    stage_selection(
+        &repo_path,
+        &file_path,
+        hunk_index,
+        Some(&line_indices),
+        expected_header.as_deref(),
+        context_lines,
+    )
+}
+
+/// Unstage only some lines of a staged hunk (inverse of `stage_lines`).
+#[tauri::command]
+pub async fn unstage_lines(
+    repo_path: String,
+    file_path: String,
+    hunk_index: usize,
+    line_indices: Vec<usize>,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    unstage_selection(
+        &repo_path,
+        &file_path,
+        hunk_index,
+        Some(&line_indices),
+        expected_header.as_deref(),
+        context_lines,
+    )
+}
+
+/// Discard a single hunk of the unstaged changes of `file_path` from the
+/// working tree, leaving its other hunks untouched.
+///
+/// `hunk_index`, `expected_header` and `context_lines` work as for `stage_hunk`.
+/// The hunk is only reverted if the file on disk still contains exactly the
+/// lines the diff was built from; the file keeps its permissions and CRLF/LF style.
+#[tauri::command]
+pub async fn discard_hunk(
+    repo_path: String,
+    file_path: String,
+    hunk_index: usize,
+    expected_header: Option<String>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    let normalized = normalize_unicode(&file_path);
+    let full_path = safe_repo_path(Path::new(&repo_path), &normalized)?;
+    let repo = open_repo(&repo_path)?;
+    let index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+
+    let meta =
+        std::fs::symlink_metadata(&full_path).map_err(|e| format!("파일 정보 읽기 실패: {}", e))?;
+    if meta.file_type().is_symlink() {
+        return Err("심볼릭 링크는 hunk 단위로 되돌릴 수 없습니다".to_string());
+    }
+    let on_disk = std::fs::read(&full_path).map_err(|e| format!("파일 읽기 실패: {}", e))?;
+
+    let mut opts = hunk_diff_options(&normalized, context_lines);
+    let diff = repo
+        .diff_index_to_workdir(Some(&index), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let hunk = reversed(select_hunk(&diff, hunk_index, expected_header.as_deref())?);
+    let hunk = with_line_endings(hunk, uses_crlf(&on_disk));
+
+    // The reversed hunk's context and removed lines must still be on disk
+    let content = apply_hunk(&on_disk, &hunk).map_err(|_| {
+        "파일이 diff를 불러온 뒤 변경되었습니다. diff를 새로 고친 후 다시 시도하세요.".to_string()
+    })?;
+
+    std::fs::write(&full_path, &content).map_err(|e| format!("파일 쓰기 실패: {}", e))?;
+    std::fs::set_permissions(&full_path, meta.permissions())
+        .map_err(|e| format!("파일 권한 복원 실패: {}", e))?;
+    Ok(())
+}
+
+/// Apply a hunk (or some of its lines) of the workdir diff to the index.
+fn stage_selection(
+    repo_path: &str,
+    file_path: &str,
+    hunk_index: usize,
+    line_indices: Option<&[usize]>,
+    expected_header: Option<&str>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    let normalized = normalize_unicode(file_path);
+    let full_path = safe_repo_path(Path::new(repo_path), &normalized)?;
+    let repo = open_repo(repo_path)?;
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+
+    let mut opts = hunk_diff_options(&normalized, context_lines);
+    // New files are untracked: include their content so they yield a single "add" hunk
+    opts.include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    let diff = repo
+        .diff_index_to_workdir(Some(&index), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    let mut hunk = select_hunk(&diff, hunk_index, expected_header)?;
+    if let Some(indices) = line_indices {
+        hunk = select_lines(hunk, indices)?;
+    }
+
+    let existing = index.get_path(Path::new(&normalized), 0);
+    let preimage = match &existing {
+        Some(entry) => repo
+            .find_blob(entry.id)
+            .map_err(|e| format!("Blob 접근 실패: {}", e))?
+            .content()
+            .to_vec(),
+        None => Vec::new(),
+    };
+    let content = apply_hunk(&preimage, &hunk)?;
+
+    let entry = existing.unwrap_or_else(|| new_index_entry(&full_path, &normalized));
+    index
+        .add_frombuffer(&entry, &content)
+        .map_err(|e| format!("hunk 스테이징 실패: {}", e))?;
+    index
+        .write()
+        .map_err(|e| format!("인덱스 쓰기 실패: {}", e))?;
+    Ok(())
+}
+
+/// Revert a hunk (or some of its lines) of the staged diff in the index.
+fn unstage_selection(
+    repo_path: &str,
+    file_path: &str,
+    hunk_index: usize,
+    line_indices: Option<&[usize]>,
+    expected_header: Option<&str>,
+    context_lines: Option<u32>,
+) -> Result<(), String> {
+    let normalized = normalize_unicode(file_path);
+    safe_repo_path(Path::new(repo_path), &normalized)?;
+    let repo = open_repo(repo_path)?;
+    let mut index = repo
+        .index()
+        .map_err(|e| format!("인덱스 접근 실패: {}", e))?;
+
+    let head_tree = repo.head().ok().and_then(|h| h.peel_to_tree().ok());
+    let mut opts = hunk_diff_options(&normalized, context_lines);
+    let diff = repo
+        .diff_tree_to_index(head_tree.as_ref(), Some(&index), Some(&mut opts))
+        .map_err(|e| format!("Diff 생성 실패: {}", e))?;
+    // Reverse first so selection keeps unselected staged additions as context
+    let mut hunk = reversed(select_hunk(&diff, hunk_index, expected_header)?);
+    if let Some(indices) = line_indices {
+        hunk = select_lines(hunk, indices)?;
+    }
+
+    let file = Path::new(&normalized);
+    let entry = index
+        .get_path(file, 0)
+        .ok_or_else(|| format!("스테이징되지 않은 파일입니다: {}", normalized))?;
+    let staged = repo
+        .find_blob(entry.id)
+        .map_err(|e| format!("Blob 접근 실패: {}", e))?
+        .content()
+        .to_vec();
+    let content = apply_hunk(&staged, &hunk)?;
+
+    let in_head = head_tree
+        .as_ref()
+        .is_some_and(|tree| tree.get_path(file).is_ok());
+    if !in_head && content.is_empty() {
+        // Unstaging the whole addition of a new file makes it untracked again
+        index
+            .remove_path(file)
+            .map_err(|e| format!("인덱스 항목 제거 실패: {}", e))?;
+    } else {
+        index
+            .add_frombuffer(&entry, &content)
+            .map_err(|e| format!("hunk 스테이징 해제 실패: {}", e))?;
+    }
+    index
+        .write()
+        .map_err(|e| format!("인덱스 쓰기 실패: {}", e))?;
+    Ok(())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Same diff options as `get_file_diff`, so hunk boundaries line up.
+fn hunk_diff_options(path: &str, context_lines: Option<u32>) -> DiffOptions {
+    let mut opts = DiffOptions::new();
+    opts.pathspec(path);
+    opts.context_lines(context_lines.unwrap_or(3));
+    opts.interhunk_lines(0);
+    opts.ignore_whitespace_eol(true);
+    if !is_image_file(path) {
+        opts.force_text(true);
+    }
+    opts
+}
+
+/// Pick hunk `hunk_index` of the (single-file) diff.
+fn select_hunk(
+    diff: &Diff,
+    hunk_index: usize,
+    expected_header: Option<&str>,
+) -> Result<HunkLines, String> {
+    let stale = || {
+        "파일이 diff를 불러온 뒤 변경되었습니다. diff를 새로 고친 후 다시 시도하세요.".to_string()
+    };
+
+    let patch = match Patch::from_diff(diff, 0).map_err(|e| format!("Diff 생성 실패: {}", e))? {
+        Some(patch) => patch,
+        None if expected_header.is_some() => return Err(stale()),
+        None => return Err("변경된 hunk가 없습니다".to_string()),
+    };
+    if patch.delta().new_file().is_binary() {
+        return Err("바이너리 파일은 hunk 단위로 스테이징할 수 없습니다".to_string());
+    }
+    if hunk_index >= patch.num_hunks() {
+        return Err(match expected_header {
+            Some(_) => stale(),
+            None => format!(
+                "hunk 번호가 범위를 벗어났습니다: {} (전체 {}개)",
+                hunk_index,
+                patch.num_hunks()
+            ),
+        });
+    }
+
+    let (hunk, line_count) = patch
+        .hunk(hunk_index)
+        .map_err(|e| format!("hunk 읽기 실패: {}", e))?;
+    let header = String::from_utf8_lossy(hunk.header())
+        .trim_end()
+        .to_string();
+    if let Some(expected) = expected_header {
+        if expected.trim_end() != header {
+            return Err(stale());
+        }
+    }
+
+    let mut lines = Vec::with_capacity(line_count);
+    for i in 0..line_count {
+        let line = patch
+            .line_in_hunk(hunk_index, i)
+            .map_err(|e| format!("hunk 읽기 실패: {}", e))?;
+        // Skip "\ No newline at end of file" markers; content already lacks the newline
+        if matches!(line.origin(), ' ' | '+' | '-') {
+            lines.push((line.origin(), line.content().to_vec()));
+        }
+    }
+
+    Ok(HunkLines {
+        header,
+        old_start: hunk.old_start() as usize,
+        old_lines: hunk.old_lines() as usize,
+        new_start: hunk.new_start() as usize,
+        new_lines: hunk.new_lines() as usize,
+        lines,
+    })
+}
+
+/// Narrow a hunk to the selected lines: unselected additions are dropped and
+/// unselected deletions become context, so only the chosen changes apply.
+fn select_lines(hunk: HunkLines, line_indices: &[usize]) -> Result<HunkLines, String> {
+    if let Some(bad) = line_indices.iter().find(|i| **i >= hunk.lines.len()) {
+        return Err(format!(
+            "줄 번호가 범위를 벗어났습니다: {} (전체 {}줄)",
+            bad,
+            hunk.lines.len()
+        ));
+    }
+    if !line_indices.iter().any(|i| hunk.lines[*i].0 != ' ') {
+        return Err("선택한 줄에 변경 사항이 없습니다".to_string());
+    }
+
+    let lines = hunk
+        .lines
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, (origin, content))| match origin {
+            '+' if !line_indices.contains(&i) => None,
+            '-' if !line_indices.contains(&i) => Some((' ', content)),
+            _ => Some((origin, content)),
+        })
+        .collect();
+    Ok(HunkLines { lines, ..hunk })
+}
+
+/// Swap the sides of a hunk so applying it undoes the change.
+fn reversed(hunk: HunkLines) -> HunkLines {
+    HunkLines {
+        header: hunk.header,
+        old_start: hunk.new_start,
+        old_lines: hunk.new_lines,
+        new_start: hunk.old_start,
+        new_lines: hunk.old_lines,
+        lines: hunk
+            .lines
+            .into_iter()
+            .map(|(origin, content)| match origin {
+                '+' => ('-', content),
+                '-' => ('+', content),
+                _ => (origin, content),
+            })
+            .collect(),
+    }
+}
+
+/// Whether most lines of `content` end with CRLF.
+fn uses_crlf(content: &[u8]) -> bool {
+    let lines = content.iter().filter(|b| **b == b'\n').count();
+    let crlf = content.windows(2).filter(|w| w == b"\r\n").count();
+    lines > 0 && crlf * 2 > lines
+}
+
+/// Rewrite the endings of the lines a hunk adds to CRLF or LF, so restored
+/// lines (taken from the index blob) match the style of the file on disk.
+fn with_line_endings(hunk: HunkLines, crlf: bool) -> HunkLines {
+    let lines = hunk
+        .lines
+        .into_iter()
+        .map(|(origin, mut content)| {
+            if origin == '+' && content.ends_with(b"\n") {
+                content.pop();
+                if content.ends_with(b"\r") {
+                    content.pop();
+                }
+                if crlf {
+                    content.push(b'\r');
+                }
+                content.push(b'\n');
+            }
+            (origin, content)
+        })
+        .collect();
+    HunkLines { lines, ..hunk }
+}
+
+/// Apply `hunk` to `preimage`, verifying its context and removed lines still match.
+fn apply_hunk(preimage: &[u8], hunk: &HunkLines) -> Result<Vec<u8>, String> {
+    let old: Vec<&[u8]> = preimage.split_inclusive(|b| *b == b'\n').collect();
+    // A zero-length range starts *after* `old_start`; otherwise it is 1-based
+    let start = if hunk.old_lines == 0 {
+        hunk.old_start
+    } else {
+        hunk.old_start.saturating_sub(1)
+    };
+    let mismatch = || {
+        format!(
+            "hunk를 적용할 수 없습니다 ({}): 인덱스 내용이 변경되었습니다",
+            hunk.header
+        )
+    };
+    if start > old.len() {
+        return Err(mismatch());
+    }
+
+    let mut result = old[..start].concat();
+    let mut pos = start;
+    for (origin, content) in &hunk.lines {
+        match origin {
+            '+' => push_line(&mut result, content),
+            _ => {
+                let current = old.get(pos).ok_or_else(mismatch)?;
+                // Diff ignores EOL whitespace, so compare the same way
+                if current.trim_ascii_end() != content.trim_ascii_end() {
+                    return Err(mismatch());
+                }
+                if *origin == ' ' {
+                    push_line(&mut result, current);
+                }
+                pos += 1;
+            }
+        }
+    }
+    for rest in &old[pos..] {
+        push_line(&mut result, rest);
+    }
+    Ok(result)
+}
+
+/// Append `line`, first terminating the previous line if it was a former
+/// last line without a newline (possible once only some lines are applied).
+fn push_line(result: &mut Vec<u8>, line: &[u8]) {
+    if !result.is_empty() && !result.ends_with(b"\n") {
+        if line.ends_with(b"\r\n") {
+            result.push(b'\r');
+        }
+        result.push(b'\n');
+    }
+    result.extend_from_slice(line);
+}
+
+/// Index entry for a file that is not in the index yet.
+fn new_index_entry(full_path: &Path, path: &str) -> git2::IndexEntry {
+    git2::IndexEntry {
+        ctime: git2::IndexTime::new(0, 0),
+        mtime: git2::IndexTime::new(0, 0),
+        dev: 0,
+        ino: 0,
+        mode: workdir_file_mode(full_path),
+        uid: 0,
+        gid: 0,
+        file_size: 0,
+        id: git2::Oid::zero(),
+        flags: 0,
+        flags_extended: 0,
+        path: path.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(unix)]
+fn workdir_file_mode(path: &Path) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    match std::fs::metadata(path) {
+        Ok(meta) if meta.permissions().mode() & 0o111 != 0 => 0o100755,
+        _ => 0o100644,
+    }
+}
+
+#[cfg(not(unix))]
+fn workdir_file_mode(_path: &Path) -> u32 {
+    0o100644
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use std::fs;
+    use tempfile::tempdir;
+
+    const ORIGINAL: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm\nn\no\n";
+
+    fn setup_repo_with_file() -> (tempfile::TempDir, String) {
+        let temp_dir = tempdir().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        fs::write(temp_dir.path().join("file.txt"), ORIGINAL).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("file.txt")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Initial commit", &tree, &[])
+            .unwrap();
+        let path = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, path)
+    }
+
+    fn staged_content(repo_path: &str, file: &str) -> Option<String> {
+        let repo = Repository::open(repo_path).unwrap();
+        let index = repo.index().unwrap();
+        let entry = index.get_path(Path::new(file), 0)?;
+        let blob = repo.find_blob(entry.id).unwrap();
+        Some(String::from_utf8(blob.content().to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_stage_and_unstage_one_of_two_hunks() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        let modified = ORIGINAL.replace("b\n", "B\n").replace("n\n", "N\n");
+        fs::write(temp_dir.path().join("file.txt"), &modified).unwrap();
+
+        stage_hunk(repo_path.clone(), "file.txt".into(), 1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            staged_content(&repo_path, "file.txt").unwrap(),
+            ORIGINAL.replace("n\n", "N\n")
+        );
+        // Working tree is untouched
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("file.txt")).unwrap(),
+            modified
+        );
+
+        // Staged diff now has a single hunk; unstage it
+        unstage_hunk(repo_path.clone(), "file.txt".into(), 0, None, None)
+            .await
+            .unwrap();
+        assert_eq!(staged_content(&repo_path, "file.txt").unwrap(), ORIGINAL);
+    }
+
+    #[tokio::test]
+    async fn test_stage_hunk_of_new_file() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        fs::write(temp_dir.path().join("new.txt"), "hello\nworld\n").unwrap();
+
+        stage_hunk(repo_path.clone(), "new.txt".into(), 0, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            staged_content(&repo_path, "new.txt").unwrap(),
+            "hello\nworld\n"
+        );
+
+        unstage_hunk(repo_path.clone(), "new.txt".into(), 0, None, None)
+            .await
+            .unwrap();
+        assert!(staged_content(&repo_path, "new.txt").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stage_hunk_rejects_stale_diff() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        fs::write(
+            temp_dir.path().join("file.txt"),
+            ORIGINAL.replace("b\n", "B\n"),
+        )
+        .unwrap();
+        let shown_header = "@@ -1,5 +1,5 @@";
+
+        // File edited again after the diff was shown: hunk moved
+        let edited = format!("z\n{}", ORIGINAL.replace("b\n", "B\n"));
+        fs::write(temp_dir.path().join("file.txt"), edited).unwrap();
+
+        let err = stage_hunk(
+            repo_path.clone(),
+            "file.txt".into(),
+            0,
+            Some(shown_header.to_string()),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("변경되었습니다"));
+        assert!(
+            stage_hunk(repo_path.clone(), "file.txt".into(), 5, None, None)
+                .await
+                .is_err()
+        );
+        assert_eq!(staged_content(&repo_path, "file.txt").unwrap(), ORIGINAL);
+    }
+
+    fn commit_file(repo_path: &str, file: &str, content: &str) {
+        let repo = Repository::open(repo_path).unwrap();
+        fs::write(Path::new(repo_path).join(file), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(file)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Update", &tree, &[&parent])
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stage_lines_round_trip() {
+        use crate::commands::diff::{get_file_diff, parse_diff};
+
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        fs::write(
+            temp_dir.path().join("file.txt"),
+            format!("{}w\nx\ny\nz\n", ORIGINAL),
+        )
+        .unwrap();
+
+        // Hunk lines: context m, n, o then +w +x +y +z
+        stage_lines(
+            repo_path.clone(),
+            "file.txt".into(),
+            0,
+            vec![3, 5],
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let staged = get_file_diff(
+            repo_path.clone(),
+            "file.txt".into(),
+            true,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+        let parsed = parse_diff(staged.patch).await.unwrap();
+        let added: Vec<&str> = parsed.hunks[0]
+            .lines
+            .iter()
+            .filter(|l| l.line_type == "addition")
+            .map(|l| l.content.as_str())
+            .collect();
+        assert_eq!(added, vec!["w", "y"]);
+        assert_eq!(parsed.deletions, 0);
+
+        // Inverse: staged hunk is m, n, o, +w, +y; unstage "y"
+        unstage_lines(repo_path.clone(), "file.txt".into(), 0, vec![4], None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            staged_content(&repo_path, "file.txt").unwrap(),
+            format!("{}w\n", ORIGINAL)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stage_lines_only_deletion() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        fs::write(
+            temp_dir.path().join("file.txt"),
+            ORIGINAL.replace("c\nd\n", ""),
+        )
+        .unwrap();
+
+        // Hunk lines: context a, b then -c -d, context e, f, g
+        stage_lines(repo_path.clone(), "file.txt".into(), 0, vec![2], None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            staged_content(&repo_path, "file.txt").unwrap(),
+            ORIGINAL.replace("c\n", "")
+        );
+
+        // Context-only selection is rejected
+        assert!(
+            stage_lines(repo_path.clone(), "file.txt".into(), 0, vec![0], None, None)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stage_lines_keeps_crlf() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        commit_file(&repo_path, "crlf.txt", "a\r\nb\r\nc\r\n");
+        fs::write(
+            temp_dir.path().join("crlf.txt"),
+            "a\r\nX\r\nb\r\nY\r\nc\r\n",
+        )
+        .unwrap();
+
+        // Hunk lines: a, +X, b, +Y, c
+        stage_lines(repo_path.clone(), "crlf.txt".into(), 0, vec![3], None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            staged_content(&repo_path, "crlf.txt").unwrap(),
+            "a\r\nb\r\nY\r\nc\r\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stage_lines_without_trailing_newline() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        commit_file(&repo_path, "eof.txt", "a\nb");
+        fs::write(temp_dir.path().join("eof.txt"), "a\nb\nc").unwrap();
+
+        // EOL-insensitive diff keeps "b" as context: a, b, +c (no newline)
+        stage_lines(repo_path.clone(), "eof.txt".into(), 0, vec![2], None, None)
+            .await
+            .unwrap();
+        assert_eq!(staged_content(&repo_path, "eof.txt").unwrap(), "a\nb\nc");
+    }
+
+    /// Thirty numbered lines; editing lines 2, 15 and 28 yields three hunks.
+    fn numbered_lines(edit: &[usize], eol: &str) -> String {
+        (1..=30)
+            .map(|n| {
+                if edit.contains(&n) {
+                    format!("changed {}{}", n, eol)
+                } else {
+                    format!("line {}{}", n, eol)
+                }
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_discard_middle_hunk() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        commit_file(&repo_path, "lines.txt", &numbered_lines(&[], "\n"));
+        let path = temp_dir.path().join("lines.txt");
+        fs::write(&path, numbered_lines(&[2, 15, 28], "\n")).unwrap();
+
+        discard_hunk(repo_path.clone(), "lines.txt".into(), 1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            numbered_lines(&[2, 28], "\n")
+        );
+        // Index is untouched
+        assert_eq!(
+            staged_content(&repo_path, "lines.txt").unwrap(),
+            numbered_lines(&[], "\n")
+        );
+
+        // Remaining hunks are still the first and last edits
+        discard_hunk(repo_path.clone(), "lines.txt".into(), 1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            numbered_lines(&[2], "\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_discard_hunk_refuses_changed_file() {
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        let path = temp_dir.path().join("file.txt");
+        fs::write(&path, ORIGINAL.replace("b\n", "B\n")).unwrap();
+        let shown_header = "@@ -1,5 +1,5 @@";
+
+        // Edited again after the diff was shown
+        let edited = format!("z\n{}", ORIGINAL.replace("b\n", "B\n"));
+        fs::write(&path, &edited).unwrap();
+
+        let err = discard_hunk(
+            repo_path.clone(),
+            "file.txt".into(),
+            0,
+            Some(shown_header.to_string()),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(err.contains("변경되었습니다"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), edited);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_discard_hunk_keeps_crlf_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (temp_dir, repo_path) = setup_repo_with_file();
+        // Committed with LF, checked out with CRLF (as autocrlf would)
+        commit_file(&repo_path, "run.sh", &numbered_lines(&[], "\n"));
+        let path = temp_dir.path().join("run.sh");
+        fs::write(&path, numbered_lines(&[2, 15, 28], "\r\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        discard_hunk(repo_path.clone(), "run.sh".into(), 1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            numbered_lines(&[2, 28], "\r\n")
+        );
+        assert_eq!(
+            fs::metadata(&path).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+    }
+}
@@ -1,7 +1,8 @@
 use git2::StashFlags;
 
-use super::models::StashInfo;
-use super::utils::open_repo;
+use super::models::{Page, StashInfo};
+use super::relative_time::{format_relative_time, relative_date_locale, Locale};
+use super::utils::{collect_page, open_repo, page_window, repo_signature};
 
 /// Create a stash with optional message.
 #[tauri::command]
@@ -12,42 +13,108 @@ pub fn stash_save(
 ) -> Result<String, String> {
     let mut repo = open_repo(&repo_path)?;
 
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let stash_msg = message.as_deref().unwrap_or("WIP");
+    let oid = save_stash(&mut repo, stash_msg, include_untracked)?
+        .ok_or("스태시 생성 실패: 스태시할 변경사항이 없습니다")?;
+
+    Ok(format!("스태시 생성 완료: {}", oid))
+}
+
+/// Stash local changes as stash 0, or return None when there is nothing to
+/// stash.
+pub(super) fn save_stash(
+    repo: &mut git2::Repository,
+    message: &str,
+    include_untracked: bool,
+) -> Result<Option<git2::Oid>, String> {
+    let sig = repo_signature(repo)?;
 
     let mut flags = StashFlags::DEFAULT;
     if include_untracked {
         flags.insert(StashFlags::INCLUDE_UNTRACKED);
     }
 
-    let stash_msg = message.as_deref().unwrap_or("WIP");
-    let oid = repo
-        .stash_save(&sig, stash_msg, Some(flags))
-        .map_err(|e| format!("스태시 생성 실패: {}", e))?;
-
-    Ok(format!("스태시 생성 완료: {}", oid))
+    match repo.stash_save(&sig, message, Some(flags)) {
+        Ok(oid) => Ok(Some(oid)),
+        Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+        Err(e) => Err(format!("스태시 생성 실패: {}", e)),
+    }
 }
 
 /// List all stashes.
+/// If `with_relative_dates` is true, fills `relative_date` in the given `locale` ("ko" | "en").
+///
+/// Compatibility wrapper returning a plain list; new callers should use
+/// `stash_list_page`.
 #[tauri::command]
-pub fn stash_list(repo_path: String) -> Result<Vec<StashInfo>, String> {
+pub fn stash_list(
+    repo_path: String,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+) -> Result<Vec<StashInfo>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
     let mut repo = open_repo(&repo_path)?;
+    let mut stashes = collect_stashes(&mut repo)?;
+    if let Some(locale) = relative_locale {
+        fill_relative_dates(&repo, &mut stashes, locale);
+    }
+    Ok(stashes)
+}
+
+/// One page of `stash_list`.
+#[tauri::command]
+pub fn stash_list_page(
+    repo_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+    with_relative_dates: Option<bool>,
+    locale: Option<String>,
+) -> Result<Page<StashInfo>, String> {
+    let relative_locale = relative_date_locale(with_relative_dates, locale)?;
+    let (offset, limit) = page_window(cursor.as_deref(), limit)?;
+    let mut repo = open_repo(&repo_path)?;
+
+    let stashes = collect_stashes(&mut repo)?;
+    let total = stashes.len() as u64;
+    let mut page = collect_page(
+        stashes.into_iter().map(Ok::<_, String>),
+        offset,
+        limit,
+        Some(total),
+    )?;
+    if let Some(locale) = relative_locale {
+        fill_relative_dates(&repo, &mut page.items, locale);
+    }
+    Ok(page)
+}
 
+fn collect_stashes(repo: &mut git2::Repository) -> Result<Vec<StashInfo>, String> {
     let mut stashes = Vec::new();
     repo.stash_foreach(|index, message, oid| {
         stashes.push(StashInfo {
             index,
             message: message.to_string(),
             oid: oid.to_string(),
+            relative_date: None,
         });
         true
     })
     .map_err(|e| format!("스태시 목록 조회 실패: {}", e))?;
-
     Ok(stashes)
 }
 
+fn fill_relative_dates(repo: &git2::Repository, stashes: &mut [StashInfo], locale: Locale) {
+    let now = chrono::Utc::now().timestamp();
+    for stash in stashes {
+        let time = git2::Oid::from_str(&stash.oid)
+            .and_then(|oid| repo.find_commit(oid))
+            .map(|c| c.time().seconds());
+        if let Ok(time) = time {
+            stash.relative_date = Some(format_relative_time(time, now, locale));
+        }
+    }
+}
+
 /// Apply a stash by index.
 #[tauri::command]
 pub fn stash_apply(repo_path: String, index: usize) -> Result<String, String> {
@@ -137,7 +204,7 @@ mod tests {
         );
         assert!(result.is_ok());
 
-        let stashes = stash_list(repo_path.to_str().unwrap().to_string()).unwrap();
+        let stashes = stash_list(repo_path.to_str().unwrap().to_string(), None, None).unwrap();
         assert_eq!(stashes.len(), 1);
         assert!(stashes[0].message.contains("My stash"));
     }
@@ -158,7 +225,7 @@ mod tests {
         let content = fs::read_to_string(repo_path.join("file1.txt")).unwrap();
         assert_eq!(content, "modified");
 
-        let stashes = stash_list(repo_path.to_str().unwrap().to_string()).unwrap();
+        let stashes = stash_list(repo_path.to_str().unwrap().to_string(), None, None).unwrap();
         assert_eq!(stashes.len(), 0);
     }
 
@@ -0,0 +1,420 @@
+//! Submodules of the open repository and how their checkouts compare with
+//! the commits the superproject records.
+
+use git2::{
+    FetchOptions, Repository, Submodule, SubmoduleIgnore, SubmoduleStatus, SubmoduleUpdateOptions,
+};
+
+use super::credentials::remote_error;
+use super::models::{SubmoduleInfo, SubmoduleUpdateResult};
+use super::remote::{fetch_progress_callbacks, repo_progress_key, update_progress};
+use super::utils::{normalize_unicode, open_repo};
+
+/// All submodules, sorted by path.
+///
+/// `status` is "uninitialized" when the submodule has not been cloned,
+/// "modified" when its working tree has uncommitted changes or is checked out
+/// at a commit that does not descend from the recorded one, "ahead" when it
+/// is checked out at a newer commit than recorded, and "clean" otherwise.
+#[tauri::command]
+pub async fn list_submodules(repo_path: String) -> Result<Vec<SubmoduleInfo>, String> {
+    let repo = open_repo(&repo_path)?;
+    let submodules = repo
+        .submodules()
+        .map_err(|e| format!("서브모듈 목록 조회 실패: {}", e))?;
+
+    let mut infos = Vec::new();
+    for submodule in &submodules {
+        let path = submodule.path().to_string_lossy().to_string();
+        let flags = repo
+            .submodule_status(&path, SubmoduleIgnore::None)
+            .map_err(|e| format!("서브모듈 '{}' 상태 조회 실패: {}", path, e))?;
+        let index_id = submodule.index_id();
+        let workdir_id = submodule.workdir_id();
+        infos.push(SubmoduleInfo {
+            path: normalize_unicode(&path),
+            url: submodule.url().map(normalize_unicode),
+            index_sha: index_id.map(|oid| oid.to_string()),
+            workdir_sha: workdir_id.map(|oid| oid.to_string()),
+            status: submodule_state(submodule, flags, index_id, workdir_id).to_string(),
+        });
+    }
+    infos.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(infos)
+}
+
+fn submodule_state(
+    submodule: &git2::Submodule,
+    flags: SubmoduleStatus,
+    index_id: Option<git2::Oid>,
+    workdir_id: Option<git2::Oid>,
+) -> &'static str {
+    let (Some(workdir_id), false) = (workdir_id, flags.is_wd_uninitialized()) else {
+        return "uninitialized";
+    };
+    if has_local_changes(flags) {
+        return "modified";
+    }
+    match index_id {
+        Some(index_id) if index_id == workdir_id => "clean",
+        Some(index_id) => {
+            let moved_forward = submodule
+                .open()
+                .and_then(|sub| sub.graph_descendant_of(workdir_id, index_id))
+                .unwrap_or(false);
+            if moved_forward {
+                "ahead"
+            } else {
+                "modified"
+            }
+        }
+        None => "modified",
+    }
+}
+
+/// Check out the commit the superproject records for submodule `name` (its
+/// name or path), cloning or fetching it as needed. With `init` an
+/// uninitialized submodule is registered first, resolving a relative URL
+/// against the superproject's origin (`git submodule update --init`); with
+/// `recursive` its nested submodules are updated too.
+///
+/// Failures are reported per submodule instead of aborting, so the result
+/// has an entry for `name` and, when recursive, for each nested submodule.
+#[tauri::command]
+pub async fn update_submodule(
+    repo_path: String,
+    name: String,
+    init: bool,
+    recursive: bool,
+) -> Result<Vec<SubmoduleUpdateResult>, String> {
+    let normalized_name = normalize_unicode(&name);
+    let repo = open_repo(&repo_path)?;
+    let mut submodule = repo
+        .find_submodule(&normalized_name)
+        .map_err(|e| format!("서브모듈 '{}' 찾기 실패: {}", normalized_name, e))?;
+
+    let progress = repo_progress_key(&repo);
+    update_progress(
+        &progress,
+        "updating_submodules",
+        &format!("서브모듈 '{}' 업데이트 중...", normalized_name),
+    );
+    let mut results = Vec::new();
+    update_one(&mut submodule, "", init, recursive, &progress, &mut results);
+    update_progress(&progress, "idle", "서브모듈 업데이트 완료");
+    Ok(results)
+}
+
+/// Initialize and check out every submodule, nested ones included
+/// (`git submodule update --init --recursive`), reporting each separately.
+#[tauri::command]
+pub async fn init_all_submodules(repo_path: String) -> Result<Vec<SubmoduleUpdateResult>, String> {
+    let repo = open_repo(&repo_path)?;
+    let progress = repo_progress_key(&repo);
+    update_progress(&progress, "updating_submodules", "서브모듈 초기화 중...");
+    let mut results = Vec::new();
+    let updated = update_nested(&repo, "", true, &progress, &mut results);
+    update_progress(&progress, "idle", "서브모듈 초기화 완료");
+    updated.map(|_| results)
+}
+
+/// Progress goes to `progress`, the `progress_key` of the repository the
+/// update started in.
+fn update_nested(
+    repo: &Repository,
+    prefix: &str,
+    init: bool,
+    progress: &str,
+    results: &mut Vec<SubmoduleUpdateResult>,
+) -> Result<(), String> {
+    let mut submodules = repo
+        .submodules()
+        .map_err(|e| format!("서브모듈 목록 조회 실패: {}", e))?;
+    for submodule in &mut submodules {
+        update_one(submodule, prefix, init, true, progress, results);
+    }
+    Ok(())
+}
+
+fn update_one(
+    submodule: &mut Submodule,
+    prefix: &str,
+    init: bool,
+    recursive: bool,
+    progress: &str,
+    results: &mut Vec<SubmoduleUpdateResult>,
+) {
+    let path = format!(
+        "{}{}",
+        prefix,
+        normalize_unicode(&submodule.path().to_string_lossy())
+    );
+    let checkout = match checkout_recorded_commit(submodule, init, progress) {
+        Ok(checkout) => checkout,
+        Err(error) => {
+            results.push(SubmoduleUpdateResult {
+                path,
+                success: false,
+                error: Some(error),
+                workdir_sha: None,
+            });
+            return;
+        }
+    };
+    results.push(SubmoduleUpdateResult {
+        path: path.clone(),
+        success: true,
+        error: None,
+        workdir_sha: checkout
+            .head()
+            .ok()
+            .and_then(|h| h.target())
+            .map(|oid| oid.to_string()),
+    });
+    if recursive {
+        let nested_prefix = format!("{}/", path);
+        if let Err(error) = update_nested(&checkout, &nested_prefix, init, progress, results) {
+            results.push(SubmoduleUpdateResult {
+                path: nested_prefix,
+                success: false,
+                error: Some(error),
+                workdir_sha: None,
+            });
+        }
+    }
+}
+
+/// `Submodule::update` with progress reporting; returns the updated checkout.
+fn checkout_recorded_commit(
+    submodule: &mut Submodule,
+    init: bool,
+    progress: &str,
+) -> Result<Repository, String> {
+    let mut fetch_options = FetchOptions::new();
+    fetch_options.remote_callbacks(fetch_progress_callbacks(progress.to_string(), None, None));
+    let mut options = SubmoduleUpdateOptions::new();
+    options.fetch(fetch_options);
+    options.allow_fetch(true);
+    submodule
+        .update(init, Some(&mut options))
+        .map_err(|e| remote_error("서브모듈 업데이트 실패", e))?;
+    submodule
+        .open()
+        .map_err(|e| format!("서브모듈 열기 실패: {}", e))
+}
+
+/// Uncommitted changes inside the submodule's own working tree or index.
+fn has_local_changes(flags: SubmoduleStatus) -> bool {
+    flags.intersects(
+        SubmoduleStatus::WD_INDEX_MODIFIED
+            | SubmoduleStatus::WD_WD_MODIFIED
+            | SubmoduleStatus::WD_UNTRACKED,
+    )
+}
+
+/// Whether the checked-out commit of the submodule at `path` differs from the
+/// one recorded in the index, i.e. the gitlink itself changed.
+pub(super) fn pointer_moved(repo: &Repository, path: &str) -> bool {
+    repo.submodule_status(path, SubmoduleIgnore::None)
+        .map(|flags| flags.is_wd_modified() || flags.is_wd_added() || flags.is_wd_deleted())
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::git::get_repository_status;
+    use git2::Signature;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn commit_file(repo: &Repository, name: &str, content: &str) -> git2::Oid {
+        std::fs::write(repo.workdir().unwrap().join(name), content).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(name)).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let parent = repo.head().and_then(|h| h.peel_to_commit()).ok();
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &sig, &sig, content, &tree, &parents)
+            .unwrap()
+    }
+
+    fn add_submodule(repo: &Repository, url: &Path, path: &str) {
+        let mut submodule = repo
+            .submodule(url.to_str().unwrap(), Path::new(path), true)
+            .unwrap();
+        submodule.clone(None).unwrap();
+        submodule.add_finalize().unwrap();
+    }
+
+    /// Clone of a superproject with submodules "libs/a" (initialized, pinned
+    /// at the library's first commit) and "libs/b" (never initialized, with a
+    /// relative URL). The superproject itself is the clone's origin.
+    /// Returns (temp dir, clone path, lib a's first and second commit).
+    fn setup_superproject() -> (TempDir, String, git2::Oid, git2::Oid) {
+        let temp_dir = TempDir::new().unwrap();
+        let lib_a_path = temp_dir.path().join("lib-a");
+        let lib_a = Repository::init(&lib_a_path).unwrap();
+        let first = commit_file(&lib_a, "lib.txt", "v1\n");
+        let second = commit_file(&lib_a, "lib.txt", "v2\n");
+        lib_a.set_head_detached(first).unwrap();
+        let lib_b_path = temp_dir.path().join("lib-b");
+        commit_file(&Repository::init(&lib_b_path).unwrap(), "b.txt", "b\n");
+
+        let super_path = temp_dir.path().join("super");
+        let repo = Repository::init(&super_path).unwrap();
+        commit_file(&repo, "README.md", "# super\n");
+        add_submodule(&repo, &lib_a_path, "libs/a");
+        add_submodule(&repo, &lib_b_path, "libs/b");
+        // lib-b sits next to the superproject; record it relative to origin
+        let gitmodules = super_path.join(".gitmodules");
+        let relative = std::fs::read_to_string(&gitmodules)
+            .unwrap()
+            .replace(lib_b_path.to_str().unwrap(), "../lib-b");
+        std::fs::write(&gitmodules, relative).unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new(".gitmodules")).unwrap();
+        index.write().unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(Some("HEAD"), &sig, &sig, "Add submodules", &tree, &[&head])
+            .unwrap();
+
+        let clone_path = temp_dir.path().join("clone");
+        let clone = Repository::clone(super_path.to_str().unwrap(), &clone_path).unwrap();
+        clone
+            .find_submodule("libs/a")
+            .unwrap()
+            .update(true, None)
+            .unwrap();
+        let clone_path = clone_path.to_str().unwrap().to_string();
+        (temp_dir, clone_path, first, second)
+    }
+
+    #[tokio::test]
+    async fn test_list_submodules() {
+        let (temp_dir, repo_path, first, second) = setup_superproject();
+
+        let submodules = list_submodules(repo_path.clone()).await.unwrap();
+        let paths: Vec<&str> = submodules.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(paths, vec!["libs/a", "libs/b"]);
+        let (a, b) = (&submodules[0], &submodules[1]);
+        assert_eq!(a.status, "clean");
+        assert_eq!(a.index_sha, Some(first.to_string()));
+        assert_eq!(a.workdir_sha, Some(first.to_string()));
+        assert_eq!(a.url.as_deref(), temp_dir.path().join("lib-a").to_str());
+        assert_eq!(b.status, "uninitialized");
+        assert!(b.index_sha.is_some());
+        assert_eq!(b.workdir_sha, None);
+        assert!(get_repository_status(repo_path.clone())
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Uncommitted edits inside the submodule are not superproject changes
+        let lib_file = Path::new(&repo_path).join("libs/a/lib.txt");
+        std::fs::write(&lib_file, "edited\n").unwrap();
+        let submodules = list_submodules(repo_path.clone()).await.unwrap();
+        assert_eq!(submodules[0].status, "modified");
+        assert!(get_repository_status(repo_path.clone())
+            .await
+            .unwrap()
+            .is_empty());
+        std::fs::write(&lib_file, "v1\n").unwrap();
+
+        // Checking out a newer commit moves the recorded pointer
+        let checkout = Repository::open(Path::new(&repo_path).join("libs/a")).unwrap();
+        checkout.set_head_detached(second).unwrap();
+        checkout
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        let submodules = list_submodules(repo_path.clone()).await.unwrap();
+        assert_eq!(submodules[0].status, "ahead");
+        assert_eq!(submodules[0].workdir_sha, Some(second.to_string()));
+        let status = get_repository_status(repo_path).await.unwrap();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].path, "libs/a");
+        assert_eq!(status[0].status, "submodule");
+    }
+
+    #[tokio::test]
+    async fn test_update_submodule_follows_moved_pointer() {
+        let (temp_dir, repo_path, first, second) = setup_superproject();
+
+        // Upstream moves libs/a to the library's second commit
+        let upstream = Repository::open(temp_dir.path().join("super")).unwrap();
+        let mut submodule = upstream.find_submodule("libs/a").unwrap();
+        let checkout = submodule.open().unwrap();
+        checkout.set_head_detached(second).unwrap();
+        checkout
+            .checkout_head(Some(git2::build::CheckoutBuilder::new().force()))
+            .unwrap();
+        submodule.add_to_index(true).unwrap();
+        let tree = upstream
+            .find_tree(upstream.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let sig = Signature::now("Test User", "test@example.com").unwrap();
+        let head = upstream.head().unwrap().peel_to_commit().unwrap();
+        let moved = upstream
+            .commit(Some("HEAD"), &sig, &sig, "Bump libs/a", &tree, &[&head])
+            .unwrap();
+
+        // The clone pulls it: the pointer moves, the checkout stays behind
+        let repo = Repository::open(&repo_path).unwrap();
+        repo.find_remote("origin")
+            .unwrap()
+            .fetch(&[] as &[&str], None, None)
+            .unwrap();
+        let moved = repo.find_object(moved, None).unwrap();
+        repo.reset(&moved, git2::ResetType::Hard, None).unwrap();
+        let before = list_submodules(repo_path.clone()).await.unwrap();
+        assert_eq!(before[0].index_sha, Some(second.to_string()));
+        assert_eq!(before[0].workdir_sha, Some(first.to_string()));
+        assert_eq!(before[0].status, "modified");
+
+        let results = update_submodule(repo_path.clone(), "libs/a".into(), false, true)
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success, "{:?}", results[0].error);
+        assert_eq!(results[0].workdir_sha, Some(second.to_string()));
+        let after = list_submodules(repo_path.clone()).await.unwrap();
+        assert_eq!(after[0].status, "clean");
+
+        // libs/b was never initialized
+        let results = update_submodule(repo_path, "libs/b".into(), false, false)
+            .await
+            .unwrap();
+        assert!(!results[0].success);
+    }
+
+    #[tokio::test]
+    async fn test_init_all_submodules_reports_each() {
+        let (temp_dir, repo_path, first, _) = setup_superproject();
+        let lib_b = temp_dir.path().join("lib-b");
+        let moved_away = temp_dir.path().join("lib-b-moved");
+        std::fs::rename(&lib_b, &moved_away).unwrap();
+
+        // An unreachable URL fails only its own submodule
+        let results = init_all_submodules(repo_path.clone()).await.unwrap();
+        let outcome: Vec<(&str, bool)> = results
+            .iter()
+            .map(|r| (r.path.as_str(), r.success))
+            .collect();
+        assert_eq!(outcome, vec![("libs/a", true), ("libs/b", false)]);
+        assert_eq!(results[0].workdir_sha, Some(first.to_string()));
+
+        // The relative URL resolves against origin once the library is back
+        std::fs::rename(&moved_away, &lib_b).unwrap();
+        let results = init_all_submodules(repo_path.clone()).await.unwrap();
+        assert!(results.iter().all(|r| r.success), "{:?}", results);
+        assert!(Path::new(&repo_path).join("libs/b/b.txt").exists());
+        let submodules = list_submodules(repo_path).await.unwrap();
+        assert_eq!(submodules[1].status, "clean");
+    }
+}
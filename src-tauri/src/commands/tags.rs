@@ -1,10 +1,32 @@
-use super::models::TagInfo;
-use super::utils::open_repo;
+use git2::PushOptions;
 
-/// List all tags.
+use super::credentials::{credential_callbacks, find_remote, remote_error};
+use super::models::{Page, TagInfo};
+use super::utils::{collect_page, open_repo, page_window, repo_signature};
+
+/// List all tags, newest first.
+///
+/// Compatibility wrapper returning a plain list; new callers should use
+/// `list_tags_page`.
 #[tauri::command]
 pub fn list_tags(repo_path: String) -> Result<Vec<TagInfo>, String> {
-    let repo = open_repo(&repo_path)?;
+    collect_tags(&open_repo(&repo_path)?)
+}
+
+/// One page of `list_tags`.
+#[tauri::command]
+pub fn list_tags_page(
+    repo_path: String,
+    cursor: Option<String>,
+    limit: Option<usize>,
+) -> Result<Page<TagInfo>, String> {
+    let (offset, limit) = page_window(cursor.as_deref(), limit)?;
+    let tags = collect_tags(&open_repo(&repo_path)?)?;
+    let total = tags.len() as u64;
+    collect_page(tags.into_iter().map(Ok), offset, limit, Some(total))
+}
+
+fn collect_tags(repo: &git2::Repository) -> Result<Vec<TagInfo>, String> {
     let mut tags = Vec::new();
 
     let tag_names = repo
@@ -91,9 +113,7 @@ pub fn create_annotated_tag(
             .map_err(|e| format!("HEAD 찾기 실패: {}", e))?
     };
 
-    let sig = repo
-        .signature()
-        .map_err(|e| format!("서명 생성 실패: {}", e))?;
+    let sig = repo_signature(&repo)?;
 
     repo.tag(&tag_name, &target_obj, &sig, &message, false)
         .map_err(|e| format!("태그 생성 실패: {}", e))?;
@@ -117,13 +137,13 @@ pub fn push_tag(
     tag_name: String,
 ) -> Result<(), String> {
     let repo = open_repo(&repo_path)?;
-    let mut remote = repo
-        .find_remote(&remote_name)
-        .map_err(|e| format!("원격 찾기 실패: {}", e))?;
+    let mut remote = find_remote(&repo, &remote_name)?;
+    let mut push_options = PushOptions::new();
+    push_options.remote_callbacks(credential_callbacks(Some(&repo), None));
 
     let refspec = format!("refs/tags/{}:refs/tags/{}", tag_name, tag_name);
     remote
-        .push(&[&refspec], None)
-        .map_err(|e| format!("태그 푸시 실패: {}", e))?;
+        .push(&[&refspec], Some(&mut push_options))
+        .map_err(|e| remote_error("태그 푸시 실패", e))?;
     Ok(())
 }
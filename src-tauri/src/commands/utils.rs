@@ -3,8 +3,11 @@
 //! Eliminates duplicate `normalize_unicode` and `Repository::open` boilerplate.
 
 use git2::Repository;
+use std::path::{Path, PathBuf};
 use unicode_normalization::UnicodeNormalization;
 
+use super::models::{CommandError, HeadState, OperationState, Page};
+
 /// Normalize a Unicode string to NFC form.
 ///
 /// macOS stores file paths in NFD; this converts them to NFC
@@ -18,6 +21,376 @@ pub fn open_repo(path: &str) -> Result<Repository, String> {
     Repository::open(path).map_err(|e| format!("레포지토리 열기 실패: {}", e))
 }
 
+/// Build a structured error string (JSON-encoded `CommandError`).
+///
+/// Use this instead of a plain message when the frontend needs to branch on
+/// the failure kind; `details` carries the code-specific payload.
+pub fn command_error(code: &str, message: impl Into<String>, details: serde_json::Value) -> String {
+    let error = CommandError {
+        code: code.to_string(),
+        message: message.into(),
+        details,
+    };
+    serde_json::to_string(&error).unwrap_or(error.message)
+}
+
+/// Page size of `*_page` commands called without `limit`.
+pub const DEFAULT_PAGE_SIZE: usize = 100;
+/// Largest page any `*_page` command returns, whatever `limit` asks for.
+pub const MAX_PAGE_SIZE: usize = 1000;
+
+/// Offset and clamped size of the page requested by `cursor` (the previous
+/// page's `next_cursor`) and `limit`.
+pub fn page_window(cursor: Option<&str>, limit: Option<usize>) -> Result<(usize, usize), String> {
+    let offset = match cursor {
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .map_err(|_| format!("잘못된 페이지 커서입니다: {}", cursor))?,
+        None => 0,
+    };
+    let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    Ok((offset, limit))
+}
+
+/// Collect the page of `items` at `offset`. Only one item past the page is
+/// read (to fill `has_more`), so lazy walks stop early.
+pub fn collect_page<T, E>(
+    items: impl IntoIterator<Item = Result<T, E>>,
+    offset: usize,
+    limit: usize,
+    total: Option<u64>,
+) -> Result<Page<T>, E> {
+    let mut page = Vec::with_capacity(limit.min(DEFAULT_PAGE_SIZE));
+    let mut has_more = false;
+    for item in items.into_iter().skip(offset) {
+        if page.len() == limit {
+            has_more = true;
+            break;
+        }
+        page.push(item?);
+    }
+    Ok(Page {
+        next_cursor: has_more.then(|| (offset + page.len()).to_string()),
+        items: page,
+        total,
+        has_more,
+    })
+}
+
+/// Resolve a repository-relative `user_path` to an absolute path inside `repo_root`.
+///
+/// Rejects absolute inputs (Unix, Windows drive and UNC forms), `..` components,
+/// paths into the `.git` directory, and paths whose directory escapes the root
+/// through a symlink. The last
+/// component is not followed, so a tracked symlink pointing anywhere is fine.
+/// Fails with an `invalid_path` error so the frontend can tell it apart.
+pub fn safe_repo_path(repo_root: &Path, user_path: &str) -> Result<PathBuf, String> {
+    let invalid = |reason: &str| {
+        command_error(
+            "invalid_path",
+            format!("잘못된 경로입니다 ({}): {}", reason, user_path),
+            serde_json::json!({ "path": user_path }),
+        )
+    };
+
+    let bytes = user_path.as_bytes();
+    let drive_absolute = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    if user_path.is_empty() {
+        return Err(invalid("빈 경로"));
+    }
+    if user_path.starts_with(['/', '\\']) || drive_absolute || Path::new(user_path).is_absolute() {
+        return Err(invalid("절대 경로"));
+    }
+
+    // Check `..` against both separators so Windows-style input is caught on any OS
+    if user_path.split(['/', '\\']).any(|part| part == "..") {
+        return Err(invalid("상위 디렉토리 참조"));
+    }
+    // Repository metadata is never a working tree file
+    let first = user_path
+        .split(['/', '\\'])
+        .find(|part| !part.is_empty() && *part != ".");
+    if first.is_some_and(|part| part.eq_ignore_ascii_case(".git")) {
+        return Err(invalid("저장소 메타데이터"));
+    }
+    let relative: PathBuf = Path::new(user_path)
+        .components()
+        .filter(|c| !matches!(c, std::path::Component::CurDir))
+        .collect();
+    if relative.as_os_str().is_empty() {
+        return Err(invalid("빈 경로"));
+    }
+
+    let root = repo_root
+        .canonicalize()
+        .map_err(|e| format!("레포지토리 경로 확인 실패: {}", e))?;
+    let full = root.join(&relative);
+
+    // The target may not exist yet: check containment on the deepest existing
+    // ancestor of its directory
+    let mut existing = full.parent().unwrap_or(&root);
+    while !existing.exists() {
+        match existing.parent() {
+            Some(parent) => existing = parent,
+            None => break,
+        }
+    }
+    let resolved = existing.canonicalize().map_err(|_| invalid("경로 확인 실패"))?;
+    if !resolved.starts_with(&root) {
+        return Err(invalid("레포지토리 밖의 경로"));
+    }
+    Ok(full)
+}
+
+/// Paths of all conflicted entries in the index (NFC-normalized, deduplicated).
+pub fn conflicted_paths(index: &git2::Index) -> Vec<String> {
+    let mut paths: Vec<String> = Vec::new();
+    if let Ok(conflicts) = index.conflicts() {
+        for conflict in conflicts.flatten() {
+            let entry = conflict.our.or(conflict.their).or(conflict.ancestor);
+            if let Some(entry) = entry {
+                let path = normalize_unicode(&String::from_utf8_lossy(&entry.path));
+                if !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Refuse to continue while the index still contains conflict entries.
+///
+/// Returns an `unresolved_conflicts` error listing the paths, so commit-like
+/// commands never write a tree from a conflicted index.
+pub fn assert_no_conflicts(index: &git2::Index) -> Result<(), String> {
+    if !index.has_conflicts() {
+        return Ok(());
+    }
+    let paths = conflicted_paths(index);
+    Err(command_error(
+        "unresolved_conflicts",
+        format!("아직 해결되지 않은 충돌이 있습니다: {} 개 파일", paths.len()),
+        serde_json::json!({ "paths": paths }),
+    ))
+}
+
+/// Remote-tracking ref a local branch is configured to follow
+/// (`branch.<name>.remote` + `branch.<name>.merge`), whether or not that ref exists.
+pub fn configured_upstream(repo: &Repository, branch_name: &str) -> Option<String> {
+    repo.branch_upstream_name(&format!("refs/heads/{}", branch_name))
+        .ok()
+        .and_then(|buf| buf.as_str().map(str::to_string))
+}
+
+/// Resolve a revision expression (branch, tag, SHA, `HEAD~2`, ...) to a commit.
+pub fn resolve_revision<'r>(repo: &'r Repository, revspec: &str) -> Result<git2::Commit<'r>, String> {
+    repo.revparse_single(revspec)
+        .and_then(|object| object.peel_to_commit())
+        .map_err(|e| format!("리비전 '{}' 해석 실패: {}", revspec, e))
+}
+
+/// Branch HEAD points at when it has no commits yet (a fresh `git init` or
+/// `git checkout --orphan`), e.g. "main". None once HEAD has a commit.
+pub fn unborn_branch(repo: &Repository) -> Option<String> {
+    match repo.head() {
+        Err(e) if e.code() == git2::ErrorCode::UnbornBranch => {}
+        _ => return None,
+    }
+    let head = repo.find_reference("HEAD").ok()?;
+    let target = head.symbolic_target()?;
+    Some(normalize_unicode(target.strip_prefix("refs/heads/").unwrap_or(target)))
+}
+
+/// Current HEAD as branch name (or detached) plus the commit it points at.
+/// An unborn HEAD has its branch name and an empty `sha`.
+pub fn head_state(repo: &Repository) -> Result<HeadState, String> {
+    if let Some(branch) = unborn_branch(repo) {
+        return Ok(HeadState {
+            name: Some(branch),
+            detached: false,
+            sha: String::new(),
+        });
+    }
+    let head = repo.head().map_err(|e| format!("HEAD 접근 실패: {}", e))?;
+    let sha = head
+        .peel_to_commit()
+        .map_err(|e| format!("HEAD 커밋 접근 실패: {}", e))?
+        .id()
+        .to_string();
+    let detached = repo.head_detached().unwrap_or(false);
+    let name = if detached {
+        None
+    } else {
+        head.shorthand().map(normalize_unicode)
+    };
+    Ok(HeadState {
+        name,
+        detached,
+        sha,
+    })
+}
+
+/// What to show as the current branch: its name, or
+/// "HEAD detached at <short sha>" like `git status`.
+pub fn head_label(repo: &Repository, state: &HeadState) -> String {
+    if let Some(name) = &state.name {
+        return name.clone();
+    }
+    let short = git2::Oid::from_str(&state.sha)
+        .and_then(|oid| repo.find_object(oid, None))
+        .and_then(|object| object.short_id())
+        .ok()
+        .and_then(|buf| buf.as_str().map(str::to_string))
+        .unwrap_or_else(|| state.sha.chars().take(7).collect());
+    format!("HEAD detached at {}", short)
+}
+
+/// Operation left in progress, from `repo.state()` and the files git keeps
+/// for it in the git directory (MERGE_HEAD, CHERRY_PICK_HEAD, REVERT_HEAD,
+/// rebase-merge/ or rebase-apply/).
+pub fn operation_state(repo: &Repository) -> OperationState {
+    use git2::RepositoryState as State;
+
+    let state = repo.state();
+    let git_dir = repo.path();
+    let read = |file: &str| {
+        std::fs::read_to_string(git_dir.join(file))
+            .ok()
+            .and_then(|content| content.lines().next().map(|line| line.trim().to_string()))
+            .filter(|line| !line.is_empty())
+    };
+    let current_branch = || head_state(repo).ok().map(|head| head_label(repo, &head));
+    let describe = |sha: String| branch_pointing_at(repo, &sha).unwrap_or(sha);
+
+    let mut operation = OperationState {
+        state: match state {
+            State::Clean => "clean",
+            State::Merge => "merge",
+            State::Revert => "revert",
+            State::RevertSequence => "revert_sequence",
+            State::CherryPick => "cherry_pick",
+            State::CherryPickSequence => "cherry_pick_sequence",
+            State::Bisect => "bisect",
+            State::Rebase => "rebase",
+            State::RebaseInteractive => "rebase_interactive",
+            State::RebaseMerge => "rebase_merge",
+            State::ApplyMailbox => "apply_mailbox",
+            State::ApplyMailboxOrRebase => "apply_mailbox_or_rebase",
+        }
+        .to_string(),
+        ..Default::default()
+    };
+    let picked = match state {
+        State::Merge => Some("MERGE_HEAD"),
+        State::CherryPick | State::CherryPickSequence => Some("CHERRY_PICK_HEAD"),
+        State::Revert | State::RevertSequence => Some("REVERT_HEAD"),
+        _ => None,
+    };
+    if let Some(file) = picked {
+        operation.source = read(file).map(describe);
+        operation.target = current_branch();
+    } else if git_dir.join("rebase-merge").is_dir() || git_dir.join("rebase-apply").is_dir() {
+        // `git rebase -m` / libgit2 count with msgnum/end, `git am` style with next/last
+        let (dir, step, total) = if git_dir.join("rebase-merge").is_dir() {
+            ("rebase-merge", "msgnum", "end")
+        } else {
+            ("rebase-apply", "next", "last")
+        };
+        let read_in = |file: &str| read(&format!("{}/{}", dir, file));
+        operation.source = read_in("head-name").map(|head| {
+            normalize_unicode(head.strip_prefix("refs/heads/").unwrap_or(&head))
+        });
+        operation.target = read_in("onto").map(describe);
+        operation.step = read_in(step).and_then(|n| n.parse().ok());
+        operation.total_steps = read_in(total).and_then(|n| n.parse().ok());
+    }
+    operation
+}
+
+/// Name of a local (preferred) or remote-tracking branch whose tip is `sha`.
+fn branch_pointing_at(repo: &Repository, sha: &str) -> Option<String> {
+    let oid = git2::Oid::from_str(sha).ok()?;
+    [git2::BranchType::Local, git2::BranchType::Remote]
+        .into_iter()
+        .find_map(|kind| {
+            repo.branches(Some(kind))
+                .ok()?
+                .filter_map(Result::ok)
+                .find(|(branch, _)| branch.get().target() == Some(oid))
+                .and_then(|(branch, _)| branch.name().ok().flatten().map(normalize_unicode))
+        })
+}
+
+/// Signature from `user.name` / `user.email` for commits the app creates.
+///
+/// When either is not configured, fails with an `identity_missing` error
+/// listing the missing keys, so the UI can open the identity dialog instead
+/// of showing libgit2's message.
+pub fn repo_signature(repo: &Repository) -> Result<git2::Signature<'static>, String> {
+    repo.signature().map_err(|e| {
+        let missing = repo
+            .config()
+            .map(|config| missing_identity(&config))
+            .unwrap_or_default();
+        if missing.is_empty() {
+            return format!("서명 생성 실패: {}", e);
+        }
+        command_error(
+            "identity_missing",
+            format!("Git 사용자 정보가 설정되지 않았습니다: {}", missing.join(", ")),
+            serde_json::json!({ "missing": missing }),
+        )
+    })
+}
+
+/// Identity keys with no usable value in `config`.
+pub fn missing_identity(config: &git2::Config) -> Vec<&'static str> {
+    ["user.name", "user.email"]
+        .into_iter()
+        .filter(|key| {
+            config
+                .get_string(key)
+                .map(|value| value.trim().is_empty())
+                .unwrap_or(true)
+        })
+        .collect()
+}
+
+/// Check out `tree` into the index and working directory without overwriting
+/// local changes. HEAD is left untouched; callers move it afterwards.
+///
+/// When local modifications would be lost, fails with a `checkout_conflicts`
+/// error listing the blocking paths.
+pub fn safe_checkout(repo: &Repository, tree: &git2::Object) -> Result<(), String> {
+    let mut blocking: Vec<String> = Vec::new();
+    let result = {
+        let mut builder = git2::build::CheckoutBuilder::new();
+        builder.safe();
+        builder.notify_on(git2::CheckoutNotificationType::CONFLICT);
+        builder.notify(|_, path, _, _, _| {
+            if let Some(path) = path {
+                blocking.push(normalize_unicode(&path.to_string_lossy()));
+            }
+            true
+        });
+        repo.checkout_tree(tree, Some(&mut builder))
+    };
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(_) if !blocking.is_empty() => Err(command_error(
+            "checkout_conflicts",
+            format!(
+                "로컬 변경사항이 덮어써질 수 있어 체크아웃할 수 없습니다: {} 개 파일",
+                blocking.len()
+            ),
+            serde_json::json!({ "paths": blocking }),
+        )),
+        Err(e) => Err(format!("체크아웃 실패: {}", e)),
+    }
+}
+
 /// Ensure UTF-8 / Korean related git config is set for the local repository.
 ///
 /// This is best-effort: callers should use `let _ = ensure_utf8_config(...)` so that
@@ -71,3 +444,147 @@ pub fn read_blob_content(repo: &Repository, oid: &git2::Oid) -> Option<String> {
         .ok()
         .and_then(|blob| String::from_utf8(blob.content().to_vec()).ok())
 }
+
+/// Points `XDG_CONFIG_HOME` (where app data is kept) at a test directory and
+/// restores the previous value when dropped. Tests using it must be `#[serial]`.
+#[cfg(test)]
+pub(crate) struct ScopedConfigHome(Option<std::ffi::OsString>);
+
+#[cfg(test)]
+impl ScopedConfigHome {
+    pub(crate) fn set(path: &Path) -> ScopedConfigHome {
+        let previous = std::env::var_os("XDG_CONFIG_HOME");
+        std::env::set_var("XDG_CONFIG_HOME", path);
+        ScopedConfigHome(previous)
+    }
+}
+
+#[cfg(test)]
+impl Drop for ScopedConfigHome {
+    fn drop(&mut self) {
+        match self.0.take() {
+            Some(previous) => std::env::set_var("XDG_CONFIG_HOME", previous),
+            None => std::env::remove_var("XDG_CONFIG_HOME"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn error_code(err: &str) -> String {
+        serde_json::from_str::<CommandError>(err).unwrap().code
+    }
+
+    #[test]
+    fn test_safe_repo_path_accepts_relative_paths() {
+        let root = tempdir().unwrap();
+        std::fs::create_dir(root.path().join("src")).unwrap();
+        let canonical = root.path().canonicalize().unwrap();
+
+        assert_eq!(
+            safe_repo_path(root.path(), "src/main.rs").unwrap(),
+            canonical.join("src").join("main.rs")
+        );
+        assert_eq!(
+            safe_repo_path(root.path(), "./기능/새 파일.txt").unwrap(),
+            canonical.join("기능").join("새 파일.txt")
+        );
+    }
+
+    #[test]
+    fn test_safe_repo_path_rejects_escapes() {
+        let root = tempdir().unwrap();
+        for input in [
+            "../../etc/passwd",
+            "src/../../etc/passwd",
+            "..\\..\\Windows\\win.ini",
+            "/etc/passwd",
+            "C:\\Windows\\System32\\drivers\\etc\\hosts",
+            "c:/Users/Public",
+            "\\\\server\\share\\file",
+            "",
+            ".",
+        ] {
+            let err = safe_repo_path(root.path(), input).unwrap_err();
+            assert_eq!(error_code(&err), "invalid_path", "input: {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_safe_repo_path_rejects_git_dir() {
+        let root = tempdir().unwrap();
+        std::fs::create_dir(root.path().join(".git")).unwrap();
+        for input in [".git", ".git/config", "./.git/hooks/pre-commit", ".GIT\\HEAD"] {
+            let err = safe_repo_path(root.path(), input).unwrap_err();
+            assert_eq!(error_code(&err), "invalid_path", "input: {:?}", input);
+        }
+        // Only the top-level metadata directory is off limits
+        assert!(safe_repo_path(root.path(), ".gitignore").is_ok());
+        assert!(safe_repo_path(root.path(), "docs/.git-notes").is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_safe_repo_path_rejects_symlink_escape() {
+        let root = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("link")).unwrap();
+
+        let err = safe_repo_path(root.path(), "link/secret.txt").unwrap_err();
+        assert_eq!(error_code(&err), "invalid_path");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_safe_repo_path_accepts_symlink_leaf_pointing_outside() {
+        let root = tempdir().unwrap();
+        let outside = tempdir().unwrap();
+        let target = outside.path().join("secret.txt");
+        std::fs::write(&target, "밖\n").unwrap();
+        std::os::unix::fs::symlink(&target, root.path().join("링크")).unwrap();
+
+        let canonical_root = root.path().canonicalize().unwrap();
+        assert_eq!(
+            safe_repo_path(root.path(), "링크").unwrap(),
+            canonical_root.join("링크")
+        );
+    }
+
+    #[test]
+    fn test_collect_page_and_window() {
+        let items = (0..5).map(Ok::<_, String>);
+        let first = collect_page(items.clone(), 0, 2, Some(5)).unwrap();
+        assert_eq!(first.items, vec![0, 1]);
+        assert!(first.has_more);
+        assert_eq!(first.total, Some(5));
+
+        let (offset, limit) = page_window(first.next_cursor.as_deref(), Some(2)).unwrap();
+        let second = collect_page(items.clone(), offset, limit, Some(5)).unwrap();
+        assert_eq!(second.items, vec![2, 3]);
+        let (offset, limit) = page_window(second.next_cursor.as_deref(), Some(2)).unwrap();
+        let last = collect_page(items, offset, limit, Some(5)).unwrap();
+        assert_eq!(last.items, vec![4]);
+        assert!(!last.has_more);
+        assert_eq!(last.next_cursor, None);
+
+        assert_eq!(page_window(None, None).unwrap(), (0, DEFAULT_PAGE_SIZE));
+        assert_eq!(page_window(None, Some(1_000_000)).unwrap().1, MAX_PAGE_SIZE);
+        assert_eq!(page_window(None, Some(0)).unwrap().1, 1);
+        assert!(page_window(Some("abc"), None).is_err());
+    }
+
+    #[test]
+    fn test_missing_identity() {
+        let dir = tempdir().unwrap();
+        let mut config = git2::Config::open(&dir.path().join("config")).unwrap();
+        assert_eq!(missing_identity(&config), vec!["user.name", "user.email"]);
+        config.set_str("user.name", "  ").unwrap();
+        config.set_str("user.email", "hong@example.com").unwrap();
+        assert_eq!(missing_identity(&config), vec!["user.name"]);
+        config.set_str("user.name", "홍길동").unwrap();
+        assert!(missing_identity(&config).is_empty());
+    }
+}
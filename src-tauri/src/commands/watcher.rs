@@ -0,0 +1,349 @@
+//! Repository change notifications, so the UI can refresh instead of
+//! polling `get_repository_status`.
+//!
+//! `watch_repository` watches the working tree and the git directory and,
+//! after ~300 ms without further changes, emits one `repo-status-changed`
+//! event naming the most significant thing that changed. Index writes that
+//! only refresh the stat cache (such as the ones status calls make
+//! themselves) are not reported, or every status refresh would trigger
+//! the next one.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use git2::Repository;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tauri::Manager;
+
+use super::models::RepoStatusChangedEvent;
+use super::utils::open_repo;
+
+/// Event carrying a `RepoStatusChangedEvent`.
+const STATUS_CHANGED_EVENT: &str = "repo-status-changed";
+/// Quiet time after the last file-system event before reporting.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// What changed, ordered by significance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ChangeReason {
+    Workdir,
+    Index,
+    Refs,
+}
+
+impl ChangeReason {
+    fn as_str(self) -> &'static str {
+        match self {
+            ChangeReason::Workdir => "workdir",
+            ChangeReason::Index => "index",
+            ChangeReason::Refs => "refs",
+        }
+    }
+}
+
+/// Active watchers keyed by repository path; managed as Tauri state.
+#[derive(Default)]
+pub struct RepoWatchers {
+    watchers: Mutex<HashMap<String, RecommendedWatcher>>,
+}
+
+/// Start emitting `repo-status-changed` events for `repo_path`. Watching an
+/// already watched repository does nothing.
+#[tauri::command]
+pub fn watch_repository(
+    app: tauri::AppHandle,
+    watchers: tauri::State<'_, RepoWatchers>,
+    repo_path: String,
+) -> Result<(), String> {
+    let mut watchers = watchers
+        .watchers
+        .lock()
+        .map_err(|_| "감시 상태에 접근할 수 없습니다".to_string())?;
+    if watchers.contains_key(&repo_path) {
+        return Ok(());
+    }
+
+    let repo = open_repo(&repo_path)?;
+    let layout = RepoLayout::of(&repo);
+    let (tx, rx) = channel();
+    let mut watcher =
+        notify::recommended_watcher(tx).map_err(|e| format!("파일 감시 시작 실패: {}", e))?;
+    for root in layout.watch_roots() {
+        watcher
+            .watch(root, RecursiveMode::Recursive)
+            .map_err(|e| format!("'{}' 감시 실패: {}", root.display(), e))?;
+    }
+
+    let path = repo_path.clone();
+    std::thread::spawn(move || {
+        report_changes(&path, &layout, rx, |reason| {
+            let _ = app.emit_all(
+                STATUS_CHANGED_EVENT,
+                RepoStatusChangedEvent {
+                    repo_path: path.clone(),
+                    reason: reason.as_str().to_string(),
+                },
+            );
+        });
+    });
+    watchers.insert(repo_path, watcher);
+    Ok(())
+}
+
+/// Stop watching `repo_path`. Unwatching a repository that is not watched
+/// does nothing.
+#[tauri::command]
+pub fn unwatch_repository(
+    watchers: tauri::State<'_, RepoWatchers>,
+    repo_path: String,
+) -> Result<(), String> {
+    let mut watchers = watchers
+        .watchers
+        .lock()
+        .map_err(|_| "감시 상태에 접근할 수 없습니다".to_string())?;
+    // Dropping the watcher closes its channel, which ends `report_changes`
+    watchers.remove(&repo_path);
+    Ok(())
+}
+
+/// Where a repository's files live, as given and canonicalized (some
+/// platforms report events under the resolved path).
+struct RepoLayout {
+    workdirs: Vec<PathBuf>,
+    git_dirs: Vec<PathBuf>,
+}
+
+impl RepoLayout {
+    fn of(repo: &Repository) -> Self {
+        let with_canonical = |path: &Path| {
+            let mut paths = vec![path.to_path_buf()];
+            if let Ok(canonical) = path.canonicalize() {
+                if canonical != path {
+                    paths.push(canonical);
+                }
+            }
+            paths
+        };
+        RepoLayout {
+            workdirs: repo.workdir().map(with_canonical).unwrap_or_default(),
+            git_dirs: with_canonical(repo.path()),
+        }
+    }
+
+    /// The working tree, plus the git directory when it lives elsewhere.
+    fn watch_roots(&self) -> Vec<&Path> {
+        let mut roots = Vec::new();
+        if let Some(workdir) = self.workdirs.first() {
+            roots.push(workdir.as_path());
+        }
+        let git_dir = &self.git_dirs[0];
+        if !roots.iter().any(|root| git_dir.starts_with(root)) {
+            roots.push(git_dir.as_path());
+        }
+        roots
+    }
+
+    /// What a change to `path` means for the repository, or None when it
+    /// does not affect status (objects, logs, lock files, ignored files).
+    fn classify(&self, repo: &Repository, path: &Path) -> Option<ChangeReason> {
+        if let Some(relative) = relative_to(path, &self.git_dirs) {
+            if relative.ends_with(".lock") {
+                return None;
+            }
+            if relative == "index" {
+                return Some(ChangeReason::Index);
+            }
+            // HEAD, ORIG_HEAD, MERGE_HEAD, ...
+            let is_ref = relative.ends_with("HEAD")
+                || relative == "packed-refs"
+                || relative.starts_with("refs/");
+            return is_ref.then_some(ChangeReason::Refs);
+        }
+        let relative = relative_to(path, &self.workdirs)?;
+        if !relative.is_empty() && repo.is_path_ignored(Path::new(&relative)).unwrap_or(false) {
+            return None;
+        }
+        Some(ChangeReason::Workdir)
+    }
+}
+
+/// `path` relative to the first of `roots` containing it, with `/`
+/// separators.
+fn relative_to(path: &Path, roots: &[PathBuf]) -> Option<String> {
+    roots
+        .iter()
+        .find_map(|root| path.strip_prefix(root).ok())
+        .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+}
+
+/// Fingerprint of the index content, ignoring the stat cache: two indexes
+/// with the same fingerprint stage exactly the same thing.
+fn index_fingerprint(repo: &Repository) -> Option<u64> {
+    let mut index = repo.index().ok()?;
+    index.read(true).ok()?;
+    let mut hasher = DefaultHasher::new();
+    for entry in index.iter() {
+        entry.path.hash(&mut hasher);
+        entry.id.as_bytes().hash(&mut hasher);
+        entry.mode.hash(&mut hasher);
+        entry.flags.hash(&mut hasher);
+        entry.flags_extended.hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+/// Collect watcher events into debounced batches and call `emit` once per
+/// batch that affects the repository. Returns when the watcher is dropped.
+fn report_changes(
+    repo_path: &str,
+    layout: &RepoLayout,
+    events: Receiver<notify::Result<Event>>,
+    mut emit: impl FnMut(ChangeReason),
+) {
+    let mut last_index = Repository::open(repo_path)
+        .ok()
+        .and_then(|repo| index_fingerprint(&repo));
+    while let Ok(first) = events.recv() {
+        let mut batch = vec![first];
+        loop {
+            match events.recv_timeout(DEBOUNCE) {
+                Ok(event) => batch.push(event),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        let repo = match Repository::open(repo_path) {
+            Ok(repo) => repo,
+            Err(_) => continue,
+        };
+        let mut reasons: Vec<ChangeReason> = batch
+            .iter()
+            .filter_map(|event| event.as_ref().ok())
+            // Reads (including our own) change nothing
+            .filter(|event| !matches!(event.kind, EventKind::Access(_)))
+            .flat_map(|event| event.paths.iter())
+            .filter_map(|path| layout.classify(&repo, path))
+            .collect();
+        if reasons.contains(&ChangeReason::Index) {
+            let fingerprint = index_fingerprint(&repo);
+            if fingerprint == last_index {
+                reasons.retain(|reason| *reason != ChangeReason::Index);
+            }
+            last_index = fingerprint;
+        }
+        if let Some(reason) = reasons.into_iter().max() {
+            emit(reason);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::ModifyKind;
+    use std::sync::mpsc::Sender;
+    use tempfile::TempDir;
+
+    fn setup_repo() -> (TempDir, Repository) {
+        let temp_dir = TempDir::new().unwrap();
+        let repo = Repository::init(temp_dir.path()).unwrap();
+        std::fs::write(temp_dir.path().join(".gitignore"), "빌드/\n").unwrap();
+        std::fs::write(temp_dir.path().join("a.txt"), "a\n").unwrap();
+        (temp_dir, repo)
+    }
+
+    fn modified(tx: &Sender<notify::Result<Event>>, path: PathBuf) {
+        tx.send(Ok(
+            Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path)
+        ))
+        .unwrap();
+    }
+
+    #[test]
+    fn test_classify_repository_paths() {
+        let (temp_dir, repo) = setup_repo();
+        let layout = RepoLayout::of(&repo);
+        let root = temp_dir.path();
+        let git_dir = repo.path();
+        let classify = |path: PathBuf| layout.classify(&repo, &path);
+
+        assert_eq!(classify(root.join("a.txt")), Some(ChangeReason::Workdir));
+        assert_eq!(
+            classify(root.join("새 폴더/b.txt")),
+            Some(ChangeReason::Workdir)
+        );
+        assert_eq!(classify(root.join("빌드/out.o")), None);
+        assert_eq!(classify(git_dir.join("index")), Some(ChangeReason::Index));
+        assert_eq!(classify(git_dir.join("index.lock")), None);
+        assert_eq!(classify(git_dir.join("HEAD")), Some(ChangeReason::Refs));
+        assert_eq!(
+            classify(git_dir.join("refs/heads/기능")),
+            Some(ChangeReason::Refs)
+        );
+        assert_eq!(classify(git_dir.join("objects/ab/cdef")), None);
+        assert_eq!(classify(PathBuf::from("/elsewhere/a.txt")), None);
+        assert_eq!(layout.watch_roots(), vec![layout.workdirs[0].as_path()]);
+    }
+
+    #[test]
+    fn test_report_changes_debounces_and_skips_stat_refresh() {
+        let (temp_dir, repo) = setup_repo();
+        let repo_path = temp_dir.path().to_str().unwrap().to_string();
+        let layout = RepoLayout::of(&repo);
+        let root = temp_dir.path().to_path_buf();
+        let git_dir = repo.path().to_path_buf();
+        let (tx, rx) = channel();
+
+        let reporter = std::thread::spawn(move || {
+            let mut reported = Vec::new();
+            report_changes(&repo_path, &layout, rx, |reason| reported.push(reason));
+            reported
+        });
+        let settle = || std::thread::sleep(DEBOUNCE * 2);
+
+        // A burst of edits is one event
+        for _ in 0..5 {
+            modified(&tx, root.join("a.txt"));
+        }
+        settle();
+        // Rewriting the index with the same content is not a change
+        repo.index().unwrap().write().unwrap();
+        modified(&tx, git_dir.join("index"));
+        settle();
+        // Staging is
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("a.txt")).unwrap();
+        index.write().unwrap();
+        modified(&tx, git_dir.join("index"));
+        modified(&tx, root.join("a.txt"));
+        settle();
+        // Ignored files and reads are not
+        modified(&tx, root.join("빌드/out.o"));
+        tx.send(Ok(Event::new(EventKind::Access(
+            notify::event::AccessKind::Any,
+        ))
+        .add_path(root.join("a.txt"))))
+            .unwrap();
+        settle();
+        // A branch move outranks the edits made with it
+        modified(&tx, root.join("a.txt"));
+        modified(&tx, git_dir.join("refs/heads/main"));
+        settle();
+        drop(tx);
+
+        assert_eq!(
+            reporter.join().unwrap(),
+            vec![
+                ChangeReason::Workdir,
+                ChangeReason::Index,
+                ChangeReason::Refs
+            ]
+        );
+    }
+}
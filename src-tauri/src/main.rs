@@ -4,84 +4,175 @@
 mod commands;
 
 use commands::amend::{amend_commit, get_last_commit_message};
+use commands::blame::get_file_blame;
 use commands::branch::{
-    create_branch, delete_branch, get_current_branch, list_branches, rename_branch, switch_branch,
+    archive_branch, checkout_commit, copy_branch, create_and_switch_branch, create_branch,
+    delete_branch, delete_merged_branches, get_branch_description, get_current_branch,
+    get_head_state, is_branch_backed_up, list_branches, list_branches_page, list_gone_branches,
+    prune_gone_branches, rename_branch, return_to_branch, set_branch_description, set_upstream,
+    switch_branch, switch_to_revision,
 };
 use commands::cherrypick::{cherry_pick, cherry_pick_abort, cherry_pick_continue};
-use commands::conflict::{abort_merge, get_conflicts, resolve_conflict};
+use commands::commit_lint::validate_commit_message;
+use commands::commit_details::{get_commit_details, prefetch_commit_details};
+use commands::conflict::{
+    abort_merge, get_conflicts, get_merge_file_versions, preview_conflict_resolution,
+    resolve_conflict, suggest_conflict_resolutions,
+};
 use commands::diff::{
     check_is_image, get_commit_diff, get_commit_file_changes, get_diff_stats, get_file_content,
-    get_file_diff, get_file_diff_at_commit, get_image_at_commit, get_image_diff, parse_diff,
+    get_file_diff, get_file_diff_at_commit, get_file_diff_structured, get_image_at_commit,
+    get_image_diff, get_image_full, get_merge_base_diff, get_merge_commit_diff,
+    get_submodule_change, parse_diff, parse_multi_diff,
 };
+use commands::diff_stream::{cancel_diff_stream, stream_commit_diff};
+use commands::export::{cancel_commit_graph_export, export_commit_graph};
+use commands::gitignore::{add_to_gitignore, is_path_ignored, list_ignored_files};
+use commands::graph::get_commit_graph;
 use commands::git::{
-    create_commit, discard_file, get_commit_history, get_repository_status, open_repository,
-    stage_all, stage_file, unstage_file,
+    clean_untracked, create_commit, discard_file, discard_file_changes, get_commit_history,
+    get_commit_history_filtered, get_commit_history_page, get_repository_state,
+    get_repository_status, get_repository_status_report, open_repository, stage_all, stage_file,
+    unstage_file,
+};
+use commands::history::{
+    get_file_at_commit, get_file_history, get_file_history_page, get_path_identity_chain,
 };
-use commands::history::{get_file_at_commit, get_file_history};
 use commands::merge::{can_merge, get_merge_conflicts, merge_branch};
+use commands::patch::{apply_patch, export_patch};
+use commands::presets::{list_command_presets, run_command_preset, save_command_preset};
 use commands::rebase::{get_rebase_status, rebase_abort, rebase_continue, start_rebase};
-use commands::reflog::{get_reflog, reset_to_reflog};
+use commands::reflog::{get_recent_branches, get_reflog, get_reflog_page, reset_to_reflog};
+use commands::reset::reset_to_commit;
 use commands::remote::{
-    add_remote, check_remote_connection, fetch_remote, get_remote_branches, get_sync_progress,
-    list_remotes, pull_changes, push_changes, remove_remote,
+    add_remote, check_remote_connection, delete_remote_branch, fetch_all_remotes, fetch_remote,
+    get_remote_branches, get_sync_progress, list_remotes, prune_remote, pull_changes,
+    push_all_branches, push_branches, push_changes, remove_remote, rename_remote,
+    retry_with_credentials, set_remote_url, unshallow,
 };
+use commands::relative_time::format_relative_times;
 use commands::repos::{add_recent_repo, get_recent_repos};
 use commands::revert::revert_commit;
-use commands::stash::{stash_apply, stash_drop, stash_list, stash_pop, stash_save};
-use commands::tags::{create_annotated_tag, create_tag, delete_tag, list_tags, push_tag};
+use commands::watcher::{unwatch_repository, watch_repository, RepoWatchers};
+use commands::submodule::{init_all_submodules, list_submodules, update_submodule};
+use commands::staging::{discard_hunk, stage_hunk, stage_lines, unstage_hunk, unstage_lines};
+use commands::stash::{
+    stash_apply, stash_drop, stash_list, stash_list_page, stash_pop, stash_save,
+};
+use commands::tags::{
+    create_annotated_tag, create_tag, delete_tag, list_tags, list_tags_page, push_tag,
+};
 use commands::bundle::{
     list_bundle_refs, create_bundle, verify_bundle, fetch_from_bundle, clone_from_bundle,
+    cancel_bundle_clone,
 };
+use commands::cli::{get_cli_info, set_git_cli_path};
 use commands::clone::clone_repository;
-use commands::git::search_commits;
-use commands::branch::get_branch_divergence;
+use commands::credentials::set_ssh_passphrase;
+use commands::git::{search_commits, search_commits_by_content};
+use commands::branch::{compare_branches, get_branch_divergence, get_branch_sync_status};
 use commands::shell::{open_in_explorer, open_terminal};
 use commands::config::{
     get_git_config, set_git_config, remove_git_config,
-    get_remote_url, set_remote_url,
-    list_git_hooks, toggle_git_hook,
+    get_git_identity, set_git_identity, get_config_value, set_config_value,
+    get_remote_url, list_git_hooks, toggle_git_hook,
 };
 
 fn main() {
     tauri::Builder::default()
+        .manage(RepoWatchers::default())
         .invoke_handler(tauri::generate_handler![
             // Repository core
             open_repository,
+            get_repository_state,
             get_commit_history,
+            get_commit_history_page,
+            get_commit_history_filtered,
             get_repository_status,
+            get_repository_status_report,
             stage_file,
             unstage_file,
+            stage_hunk,
+            unstage_hunk,
+            stage_lines,
+            unstage_lines,
             stage_all,
             discard_file,
+            discard_file_changes,
+            clean_untracked,
+            discard_hunk,
             create_commit,
+            validate_commit_message,
+            // Ignore rules
+            is_path_ignored,
+            add_to_gitignore,
+            list_ignored_files,
+            // File watching
+            watch_repository,
+            unwatch_repository,
             // Recent repos
             get_recent_repos,
             add_recent_repo,
             // Branch
             list_branches,
+            list_branches_page,
+            delete_merged_branches,
+            list_gone_branches,
+            prune_gone_branches,
             create_branch,
             switch_branch,
+            create_and_switch_branch,
+            switch_to_revision,
+            checkout_commit,
+            return_to_branch,
             delete_branch,
             rename_branch,
+            copy_branch,
+            archive_branch,
+            get_branch_description,
+            set_branch_description,
             get_current_branch,
+            get_head_state,
+            is_branch_backed_up,
             // Diff
             get_file_diff,
+            get_file_diff_structured,
             get_file_diff_at_commit,
             get_commit_diff,
             get_commit_file_changes,
+            get_merge_base_diff,
+            get_merge_commit_diff,
+            get_submodule_change,
             parse_diff,
+            parse_multi_diff,
             get_file_content,
             get_diff_stats,
             check_is_image,
             get_image_diff,
             get_image_at_commit,
+            get_image_full,
+            // Diff streaming
+            stream_commit_diff,
+            cancel_diff_stream,
+            // Commit details
+            get_commit_details,
+            prefetch_commit_details,
             // Remote
             list_remotes,
             add_remote,
             remove_remote,
+            set_remote_url,
+            rename_remote,
             fetch_remote,
+            fetch_all_remotes,
+            unshallow,
+            prune_remote,
+            delete_remote_branch,
             pull_changes,
             push_changes,
+            push_branches,
+            push_all_branches,
+            retry_with_credentials,
             get_remote_branches,
             get_sync_progress,
             check_remote_connection,
@@ -91,6 +182,7 @@ fn main() {
             // Stash
             stash_save,
             stash_list,
+            stash_list_page,
             stash_apply,
             stash_pop,
             stash_drop,
@@ -101,6 +193,9 @@ fn main() {
             // Conflict Resolution
             get_conflicts,
             resolve_conflict,
+            preview_conflict_resolution,
+            suggest_conflict_resolutions,
+            get_merge_file_versions,
             abort_merge,
             // Cherry-pick
             cherry_pick,
@@ -108,15 +203,32 @@ fn main() {
             cherry_pick_abort,
             // Revert
             revert_commit,
+            // Submodules
+            list_submodules,
+            update_submodule,
+            init_all_submodules,
             // Tags
             list_tags,
+            list_tags_page,
             create_tag,
             create_annotated_tag,
             delete_tag,
             push_tag,
             // File History
             get_file_history,
+            get_file_history_page,
             get_file_at_commit,
+            get_path_identity_chain,
+            // Blame
+            get_file_blame,
+            // Commit graph
+            get_commit_graph,
+            // Graph export
+            export_commit_graph,
+            cancel_commit_graph_export,
+            // Patch export / apply
+            export_patch,
+            apply_patch,
             // Rebase
             start_rebase,
             rebase_continue,
@@ -124,19 +236,29 @@ fn main() {
             get_rebase_status,
             // Reflog
             get_reflog,
+            get_reflog_page,
+            get_recent_branches,
             reset_to_reflog,
+            reset_to_commit,
             // Bundle
             list_bundle_refs,
             create_bundle,
             verify_bundle,
             fetch_from_bundle,
             clone_from_bundle,
+            cancel_bundle_clone,
             // Clone
             clone_repository,
+            // Credentials
+            set_ssh_passphrase,
             // Search
             search_commits,
+            search_commits_by_content,
             // Branch divergence
             get_branch_divergence,
+            get_branch_sync_status,
+            set_upstream,
+            compare_branches,
             // Shell / System
             open_in_explorer,
             open_terminal,
@@ -144,10 +266,22 @@ fn main() {
             get_git_config,
             set_git_config,
             remove_git_config,
+            get_git_identity,
+            set_git_identity,
+            get_config_value,
+            set_config_value,
             get_remote_url,
-            set_remote_url,
             list_git_hooks,
             toggle_git_hook,
+            // Command presets
+            save_command_preset,
+            list_command_presets,
+            run_command_preset,
+            // Formatting
+            format_relative_times,
+            // Git CLI
+            get_cli_info,
+            set_git_cli_path,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
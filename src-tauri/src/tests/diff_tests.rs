@@ -174,7 +174,8 @@ index 1234567..abcdefg 100644
         assert!(result.is_ok());
         
         let content = result.unwrap();
-        assert!(content.contains("Test Repository"));
+        assert!(!content.is_binary);
+        assert!(content.text.unwrap().contains("Test Repository"));
     }
 
     #[tokio::test]
@@ -194,7 +195,8 @@ index 1234567..abcdefg 100644
         assert!(result.is_ok());
         
         let content = result.unwrap();
-        assert!(content.contains("Test Repository"));
+        assert!(!content.is_binary);
+        assert!(content.text.unwrap().contains("Test Repository"));
     }
 
     // Performance benchmarks
@@ -61,7 +61,7 @@ async fn test_command_create_commit_korean() {
     // 한글 커밋 메시지
     let korean_message = "기능: 사용자 대시보드 추가\n\n- 통계 차트 구현\n- 실시간 업데이트";
     
-    let result = create_commit(repo_path.clone(), korean_message.to_string()).await;
+    let result = create_commit(repo_path.clone(), korean_message.to_string(), None, None, None, None, None).await;
     assert!(result.is_ok(), "커밋 생성 실패: {:?}", result.err());
     
     // 커밋 확인
@@ -119,7 +119,7 @@ async fn test_command_commit_history_korean() {
         let filename = format!("file{}.txt", i);
         test_repo.create_file(&filename, "content").unwrap();
         stage_file(repo_path.clone(), filename).await.unwrap();
-        create_commit(repo_path.clone(), msg.to_string()).await.unwrap();
+        create_commit(repo_path.clone(), msg.to_string(), None, None, None, None, None).await.unwrap();
     }
     
     // 커밋 히스토리 확인
@@ -144,7 +144,7 @@ async fn test_command_korean_author_in_commit_history() {
     
     test_repo.create_file("test.txt", "content").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "테스트 커밋".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "테스트 커밋".to_string(), None, None, None, None, None).await.unwrap();
     
     let history = get_commit_history(repo_path, 1).await.unwrap();
     assert_eq!(history[0].author, "김철수");
@@ -174,7 +174,7 @@ async fn test_full_workflow_korean() {
     
     // 4. 한글 커밋
     let commit_msg = "기능: 사용자 인증 모듈 추가";
-    create_commit(repo_path.clone(), commit_msg.to_string()).await.unwrap();
+    create_commit(repo_path.clone(), commit_msg.to_string(), None, None, None, None, None).await.unwrap();
     
     // 5. 히스토리 확인
     let history = get_commit_history(repo_path.clone(), 1).await.unwrap();
@@ -185,7 +185,7 @@ async fn test_full_workflow_korean() {
     
     // 7. 두 번째 커밋
     let commit_msg2 = "문서: README 작성";
-    create_commit(repo_path.clone(), commit_msg2.to_string()).await.unwrap();
+    create_commit(repo_path.clone(), commit_msg2.to_string(), None, None, None, None, None).await.unwrap();
     
     // 8. 전체 히스토리 확인
     let history = get_commit_history(repo_path, 10).await.unwrap();
@@ -207,11 +207,11 @@ async fn test_command_get_branches() {
     // 초기 커밋
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     // 브랜치 생성
-    create_branch(repo_path.clone(), "feature/test".to_string()).await.unwrap();
-    create_branch(repo_path.clone(), "기능/테스트".to_string()).await.unwrap();
+    create_branch(repo_path.clone(), "feature/test".to_string(), None).await.unwrap();
+    create_branch(repo_path.clone(), "기능/테스트".to_string(), None).await.unwrap();
     
     // 브랜치 목록 조회
     let branches = get_branches(repo_path).await.unwrap();
@@ -229,11 +229,11 @@ async fn test_command_create_korean_branch() {
     
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     // 한글 브랜치 생성
     let korean_branch = "기능/사용자인증";
-    let result = create_branch(repo_path.clone(), korean_branch.to_string()).await;
+    let result = create_branch(repo_path.clone(), korean_branch.to_string(), None).await;
     
     assert!(result.is_ok(), "한글 브랜치 생성 실패: {:?}", result.err());
     
@@ -251,13 +251,13 @@ async fn test_command_switch_branch() {
     
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     let branch_name = "feature/new";
-    create_branch(repo_path.clone(), branch_name.to_string()).await.unwrap();
+    create_branch(repo_path.clone(), branch_name.to_string(), None).await.unwrap();
     
     // 브랜치 전환
-    let result = switch_branch(repo_path.clone(), branch_name.to_string()).await;
+    let result = switch_branch(repo_path.clone(), branch_name.to_string(), None, None).await;
     assert!(result.is_ok(), "브랜치 전환 실패: {:?}", result.err());
     
     // 현재 브랜치 확인
@@ -273,12 +273,12 @@ async fn test_command_switch_korean_branch() {
     
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     let korean_branch = "기능/대시보드";
-    create_branch(repo_path.clone(), korean_branch.to_string()).await.unwrap();
+    create_branch(repo_path.clone(), korean_branch.to_string(), None).await.unwrap();
     
-    let result = switch_branch(repo_path.clone(), korean_branch.to_string()).await;
+    let result = switch_branch(repo_path.clone(), korean_branch.to_string(), None, None).await;
     assert!(result.is_ok());
     
     let current = get_current_branch(repo_path).await.unwrap();
@@ -293,13 +293,13 @@ async fn test_command_delete_branch() {
     
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     let branch_name = "to-delete";
-    create_branch(repo_path.clone(), branch_name.to_string()).await.unwrap();
+    create_branch(repo_path.clone(), branch_name.to_string(), None).await.unwrap();
     
     // 삭제
-    let result = delete_branch(repo_path.clone(), branch_name.to_string()).await;
+    let result = delete_branch(repo_path.clone(), branch_name.to_string(), None).await;
     assert!(result.is_ok());
     
     // 확인
@@ -316,12 +316,12 @@ async fn test_command_branch_performance() {
     
     test_repo.create_file("test.txt", "initial").unwrap();
     stage_file(repo_path.clone(), "test.txt".to_string()).await.unwrap();
-    create_commit(repo_path.clone(), "Initial commit".to_string()).await.unwrap();
+    create_commit(repo_path.clone(), "Initial commit".to_string(), None, None, None, None, None).await.unwrap();
     
     // 브랜치 생성 속도
     let start = Instant::now();
     for i in 0..10 {
-        create_branch(repo_path.clone(), format!("branch-{}", i)).await.unwrap();
+        create_branch(repo_path.clone(), format!("branch-{}", i), None).await.unwrap();
     }
     let create_duration = start.elapsed();
     
@@ -332,7 +332,7 @@ async fn test_command_branch_performance() {
     
     // 브랜치 전환 속도
     let start = Instant::now();
-    switch_branch(repo_path.clone(), "branch-5".to_string()).await.unwrap();
+    switch_branch(repo_path.clone(), "branch-5".to_string(), None, None).await.unwrap();
     let switch_duration = start.elapsed();
     
     println!("┌────────────────────────────────────────┐");
@@ -347,3 +347,71 @@ async fn test_command_branch_performance() {
     assert!(list_duration.as_millis() < 100, "목록 조회 느림: {:?}", list_duration);
     assert!(switch_duration.as_millis() < 100, "전환 느림: {:?}", switch_duration);
 }
+
+/// 대용량 작업 트리 상태 조회 벤치마크.
+/// 실행: `cargo test test_command_status_performance_large_tree -- --ignored --nocapture`
+/// 시간은 출력만 하고 비교하지 않음 (러너마다 편차가 큼)
+#[tokio::test]
+#[serial]
+#[ignore]
+async fn test_command_status_performance_large_tree() {
+    let test_repo = TestRepo::new().unwrap();
+    let repo_path = test_repo.path.to_str().unwrap().to_string();
+    
+    // 추적 파일 2,000개 커밋
+    for dir in 0..20 {
+        std::fs::create_dir_all(test_repo.path.join(format!("src/모듈{}", dir))).unwrap();
+        for file in 0..100 {
+            test_repo.create_file(&format!("src/모듈{}/파일{}.txt", dir, file), "내용").unwrap();
+        }
+    }
+    let mut index = test_repo.repo.index().unwrap();
+    index.add_all(["src"], git2::IndexAddOption::DEFAULT, None).unwrap();
+    index.write().unwrap();
+    test_repo.commit("Initial commit").unwrap();
+    
+    // 빌드 결과처럼 커다란 미추적 폴더 (10,000개)
+    for dir in 0..50 {
+        let build_dir = test_repo.path.join(format!("build/출력{}", dir));
+        std::fs::create_dir_all(&build_dir).unwrap();
+        for file in 0..200 {
+            std::fs::write(build_dir.join(format!("{}.o", file)), "obj").unwrap();
+        }
+    }
+    
+    // 첫 호출: stat 캐시 갱신
+    let start = Instant::now();
+    let first = get_repository_status_report(repo_path.clone(), true, false).await.unwrap();
+    let first_duration = start.elapsed();
+    
+    // 두 번째 호출: 갱신된 stat 캐시 사용
+    let start = Instant::now();
+    let full = get_repository_status_report(repo_path.clone(), true, false).await.unwrap();
+    let full_duration = start.elapsed();
+    
+    // 빠른 모드: 미추적 폴더를 한 항목으로
+    let start = Instant::now();
+    let collapsed = get_repository_status_report(repo_path.clone(), true, true).await.unwrap();
+    let collapsed_duration = start.elapsed();
+    
+    // 빠른 모드: 미추적 파일 제외
+    let start = Instant::now();
+    let tracked_only = get_repository_status_report(repo_path.clone(), false, false).await.unwrap();
+    let tracked_only_duration = start.elapsed();
+    
+    println!("┌────────────────────────────────────────┐");
+    println!("│ 대용량 작업 트리 상태 조회 벤치마크    │");
+    println!("├────────────────────────────────────────┤");
+    println!("│ 전체 (첫 호출): {:?} ({}개)", first_duration, first.files.len());
+    println!(
+        "│ 전체 (캐시): {:?} ({}개, 보고 {}ms)",
+        full_duration, full.files.len(), full.elapsed_ms
+    );
+    println!("│ 미추적 폴더 묶음: {:?} ({}개)", collapsed_duration, collapsed.files.len());
+    println!("│ 미추적 제외: {:?} ({}개)", tracked_only_duration, tracked_only.files.len());
+    println!("└────────────────────────────────────────┘");
+    
+    assert_eq!(full.files.len(), 10_000);
+    assert_eq!(collapsed.files.len(), 1, "미추적 폴더가 한 항목이 아님");
+    assert!(tracked_only.files.is_empty());
+}
@@ -76,7 +76,13 @@ mod tests {
         remote.push(&["refs/heads/main:refs/heads/main"], None).unwrap();
         
         // Fetch remote branches
-        let result = fetch_remote(local_path.clone(), "origin".to_string()).await;
+        let result = fetch_remote_with_events(
+            None,
+            local_path.clone(),
+            "origin".to_string(),
+            None,
+            false,
+        ).await;
         assert!(result.is_ok());
         
         // List remote branches
@@ -91,7 +97,13 @@ mod tests {
     async fn test_fetch_remote() {
         let (_local_dir, local_path, _remote_dir, _remote_path) = setup_test_repo_with_remote();
         
-        let result = fetch_remote(local_path, "origin".to_string()).await;
+        let result = fetch_remote_with_events(
+            None,
+            local_path,
+            "origin".to_string(),
+            None,
+            false,
+        ).await;
         assert!(result.is_ok());
     }
 
@@ -108,7 +120,12 @@ mod tests {
         let remote_repo = Repository::open(&remote_path).unwrap();
         // Note: Can't easily simulate remote changes in bare repo, skip for now
         
-        let result = pull_changes(local_path, "origin".to_string(), "main".to_string()).await;
+        let result = pull_changes_with_events(
+            None,
+            local_path,
+            "origin".to_string(),
+            "main".to_string(),
+        ).await;
         // Should succeed even if no changes
         assert!(result.is_ok());
     }
@@ -117,11 +134,15 @@ mod tests {
     async fn test_push_changes() {
         let (_local_dir, local_path, _remote_dir, _remote_path) = setup_test_repo_with_remote();
         
-        let result = push_changes(
+        let result = push_changes_with_events(
+            None,
             local_path,
             "origin".to_string(),
             "main".to_string(),
             false,
+            None,
+            None,
+            None,
         ).await;
         assert!(result.is_ok());
     }
@@ -169,7 +190,13 @@ mod tests {
         let (_local_dir, local_path, _remote_dir, _remote_path) = setup_test_repo_with_remote();
         
         // Start fetch (in background)
-        let result = fetch_remote(local_path.clone(), "origin".to_string()).await;
+        let result = fetch_remote_with_events(
+            None,
+            local_path.clone(),
+            "origin".to_string(),
+            None,
+            false,
+        ).await;
         assert!(result.is_ok());
         
         // Get progress (should complete quickly)
@@ -205,7 +232,13 @@ mod tests {
         let (_local_dir, local_path, _remote_dir, _remote_path) = setup_test_repo_with_remote();
         
         let start = std::time::Instant::now();
-        let result = fetch_remote(local_path, "origin".to_string()).await;
+        let result = fetch_remote_with_events(
+            None,
+            local_path,
+            "origin".to_string(),
+            None,
+            false,
+        ).await;
         let duration = start.elapsed();
         
         assert!(result.is_ok());
@@ -218,11 +251,15 @@ mod tests {
         let (_local_dir, local_path, _remote_dir, _remote_path) = setup_test_repo_with_remote();
         
         let start = std::time::Instant::now();
-        let result = push_changes(
+        let result = push_changes_with_events(
+            None,
             local_path,
             "origin".to_string(),
             "main".to_string(),
             false,
+            None,
+            None,
+            None,
         ).await;
         let duration = start.elapsed();
         
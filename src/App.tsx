@@ -167,7 +167,8 @@ function App() {
   const handleFetch = async () => {
     if (!activeTab?.dataState.currentRepo) return;
     try {
-      await api.fetchRemote(activeTab.dataState.currentRepo.path, 'origin');
+      const repoPath = activeTab.dataState.currentRepo.path;
+      await api.withCredentialRetry(() => api.fetchRemote(repoPath, 'origin'));
       showSuccess('Fetch completed');
       refreshRepository();
     } catch (err) {
@@ -178,13 +179,15 @@ function App() {
   const handlePull = async () => {
     if (!activeTab?.dataState.currentRepo) return;
     try {
-      await api.pullChanges(
-        activeTab.dataState.currentRepo.path,
-        'origin',
-        activeTab.dataState.currentRepo.current_branch,
-      );
-      showSuccess('Pull completed');
+      const { path, current_branch } = activeTab.dataState.currentRepo;
+      const result = await api.withCredentialRetry(() => api.pullChanges(path, 'origin', current_branch));
       refreshRepository();
+      if (result.kind === 'conflicts') {
+        showError(`Pull stopped on conflicts in ${result.conflicted_files.length} file(s)`);
+        updateTabUIState(activeTabId!, { showConflictResolver: true });
+        return;
+      }
+      showSuccess(result.kind === 'up_to_date' ? 'Already up to date' : 'Pull completed');
     } catch (err) {
       showError(`Pull failed: ${err}`);
     }
@@ -193,11 +196,8 @@ function App() {
   const handlePush = async () => {
     if (!activeTab?.dataState.currentRepo) return;
     try {
-      await api.pushChanges(
-        activeTab.dataState.currentRepo.path,
-        'origin',
-        activeTab.dataState.currentRepo.current_branch,
-      );
+      const { path, current_branch } = activeTab.dataState.currentRepo;
+      await api.withCredentialRetry(() => api.pushChanges(path, 'origin', current_branch));
       showSuccess('Push completed');
       refreshRepository();
     } catch (err) {
@@ -66,26 +66,20 @@ export default function BranchManager({ repoPath, onClose }: BranchManagerProps)
     if (branchName === currentBranch) return;
 
     try {
-      // Check for uncommitted changes before switching
+      setLoading(true);
+      setError('');
       try {
-        const fileStatuses = await api.getRepositoryStatus(repoPath);
-        const unstagedCount = fileStatuses.filter(f => !f.staged).length;
-        const stagedCount = fileStatuses.filter(f => f.staged).length;
-        if (unstagedCount > 0 || stagedCount > 0) {
-          const parts: string[] = [];
-          if (stagedCount > 0) parts.push(`스테이지됨: ${stagedCount}개`);
-          if (unstagedCount > 0) parts.push(`미스테이지: ${unstagedCount}개`);
-          if (!confirm(`커밋되지 않은 변경사항이 있습니다 (${parts.join(', ')}).\n브랜치를 전환하면 변경사항이 손실될 수 있습니다.\n계속하시겠습니까?`)) {
-            return;
-          }
+        await api.switchBranch(repoPath, branchName);
+      } catch (err) {
+        // Local changes the switch would overwrite: offer to stash them first
+        const blocked = api.parseCommandError(err);
+        if (blocked?.code !== 'checkout_conflicts') throw err;
+        const paths: string[] = blocked.details.paths;
+        if (!confirm(`커밋되지 않은 변경사항 때문에 전환할 수 없습니다:\n${paths.join('\n')}\n\n변경사항을 스태시하고 전환하시겠습니까?`)) {
+          return;
         }
-      } catch {
-        // If status check fails, proceed with switch anyway
+        await api.switchBranch(repoPath, branchName, { stashChanges: true });
       }
-
-      setLoading(true);
-      setError('');
-      await api.switchBranch(repoPath, branchName);
       await loadBranches();
     } catch (err: any) {
       setError(err.toString());
@@ -107,7 +101,17 @@ export default function BranchManager({ repoPath, onClose }: BranchManagerProps)
     try {
       setLoading(true);
       setError('');
-      await api.deleteBranch(repoPath, branchName);
+      try {
+        await api.deleteBranch(repoPath, branchName);
+      } catch (err) {
+        const notMerged = api.parseCommandError(err);
+        if (notMerged?.code !== 'branch_not_merged') throw err;
+        const lost = notMerged.details.unmerged_commits;
+        if (!confirm(`브랜치 '${branchName}'는 병합되지 않았습니다. 커밋 ${lost}개가 다른 브랜치에 없습니다. 그래도 삭제하시겠습니까?`)) {
+          return;
+        }
+        await api.deleteBranch(repoPath, branchName, true);
+      }
       await loadBranches();
     } catch (err: any) {
       setError(err.toString());
@@ -125,7 +129,20 @@ export default function BranchManager({ repoPath, onClose }: BranchManagerProps)
     try {
       setLoading(true);
       setError('');
-      await api.renameBranch(repoPath, selectedBranch, renameBranchName);
+      let renamed;
+      try {
+        renamed = await api.renameBranch(repoPath, selectedBranch, renameBranchName);
+      } catch (err) {
+        const exists = api.parseCommandError(err);
+        if (exists?.code !== 'branch_exists') throw err;
+        if (!confirm(`브랜치 '${renameBranchName}'가 이미 존재합니다. 덮어쓰시겠습니까?`)) {
+          return;
+        }
+        renamed = await api.renameBranch(repoPath, selectedBranch, renameBranchName, true);
+      }
+      if (renamed.pushed_under_old_name) {
+        alert(`원격에는 아직 '${renamed.upstream}'(으)로 남아 있습니다. 새 이름으로 푸시한 뒤 이전 원격 브랜치를 삭제하세요.`);
+      }
       setShowRenameDialog(false);
       setRenameBranchName('');
       setSelectedBranch('');
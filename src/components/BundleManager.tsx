@@ -146,7 +146,8 @@ const BundleManager: React.FC<BundleManagerProps> = ({
       setLoading(true);
       setStatusMessage('번들에서 클론 중...');
 
-      const result = await api.cloneFromBundle(filePath as string, targetPath as string);
+      const repo = await api.cloneFromBundle(filePath as string, targetPath as string);
+      const result = `번들에서 클론 완료: ${repo.path}`;
       setStatusMessage(`✅ ${result}`);
       onSuccess?.(result);
     } catch (err: any) {
@@ -15,6 +15,9 @@ interface DiffViewerProps {
 const IMAGE_EXTENSIONS = /\.(png|jpe?g|gif|svg|webp|bmp|ico|tiff?)$/i;
 const isImageFile = (path: string): boolean => IMAGE_EXTENSIONS.test(path);
 
+/** Larger diffs are cut at a hunk boundary by the backend to keep the UI responsive. */
+const MAX_DIFF_LINES = 20000;
+
 /**
  * DiffViewer - Routes to ImageDiff for image files, TextDiffViewer for text files
  */
@@ -50,6 +53,7 @@ function TextDiffViewer({
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string>('');
   const [forceTextView, setForceTextView] = useState(false);
+  const [truncation, setTruncation] = useState<{ shown: number; total: number } | null>(null);
 
   // Refs for minimap
   const scrollContainerRef = useRef<HTMLDivElement>(null);
@@ -66,9 +70,15 @@ function TextDiffViewer({
       setLoading(true);
       setError('');
       const ctxLines = showFullFile ? 999999 : undefined;
-      const diff = commitSha
-        ? await api.getFileDiffAtCommit(repoPath, filePath, commitSha, ctxLines)
-        : await api.getFileDiff(repoPath, filePath, staged, ctxLines);
+      let diff: string;
+      if (commitSha) {
+        diff = await api.getFileDiffAtCommit(repoPath, filePath, commitSha, ctxLines);
+        setTruncation(null);
+      } else {
+        const result = await api.getFileDiff(repoPath, filePath, staged, ctxLines, MAX_DIFF_LINES);
+        diff = result.patch;
+        setTruncation(result.truncated ? { shown: result.returned_lines, total: result.total_lines } : null);
+      }
       const parsed = await api.parseDiff(diff);
       setParsedDiff(parsed);
     } catch (err: any) {
@@ -508,6 +518,12 @@ function TextDiffViewer({
         </div>
       </div>
 
+      {truncation && (
+        <div className="px-4 py-1.5 text-[12px] text-[#d7ba7d] bg-[#2d2a1e] border-b border-[#3c3c3c] flex-shrink-0">
+          diff가 너무 커서 일부만 표시합니다 ({truncation.shown.toLocaleString()} / {truncation.total.toLocaleString()}줄)
+        </div>
+      )}
+
       {/* Diff content + Minimap */}
       <div className="flex-1 flex overflow-hidden relative">
         {/* Scrollable diff content */}
@@ -46,6 +46,7 @@ const STATUS_COLORS: Record<string, string> = {
   deleted: '#e57373',
   renamed: '#64b5f6',
   copied: '#64b5f6',
+  conflicted: '#ff9800',
 };
 const DEFAULT_COLOR = '#888';
 const statusColor = (s: string) => STATUS_COLORS[s] ?? DEFAULT_COLOR;
@@ -57,6 +58,7 @@ const statusLabel = (s: string) => {
     case 'deleted': return 'D';
     case 'renamed': return 'R';
     case 'copied': return 'C';
+    case 'conflicted': return '!';
     default: return '?';
   }
 };
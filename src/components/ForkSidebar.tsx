@@ -106,6 +106,12 @@ function BranchTreeItem({ branch, active, onClick, onSwitchBranch, onDeleteBranc
       >
         <GitBranch size={11} className={branch.is_current ? 'text-green-400' : 'text-[#666]'} />
         <span className="truncate flex-1 text-left">{branch.name}</span>
+        {(branch.ahead ?? 0) > 0 && (
+          <span className="text-[9px] text-[#888] flex-shrink-0" title="푸시할 커밋">&#8593;{branch.ahead}</span>
+        )}
+        {(branch.behind ?? 0) > 0 && (
+          <span className="text-[9px] text-[#888] flex-shrink-0" title="받아올 커밋">&#8595;{branch.behind}</span>
+        )}
         {branch.is_current && (
           <span className="text-[9px] text-green-400 flex-shrink-0">&#10003;</span>
         )}
@@ -238,8 +244,7 @@ export default function ForkSidebar({
   // Group remote branches by remote name
   const remoteGroups: Record<string, BranchInfo[]> = {};
   remoteBranches.forEach(b => {
-    const parts = b.name.split('/');
-    const remoteName = parts[0];
+    const remoteName = b.remote_name ?? b.name.split('/')[0];
     if (!remoteGroups[remoteName]) remoteGroups[remoteName] = [];
     remoteGroups[remoteName].push(b);
   });
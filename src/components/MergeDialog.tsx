@@ -46,10 +46,9 @@ const MergeDialog: React.FC<MergeDialogProps> = ({
       // Perform merge
       const mergeResult = await api.mergeBranch(repoPath, selectedBranch);
 
-      // merge_branch returns a string, not an object
-      setResult({ success: true, message: mergeResult as string, conflicts: [] });
+      setResult({ success: true, message: mergeResult.message, conflicts: [] });
       
-      if ((mergeResult as string).includes('conflict')) {
+      if (mergeResult.message.includes('conflict')) {
         // Has conflicts
         setTimeout(() => {
           if (onConflict) onConflict();
@@ -1,7 +1,7 @@
 import { useState, useEffect } from 'react';
 import * as api from '../services/api';
 import type { RemoteInfo, RemoteBranchInfo, SyncProgress } from '../types/git';
-import { Cloud, CloudOff, Download, Upload, RefreshCw, Plus, Trash2, X, GitBranch, Loader2, AlertCircle } from 'lucide-react';
+import { Cloud, CloudOff, Download, Upload, RefreshCw, Plus, Pencil, Trash2, X, GitBranch, Loader2, AlertCircle } from 'lucide-react';
 
 interface RemoteManagerProps {
   repoPath: string;
@@ -24,6 +24,12 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
   const [newRemoteName, setNewRemoteName] = useState('');
   const [newRemoteUrl, setNewRemoteUrl] = useState('');
 
+  // Edit remote dialog
+  const [editingRemote, setEditingRemote] = useState<RemoteInfo | null>(null);
+  const [editName, setEditName] = useState('');
+  const [editUrl, setEditUrl] = useState('');
+  const [editPushUrl, setEditPushUrl] = useState('');
+
   useEffect(() => {
     loadRemotes();
   }, [repoPath]);
@@ -34,19 +40,26 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
     }
   }, [selectedRemote]);
 
+  // Progress is pushed by the backend; events of an operation that already
+  // finished can still arrive late and are dropped
   useEffect(() => {
-    if (progress && progress.phase !== 'idle') {
-      const interval = setInterval(async () => {
-        try {
-          const newProgress = await api.getSyncProgress(repoPath);
-          setProgress(newProgress);
-        } catch (err) {
-          console.error('Failed to get progress:', err);
+    let lastFinished = 0;
+    const unlisteners = [
+      api.onSyncProgress((event) => {
+        if (event.repo_path === repoPath && event.operation_id > lastFinished) {
+          setProgress(event.progress);
         }
-      }, 500);
-      return () => clearInterval(interval);
-    }
-  }, [progress]);
+      }),
+      api.onSyncFinished((event) => {
+        if (event.repo_path === repoPath) {
+          lastFinished = Math.max(lastFinished, event.operation_id);
+        }
+      }),
+    ];
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten.then((stop) => stop()));
+    };
+  }, [repoPath]);
 
   const loadRemotes = async () => {
     try {
@@ -93,6 +106,42 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
     }
   };
 
+  const openEditRemote = (remoteName: string) => {
+    const remote = remotes.find((r) => r.name === remoteName);
+    if (!remote) return;
+    setEditingRemote(remote);
+    setEditName(remote.name);
+    setEditUrl(remote.url);
+    setEditPushUrl(remote.separate_push_url ? remote.push_url : '');
+  };
+
+  const handleEditRemote = async () => {
+    if (!editingRemote || !editName.trim() || !editUrl.trim()) return;
+    try {
+      setLoading(true);
+      setError('');
+      const name = editName.trim();
+      if (name !== editingRemote.name) {
+        const problems = await api.renameRemote(repoPath, editingRemote.name, name);
+        if (problems.length > 0) {
+          setError(`다음 refspec은 새 이름으로 바꾸지 못했습니다:\n${problems.join('\n')}`);
+        }
+        setSelectedRemote(name);
+      }
+      const pushUrl = editPushUrl.trim();
+      const oldPushUrl = editingRemote.separate_push_url ? editingRemote.push_url : '';
+      if (editUrl.trim() !== editingRemote.url || pushUrl !== oldPushUrl) {
+        await api.setRemoteUrl(repoPath, name, editUrl.trim(), pushUrl);
+      }
+      setEditingRemote(null);
+      await loadRemotes();
+    } catch (err: any) {
+      setError(err.toString());
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const handleRemoveRemote = async (remoteName: string) => {
     if (!confirm(`리모트 '${remoteName}'을(를) 삭제하시겠습니까?`)) return;
     try {
@@ -116,8 +165,13 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
       setLoading(true);
       setError('');
       setProgress({ phase: 'fetching', current: 0, total: 0, bytes: 0, message: 'Fetching...' });
-      const result = await api.fetchRemote(repoPath, selectedRemote);
-      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message: result });
+      // Prune so branches deleted on the server drop out of the list below
+      const result = await api.withCredentialRetry(() =>
+        api.fetchRemote(repoPath, selectedRemote, undefined, true),
+      );
+      const pruned = result.pruned_refs.length;
+      const message = pruned > 0 ? `${result.message} (${pruned} pruned)` : result.message;
+      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message });
       await loadRemoteBranches(selectedRemote);
     } catch (err: any) {
       setError(err.toString());
@@ -127,15 +181,47 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
     }
   };
 
+  const handleFetchAll = async () => {
+    try {
+      setLoading(true);
+      setError('');
+      setProgress({ phase: 'fetching', current: 0, total: 0, bytes: 0, message: 'Fetching all remotes...' });
+      const results = await api.fetchAllRemotes(repoPath);
+      const updated = results.reduce((n, r) => n + r.updated_refs.length, 0);
+      setProgress({
+        phase: 'idle', current: 0, total: 0, bytes: 0,
+        message: `Fetched ${results.length} remotes, ${updated} refs updated`,
+      });
+      const failed = results.filter((r) => r.error);
+      if (failed.length > 0) {
+        setError(failed.map((r) => `${r.remote}: ${api.parseCommandError(r.error)?.message ?? r.error}`).join('\n'));
+      }
+      if (selectedRemote) await loadRemoteBranches(selectedRemote);
+    } catch (err: any) {
+      setError(err.toString());
+      setProgress(null);
+    } finally {
+      setLoading(false);
+    }
+  };
+
   const handlePull = async () => {
     if (!selectedRemote) return;
     try {
       setLoading(true);
       setError('');
       setProgress({ phase: 'pulling', current: 0, total: 0, bytes: 0, message: 'Pulling...' });
-      const result = await api.pullChanges(repoPath, selectedRemote, currentBranch);
-      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message: result });
-      onSuccess?.(result);
+      const result = await api.withCredentialRetry(() =>
+        api.pullChanges(repoPath, selectedRemote, currentBranch),
+      );
+      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message: result.message });
+      if (result.kind === 'conflicts') {
+        const msg = `${result.message}\n${result.conflicted_files.join('\n')}`;
+        setError(msg);
+        onErrorProp?.(msg);
+        return;
+      }
+      onSuccess?.(result.message);
     } catch (err: any) {
       const msg = err.toString();
       setError(msg);
@@ -153,10 +239,19 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
       setLoading(true);
       setError('');
       setProgress({ phase: 'pushing', current: 0, total: 0, bytes: 0, message: 'Pushing...' });
-      const result = await api.pushChanges(repoPath, selectedRemote, currentBranch, force);
-      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message: result });
-      onSuccess?.(result);
+      // Force pushes keep a lease, so commits pushed since the last fetch are not lost
+      const result = await api.withCredentialRetry(() =>
+        api.pushChanges(repoPath, selectedRemote, currentBranch, false, { forceWithLease: force }),
+      );
+      setProgress({ phase: 'idle', current: 0, total: 0, bytes: 0, message: result.message });
+      onSuccess?.(result.message);
     } catch (err: any) {
+      const lease = api.parseCommandError(err);
+      if (lease?.code === 'lease_failed') {
+        setProgress(null);
+        if (confirm(`${lease.message}\n\n지금 페치할까요?`)) await handleFetch();
+        return;
+      }
       setError(err.toString());
       setProgress(null);
     } finally {
@@ -210,6 +305,14 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
                 </option>
               ))}
             </select>
+            <button
+              onClick={() => openEditRemote(selectedRemote)}
+              disabled={loading}
+              className="p-1.5 text-[#888] hover:text-white hover:bg-[#3c3c3c] rounded transition-colors disabled:opacity-50"
+              title="리모트 편집"
+            >
+              <Pencil size={14} />
+            </button>
             <button
               onClick={() => handleRemoveRemote(selectedRemote)}
               disabled={loading}
@@ -266,6 +369,17 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
               <RefreshCw size={13} />
               Fetch
             </button>
+            {remotes.length > 1 && (
+              <button
+                onClick={handleFetchAll}
+                disabled={loading}
+                title="Fetch all remotes"
+                className="flex-1 px-3 py-1.5 text-[12px] bg-[#333] text-[#ccc] rounded hover:bg-[#444] disabled:opacity-50 transition-colors flex items-center justify-center gap-1.5"
+              >
+                <RefreshCw size={13} />
+                Fetch all
+              </button>
+            )}
             <button
               onClick={handlePull}
               disabled={loading}
@@ -335,6 +449,60 @@ export default function RemoteManager({ repoPath, currentBranch, onClose, onSucc
       </div>
 
       {/* Add Remote Dialog */}
+      {editingRemote && (
+        <div className="fixed inset-0 bg-black/60 flex items-center justify-center z-50 p-4">
+          <div className="bg-[#252526] border border-[#3c3c3c] rounded-lg shadow-xl p-5 w-full max-w-md">
+            <h3 className="text-[14px] font-semibold mb-4 text-white">리모트 편집</h3>
+            <div className="space-y-3">
+              <div>
+                <label className="block text-[12px] text-[#888] mb-1">이름:</label>
+                <input
+                  type="text"
+                  value={editName}
+                  onChange={(e) => setEditName(e.target.value)}
+                  className="w-full px-3 py-2 text-[13px] border border-[#3c3c3c] rounded bg-[#1e1e1e] text-[#ccc] placeholder-[#555] outline-none focus:border-[#0078d4]"
+                  autoFocus
+                />
+              </div>
+              <div>
+                <label className="block text-[12px] text-[#888] mb-1">URL:</label>
+                <input
+                  type="text"
+                  value={editUrl}
+                  onChange={(e) => setEditUrl(e.target.value)}
+                  className="w-full px-3 py-2 text-[13px] border border-[#3c3c3c] rounded bg-[#1e1e1e] text-[#ccc] placeholder-[#555] outline-none focus:border-[#0078d4]"
+                />
+              </div>
+              <div>
+                <label className="block text-[12px] text-[#888] mb-1">푸시 URL (선택):</label>
+                <input
+                  type="text"
+                  value={editPushUrl}
+                  onChange={(e) => setEditPushUrl(e.target.value)}
+                  placeholder="비워두면 URL로 푸시합니다"
+                  className="w-full px-3 py-2 text-[13px] border border-[#3c3c3c] rounded bg-[#1e1e1e] text-[#ccc] placeholder-[#555] outline-none focus:border-[#0078d4]"
+                />
+              </div>
+            </div>
+            <div className="flex justify-end gap-2 mt-4">
+              <button
+                onClick={() => { setEditingRemote(null); setError(''); }}
+                className="px-3 py-1.5 text-[13px] text-[#ccc] hover:bg-[#3c3c3c] rounded transition-colors"
+              >
+                취소
+              </button>
+              <button
+                onClick={handleEditRemote}
+                disabled={loading || !editName.trim() || !editUrl.trim()}
+                className="px-3 py-1.5 text-[13px] bg-[#0078d4] text-white rounded hover:bg-[#1a8ad4] disabled:opacity-50 disabled:cursor-not-allowed transition-colors"
+              >
+                {loading ? '저장 중...' : '저장'}
+              </button>
+            </div>
+          </div>
+        </div>
+      )}
+
       {showAddRemote && (
         <div className="fixed inset-0 bg-black/60 flex items-center justify-center z-50 p-4">
           <div className="bg-[#252526] border border-[#3c3c3c] rounded-lg shadow-xl p-5 w-full max-w-md">
@@ -90,7 +90,7 @@ export function useRepository({ tabManager, onSuccess, onError, getAllBranches }
       const [commitsResult, changesResult, branchesResult] = await Promise.allSettled([
         api.getCommitHistory(repoPath, commitLimit, allBranches),
         api.getRepositoryStatus(repoPath),
-        api.listBranches(repoPath),
+        api.listBranches(repoPath, { includeTracking: true }),
       ]);
 
       const commits = commitsResult.status === 'fulfilled' ? commitsResult.value : [];
@@ -168,7 +168,7 @@ export function useRepository({ tabManager, onSuccess, onError, getAllBranches }
 
       const [changesResult, branchesResult] = await Promise.allSettled([
         api.getRepositoryStatus(repoPath),
-        api.listBranches(repoPath),
+        api.listBranches(repoPath, { includeTracking: true }),
       ]);
 
       const newChanges = changesResult.status === 'fulfilled' ? changesResult.value : null;
@@ -358,6 +358,7 @@ export const MOCK_BRANCHES: BranchInfo[] = [
     name: 'origin/main',
     is_current: false,
     is_remote: true,
+    remote_name: 'origin',
     commit_sha: sha(4),
     commit_message: 'Merge branch feature/dark-mode into main',
     author: 'Park Jihoon',
@@ -367,6 +368,7 @@ export const MOCK_BRANCHES: BranchInfo[] = [
     name: 'origin/develop',
     is_current: false,
     is_remote: true,
+    remote_name: 'origin',
     commit_sha: sha(3),
     commit_message: 'fix: address code review comments on API layer',
     author: 'Lee Soyeon',
@@ -458,8 +460,8 @@ export const MOCK_COMMIT_FILE_CHANGES: CommitFileChange[] = [
 // ============================================================================
 
 export const MOCK_REMOTES: RemoteInfo[] = [
-  { name: 'origin', url: 'https://github.com/user/my-awesome-app.git', fetch_url: 'https://github.com/user/my-awesome-app.git', push_url: 'https://github.com/user/my-awesome-app.git' },
-  { name: 'upstream', url: 'https://github.com/original/my-awesome-app.git', fetch_url: 'https://github.com/original/my-awesome-app.git', push_url: 'https://github.com/original/my-awesome-app.git' },
+  { name: 'origin', url: 'https://github.com/user/my-awesome-app.git', fetch_url: 'https://github.com/user/my-awesome-app.git', push_url: 'https://github.com/user/my-awesome-app.git', separate_push_url: false },
+  { name: 'upstream', url: 'https://github.com/original/my-awesome-app.git', fetch_url: 'https://github.com/original/my-awesome-app.git', push_url: 'https://github.com/original/my-awesome-app.git', separate_push_url: false },
 ];
 
 export const MOCK_REMOTE_BRANCHES: RemoteBranchInfo[] = [
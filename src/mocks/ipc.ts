@@ -464,12 +464,25 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
     case 'open_repository':
       return { ...mock.MOCK_REPO, path: args?.path ?? mock.MOCK_REPO.path } as unknown as T;
 
+    case 'get_repository_state':
+      return { state: 'clean', source: null, target: null, step: null, total_steps: null } as unknown as T;
+
     case 'get_commit_history':
       return commits.slice(0, args?.limit ?? 100) as unknown as T;
 
     case 'get_repository_status':
       return [...fileChanges] as unknown as T;
 
+    case 'get_repository_status_report': {
+      const files = args?.includeUntracked === false
+        ? fileChanges.filter(f => f.status !== 'untracked')
+        : [...fileChanges];
+      return { files, elapsed_ms: 12, suggest_fast_mode: false } as unknown as T;
+    }
+
+    case 'validate_commit_message':
+      return [] as unknown as T;
+
     case 'stage_file': {
       const p = args?.path as string;
       fileChanges = fileChanges.map(f => f.path === p ? { ...f, staged: true } : f);
@@ -509,7 +522,12 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
       ];
       // Remove staged files
       fileChanges = fileChanges.filter(f => !f.staged);
-      return newSha as unknown as T;
+      return {
+        commit_sha: newSha,
+        message: `커밋 성공: ${newSha}`,
+        signed: false,
+        signature_type: null,
+      } as unknown as T;
     }
 
     // ---- Recent Repos ----
@@ -520,8 +538,17 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
       return undefined as unknown as T;
 
     // ---- Branch ----
-    case 'list_branches':
-      return [...branches] as unknown as T;
+    case 'list_branches': {
+      const scope = (args?.scope as string) ?? 'all';
+      return branches.filter(
+        b => scope === 'all' || b.is_remote === (scope === 'remote'),
+      ) as unknown as T;
+    }
+
+    case 'delete_merged_branches':
+    case 'list_gone_branches':
+    case 'prune_gone_branches':
+      return [] as unknown as T;
 
     case 'get_current_branch':
       return (branches.find(b => b.is_current)?.name ?? 'main') as unknown as T;
@@ -546,20 +573,73 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
     case 'switch_branch': {
       const target = args?.branchName as string;
       branches = branches.map(b => ({ ...b, is_current: b.name === target }));
-      return `'${target}'(으)로 전환 완료` as unknown as T;
+      const sha = branches.find(b => b.name === target)?.commit_sha ?? '';
+      return {
+        head: { name: target, detached: false, sha },
+        stash_index: null,
+      } as unknown as T;
+    }
+
+    case 'checkout_commit':
+      branches = branches.map(b => ({ ...b, is_current: false }));
+      return { name: null, detached: true, sha: args?.commitSha as string } as unknown as T;
+
+    case 'return_to_branch': {
+      const target = args?.branch as string;
+      branches = branches.map(b => ({ ...b, is_current: b.name === target }));
+      const sha = branches.find(b => b.name === target)?.commit_sha ?? '';
+      return { name: target, detached: false, sha } as unknown as T;
     }
 
     case 'delete_branch': {
       const del = args?.branchName as string;
+      const tip = branches.find(b => b.name === del)?.commit_sha ?? '';
       branches = branches.filter(b => b.name !== del);
-      return `브랜치 '${del}' 삭제 완료` as unknown as T;
+      return { name: del, tip_sha: tip } as unknown as T;
+    }
+
+    case 'get_branch_description':
+      return (branches.find(b => b.name === (args?.branch as string))?.description ?? null) as unknown as T;
+
+    case 'set_branch_description': {
+      const description = (args?.description as string | null)?.trim() ? args?.description as string : null;
+      branches = branches.map(b => b.name === args?.branch ? { ...b, description } : b);
+      return undefined as unknown as T;
+    }
+
+    case 'copy_branch': {
+      const source = branches.find(b => b.name === (args?.sourceName as string));
+      const copy = { ...source!, name: args?.newName as string, is_current: false };
+      branches = [...branches, copy];
+      return copy as unknown as T;
+    }
+
+    case 'archive_branch': {
+      const name = args?.branchName as string;
+      const archived = branches.find(b => b.name === name);
+      branches = branches.filter(b => b.name !== name);
+      return {
+        branch: name,
+        tag_name: `archive/${name}`,
+        tip_sha: archived?.commit_sha ?? '',
+        remote: null,
+        tag_pushed: false,
+        tag_push_error: null,
+        remote_branch_deleted: false,
+        remote_delete_error: null,
+      } as unknown as T;
     }
 
     case 'rename_branch': {
       const old = args?.oldName as string;
       const newN = args?.newName as string;
       branches = branches.map(b => b.name === old ? { ...b, name: newN } : b);
-      return `브랜치 '${old}' → '${newN}' 이름 변경 완료` as unknown as T;
+      return {
+        old_name: old,
+        new_name: newN,
+        upstream: null,
+        pushed_under_old_name: false,
+      } as unknown as T;
     }
 
     // ---- Diff ----
@@ -567,7 +647,9 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
       const fp = (args?.filePath as string) ?? '';
       const fileEntry = fileChanges.find(f => f.path === fp);
       const st = fileEntry?.status ?? 'modified';
-      return generateMockDiffText(fp, st) as unknown as T;
+      const patch = generateMockDiffText(fp, st);
+      const lines = patch.split('\n').length;
+      return { patch, truncated: false, total_lines: lines, returned_lines: lines } as unknown as T;
     }
 
     case 'get_commit_diff':
@@ -581,8 +663,16 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
       return parseMockDiff(text) as unknown as T;
     }
 
-    case 'get_file_content':
-      return '// Mock file content\nexport default function Hello() {\n  return <div>Hello World</div>;\n}\n' as unknown as T;
+    case 'get_file_content': {
+      const text = '// Mock file content\nexport default function Hello() {\n  return <div>Hello World</div>;\n}\n';
+      return {
+        is_binary: false,
+        text,
+        base64: null,
+        size: text.length,
+        encoding_guess: 'utf-8',
+      } as unknown as T;
+    }
 
     case 'get_diff_stats':
       return [...mock.MOCK_DIFF_STATS] as unknown as T;
@@ -606,24 +696,79 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
     case 'add_remote': {
       const rn = args?.name as string;
       const ru = args?.url as string;
-      remotes = [...remotes, { name: rn, url: ru, fetch_url: ru, push_url: ru }];
+      remotes = [...remotes, { name: rn, url: ru, fetch_url: ru, push_url: ru, separate_push_url: false }];
       return `원격 '${rn}' 추가 완료` as unknown as T;
     }
 
+    case 'set_remote_url': {
+      const rn = args?.name as string;
+      const ru = args?.url as string;
+      const pu = args?.pushUrl as string | undefined;
+      remotes = remotes.map(r => {
+        if (r.name !== rn) return r;
+        const separate = pu === undefined ? r.separate_push_url : pu !== '';
+        const push = pu === undefined ? (r.separate_push_url ? r.push_url : ru) : pu || ru;
+        return { ...r, url: ru, fetch_url: ru, push_url: push, separate_push_url: separate };
+      });
+      return remotes.find(r => r.name === rn) as unknown as T;
+    }
+
+    case 'rename_remote': {
+      const oldName = args?.oldName as string;
+      remotes = remotes.map(r => (r.name === oldName ? { ...r, name: args?.newName as string } : r));
+      return [] as unknown as T;
+    }
+
     case 'remove_remote': {
       const rn = args?.name as string;
       remotes = remotes.filter(r => r.name !== rn);
       return `원격 '${rn}' 삭제 완료` as unknown as T;
     }
 
+    case 'set_ssh_passphrase':
+      return undefined as unknown as T;
+
+    case 'retry_with_credentials':
+      return `작업 ${args?.operationId} 재시도 완료 (mock)` as unknown as T;
+
     case 'fetch_remote':
-      return `'${args?.remoteName}' 페치 완료 (mock)` as unknown as T;
+      return {
+        message: `'${args?.remoteName}' 페치 완료 (mock)`,
+        pruned_refs: [],
+      } as unknown as T;
+
+    case 'fetch_all_remotes':
+      return [
+        { remote: 'origin', updated_refs: ['refs/remotes/origin/main'], error: null },
+      ] as unknown as T;
+
+    case 'unshallow':
+      return `'${args?.remoteName}'에서 전체 기록을 가져왔습니다 (mock)` as unknown as T;
 
     case 'pull_changes':
-      return '풀 성공 (mock - fast-forward)' as unknown as T;
+      return {
+        kind: 'fast_forward',
+        message: '풀 성공 (mock - fast-forward)',
+        commit_sha: null,
+        conflicted_files: [],
+      } as unknown as T;
 
     case 'push_changes':
-      return `'${args?.remoteName}/${args?.branchName}' 푸시 완료 (mock)` as unknown as T;
+      return {
+        message: `'${args?.remoteName}/${args?.branchName}' 푸시 완료 (mock)`,
+        upstream: args?.setUpstream === false ? null : `${args?.remoteName}/${args?.branchName}`,
+        refs: [{ refname: `refs/heads/${args?.branchName}`, accepted: true, message: null }],
+      } as unknown as T;
+
+    case 'push_branches':
+      return ((args?.branches as string[]) ?? []).map((branch) => ({
+        branch,
+        status: 'pushed',
+        message: null,
+      })) as unknown as T;
+
+    case 'push_all_branches':
+      return [{ branch: 'main', status: 'up_to_date', message: null }] as unknown as T;
 
     case 'get_remote_branches':
       return [...mock.MOCK_REMOTE_BRANCHES] as unknown as T;
@@ -639,7 +784,13 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
       if (commits.length > 0) {
         commits[0] = { ...commits[0], message: args?.message ?? commits[0].message };
       }
-      return (commits[0]?.sha ?? '') as unknown as T;
+      return {
+        commit_sha: commits[0]?.sha ?? '',
+        message: '커밋 수정 완료',
+        auto_staged_paths: [],
+        signed: false,
+        signature_type: null,
+      } as unknown as T;
 
     case 'get_last_commit_message':
       return (commits[0]?.message ?? '') as unknown as T;
@@ -671,7 +822,12 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
 
     // ---- Merge ----
     case 'merge_branch':
-      return '병합 완료 (mock - fast-forward)' as unknown as T;
+      return {
+        message: '병합 완료 (mock - fast-forward)',
+        commit_sha: null,
+        signed: false,
+        signature_type: null,
+      } as unknown as T;
 
     case 'can_merge':
       return true as unknown as T;
@@ -692,7 +848,7 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
 
     // ---- Cherry-pick ----
     case 'cherry_pick':
-      return { success: true, conflicts: [], message: 'Cherry-pick 성공 (mock)' } as unknown as T;
+      return { success: true, conflicts: [], message: 'Cherry-pick 성공 (mock)', signed: false, signature_type: null } as unknown as T;
 
     case 'cherry_pick_continue':
     case 'cherry_pick_abort':
@@ -700,7 +856,7 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
 
     // ---- Revert ----
     case 'revert_commit':
-      return { success: true, conflicts: [], message: 'Revert 성공 (mock)' } as unknown as T;
+      return { success: true, conflicts: [], message: 'Revert 성공 (mock)', signed: false, signature_type: null } as unknown as T;
 
     // ---- Tags ----
     case 'list_tags':
@@ -744,9 +900,22 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
     case 'get_reflog':
       return [...mock.MOCK_REFLOG] as unknown as T;
 
+    case 'get_recent_branches':
+      return branches
+        .filter(b => !b.is_current && !b.is_remote)
+        .slice(0, (args?.limit as number | null) ?? 10)
+        .map(b => ({ name: b.name, detached: false, timestamp: b.timestamp ?? 0 })) as unknown as T;
+
     case 'reset_to_reflog':
       return undefined as unknown as T;
 
+    case 'reset_to_commit':
+      return {
+        previous_head: mock.MOCK_COMMITS[0]?.sha ?? '',
+        new_head: String(args?.commitSha ?? ''),
+        mode: String(args?.mode ?? 'mixed'),
+      } as unknown as T;
+
     // ---- Bundle ----
     case 'list_bundle_refs':
       return [...mock.MOCK_BUNDLE_REFS] as unknown as T;
@@ -769,8 +938,15 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
     case 'fetch_from_bundle':
       return '번들에서 페치 완료 (mock)' as unknown as T;
 
-    case 'clone_from_bundle':
-      return `번들에서 클론 완료: ${args?.targetPath ?? '/tmp/cloned'}` as unknown as T;
+    case 'clone_from_bundle': {
+      const path = args?.targetPath ?? '/tmp/cloned';
+      return {
+        path,
+        name: path.split('/').pop() ?? 'cloned',
+        current_branch: 'main',
+        remote_url: args?.bundlePath,
+      } as unknown as T;
+    }
 
     // ---- Clone ----
     case 'clone_repository': {
@@ -801,6 +977,14 @@ export async function mockInvoke<T>(cmd: string, args?: Record<string, any>): Pr
 // Mock Dialog APIs
 // ============================================================================
 
+/**
+ * Mock event listener. No backend emits events in the browser, so the handler
+ * is never called; the returned function unlistens.
+ */
+export async function mockListen<T>(_event: string, _handler: (payload: T) => void): Promise<() => void> {
+  return () => {};
+}
+
 export async function mockOpenDialog(options?: any): Promise<string | string[] | null> {
   const isDir = options?.directory === true;
   const title = options?.title ?? (isDir ? 'Select directory' : 'Select file');
@@ -11,13 +11,14 @@
  * previewed and tested without the Rust backend.
  */
 
-import { isTauri, mockInvoke, mockOpenDialog, mockSaveDialog } from '../mocks/ipc';
+import { isTauri, mockInvoke, mockListen, mockOpenDialog, mockSaveDialog } from '../mocks/ipc';
 
 // Lazy-load real Tauri APIs only when the runtime is present.
 // This avoids import errors in plain browsers.
 let _invoke: typeof import('@tauri-apps/api/tauri').invoke;
 let _open: typeof import('@tauri-apps/api/dialog').open;
 let _save: typeof import('@tauri-apps/api/dialog').save;
+let _listen: typeof import('@tauri-apps/api/event').listen;
 
 const ensureTauri = async () => {
   if (!_invoke) {
@@ -29,6 +30,10 @@ const ensureTauri = async () => {
     _open = dialog.open;
     _save = dialog.save;
   }
+  if (!_listen) {
+    const event = await import('@tauri-apps/api/event');
+    _listen = event.listen;
+  }
 };
 
 /**
@@ -67,12 +72,40 @@ async function saveDialog(options?: any) {
   return mockSaveDialog(options);
 }
 
+/**
+ * Universal event listener: calls `handler` with each payload of `event`.
+ * Resolves to the function that stops listening.
+ */
+async function listenEvent<T>(event: string, handler: (payload: T) => void): Promise<() => void> {
+  if (isTauri()) {
+    await ensureTauri();
+    return _listen<T>(event, (e) => handler(e.payload));
+  }
+  return mockListen<T>(event, handler);
+}
+
 import type {
+  CommandError,
   RepositoryInfo,
+  OperationState,
   CommitInfo,
+  CommitResult,
   FileStatus,
+  RepositoryStatusReport,
+  CommitLintConfig,
+  LintFinding,
   BranchInfo,
+  DeletedBranch,
+  BranchDivergence,
+  GoneBranchResult,
+  RenamedBranch,
+  ArchivedBranch,
+  BranchTracking,
+  HeadState,
+  BranchSwitchResult,
   RecentRepo,
+  FileContent,
+  FileDiff,
   ParsedDiff,
   DiffStat,
   CommitFileChange,
@@ -80,20 +113,45 @@ import type {
   RemoteInfo,
   RemoteBranchInfo,
   SyncProgress,
+  SyncProgressEvent,
+  SyncFinishedEvent,
+  FetchResult,
+  PullResult,
+  PushResult,
+  BranchPushStatus,
+  RemoteFetchResult,
+  RemoteCredential,
   ConflictInfo,
+  MergeResult,
   CherryPickResult,
   RevertResult,
   RebaseInfo,
   RebaseResult,
+  AmendResult,
   StashInfo,
   TagInfo,
   FileHistoryEntry,
   ReflogEntry,
+  RecentBranch,
+  ResetResult,
   BundleRefInfo,
   BundleCreateResult,
   BundleVerifyResult,
 } from '../types/git';
 
+/**
+ * Decode a structured backend error. Returns null for plain-message errors.
+ */
+export const parseCommandError = (error: unknown): CommandError | null => {
+  if (typeof error !== 'string' || !error.startsWith('{')) return null;
+  try {
+    const parsed = JSON.parse(error);
+    return typeof parsed?.code === 'string' ? (parsed as CommandError) : null;
+  } catch {
+    return null;
+  }
+};
+
 // ============================================================================
 // Repository Core
 // ============================================================================
@@ -101,12 +159,39 @@ import type {
 export const openRepository = (path: string) =>
   invoke<RepositoryInfo>('open_repository', { path });
 
-export const getCommitHistory = (repoPath: string, limit = 100, allBranches = false) =>
-  invoke<CommitInfo[]>('get_commit_history', { repoPath, limit, allBranches });
+/** Merge / rebase / cherry-pick / revert in progress; also in `RepositoryInfo.state`. */
+export const getRepositoryState = (repoPath: string) =>
+  invoke<OperationState>('get_repository_state', { repoPath });
+
+/** `startFrom` (a sha) continues after that commit, e.g. the last one already loaded. */
+export const getCommitHistory = (
+  repoPath: string,
+  limit = 100,
+  allBranches = false,
+  startFrom?: string,
+) =>
+  invoke<CommitInfo[]>('get_commit_history', {
+    repoPath,
+    limit,
+    allBranches,
+    startFrom: startFrom ?? null,
+  });
 
 export const getRepositoryStatus = (repoPath: string) =>
   invoke<FileStatus[]>('get_repository_status', { repoPath });
 
+/** Status with its scan time; the fast modes skip or collapse untracked files. */
+export const getRepositoryStatusReport = (
+  repoPath: string,
+  includeUntracked = true,
+  untrackedDirsAsSingleEntries = false,
+) =>
+  invoke<RepositoryStatusReport>('get_repository_status_report', {
+    repoPath,
+    includeUntracked,
+    untrackedDirsAsSingleEntries,
+  });
+
 export const stageFile = (repoPath: string, path: string) =>
   invoke<void>('stage_file', { repoPath, path });
 
@@ -124,8 +209,31 @@ export const stageFiles = (repoPath: string, paths: string[]) =>
 export const unstageFiles = (repoPath: string, paths: string[]) =>
   Promise.all(paths.map(p => invoke<void>('unstage_file', { repoPath, path: p })));
 
-export const createCommit = (repoPath: string, message: string) =>
-  invoke<string>('create_commit', { repoPath, message });
+/**
+ * `author` commits on behalf of someone; `coAuthors` are "이름 <email>" entries.
+ * `skipHooks` bypasses pre-commit / commit-msg (like `--no-verify`).
+ */
+export const createCommit = (
+  repoPath: string,
+  message: string,
+  paths?: string[],
+  author?: { name?: string; email?: string },
+  coAuthors?: string[],
+  skipHooks = false,
+) =>
+  invoke<CommitResult>('create_commit', {
+    repoPath,
+    message,
+    paths: paths ?? null,
+    authorName: author?.name ?? null,
+    authorEmail: author?.email ?? null,
+    coAuthors: coAuthors ?? null,
+    skipHooks,
+  });
+
+/** Lint a commit message (pure and fast; safe to call on every keystroke). */
+export const validateCommitMessage = (message: string, config?: CommitLintConfig) =>
+  invoke<LintFinding[]>('validate_commit_message', { message, config: config ?? null });
 
 export const discardFile = (repoPath: string, path: string) =>
   invoke<void>('discard_file', { repoPath, path });
@@ -144,30 +252,145 @@ export const addRecentRepo = (path: string) =>
 // Branch
 // ============================================================================
 
-export const listBranches = (repoPath: string) =>
-  invoke<BranchInfo[]>('list_branches', { repoPath });
+/**
+ * `scope` limits the list to local or remote-tracking branches.
+ * `details: false` returns only names, kinds and tip shas, without reading commits.
+ * `includeMergeStatus` fills `is_merged`; `includeTracking` fills `ahead` / `behind`.
+ * Both cost a graph walk per branch.
+ */
+export const listBranches = (
+  repoPath: string,
+  {
+    scope = 'all' as 'local' | 'remote' | 'all',
+    includeMergeStatus = false,
+    includeTracking = false,
+    details = true,
+  } = {},
+) =>
+  invoke<BranchInfo[]>('list_branches', {
+    repoPath,
+    scope,
+    includeMergeStatus,
+    includeTracking,
+    details,
+  });
+
+/** Local branches merged into HEAD, except the current one; `dryRun` only lists them. */
+export const deleteMergedBranches = (repoPath: string, dryRun: boolean) =>
+  invoke<DeletedBranch[]>('delete_merged_branches', { repoPath, dryRun });
+
+/** Local branches whose upstream was deleted; `pruneFirst` fetches every remote with prune. */
+export const listGoneBranches = (repoPath: string, pruneFirst = false) =>
+  invoke<BranchInfo[]>('list_gone_branches', { repoPath, pruneFirst });
+
+/**
+ * Delete the branches `listGoneBranches` reports, never the current one. Failures are
+ * reported per branch; unmerged branches need `force`.
+ */
+export const pruneGoneBranches = (repoPath: string, dryRun: boolean, force = false) =>
+  invoke<GoneBranchResult[]>('prune_gone_branches', { repoPath, dryRun, force });
 
 export const getCurrentBranch = (repoPath: string) =>
   invoke<string>('get_current_branch', { repoPath });
 
-export const createBranch = (repoPath: string, branchName: string) =>
-  invoke<string>('create_branch', { repoPath, branchName });
+/**
+ * `startPoint` is a SHA, tag, branch or remote branch ("origin/feature", which the
+ * new branch then tracks); HEAD when omitted.
+ */
+export const createBranch = (repoPath: string, branchName: string, startPoint?: string) =>
+  invoke<string>('create_branch', { repoPath, branchName, startPoint: startPoint ?? null });
 
-export const switchBranch = (repoPath: string, branchName: string) =>
-  invoke<string>('switch_branch', { repoPath, branchName });
+/**
+ * Local changes are kept; if the switch would overwrite them it fails with
+ * `checkout_conflicts` (details.paths). `stashChanges` stashes them first,
+ * `force` discards them.
+ */
+export const switchBranch = (
+  repoPath: string,
+  branchName: string,
+  { force = false, stashChanges = false } = {},
+) => invoke<BranchSwitchResult>('switch_branch', { repoPath, branchName, force, stashChanges });
 
-export const deleteBranch = (repoPath: string, branchName: string) =>
-  invoke<string>('delete_branch', { repoPath, branchName });
+/**
+ * Detach HEAD at a commit. Local changes in the way fail with `checkout_conflicts`
+ * (details.paths) unless `force` discards them.
+ */
+export const checkoutCommit = (repoPath: string, commitSha: string, force = false) =>
+  invoke<HeadState>('checkout_commit', { repoPath, commitSha, force });
+
+/** Re-attach HEAD to a branch after `checkoutCommit`. */
+export const returnToBranch = (repoPath: string, branch: string) =>
+  invoke<HeadState>('return_to_branch', { repoPath, branch });
+
+/**
+ * Unmerged branches are refused with `branch_not_merged` (details.unmerged_commits)
+ * unless `force` is set.
+ */
+export const deleteBranch = (repoPath: string, branchName: string, force = false) =>
+  invoke<DeletedBranch>('delete_branch', { repoPath, branchName, force });
+
+/**
+ * Create a branch and check it out. Local changes the switch would overwrite fail
+ * with `checkout_conflicts` (details.paths) and the branch is not created.
+ */
+export const createAndSwitchBranch = (repoPath: string, branchName: string, startPoint?: string) =>
+  invoke<HeadState>('create_and_switch_branch', {
+    repoPath,
+    branchName,
+    startPoint: startPoint ?? null,
+  });
+
+/**
+ * Track `upstream` ("origin/main"), or nothing with null. A remote-tracking ref
+ * that does not exist yet fails with `upstream_not_found` (fetch details.remote first).
+ */
+export const setUpstream = (repoPath: string, branchName: string, upstream: string | null) =>
+  invoke<BranchTracking>('set_upstream', { repoPath, branchName, upstream });
 
-export const renameBranch = (repoPath: string, oldName: string, newName: string) =>
-  invoke<string>('rename_branch', { repoPath, oldName, newName });
+export const getBranchDescription = (repoPath: string, branch: string) =>
+  invoke<string | null>('get_branch_description', { repoPath, branch });
+
+/** `null` or blank text removes the description. */
+export const setBranchDescription = (repoPath: string, branch: string, description: string | null) =>
+  invoke<void>('set_branch_description', { repoPath, branch, description });
+
+/**
+ * Tag the branch as `archive/<branchName>` and delete it. With `deleteRemote`, the tag is
+ * pushed to the upstream's remote and the remote branch deleted; each step is reported
+ * in the result rather than failing the call. An existing tag fails with `tag_exists`.
+ */
+export const archiveBranch = (repoPath: string, branchName: string, deleteRemote = false) =>
+  invoke<ArchivedBranch>('archive_branch', { repoPath, branchName, deleteRemote });
+
+/** Like `git branch --copy`: same commit, upstream and description. */
+export const copyBranch = (repoPath: string, sourceName: string, newName: string, force = false) =>
+  invoke<BranchInfo>('copy_branch', { repoPath, sourceName, newName, force });
+
+/**
+ * Carries the upstream over to the new name. Fails with `branch_exists` when `newName`
+ * is taken, unless `force` replaces that branch.
+ */
+export const renameBranch = (repoPath: string, oldName: string, newName: string, force = false) =>
+  invoke<RenamedBranch>('rename_branch', { repoPath, oldName, newName, force });
 
 // ============================================================================
 // Diff
 // ============================================================================
 
-export const getFileDiff = (repoPath: string, filePath: string, staged: boolean, contextLines?: number) =>
-  invoke<string>('get_file_diff', { repoPath, filePath, staged, contextLines: contextLines ?? null });
+export const getFileDiff = (
+  repoPath: string,
+  filePath: string,
+  staged: boolean,
+  contextLines?: number,
+  maxLines?: number,
+) =>
+  invoke<FileDiff>('get_file_diff', {
+    repoPath,
+    filePath,
+    staged,
+    contextLines: contextLines ?? null,
+    maxLines: maxLines ?? null,
+  });
 
 export const getCommitDiff = (repoPath: string, commitId: string) =>
   invoke<string>('get_commit_diff', { repoPath, commitId });
@@ -182,7 +405,7 @@ export const parseDiff = (diffText: string) =>
   invoke<ParsedDiff>('parse_diff', { diffText });
 
 export const getFileContent = (repoPath: string, filePath: string, commitId?: string) =>
-  invoke<string>('get_file_content', { repoPath, filePath, commitId: commitId ?? null });
+  invoke<FileContent>('get_file_content', { repoPath, filePath, commitId: commitId ?? null });
 
 export const getDiffStats = (repoPath: string, staged: boolean) =>
   invoke<DiffStat[]>('get_diff_stats', { repoPath, staged });
@@ -194,11 +417,19 @@ export const getDiffStats = (repoPath: string, staged: boolean) =>
 export const checkIsImage = (filePath: string) =>
   invoke<boolean>('check_is_image', { filePath });
 
-export const getImageDiff = (repoPath: string, filePath: string, staged: boolean) =>
-  invoke<ImageDiffResult>('get_image_diff', { repoPath, filePath, staged });
+export const getImageDiff = (
+  repoPath: string,
+  filePath: string,
+  staged: boolean,
+  maxDimension?: number,
+) => invoke<ImageDiffResult>('get_image_diff', { repoPath, filePath, staged, maxDimension });
 
-export const getImageAtCommit = (repoPath: string, filePath: string, commitId: string) =>
-  invoke<ImageDiffResult | null>('get_image_at_commit', { repoPath, filePath, commitId });
+export const getImageAtCommit = (
+  repoPath: string,
+  filePath: string,
+  commitId: string,
+  maxDimension?: number,
+) => invoke<ImageDiffResult | null>('get_image_at_commit', { repoPath, filePath, commitId, maxDimension });
 
 // ============================================================================
 // Remote
@@ -210,17 +441,70 @@ export const listRemotes = (repoPath: string) =>
 export const addRemote = (repoPath: string, name: string, url: string) =>
   invoke<string>('add_remote', { repoPath, name, url });
 
+/**
+ * `pushUrl` sets a separate URL for pushes; '' removes it, undefined leaves it as is.
+ */
+export const setRemoteUrl = (repoPath: string, name: string, url: string, pushUrl?: string) =>
+  invoke<RemoteInfo>('set_remote_url', { repoPath, name, url, pushUrl });
+
+/** Returns the non-default fetch refspecs that were left under the old name. */
+export const renameRemote = (repoPath: string, oldName: string, newName: string) =>
+  invoke<string[]>('rename_remote', { repoPath, oldName, newName });
+
 export const removeRemote = (repoPath: string, name: string) =>
   invoke<string>('remove_remote', { repoPath, name });
 
-export const fetchRemote = (repoPath: string, remoteName: string) =>
-  invoke<string>('fetch_remote', { repoPath, remoteName });
+/** With `prune`, remote-tracking refs of branches deleted on the remote are removed. */
+export const fetchRemote = (repoPath: string, remoteName: string, depth?: number, prune = false) =>
+  invoke<FetchResult>('fetch_remote', { repoPath, remoteName, depth, prune });
+
+/** Fetch every remote; one failing does not stop the others. */
+export const fetchAllRemotes = (repoPath: string, prune = false) =>
+  invoke<RemoteFetchResult[]>('fetch_all_remotes', { repoPath, prune });
+
+export const unshallow = (repoPath: string, remoteName: string) =>
+  invoke<string>('unshallow', { repoPath, remoteName });
 
 export const pullChanges = (repoPath: string, remoteName: string, branchName: string) =>
-  invoke<string>('pull_changes', { repoPath, remoteName, branchName });
+  invoke<PullResult>('pull_changes', { repoPath, remoteName, branchName });
+
+/**
+ * `setUpstream` defaults to true for a branch without an upstream (`git push -u`).
+ * `pushModeTags` "follow" adds the annotated tags on the pushed history, "all" every tag.
+ * `forceWithLease` overwrites only while the remote branch is where the last fetch saw it;
+ * otherwise it fails with a `lease_failed` error (details.remote_sha, details.tracking_sha).
+ */
+export const pushChanges = (
+  repoPath: string,
+  remoteName: string,
+  branchName: string,
+  force = false,
+  {
+    setUpstream = undefined as boolean | undefined,
+    pushModeTags = 'none' as 'none' | 'follow' | 'all',
+    forceWithLease = false,
+  } = {},
+) =>
+  invoke<PushResult>('push_changes', {
+    repoPath,
+    remoteName,
+    branchName,
+    force,
+    setUpstream,
+    pushModeTags,
+    forceWithLease,
+  });
+
+/**
+ * Push several branches in one operation. A branch the remote has moved past is reported
+ * "rejected" unless `force`; the others still go through.
+ */
+export const pushBranches = (repoPath: string, remoteName: string, branches: string[], force = false) =>
+  invoke<BranchPushStatus[]>('push_branches', { repoPath, remoteName, branches, force });
 
-export const pushChanges = (repoPath: string, remoteName: string, branchName: string, force = false) =>
-  invoke<string>('push_changes', { repoPath, remoteName, branchName, force });
+/** `pushBranches` for every local branch whose upstream is on `remoteName`. */
+export const pushAllBranches = (repoPath: string, remoteName: string, force = false) =>
+  invoke<BranchPushStatus[]>('push_all_branches', { repoPath, remoteName, force });
 
 export const getRemoteBranches = (repoPath: string, remoteName: string) =>
   invoke<RemoteBranchInfo[]>('get_remote_branches', { repoPath, remoteName });
@@ -228,15 +512,88 @@ export const getRemoteBranches = (repoPath: string, remoteName: string) =>
 export const getSyncProgress = (repoPath: string) =>
   invoke<SyncProgress>('get_sync_progress', { repoPath });
 
+/**
+ * Progress of `fetchRemote`, `pullChanges` and `pushChanges` as it happens (about 10 per
+ * second during transfers). Resolves to the unlisten function.
+ */
+export const onSyncProgress = (handler: (event: SyncProgressEvent) => void) =>
+  listenEvent<SyncProgressEvent>('sync-progress', handler);
+
+/** The end of a fetch, pull or push: `sync-complete` with its result or `sync-error`. */
+export const onSyncFinished = (handler: (event: SyncFinishedEvent, failed: boolean) => void) =>
+  Promise.all([
+    listenEvent<SyncFinishedEvent>('sync-complete', (event) => handler(event, false)),
+    listenEvent<SyncFinishedEvent>('sync-error', (event) => handler(event, true)),
+  ]).then((unlisteners) => () => unlisteners.forEach((unlisten) => unlisten()));
+
 export const checkRemoteConnection = (repoPath: string, remoteName: string) =>
   invoke<boolean>('check_remote_connection', { repoPath, remoteName });
 
+/** Remember an SSH key's passphrase for this session; `null` forgets it. */
+export const setSshPassphrase = (keyPath: string, passphrase: string | null) =>
+  invoke<void>('set_ssh_passphrase', { keyPath, passphrase });
+
+/**
+ * Run again a fetch, pull or push that failed with `auth_required` (details.operation_id);
+ * resolves to what that command returns.
+ */
+export const retryWithCredentials = <T>(operationId: string, credential: RemoteCredential) =>
+  invoke<T>('retry_with_credentials', { operationId, credential });
+
+/**
+ * Ask the user for what an `auth_required` error is missing (details.kind "ssh_passphrase",
+ * "ssh_key" or "password"); `null` when they cancel.
+ */
+// eslint-disable-next-line @typescript-eslint/no-explicit-any
+const askCredential = (details: any): RemoteCredential | null => {
+  if (details.kind === 'ssh_passphrase') {
+    const passphrase = prompt(`SSH 키 암호를 입력하세요:\n${details.key_path}`);
+    return passphrase ? { type: 'ssh_key', key_path: details.key_path, passphrase } : null;
+  }
+  if (details.kind === 'ssh_key') {
+    const keyPath = prompt(`${details.host}에 사용할 SSH 키 경로를 입력하세요:`, '~/.ssh/id_ed25519');
+    return keyPath ? { type: 'ssh_key', key_path: keyPath } : null;
+  }
+  const username = prompt(`${details.url}\n사용자 이름을 입력하세요:`);
+  if (!username) return null;
+  const password = prompt(`${details.url}\n${username}의 비밀번호 또는 토큰을 입력하세요:`);
+  return password ? { type: 'user_pass', username, password } : null;
+};
+
+/**
+ * Run a fetch, pull or push; while it fails with `auth_required`, ask for the missing
+ * credential and retry with it. A passphrase given for an SSH key is remembered for the session.
+ */
+export const withCredentialRetry = async <T>(run: () => Promise<T>): Promise<T> => {
+  let attempt = run;
+  for (;;) {
+    try {
+      return await attempt();
+    } catch (err) {
+      const required = parseCommandError(err);
+      if (required?.code !== 'auth_required' || !required.details.operation_id) throw err;
+      const operationId: string = required.details.operation_id;
+      const credential = askCredential(required.details);
+      if (!credential) throw err;
+      if (credential.type === 'ssh_key' && credential.passphrase) {
+        await setSshPassphrase(credential.key_path, credential.passphrase);
+      }
+      attempt = () => retryWithCredentials<T>(operationId, credential);
+    }
+  }
+};
+
 // ============================================================================
 // Amend
 // ============================================================================
 
-export const amendCommit = (repoPath: string, message: string) =>
-  invoke<string>('amend_commit', { repoPath, message });
+export const amendCommit = (
+  repoPath: string,
+  message: string,
+  includeWorktree?: boolean,
+  skipHooks = false,
+) =>
+  invoke<AmendResult>('amend_commit', { repoPath, message, includeWorktree, skipHooks });
 
 export const getLastCommitMessage = (repoPath: string) =>
   invoke<string>('get_last_commit_message', { repoPath });
@@ -265,7 +622,7 @@ export const stashDrop = (repoPath: string, index: number) =>
 // ============================================================================
 
 export const mergeBranch = (repoPath: string, sourceBranch: string, noFastForward = false) =>
-  invoke<string>('merge_branch', { repoPath, sourceBranch, noFastForward });
+  invoke<MergeResult>('merge_branch', { repoPath, sourceBranch, noFastForward });
 
 export const canMerge = (repoPath: string, sourceBranch: string) =>
   invoke<boolean>('can_merge', { repoPath, sourceBranch });
@@ -358,9 +715,29 @@ export const getRebaseStatus = (repoPath: string) =>
 export const getReflog = (repoPath: string, refName?: string, limit?: number) =>
   invoke<ReflogEntry[]>('get_reflog', { repoPath, refName: refName ?? null, limit: limit ?? null });
 
+/**
+ * Branches checked out recently, most recent first, without the current one; the first is
+ * where `git checkout -` goes. Detached checkouts are listed only with `includeCommits`.
+ */
+export const getRecentBranches = (
+  repoPath: string,
+  { limit = 10, includeCommits = false } = {},
+) => invoke<RecentBranch[]>('get_recent_branches', { repoPath, limit, includeCommits });
+
 export const resetToReflog = (repoPath: string, refName: string, resetType: string) =>
   invoke<void>('reset_to_reflog', { repoPath, refName, resetType });
 
+/**
+ * Move the current branch to `commitSha`. A hard reset over local changes
+ * fails with `reset_would_discard` (details.paths) unless `force` is set.
+ */
+export const resetToCommit = (
+  repoPath: string,
+  commitSha: string,
+  mode: 'soft' | 'mixed' | 'hard',
+  force = false,
+) => invoke<ResetResult>('reset_to_commit', { repoPath, commitSha, mode, force });
+
 // ============================================================================
 // Bundle
 // ============================================================================
@@ -378,20 +755,31 @@ export const fetchFromBundle = (repoPath: string, bundlePath: string) =>
   invoke<string>('fetch_from_bundle', { repoPath, bundlePath });
 
 export const cloneFromBundle = (bundlePath: string, targetPath: string) =>
-  invoke<string>('clone_from_bundle', { bundlePath, targetPath });
+  invoke<RepositoryInfo>('clone_from_bundle', { bundlePath, targetPath });
+
+export const cancelBundleClone = (targetPath: string) =>
+  invoke<void>('cancel_bundle_clone', { targetPath });
 
 // ============================================================================
 // Clone
 // ============================================================================
 
-export const cloneRepository = (url: string, targetPath: string) =>
-  invoke<string>('clone_repository', { url, targetPath });
+export const cloneRepository = (url: string, targetPath: string, depth?: number) =>
+  invoke<string>('clone_repository', { url, targetPath, depth });
 
 export const searchCommits = (repoPath: string, query: string, limit = 100) =>
   invoke<CommitInfo[]>('search_commits', { repoPath, query, limit });
 
-export const getBranchDivergence = (repoPath: string, branch: string, base: string) =>
-  invoke<[number, number]>('get_branch_divergence', { repoPath, branch, base });
+/**
+ * Commits unique to each branch (local or remote-tracking), at most `limit` per side.
+ * Fails with `unrelated_histories` when the branches share no commit.
+ */
+export const getBranchDivergence = (
+  repoPath: string,
+  branchA: string,
+  branchB: string,
+  limit?: number,
+) => invoke<BranchDivergence>('get_branch_divergence', { repoPath, branchA, branchB, limit });
 
 // ============================================================================
 // Dialog Helpers (Tauri Dialog API)
@@ -435,12 +823,40 @@ export const setGitConfig = (repoPath: string, key: string, value: string) =>
 export const removeGitConfig = (repoPath: string, key: string) =>
   invoke<void>('remove_git_config', { repoPath, key });
 
+/** Where a config value comes from: "local", "global", "xdg", "system" or "app". */
+export interface GitIdentity {
+  name: string | null;
+  email: string | null;
+  name_level: string | null;
+  email_level: string | null;
+}
+
+export interface ConfigValue {
+  key: string;
+  value: string | null;
+  level: string | null;
+}
+
+export const getGitIdentity = (repoPath: string) =>
+  invoke<GitIdentity>('get_git_identity', { repoPath });
+
+/**
+ * Writes to the repository config, or the global one with `global`.
+ * Commits without an identity fail with the `identity_missing` error code.
+ */
+export const setGitIdentity = (repoPath: string, name: string, email: string, global: boolean) =>
+  invoke<GitIdentity>('set_git_identity', { repoPath, name, email, global });
+
+/** Only core.autocrlf, pull.rebase and init.defaultBranch are accepted. */
+export const getConfigValue = (repoPath: string, key: string) =>
+  invoke<ConfigValue>('get_config_value', { repoPath, key });
+
+export const setConfigValue = (repoPath: string, key: string, value: string, global: boolean) =>
+  invoke<ConfigValue>('set_config_value', { repoPath, key, value, global });
+
 export const getRemoteUrl = (repoPath: string, remoteName: string) =>
   invoke<string>('get_remote_url', { repoPath, remoteName });
 
-export const setRemoteUrl = (repoPath: string, remoteName: string, url: string) =>
-  invoke<void>('set_remote_url', { repoPath, remoteName, url });
-
 export const listGitHooks = (repoPath: string) =>
   invoke<HookInfo[]>('list_git_hooks', { repoPath });
 
@@ -2,12 +2,60 @@
 // Git Data Models (match Rust backend models 1:1)
 // ============================================================================
 
+/** Structured command error (JSON-encoded inside the rejected invoke string). */
+export interface CommandError {
+  code: string;
+  message: string;
+  // eslint-disable-next-line @typescript-eslint/no-explicit-any
+  details: any;
+}
+
 /** Repository metadata. */
 export interface RepositoryInfo {
   path: string;
   name: string;
   current_branch: string;
   remote_url?: string;
+  /** No commit on current_branch yet (freshly initialized repository) */
+  is_empty?: boolean;
+  /** History cut off by a shallow clone (see unshallow) */
+  is_shallow?: boolean;
+  /** HEAD is on a commit; current_branch is then "HEAD detached at <short sha>" */
+  is_detached?: boolean;
+  /** Commit HEAD points at (empty when is_empty) */
+  head_sha?: string;
+  /** Current branch vs. its upstream (see get_branch_sync_status) */
+  upstream?: string | null;
+  ahead?: number;
+  behind?: number;
+  has_upstream?: boolean;
+  upstream_gone?: boolean;
+  detached?: boolean;
+  /** Operation left in progress, to reopen its screen */
+  state?: OperationState;
+}
+
+/** Operation in progress, e.g. merging `source` into `target`. */
+export interface OperationState {
+  state:
+    | 'clean'
+    | 'merge'
+    | 'rebase'
+    | 'rebase_interactive'
+    | 'rebase_merge'
+    | 'apply_mailbox'
+    | 'apply_mailbox_or_rebase'
+    | 'cherry_pick'
+    | 'cherry_pick_sequence'
+    | 'revert'
+    | 'revert_sequence'
+    | 'bisect';
+  /** Branch name when one points at the commit, sha otherwise */
+  source: string | null;
+  target: string | null;
+  /** Rebase progress (1-based step) */
+  step: number | null;
+  total_steps: number | null;
 }
 
 /** A single commit. */
@@ -24,8 +72,57 @@ export interface CommitInfo {
 /** Working tree / index file status. */
 export interface FileStatus {
   path: string;
+  /** "added" | "modified" | "deleted" | "renamed" | "untracked" | "conflicted" | "submodule" */
   status: string;
   staged: boolean;
+  /** Previous path of a "renamed" entry */
+  old_path?: string | null;
+}
+
+/** Repository status with how long the scan took. */
+export interface RepositoryStatusReport {
+  files: FileStatus[];
+  elapsed_ms: number;
+  /** The full scan was slow; offer skipping or collapsing untracked files */
+  suggest_fast_mode: boolean;
+}
+
+/** Options for validateCommitMessage; omitted fields use the defaults. */
+export interface CommitLintConfig {
+  /** Check the `type(scope): description` header (default true) */
+  conventional?: boolean;
+  allowed_types?: string[];
+  /** In user-perceived characters (default 72) */
+  max_subject_length?: number;
+}
+
+/** One problem found by validateCommitMessage. */
+export interface LintFinding {
+  /** e.g. "header-max-length", "type-enum", "body-leading-blank" */
+  rule: string;
+  severity: 'error' | 'warning';
+  message: string;
+  /** 1-based */
+  line: number;
+}
+
+/** Result of creating a commit. */
+export interface CommitResult {
+  commit_sha: string;
+  message: string;
+  /** Signed per commit.gpgsign / gpg.format=ssh */
+  signed: boolean;
+  signature_type: string | null;
+}
+
+/** Result of merging a branch. */
+export interface MergeResult {
+  message: string;
+  /** The merge commit, when one was created */
+  commit_sha: string | null;
+  /** Signed per commit.gpgsign / gpg.format=ssh */
+  signed: boolean;
+  signature_type: string | null;
 }
 
 /** Recently opened repository. */
@@ -40,10 +137,97 @@ export interface BranchInfo {
   name: string;
   is_current: boolean;
   is_remote: boolean;
+  /** Remote of a remote-tracking branch ("origin"), null for local branches */
+  remote_name?: string | null;
   commit_sha: string;
   commit_message?: string;
   author?: string;
   timestamp?: number;
+  /** Only computed when listing with `includeMergeStatus` */
+  is_merged?: boolean;
+  /** Configured upstream of a local branch ("origin/main") */
+  upstream?: string | null;
+  /** Only computed when listing with `includeTracking` */
+  ahead?: number;
+  behind?: number;
+  upstream_gone?: boolean;
+  /** `branch.<name>.description`, multi-line */
+  description?: string | null;
+}
+
+/** Where HEAD points after a checkout-like operation. */
+export interface HeadState {
+  /** Branch name, null when detached */
+  name: string | null;
+  detached: boolean;
+  sha: string;
+}
+
+export interface BranchSwitchResult {
+  head: HeadState;
+  /** Stash holding the changes set aside with `stashChanges` */
+  stash_index: number | null;
+}
+
+/** A local branch's upstream as stored in `branch.<name>.remote` / `.merge`. */
+export interface BranchTracking {
+  branch: string;
+  upstream: string | null;
+  remote: string | null;
+  merge: string | null;
+}
+
+/** A deleted branch; recreating `name` at `tip_sha` undoes the delete. */
+export interface DeletedBranch {
+  name: string;
+  tip_sha: string;
+}
+
+/** Commits unique to each of two branches, most recent first. */
+export interface BranchDivergence {
+  branch_a: string;
+  branch_b: string;
+  merge_base: string;
+  only_in_a: CommitInfo[];
+  only_in_a_truncated: boolean;
+  only_in_b: CommitInfo[];
+  only_in_b_truncated: boolean;
+}
+
+/** Result of `archiveBranch`; the two remote steps are reported separately. */
+export interface ArchivedBranch {
+  branch: string;
+  /** `archive/<branch>` */
+  tag_name: string;
+  tip_sha: string;
+  /** Remote of the upstream, when remote steps were attempted */
+  remote: string | null;
+  tag_pushed: boolean;
+  tag_push_error: string | null;
+  remote_branch_deleted: boolean;
+  remote_delete_error: string | null;
+}
+
+export interface RenamedBranch {
+  old_name: string;
+  new_name: string;
+  /** Upstream carried over from the old name ("origin/old") */
+  upstream: string | null;
+  /** The upstream still exists under the old name */
+  pushed_under_old_name: boolean;
+}
+
+/** Per-branch outcome of `pruneGoneBranches`. */
+export interface GoneBranchResult {
+  name: string;
+  tip_sha: string;
+  upstream: string | null;
+  deleted: boolean;
+  /** May be a structured `branch_not_merged` error; see `parseCommandError` */
+  error: string | null;
+  /** The tip is reachable from another ref (checked before deleting) */
+  backed_up: boolean;
+  containing_refs: string[];
 }
 
 // ============================================================================
@@ -68,15 +252,41 @@ export interface DiffHunk {
   lines: DiffLine[];
 }
 
+/** Patch text of a file diff, cut at a hunk boundary when limits are exceeded. */
+export interface FileDiff {
+  patch: string;
+  truncated: boolean;
+  total_lines: number;
+  returned_lines: number;
+}
+
+/** File contents from get_file_content; binary files come back as base64. */
+export interface FileContent {
+  is_binary: boolean;
+  text: string | null;
+  base64: string | null;
+  size: number;
+  encoding_guess: string;
+}
+
 /** Parsed diff for a single file. */
 export interface ParsedDiff {
   file_path: string;
   old_path: string;
   new_path: string;
   is_binary: boolean;
+  /** File modes as numbers, e.g. 0o100644; format with `toString(8)` (null when unchanged). */
+  old_mode?: number | null;
+  new_mode?: number | null;
+  /** Only the mode changed; render "mode changed old → new" instead of hunks. */
+  mode_only?: boolean;
   hunks: DiffHunk[];
   additions: number;
   deletions: number;
+  /** Binary files only: byte sizes (null for a missing side) and new - old. */
+  old_size?: number | null;
+  new_size?: number | null;
+  size_delta?: number | null;
 }
 
 /** Per-file diff statistics. */
@@ -85,6 +295,10 @@ export interface DiffStat {
   additions: number;
   deletions: number;
   is_binary: boolean;
+  /** Binary files only: byte sizes (null for a missing side) and new - old. */
+  old_size?: number | null;
+  new_size?: number | null;
+  size_delta?: number | null;
 }
 
 /** A changed file in a commit with status and diff stats. */
@@ -95,6 +309,10 @@ export interface CommitFileChange {
   deletions: number;
   is_binary: boolean;
   old_path: string | null;
+  /** Binary files only: byte sizes (null for a missing side) and new - old. */
+  old_size?: number | null;
+  new_size?: number | null;
+  size_delta?: number | null;
 }
 
 // ============================================================================
@@ -109,6 +327,10 @@ export interface ImageData {
   width: number;
   height: number;
   format: string;
+  /** Full-size metadata; differs from width/height/size for thumbnails. */
+  original_width?: number;
+  original_height?: number;
+  original_size?: number;
 }
 
 /** Result of comparing two image versions. */
@@ -117,6 +339,11 @@ export interface ImageDiffResult {
   new_image: ImageData | null;
   is_image: boolean;
   file_path: string;
+  /** Percentage of differing pixels when both versions decode to the same size. */
+  diff_percentage?: number | null;
+  /** PNG mask highlighting the differing pixels. */
+  diff_mask?: ImageData | null;
+  dimensions_changed?: boolean;
 }
 
 // ============================================================================
@@ -128,7 +355,10 @@ export interface RemoteInfo {
   name: string;
   url: string;
   fetch_url: string;
+  /** Where pushes go: `pushurl` when set, else `url` */
   push_url: string;
+  /** `remote.<name>.pushurl` is set */
+  separate_push_url: boolean;
 }
 
 /** A branch on a remote. */
@@ -149,6 +379,80 @@ export interface SyncProgress {
   message: string;
 }
 
+/** Payload of `sync-progress` events. */
+export interface SyncProgressEvent {
+  repo_path: string;
+  /** Increases with every fetch, pull or push */
+  operation_id: number;
+  progress: SyncProgress;
+}
+
+/** Payload of the `sync-complete` / `sync-error` events ending an operation. */
+export interface SyncFinishedEvent {
+  repo_path: string;
+  operation_id: number;
+  /** What the command returned (`sync-complete`) */
+  result: unknown | null;
+  /** The command's error (`sync-error`) */
+  error: string | null;
+}
+
+/** Result of `push_changes`. */
+/** Result of `fetch_remote`. */
+export interface FetchResult {
+  message: string;
+  /** Remote-tracking refs deleted with `prune`, e.g. "refs/remotes/origin/old" */
+  pruned_refs: string[];
+}
+
+/** Result of `pull_changes`. */
+export interface PullResult {
+  kind: 'fast_forward' | 'merged' | 'conflicts' | 'up_to_date';
+  message: string;
+  /** The merge commit when `kind` is "merged" */
+  commit_sha: string | null;
+  /** Paths left to resolve when `kind` is "conflicts"; the merge stays in progress */
+  conflicted_files: string[];
+}
+
+export interface PushResult {
+  message: string;
+  /** The branch's upstream after the push, e.g. "origin/feature" */
+  upstream: string | null;
+  /** What the remote made of each ref sent: the branch and any tags */
+  refs: PushRefResult[];
+}
+
+/** One ref of a push, as the remote reported it. */
+export interface PushRefResult {
+  refname: string;
+  accepted: boolean;
+  /** The remote's reason when it was rejected */
+  message: string | null;
+}
+
+/** Outcome for one branch of `push_branches`. */
+export interface BranchPushStatus {
+  branch: string;
+  status: 'pushed' | 'up_to_date' | 'rejected';
+  /** Why it was rejected */
+  message: string | null;
+}
+
+/** Outcome for one remote of `fetch_all_remotes`. */
+export interface RemoteFetchResult {
+  remote: string;
+  /** Refs the fetch created, moved or pruned, e.g. "refs/remotes/origin/main" */
+  updated_refs: string[];
+  /** Why the fetch failed; the other remotes are fetched regardless */
+  error: string | null;
+}
+
+/** A credential for `retry_with_credentials`, used for one attempt only. */
+export type RemoteCredential =
+  | { type: 'user_pass'; username: string; password: string }
+  | { type: 'ssh_key'; username?: string; key_path: string; passphrase?: string };
+
 // ============================================================================
 // Conflict Resolution
 // ============================================================================
@@ -179,6 +483,9 @@ export interface CherryPickResult {
   success: boolean;
   conflicts: string[];
   message: string;
+  /** Signed per commit.gpgsign / gpg.format=ssh */
+  signed: boolean;
+  signature_type: string | null;
 }
 
 /** Revert result. */
@@ -186,6 +493,9 @@ export interface RevertResult {
   success: boolean;
   conflicts: string[];
   message: string;
+  /** Signed per commit.gpgsign / gpg.format=ssh */
+  signed: boolean;
+  signature_type: string | null;
 }
 
 /** Rebase status. */
@@ -202,6 +512,20 @@ export interface RebaseResult {
   message: string;
 }
 
+// ============================================================================
+// Amend
+// ============================================================================
+
+/** Result of amending HEAD. */
+export interface AmendResult {
+  commit_sha: string;
+  message: string;
+  auto_staged_paths: string[];
+  /** Signed per commit.gpgsign / gpg.format=ssh */
+  signed: boolean;
+  signature_type: string | null;
+}
+
 // ============================================================================
 // Stash
 // ============================================================================
@@ -254,6 +578,22 @@ export interface ReflogEntry {
   timestamp: number;
 }
 
+/** A recently checked-out branch, for quick switching. */
+export interface RecentBranch {
+  /** Branch name, or the commit / tag of a detached checkout */
+  name: string;
+  detached: boolean;
+  /** Last checked out or left */
+  timestamp: number;
+}
+
+export interface ResetResult {
+  /** Empty when HEAD had no commit yet */
+  previous_head: string;
+  new_head: string;
+  mode: 'soft' | 'mixed' | 'hard';
+}
+
 // ============================================================================
 // Bundle
 // ============================================================================